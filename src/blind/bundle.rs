@@ -29,21 +29,44 @@ pub struct BlindCredentialBundle<S: ShortGroupSignatureScheme> {
 }
 
 impl<S: ShortGroupSignatureScheme> BlindCredentialBundle<S> {
-    /// Create a unblinded credential
+    /// Create a unblinded credential.
+    ///
+    /// The credential is checked with [`CredentialBundle::verify`].
     pub fn to_unblinded(
         mut self,
         blind_claims: &BTreeMap<String, ClaimData>,
         blinder: Scalar,
     ) -> CredxResult<CredentialBundle<S>> {
-        for label in blind_claims.keys() {
+        for (label, claim) in blind_claims {
             if !self.issuer.schema.blind_claims.contains(label) {
                 return Err(Error::InvalidClaimData("claim is not blindable"));
             }
+            if claim.is_absent()
+                && !self
+                    .issuer
+                    .schema
+                    .claims
+                    .iter()
+                    .any(|c| c.optional && &c.label == label)
+            {
+                return Err(Error::InvalidClaimData("claim is not optional"));
+            }
             if self.credential.claims.contains_key(label) {
                 return Err(Error::InvalidClaimData("duplicate claim detected"));
             }
         }
-        self.credential.claims.append(&mut blind_claims.clone());
+        for (label, claim) in blind_claims {
+            let index = self
+                .issuer
+                .schema
+                .claim_indices
+                .get_index_of(label)
+                .ok_or(Error::InvalidClaimData("claim does not exist in schema"))?;
+            self.credential.claims.insert(
+                label.clone(),
+                self.issuer.schema.claims[index].normalize(claim),
+            );
+        }
         let mut ordering = vec![String::new(); self.credential.claims.len()];
 
         for label in self.credential.claims.keys() {
@@ -71,7 +94,7 @@ impl<S: ShortGroupSignatureScheme> BlindCredentialBundle<S> {
             .ok_or(Error::InvalidClaimData(
                 "revocation label not found in claims",
             ))?;
-        Ok(CredentialBundle {
+        let bundle = CredentialBundle {
             issuer: self.issuer,
             credential: Credential {
                 claims,
@@ -79,6 +102,8 @@ impl<S: ShortGroupSignatureScheme> BlindCredentialBundle<S> {
                 revocation_handle: self.credential.revocation_handle,
                 revocation_index,
             },
-        })
+        };
+        bundle.verify()?;
+        Ok(bundle)
     }
 }
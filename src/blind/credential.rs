@@ -49,7 +49,7 @@ impl<S: ShortGroupSignatureScheme> BlindCredential<S> {
         for claim in &issuer.schema.claims {
             match combined_claims.remove(&claim.label) {
                 None => return Err(Error::InvalidClaimData("claim not found list")),
-                Some(claim) => claims.push(claim),
+                Some(c) => claims.push(claim.normalize(&c)),
             }
         }
 
@@ -1,15 +1,22 @@
 use crate::knox::short_group_sig_core::short_group_traits::{
-    BlindSignatureContext as _, ShortGroupSignatureScheme,
+    BlindSignatureContext as _, PublicKey as _, ShortGroupSignatureScheme,
 };
+use crate::knox::short_group_sig_core::BlindSignatureFormat;
 use crate::{
     claim::ClaimData,
     error::Error,
     issuer::{Issuer, IssuerPublic},
+    presentation::{
+        BlindClaimsCredential, Presentation, PresentationCredential, PresentationProofs,
+        PresentationSchema,
+    },
+    statement::Statements,
     CredxResult,
 };
 use blsful::inner_types::*;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 /// A blind credential signing request
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -20,6 +27,37 @@ pub struct BlindCredentialRequest<S: ShortGroupSignatureScheme> {
     pub blind_claim_labels: Vec<String>,
     /// The nonce for this context
     pub nonce: Scalar,
+    /// A presentation over the holder's existing credentials that proves
+    /// predicates about the blinded claims
+    #[serde(
+        default = "Option::default",
+        bound(
+            serialize = "BlindClaimsPresentation<S>: Serialize",
+            deserialize = "BlindClaimsPresentation<S>: Deserialize<'de>"
+        )
+    )]
+    pub presentation: Option<BlindClaimsPresentation<S>>,
+}
+
+/// A presentation included in a blind signing request.
+///
+/// The schema must contain at least one [`crate::statement::BlindClaimsStatement`]
+/// for the blinded claims. Other statements reference it by its id to prove
+/// equality, range or other predicates about the blinded claims.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BlindClaimsPresentation<S: ShortGroupSignatureScheme> {
+    /// The presentation schema
+    #[serde(bound(
+        serialize = "PresentationSchema<S>: Serialize",
+        deserialize = "PresentationSchema<S>: Deserialize<'de>"
+    ))]
+    pub schema: PresentationSchema<S>,
+    /// The presentation bound to the request nonce
+    #[serde(bound(
+        serialize = "Presentation<S>: Serialize",
+        deserialize = "Presentation<S>: Deserialize<'de>"
+    ))]
+    pub presentation: Presentation<S>,
 }
 
 impl<S: ShortGroupSignatureScheme> BlindCredentialRequest<S> {
@@ -28,25 +66,51 @@ impl<S: ShortGroupSignatureScheme> BlindCredentialRequest<S> {
         issuer: &IssuerPublic<S>,
         claims: &BTreeMap<String, ClaimData>,
     ) -> CredxResult<(Self, Scalar)> {
-        let nonce = Scalar::random(rand::thread_rng());
+        Self::new_with_nonce(issuer, claims, Scalar::random(rand::thread_rng()))
+    }
+
+    /// Create a new request for the nonce chosen by the issuer
+    pub fn new_with_nonce(
+        issuer: &IssuerPublic<S>,
+        claims: &BTreeMap<String, ClaimData>,
+        nonce: Scalar,
+    ) -> CredxResult<(Self, Scalar)> {
+        Self::new_with_format(issuer, claims, nonce, BlindSignatureFormat::default())
+    }
+
+    /// Create a new request for the nonce chosen by the issuer with the
+    /// commitment proof in `format`
+    pub fn new_with_format(
+        issuer: &IssuerPublic<S>,
+        claims: &BTreeMap<String, ClaimData>,
+        nonce: Scalar,
+        format: BlindSignatureFormat,
+    ) -> CredxResult<(Self, Scalar)> {
         let mut messages = Vec::with_capacity(claims.len());
         for (label, claim) in claims {
             if !issuer.schema.blind_claims.contains(label) {
                 return Err(Error::InvalidClaimData("claim is not blindable"));
             }
+            let index = issuer
+                .schema
+                .claim_indices
+                .get_index_of(label)
+                .ok_or(Error::InvalidClaimData("claim does not exist in schema"))?;
+            if claim.is_absent() && !issuer.schema.claims[index].optional {
+                return Err(Error::InvalidClaimData("claim is not optional"));
+            }
             messages.push((
-                issuer
-                    .schema
-                    .claim_indices
-                    .get_index_of(label)
-                    .ok_or(Error::InvalidClaimData("claim does not exist in schema"))?,
-                claim.to_scalar(),
+                index,
+                issuer.schema.claims[index].normalize(claim).to_scalar(),
             ));
         }
-        let (ctx, blinder) = S::new_blind_signature_context(
+        // The issuer checks the context proof in schema order
+        messages.sort_unstable_by_key(|(index, _)| *index);
+        let (ctx, blinder) = S::new_blind_signature_context_with_format(
             &messages,
             &issuer.verifying_key,
             nonce,
+            format,
             rand::thread_rng(),
         )
         .map_err(|_| Error::InvalidClaimData("unable to create blind signature context"))?;
@@ -55,11 +119,140 @@ impl<S: ShortGroupSignatureScheme> BlindCredentialRequest<S> {
                 blind_signature_context: ctx,
                 blind_claim_labels: claims.keys().cloned().collect(),
                 nonce,
+                presentation: None,
             },
             blinder,
         ))
     }
 
+    /// Create a new request for the nonce chosen by the issuer that includes
+    /// a presentation of `schema` proving predicates about the blinded claims.
+    ///
+    /// `credentials` holds the holder's existing credentials. The blinded claims
+    /// are supplied for each blind claims statement in `schema` by this request.
+    pub fn new_with_presentation(
+        issuer: &IssuerPublic<S>,
+        claims: &BTreeMap<String, ClaimData>,
+        nonce: Scalar,
+        credentials: &IndexMap<String, PresentationCredential<S>>,
+        schema: &PresentationSchema<S>,
+    ) -> CredxResult<(Self, Scalar)> {
+        Self::new_with_presentation_and_format(
+            issuer,
+            claims,
+            nonce,
+            credentials,
+            schema,
+            BlindSignatureFormat::default(),
+        )
+    }
+
+    /// Create a new request like [`Self::new_with_presentation`] with the
+    /// commitment proof in `format`
+    pub fn new_with_presentation_and_format(
+        issuer: &IssuerPublic<S>,
+        claims: &BTreeMap<String, ClaimData>,
+        nonce: Scalar,
+        credentials: &IndexMap<String, PresentationCredential<S>>,
+        schema: &PresentationSchema<S>,
+        format: BlindSignatureFormat,
+    ) -> CredxResult<(Self, Scalar)> {
+        let (mut request, blinder) = Self::new_with_format(issuer, claims, nonce, format)?;
+        let mut blinded = BTreeMap::new();
+        for (label, claim) in claims {
+            let index = issuer
+                .schema
+                .claim_indices
+                .get_index_of(label)
+                .ok_or(Error::InvalidClaimData("claim does not exist in schema"))?;
+            blinded.insert(index, issuer.schema.claims[index].normalize(claim));
+        }
+        let mut credentials = credentials.clone();
+        for (id, statement) in &schema.statements {
+            if let Statements::BlindClaims(_) = statement {
+                credentials.insert(
+                    id.clone(),
+                    BlindClaimsCredential {
+                        claims: blinded.clone(),
+                        blinder,
+                    }
+                    .into(),
+                );
+            }
+        }
+        let presentation = Presentation::create(&credentials, schema, &nonce.to_be_bytes())?;
+        request.presentation = Some(BlindClaimsPresentation {
+            schema: schema.clone(),
+            presentation,
+        });
+        request.verify_presentation(issuer, schema)?;
+        Ok((request, blinder))
+    }
+
+    /// Verify the presentation in this request against `schema`.
+    ///
+    /// `schema` is the one the issuer requires, not the one in the request.
+    /// Every blind claims statement must be for `issuer` and the blinded claims
+    /// in this request and its proof must open the blind signature context.
+    pub fn verify_presentation(
+        &self,
+        issuer: &IssuerPublic<S>,
+        schema: &PresentationSchema<S>,
+    ) -> CredxResult<()> {
+        let presentation = self
+            .presentation
+            .as_ref()
+            .ok_or(Error::InvalidPresentationData(
+                "blind signing request has no presentation".to_string(),
+            ))?;
+        if presentation.schema.id != schema.id {
+            return Err(Error::InvalidPresentationData(
+                "blind signing request presentation is for a different schema".to_string(),
+            ));
+        }
+        presentation
+            .presentation
+            .verify(schema, &self.nonce.to_be_bytes())?;
+        let labels = self.blind_claim_labels.iter().collect::<BTreeSet<_>>();
+        let commitment = self.blind_signature_context.commitment();
+        let mut found = false;
+        for (id, statement) in &schema.statements {
+            let Statements::BlindClaims(bs) = statement else {
+                continue;
+            };
+            if bs.issuer.id != issuer.id
+                || bs.issuer.verifying_key.to_bytes() != issuer.verifying_key.to_bytes()
+            {
+                return Err(Error::InvalidPresentationData(format!(
+                    "blind claims statement '{}' is for a different issuer",
+                    id
+                )));
+            }
+            if bs.claims.iter().collect::<BTreeSet<_>>() != labels {
+                return Err(Error::InvalidPresentationData(format!(
+                    "blind claims statement '{}' doesn't match the blinded claims",
+                    id
+                )));
+            }
+            match presentation.presentation.proofs.get(id) {
+                Some(PresentationProofs::BlindClaims(proof)) if proof.commitment == commitment => {}
+                _ => {
+                    return Err(Error::InvalidPresentationData(format!(
+                        "blind claims proof '{}' doesn't open the blind signature context",
+                        id
+                    )))
+                }
+            }
+            found = true;
+        }
+        if !found {
+            return Err(Error::InvalidPresentationData(
+                "presentation doesn't have a blind claims statement".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
     /// Verify the signing request is well-formed
     pub fn verify(&self, issuer: &Issuer<S>) -> CredxResult<()> {
         let mut known_messages =
@@ -78,7 +271,7 @@ impl<S: ShortGroupSignatureScheme> BlindCredentialRequest<S> {
         }
         let res = self
             .blind_signature_context
-            .verify(&known_messages, &issuer.signing_key, self.nonce)
+            .verify(&known_messages, &issuer.signer.signing_key, self.nonce)
             .map_err(|_| Error::InvalidSigningOperation)?;
         if !res {
             return Err(Error::InvalidSigningOperation);
@@ -1,15 +1,25 @@
+mod absent;
+mod custom_validator;
 mod data;
 mod enumeration;
+mod geolocation;
 mod hashed;
+mod nested;
+mod normalization;
 mod number;
 mod revocation;
 mod scalar;
 mod r#type;
 mod validator;
 
+pub use absent::*;
+pub use custom_validator::*;
 pub use data::*;
 pub use enumeration::*;
+pub use geolocation::*;
 pub use hashed::*;
+pub use nested::*;
+pub use normalization::*;
 pub use number::*;
 pub use r#type::*;
 pub use revocation::*;
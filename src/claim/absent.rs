@@ -0,0 +1,45 @@
+use super::{Claim, ClaimType};
+use crate::knox::Knox;
+use blsful::inner_types::Scalar;
+use core::fmt::{self, Display, Formatter};
+use serde::{Deserialize, Serialize};
+use sha3::Shake256;
+
+/// The domain separation tag for the absent claim value
+const ABSENT_CLAIM_DST: &[u8] = b"credx absent optional claim value";
+
+/// A placeholder for an optional claim that was not provided.
+///
+/// Every absent claim is signed as the same fixed scalar, which issuers
+/// refuse to sign for any real claim value.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct AbsentClaim;
+
+impl Display for AbsentClaim {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "AbsentClaim")
+    }
+}
+
+impl Claim for AbsentClaim {
+    type Value = ();
+
+    fn get_type(&self) -> ClaimType {
+        ClaimType::Unknown
+    }
+
+    fn to_scalar(&self) -> Scalar {
+        Self::scalar()
+    }
+
+    fn get_value(&self) -> Self::Value {}
+}
+
+impl AbsentClaim {
+    /// The scalar that is signed for every absent claim
+    pub fn scalar() -> Scalar {
+        let mut buffer = [0u8; 64];
+        Knox::xof_digest::<Shake256>(ABSENT_CLAIM_DST, &mut buffer);
+        Scalar::from_bytes_wide(&buffer)
+    }
+}
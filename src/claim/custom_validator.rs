@@ -0,0 +1,324 @@
+use crate::claim::ClaimData;
+use crate::error::Error;
+use crate::CredxResult;
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// The name of the Luhn check digit validator
+pub const LUHN_VALIDATOR: &str = "luhn";
+/// The name of the ISO 7064 MOD 97-10 check digit validator
+pub const ISO7064_MOD97_10_VALIDATOR: &str = "iso7064_mod97_10";
+/// The name of the IBAN validator
+pub const IBAN_VALIDATOR: &str = "iban";
+/// The name of the ISO 3166-1 alpha-2 country code validator
+pub const ISO3166_ALPHA2_VALIDATOR: &str = "iso3166_alpha2";
+/// The name of the date not in the future validator
+pub const DATE_NOT_IN_FUTURE_VALIDATOR: &str = "date_not_in_future";
+/// The name of the email address syntax validator
+pub const EMAIL_VALIDATOR: &str = "email";
+
+/// A claim validator that can be registered by name and referenced by
+/// [`super::ClaimValidator::Custom`] in a claim schema.
+///
+/// The same validators must be registered wherever the schema is used
+/// since only the name and parameters are serialized.
+pub trait CustomClaimValidator: Send + Sync {
+    /// [`Some(true)`] if the claim is the right type and meets the validator requirements
+    /// [`Some(false)`] if the claim is the right type but doesn't meet the requirements
+    /// [`None`] if the claim is the incorrect type
+    fn is_valid(&self, parameters: &[String], claim: &ClaimData) -> Option<bool>;
+}
+
+impl<F> CustomClaimValidator for F
+where
+    F: Fn(&[String], &ClaimData) -> Option<bool> + Send + Sync,
+{
+    fn is_valid(&self, parameters: &[String], claim: &ClaimData) -> Option<bool> {
+        self(parameters, claim)
+    }
+}
+
+type Registry = RwLock<HashMap<String, Arc<dyn CustomClaimValidator>>>;
+
+fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let mut validators: HashMap<String, Arc<dyn CustomClaimValidator>> = HashMap::new();
+        validators.insert(LUHN_VALIDATOR.to_string(), Arc::new(luhn));
+        validators.insert(
+            ISO7064_MOD97_10_VALIDATOR.to_string(),
+            Arc::new(iso7064_mod97_10),
+        );
+        validators.insert(IBAN_VALIDATOR.to_string(), Arc::new(iban));
+        validators.insert(
+            ISO3166_ALPHA2_VALIDATOR.to_string(),
+            Arc::new(iso3166_alpha2),
+        );
+        validators.insert(
+            DATE_NOT_IN_FUTURE_VALIDATOR.to_string(),
+            Arc::new(date_not_in_future),
+        );
+        validators.insert(EMAIL_VALIDATOR.to_string(), Arc::new(email));
+        RwLock::new(validators)
+    })
+}
+
+/// Register a custom claim validator with a name.
+/// Names can only be registered once, including the built-in names.
+pub fn register_claim_validator<V: CustomClaimValidator + 'static>(
+    name: &str,
+    validator: V,
+) -> CredxResult<()> {
+    let mut validators = registry()
+        .write()
+        .map_err(|_| Error::General("claim validator registry is poisoned"))?;
+    if validators.contains_key(name) {
+        return Err(Error::General("claim validator name is already registered"));
+    }
+    validators.insert(name.to_string(), Arc::new(validator));
+    Ok(())
+}
+
+/// Get the custom claim validator registered with `name`
+pub fn get_claim_validator(name: &str) -> Option<Arc<dyn CustomClaimValidator>> {
+    registry().read().ok()?.get(name).cloned()
+}
+
+fn text(claim: &ClaimData) -> Option<String> {
+    match claim {
+        ClaimData::Hashed(h) => String::from_utf8(h.value.clone()).ok(),
+        ClaimData::Revocation(r) => Some(r.value.clone()),
+        ClaimData::Number(n) => Some(n.value.to_string()),
+        _ => None,
+    }
+}
+
+/// The Luhn mod 10 check digit used by payment cards and IMEIs
+fn luhn(_: &[String], claim: &ClaimData) -> Option<bool> {
+    let value = text(claim)?;
+    if value.len() < 2 || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return Some(false);
+    }
+    let sum = value
+        .bytes()
+        .rev()
+        .enumerate()
+        .map(|(i, b)| {
+            let d = (b - b'0') as u32;
+            if i % 2 == 1 {
+                let d = d * 2;
+                if d > 9 {
+                    d - 9
+                } else {
+                    d
+                }
+            } else {
+                d
+            }
+        })
+        .sum::<u32>();
+    Some(sum % 10 == 0)
+}
+
+/// ISO 7064 MOD 97-10 over digits and letters where `A` = 10 to `Z` = 35
+fn mod97_10(value: &str) -> bool {
+    let mut remainder = 0u32;
+    for c in value.chars() {
+        let d = match c.to_digit(36) {
+            Some(d) => d,
+            None => return false,
+        };
+        remainder = if d > 9 {
+            (remainder * 100 + d) % 97
+        } else {
+            (remainder * 10 + d) % 97
+        };
+    }
+    remainder == 1
+}
+
+/// The ISO 7064 MOD 97-10 check digits used by LEIs
+fn iso7064_mod97_10(_: &[String], claim: &ClaimData) -> Option<bool> {
+    let value = text(claim)?;
+    Some(
+        value.len() > 2
+            && value
+                .chars()
+                .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase())
+            && mod97_10(&value),
+    )
+}
+
+/// International bank account numbers without spaces
+fn iban(_: &[String], claim: &ClaimData) -> Option<bool> {
+    let value = text(claim)?;
+    if !(15..=34).contains(&value.len())
+        || !value
+            .chars()
+            .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase())
+        || !value[..2].chars().all(|c| c.is_ascii_uppercase())
+        || !value[2..4].chars().all(|c| c.is_ascii_digit())
+    {
+        return Some(false);
+    }
+    let rearranged = format!("{}{}", &value[4..], &value[..4]);
+    Some(mod97_10(&rearranged))
+}
+
+const ISO3166_ALPHA2: [&str; 249] = [
+    "AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT", "AU", "AW", "AX", "AZ",
+    "BA", "BB", "BD", "BE", "BF", "BG", "BH", "BI", "BJ", "BL", "BM", "BN", "BO", "BQ", "BR", "BS",
+    "BT", "BV", "BW", "BY", "BZ", "CA", "CC", "CD", "CF", "CG", "CH", "CI", "CK", "CL", "CM", "CN",
+    "CO", "CR", "CU", "CV", "CW", "CX", "CY", "CZ", "DE", "DJ", "DK", "DM", "DO", "DZ", "EC", "EE",
+    "EG", "EH", "ER", "ES", "ET", "FI", "FJ", "FK", "FM", "FO", "FR", "GA", "GB", "GD", "GE", "GF",
+    "GG", "GH", "GI", "GL", "GM", "GN", "GP", "GQ", "GR", "GS", "GT", "GU", "GW", "GY", "HK", "HM",
+    "HN", "HR", "HT", "HU", "ID", "IE", "IL", "IM", "IN", "IO", "IQ", "IR", "IS", "IT", "JE", "JM",
+    "JO", "JP", "KE", "KG", "KH", "KI", "KM", "KN", "KP", "KR", "KW", "KY", "KZ", "LA", "LB", "LC",
+    "LI", "LK", "LR", "LS", "LT", "LU", "LV", "LY", "MA", "MC", "MD", "ME", "MF", "MG", "MH", "MK",
+    "ML", "MM", "MN", "MO", "MP", "MQ", "MR", "MS", "MT", "MU", "MV", "MW", "MX", "MY", "MZ", "NA",
+    "NC", "NE", "NF", "NG", "NI", "NL", "NO", "NP", "NR", "NU", "NZ", "OM", "PA", "PE", "PF", "PG",
+    "PH", "PK", "PL", "PM", "PN", "PR", "PS", "PT", "PW", "PY", "QA", "RE", "RO", "RS", "RU", "RW",
+    "SA", "SB", "SC", "SD", "SE", "SG", "SH", "SI", "SJ", "SK", "SL", "SM", "SN", "SO", "SR", "SS",
+    "ST", "SV", "SX", "SY", "SZ", "TC", "TD", "TF", "TG", "TH", "TJ", "TK", "TL", "TM", "TN", "TO",
+    "TR", "TT", "TV", "TW", "TZ", "UA", "UG", "UM", "US", "UY", "UZ", "VA", "VC", "VE", "VG", "VI",
+    "VN", "VU", "WF", "WS", "YE", "YT", "ZA", "ZM", "ZW",
+];
+
+/// Officially assigned ISO 3166-1 alpha-2 country codes in upper case
+fn iso3166_alpha2(_: &[String], claim: &ClaimData) -> Option<bool> {
+    match claim {
+        ClaimData::Hashed(_) | ClaimData::Revocation(_) => {
+            let value = text(claim)?;
+            Some(ISO3166_ALPHA2.binary_search(&value.as_str()).is_ok())
+        }
+        _ => None,
+    }
+}
+
+/// Number claims that are not after the current UTC date.
+///
+/// The parameter `date` (the default) treats the value as `YYYYMMDD`
+/// like [`super::NumberClaim::parse_rfc3339_date`] and `datetime`
+/// as seconds since the Unix epoch.
+fn date_not_in_future(parameters: &[String], claim: &ClaimData) -> Option<bool> {
+    let value = match claim {
+        ClaimData::Number(n) => n.value as i64,
+        _ => return None,
+    };
+    let now = chrono::Utc::now();
+    match parameters.first().map(|p| p.as_str()) {
+        None | Some("date") => {
+            let today = now.format("%Y%m%d").to_string().parse::<i64>().ok()?;
+            Some(value <= today)
+        }
+        Some("datetime") => Some(value <= now.timestamp()),
+        Some(_) => Some(false),
+    }
+}
+
+/// A practical subset of RFC 5322 addresses, `local@domain.tld`
+fn email(_: &[String], claim: &ClaimData) -> Option<bool> {
+    static EMAIL: OnceLock<Regex> = OnceLock::new();
+    let rx = EMAIL.get_or_init(|| {
+        Regex::new(
+            r"^[A-Za-z0-9.!#$%&'*+/=?^_`{|}~-]+@[A-Za-z0-9](?:[A-Za-z0-9-]{0,61}[A-Za-z0-9])?(?:\.[A-Za-z0-9](?:[A-Za-z0-9-]{0,61}[A-Za-z0-9])?)+$",
+        )
+        .expect("email regex is valid")
+    });
+    match claim {
+        ClaimData::Hashed(_) | ClaimData::Revocation(_) => {
+            let value = text(claim)?;
+            Some(value.len() <= 254 && rx.is_match(&value))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claim::{HashedClaim, NumberClaim};
+
+    fn check(name: &str, claim: ClaimData) -> Option<bool> {
+        get_claim_validator(name).unwrap().is_valid(&[], &claim)
+    }
+
+    #[test]
+    fn built_in_validators() {
+        assert_eq!(
+            check(LUHN_VALIDATOR, HashedClaim::from("4111111111111111").into()),
+            Some(true)
+        );
+        assert_eq!(
+            check(LUHN_VALIDATOR, HashedClaim::from("4111111111111112").into()),
+            Some(false)
+        );
+        assert_eq!(
+            check(LUHN_VALIDATOR, NumberClaim::from(79927398713i64).into()),
+            Some(true)
+        );
+        assert_eq!(
+            check(
+                ISO7064_MOD97_10_VALIDATOR,
+                HashedClaim::from("5493001KJTIIGC8Y1R12").into()
+            ),
+            Some(true)
+        );
+        assert_eq!(
+            check(
+                IBAN_VALIDATOR,
+                HashedClaim::from("GB82WEST12345698765432").into()
+            ),
+            Some(true)
+        );
+        assert_eq!(
+            check(
+                IBAN_VALIDATOR,
+                HashedClaim::from("GB82WEST12345698765433").into()
+            ),
+            Some(false)
+        );
+        assert!(ISO3166_ALPHA2.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(
+            check(ISO3166_ALPHA2_VALIDATOR, HashedClaim::from("CI").into()),
+            Some(true)
+        );
+        assert_eq!(
+            check(ISO3166_ALPHA2_VALIDATOR, HashedClaim::from("XX").into()),
+            Some(false)
+        );
+        assert_eq!(
+            check(ISO3166_ALPHA2_VALIDATOR, NumberClaim::from(840).into()),
+            None
+        );
+        assert_eq!(
+            check(
+                DATE_NOT_IN_FUTURE_VALIDATOR,
+                NumberClaim::from(19821231).into()
+            ),
+            Some(true)
+        );
+        assert_eq!(
+            check(
+                DATE_NOT_IN_FUTURE_VALIDATOR,
+                NumberClaim::from(99991231).into()
+            ),
+            Some(false)
+        );
+        assert_eq!(
+            check(
+                EMAIL_VALIDATOR,
+                HashedClaim::from("jane.doe+vc@example.co.uk").into()
+            ),
+            Some(true)
+        );
+        assert_eq!(
+            check(
+                EMAIL_VALIDATOR,
+                HashedClaim::from("jane.doe@localhost").into()
+            ),
+            Some(false)
+        );
+    }
+}
@@ -15,6 +15,8 @@ pub const NUMBER: &str = "num:";
 pub const REVOCATION: &str = "rev:";
 /// Enumeration
 pub const ENUMERATION: &str = "enm:";
+/// Absent optional claim
+pub const ABSENT: &str = "abs:";
 
 /// The type of claim data that can be signed
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize, Hash)]
@@ -29,6 +31,8 @@ pub enum ClaimData {
     Revocation(RevocationClaim),
     /// Data is from a list of unique values
     Enumeration(EnumerationClaim),
+    /// Data is an optional claim that was not provided
+    Absent(AbsentClaim),
 }
 
 impl From<HashedClaim> for ClaimData {
@@ -61,6 +65,12 @@ impl From<EnumerationClaim> for ClaimData {
     }
 }
 
+impl From<AbsentClaim> for ClaimData {
+    fn from(c: AbsentClaim) -> Self {
+        Self::Absent(c)
+    }
+}
+
 impl ClaimData {
     /// Get the scalar to be signed
     pub fn to_scalar(&self) -> Scalar {
@@ -70,6 +80,7 @@ impl ClaimData {
             Self::Scalar(s) => s.to_scalar(),
             Self::Revocation(r) => r.to_scalar(),
             Self::Enumeration(e) => e.to_scalar(),
+            Self::Absent(a) => a.to_scalar(),
         }
     }
 
@@ -81,6 +92,7 @@ impl ClaimData {
             Self::Scalar(s) => s.value.to_be_bytes().to_vec(),
             Self::Revocation(r) => r.value.as_bytes().to_vec(),
             Self::Enumeration(e) => vec![e.value],
+            Self::Absent(_) => Vec::new(),
         }
     }
 
@@ -138,6 +150,11 @@ impl ClaimData {
         }
     }
 
+    /// [`true`] if this is the placeholder for an absent optional claim
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Self::Absent(_))
+    }
+
     /// [`true`] if the claim is the right type
     /// [`false`] if the claim is the incorrect type
     pub fn is_type(&self, claim_type: ClaimType) -> bool {
@@ -184,6 +201,9 @@ impl ClaimData {
                 let data = serde_bare::to_vec(&e).unwrap();
                 s.push_str(&hex::encode(data.as_slice()))
             }
+            ClaimData::Absent(_) => {
+                s.push_str(ABSENT);
+            }
         }
         s
     }
@@ -233,6 +253,7 @@ impl ClaimData {
                     })?;
                 Ok(ClaimData::Enumeration(e))
             }
+            ABSENT => Ok(ClaimData::Absent(AbsentClaim)),
             _ => Err(Error::InvalidClaimData("unknown claim type")),
         }
     }
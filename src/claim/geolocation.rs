@@ -0,0 +1,152 @@
+use super::{ClaimData, NestedClaimData, NumberClaim};
+use crate::{error::Error, CredxResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The number of fixed-point units per degree, about 1.1cm at the equator
+pub const GEOLOCATION_SCALE: isize = 10_000_000;
+/// The nested claim label for the latitude
+pub const LATITUDE_LABEL: &str = "latitude";
+/// The nested claim label for the longitude
+pub const LONGITUDE_LABEL: &str = "longitude";
+/// The minimum latitude in fixed-point units
+pub const MIN_LATITUDE: isize = -90 * GEOLOCATION_SCALE;
+/// The maximum latitude in fixed-point units
+pub const MAX_LATITUDE: isize = 90 * GEOLOCATION_SCALE;
+/// The minimum longitude in fixed-point units
+pub const MIN_LONGITUDE: isize = -180 * GEOLOCATION_SCALE;
+/// The maximum longitude in fixed-point units
+pub const MAX_LONGITUDE: isize = 180 * GEOLOCATION_SCALE;
+
+/// A geographic location signed as two number claims,
+/// the latitude then the longitude, in fixed-point units of
+/// 1 / [`GEOLOCATION_SCALE`] degrees.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct GeolocationClaim {
+    /// The latitude in fixed-point units
+    pub latitude: isize,
+    /// The longitude in fixed-point units
+    pub longitude: isize,
+}
+
+impl GeolocationClaim {
+    /// Create a new geolocation from fixed-point units
+    pub fn new(latitude: isize, longitude: isize) -> CredxResult<Self> {
+        if !(MIN_LATITUDE..=MAX_LATITUDE).contains(&latitude) {
+            return Err(Error::InvalidClaimData("latitude is out of range"));
+        }
+        if !(MIN_LONGITUDE..=MAX_LONGITUDE).contains(&longitude) {
+            return Err(Error::InvalidClaimData("longitude is out of range"));
+        }
+        Ok(Self {
+            latitude,
+            longitude,
+        })
+    }
+
+    /// Create a new geolocation from decimal degrees.
+    /// The values are rounded to the nearest fixed-point unit.
+    pub fn from_degrees(latitude: f64, longitude: f64) -> CredxResult<Self> {
+        if !latitude.is_finite() || !longitude.is_finite() {
+            return Err(Error::InvalidClaimData(
+                "geolocation is not a finite number",
+            ));
+        }
+        let scale = GEOLOCATION_SCALE as f64;
+        Self::new(
+            (latitude * scale).round() as isize,
+            (longitude * scale).round() as isize,
+        )
+    }
+
+    /// The latitude in decimal degrees
+    pub fn latitude_degrees(&self) -> f64 {
+        self.latitude as f64 / GEOLOCATION_SCALE as f64
+    }
+
+    /// The longitude in decimal degrees
+    pub fn longitude_degrees(&self) -> f64 {
+        self.longitude as f64 / GEOLOCATION_SCALE as f64
+    }
+
+    /// The latitude and longitude claims in signing order
+    pub fn to_claims(&self) -> [ClaimData; 2] {
+        [
+            NumberClaim::from(self.latitude).into(),
+            NumberClaim::from(self.longitude).into(),
+        ]
+    }
+
+    /// Read the geolocation from the signed latitude and longitude claims
+    pub fn from_claims(latitude: &ClaimData, longitude: &ClaimData) -> CredxResult<Self> {
+        match (latitude, longitude) {
+            (ClaimData::Number(latitude), ClaimData::Number(longitude)) => {
+                Self::new(latitude.value, longitude.value)
+            }
+            (_, _) => Err(Error::InvalidClaimData(
+                "geolocation claims must be number claims",
+            )),
+        }
+    }
+}
+
+impl From<GeolocationClaim> for NestedClaimData {
+    fn from(g: GeolocationClaim) -> Self {
+        let [latitude, longitude] = g.to_claims();
+        let mut object = BTreeMap::new();
+        object.insert(LATITUDE_LABEL.to_string(), latitude.into());
+        object.insert(LONGITUDE_LABEL.to_string(), longitude.into());
+        Self::Object(object)
+    }
+}
+
+/// An inclusive box of latitudes and longitudes in fixed-point units.
+///
+/// Boxes cannot cross the antimeridian, split these into two boxes instead.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct GeoBoundingBox {
+    /// The southern most latitude
+    pub south: isize,
+    /// The western most longitude
+    pub west: isize,
+    /// The northern most latitude
+    pub north: isize,
+    /// The eastern most longitude
+    pub east: isize,
+}
+
+impl GeoBoundingBox {
+    /// Create a new bounding box from the south west and north east corners
+    pub fn new(south_west: GeolocationClaim, north_east: GeolocationClaim) -> CredxResult<Self> {
+        if south_west.latitude > north_east.latitude {
+            return Err(Error::InvalidClaimData(
+                "bounding box south is greater than north",
+            ));
+        }
+        if south_west.longitude > north_east.longitude {
+            return Err(Error::InvalidClaimData(
+                "bounding box west is greater than east",
+            ));
+        }
+        Ok(Self {
+            south: south_west.latitude,
+            west: south_west.longitude,
+            north: north_east.latitude,
+            east: north_east.longitude,
+        })
+    }
+
+    /// Create a new bounding box from decimal degrees
+    pub fn from_degrees(south: f64, west: f64, north: f64, east: f64) -> CredxResult<Self> {
+        Self::new(
+            GeolocationClaim::from_degrees(south, west)?,
+            GeolocationClaim::from_degrees(north, east)?,
+        )
+    }
+
+    /// [`true`] if the point lies within or on the edge of this box
+    pub fn contains(&self, point: &GeolocationClaim) -> bool {
+        (self.south..=self.north).contains(&point.latitude)
+            && (self.west..=self.east).contains(&point.longitude)
+    }
+}
@@ -0,0 +1,184 @@
+use super::ClaimData;
+use crate::{error::Error, CredxResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The separator between the segments of a claim path
+pub const CLAIM_PATH_SEPARATOR: char = '.';
+/// The optional JSON-path root prefix for a claim path
+pub const CLAIM_PATH_ROOT: &str = "$.";
+
+/// Strip the optional JSON-path root `$.` from a claim path
+pub fn normalize_claim_path(path: &str) -> &str {
+    path.strip_prefix(CLAIM_PATH_ROOT).unwrap_or(path)
+}
+
+/// Append `label` to the `parent` claim path
+pub fn join_claim_path(parent: &str, label: &str) -> String {
+    let parent = normalize_claim_path(parent);
+    if parent.is_empty() {
+        label.to_string()
+    } else {
+        format!("{}{}{}", parent, CLAIM_PATH_SEPARATOR, label)
+    }
+}
+
+/// [`true`] if `path` is equal to `prefix` or is a descendant of `prefix`
+pub fn claim_path_starts_with(path: &str, prefix: &str) -> bool {
+    let path = normalize_claim_path(path);
+    let prefix = normalize_claim_path(prefix);
+    match path.strip_prefix(prefix) {
+        None => false,
+        Some(rest) => rest.is_empty() || rest.starts_with(CLAIM_PATH_SEPARATOR),
+    }
+}
+
+/// Claim data structured as nested objects like a JSON credential subject
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub enum NestedClaimData {
+    /// A single claim value
+    Claim(ClaimData),
+    /// An object of named claim values
+    Object(BTreeMap<String, NestedClaimData>),
+}
+
+impl From<ClaimData> for NestedClaimData {
+    fn from(c: ClaimData) -> Self {
+        Self::Claim(c)
+    }
+}
+
+impl From<BTreeMap<String, NestedClaimData>> for NestedClaimData {
+    fn from(m: BTreeMap<String, NestedClaimData>) -> Self {
+        Self::Object(m)
+    }
+}
+
+impl NestedClaimData {
+    /// Flatten the nested claims into claim path to claim data pairs
+    pub fn flatten(&self) -> CredxResult<BTreeMap<String, ClaimData>> {
+        let mut claims = BTreeMap::new();
+        self.flatten_into("", &mut claims)?;
+        Ok(claims)
+    }
+
+    fn flatten_into(
+        &self,
+        path: &str,
+        claims: &mut BTreeMap<String, ClaimData>,
+    ) -> CredxResult<()> {
+        match self {
+            Self::Claim(c) => {
+                if path.is_empty() {
+                    return Err(Error::InvalidClaimData("nested claim data has no label"));
+                }
+                claims.insert(path.to_string(), c.clone());
+            }
+            Self::Object(m) => {
+                for (label, value) in m {
+                    if label.is_empty() || label.contains(CLAIM_PATH_SEPARATOR) {
+                        return Err(Error::InvalidClaimData(
+                            "nested claim label cannot be empty or contain the path separator",
+                        ));
+                    }
+                    value.flatten_into(&join_claim_path(path, label), claims)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Reassemble nested claims from claim path to claim data pairs
+    pub fn from_paths<'a, I>(claims: I) -> CredxResult<Self>
+    where
+        I: IntoIterator<Item = (&'a String, &'a ClaimData)>,
+    {
+        let mut root = BTreeMap::new();
+        for (path, claim) in claims {
+            let mut segments = normalize_claim_path(path)
+                .split(CLAIM_PATH_SEPARATOR)
+                .peekable();
+            let mut current = &mut root;
+            while let Some(segment) = segments.next() {
+                if segment.is_empty() {
+                    return Err(Error::InvalidClaimData("claim path has an empty segment"));
+                }
+                if segments.peek().is_none() {
+                    if current
+                        .insert(segment.to_string(), Self::Claim(claim.clone()))
+                        .is_some()
+                    {
+                        return Err(Error::InvalidClaimData("conflicting claim paths"));
+                    }
+                    break;
+                }
+                let entry = current
+                    .entry(segment.to_string())
+                    .or_insert_with(|| Self::Object(BTreeMap::new()));
+                current = match entry {
+                    Self::Object(m) => m,
+                    Self::Claim(_) => {
+                        return Err(Error::InvalidClaimData("conflicting claim paths"))
+                    }
+                };
+            }
+        }
+        Ok(Self::Object(root))
+    }
+
+    /// Get the claim or object at `path`
+    pub fn get(&self, path: &str) -> Option<&Self> {
+        let path = normalize_claim_path(path);
+        if path.is_empty() {
+            return Some(self);
+        }
+        let mut current = self;
+        for segment in path.split(CLAIM_PATH_SEPARATOR) {
+            match current {
+                Self::Object(m) => current = m.get(segment)?,
+                Self::Claim(_) => return None,
+            }
+        }
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claim::{HashedClaim, NumberClaim};
+    use maplit::btreemap;
+
+    #[test]
+    fn flatten_and_reassemble() {
+        let nested = NestedClaimData::Object(btreemap! {
+            "name".to_string() => ClaimData::from(HashedClaim::from("John Doe")).into(),
+            "address".to_string() => NestedClaimData::Object(btreemap! {
+                "street".to_string() => ClaimData::from(HashedClaim::from("42 Wallaby Way")).into(),
+                "postal_code".to_string() => ClaimData::from(NumberClaim::from(2000)).into(),
+            }),
+        });
+        let flat = nested.flatten().unwrap();
+        assert_eq!(
+            flat.keys().cloned().collect::<Vec<_>>(),
+            vec!["address.postal_code", "address.street", "name"]
+        );
+        let reassembled = NestedClaimData::from_paths(flat.iter()).unwrap();
+        assert_eq!(nested, reassembled);
+        assert_eq!(
+            nested.get("$.address.postal_code"),
+            Some(&NestedClaimData::Claim(NumberClaim::from(2000).into()))
+        );
+    }
+
+    #[test]
+    fn conflicting_paths() {
+        let a = "a".to_string();
+        let ab = "a.b".to_string();
+        let claim = ClaimData::from(NumberClaim::from(1));
+        assert!(NestedClaimData::from_paths([(&a, &claim), (&ab, &claim)]).is_err());
+        assert!(NestedClaimData::from_paths([(&ab, &claim), (&a, &claim)]).is_err());
+        assert!(claim_path_starts_with("$.a.b", "a"));
+        assert!(!claim_path_starts_with("ab", "a"));
+    }
+}
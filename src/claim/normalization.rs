@@ -0,0 +1,112 @@
+use super::{ClaimData, HashedClaim};
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization as _;
+
+/// The unicode normalization forms for hashed claims
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[repr(u8)]
+pub enum UnicodeNormalization {
+    /// Canonical decomposition followed by canonical composition
+    Nfc = 1,
+    /// Compatibility decomposition followed by canonical composition
+    Nfkc = 2,
+}
+
+/// How to normalize hashed claims before they are validated and signed
+/// so equivalent text always maps to the same scalar
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ClaimNormalization {
+    /// The unicode normalization form to apply
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub unicode: Option<UnicodeNormalization>,
+    /// Apply unicode default case folding
+    #[serde(default)]
+    pub case_fold: bool,
+    /// Remove leading and trailing whitespace
+    #[serde(default)]
+    pub trim_whitespace: bool,
+}
+
+impl ClaimNormalization {
+    /// Normalize the string.
+    ///
+    /// Whitespace is trimmed first, then case folded, then unicode normalized
+    /// since case folding can produce unnormalized output.
+    pub fn normalize_str(&self, value: &str) -> String {
+        let mut value = if self.trim_whitespace {
+            value.trim().to_string()
+        } else {
+            value.to_string()
+        };
+        if self.case_fold {
+            value = caseless::default_case_fold_str(&value);
+        }
+        match self.unicode {
+            None => value,
+            Some(UnicodeNormalization::Nfc) => value.nfc().collect(),
+            Some(UnicodeNormalization::Nfkc) => value.nfkc().collect(),
+        }
+    }
+
+    /// Normalize the claim. Only hashed claims that are valid UTF-8 are changed
+    pub fn normalize(&self, claim: &ClaimData) -> ClaimData {
+        match claim {
+            ClaimData::Hashed(h) => match std::str::from_utf8(&h.value) {
+                Ok(s) => ClaimData::Hashed(HashedClaim {
+                    value: self.normalize_str(s).into_bytes(),
+                    print_friendly: h.print_friendly,
+                }),
+                Err(_) => claim.clone(),
+            },
+            _ => claim.clone(),
+        }
+    }
+
+    /// Add the public data to the transcript
+    pub fn add_challenge_contribution(&self, transcript: &mut merlin::Transcript) {
+        transcript.append_message(
+            b"claim normalization unicode",
+            &[self.unicode.map_or(0u8, |u| u as u8)],
+        );
+        transcript.append_message(
+            b"claim normalization case fold",
+            &[u8::from(self.case_fold)],
+        );
+        transcript.append_message(
+            b"claim normalization trim whitespace",
+            &[u8::from(self.trim_whitespace)],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equivalent_text_normalizes_the_same() {
+        let normalization = ClaimNormalization {
+            unicode: Some(UnicodeNormalization::Nfc),
+            case_fold: true,
+            trim_whitespace: true,
+        };
+        let nfc = ClaimData::from(HashedClaim::from("José"));
+        let nfd = ClaimData::from(HashedClaim::from(" JOSE\u{301}\t"));
+        assert_ne!(nfc.to_scalar(), nfd.to_scalar());
+        assert_eq!(
+            normalization.normalize(&nfc).to_scalar(),
+            normalization.normalize(&nfd).to_scalar()
+        );
+        assert_eq!(normalization.normalize_str("Straße"), "strasse");
+
+        let nfkc = ClaimNormalization {
+            unicode: Some(UnicodeNormalization::Nfkc),
+            ..Default::default()
+        };
+        assert_eq!(nfkc.normalize_str("ﬁle"), "file");
+        assert_eq!(
+            ClaimNormalization::default().normalize_str(" ﬁle "),
+            " ﬁle "
+        );
+    }
+}
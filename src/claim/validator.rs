@@ -1,4 +1,4 @@
-use crate::claim::ClaimData;
+use crate::claim::{get_claim_validator, ClaimData};
 use crate::error::Error;
 use crate::CredxResult;
 use regex::Regex;
@@ -19,6 +19,8 @@ pub enum ClaimValidatorType {
     Regex = 3,
     /// The any one list type
     AnyOne = 4,
+    /// The registered custom type
+    Custom = 5,
 }
 
 impl From<u8> for ClaimValidatorType {
@@ -28,6 +30,7 @@ impl From<u8> for ClaimValidatorType {
             2 => Self::Range,
             3 => Self::Regex,
             4 => Self::AnyOne,
+            5 => Self::Custom,
             _ => Self::Unknown,
         }
     }
@@ -63,6 +66,15 @@ pub enum ClaimValidator {
     Regex(Regex),
     /// The claim data must be one of these
     AnyOne(Vec<ClaimData>),
+    /// The claim data must pass the validator registered with `name`.
+    /// An unregistered validator is never satisfied.
+    Custom {
+        /// The registered validator name
+        name: String,
+        /// The validator specific parameters
+        #[serde(skip_serializing_if = "Vec::is_empty", default)]
+        parameters: Vec<String>,
+    },
 }
 
 impl ClaimValidator {
@@ -103,6 +115,10 @@ impl ClaimValidator {
                 _ => None,
             },
             Self::AnyOne(claims) => Some(claims.iter().any(|c| c == claim)),
+            Self::Custom { name, parameters } => match get_claim_validator(name) {
+                Some(v) => v.is_valid(parameters, claim),
+                None => Some(false),
+            },
         }
     }
 
@@ -158,6 +174,18 @@ impl ClaimValidator {
                     );
                 }
             }
+            Self::Custom { name, parameters } => {
+                transcript
+                    .append_message(b"claim validator type", &[ClaimValidatorType::Custom as u8]);
+                transcript.append_message(b"claim validator custom name", name.as_bytes());
+                transcript.append_message(
+                    b"claim validator custom parameters length",
+                    &Uint::from(parameters.len()).to_vec(),
+                );
+                for p in parameters {
+                    transcript.append_message(b"claim validator custom parameter", p.as_bytes());
+                }
+            }
         }
     }
 
@@ -166,4 +194,12 @@ impl ClaimValidator {
         let rx = Regex::new(regex).map_err(|_| Error::General("invalid regex"))?;
         Ok(Self::Regex(rx))
     }
+
+    /// A registered custom validator without parameters
+    pub fn custom(name: &str) -> Self {
+        Self::Custom {
+            name: name.to_string(),
+            parameters: Vec::new(),
+        }
+    }
 }
@@ -1,11 +1,15 @@
 mod bundle;
 mod membership;
 mod offer;
+mod request;
+mod response;
 mod schema;
 
 pub use bundle::*;
 pub use membership::*;
 pub use offer::*;
+pub use request::*;
+pub use response::*;
 pub use schema::*;
 
 use super::claim::*;
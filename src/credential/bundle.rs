@@ -1,6 +1,7 @@
-use crate::knox::accumulator::vb20::Accumulator;
+use crate::error::Error;
+use crate::knox::accumulator::vb20::{Accumulator, Element};
 use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
-use crate::{credential::*, issuer::*};
+use crate::{credential::*, issuer::*, CredxResult};
 use serde::{Deserialize, Serialize};
 
 /// A credential and the issuer's information
@@ -30,4 +31,78 @@ impl<S: ShortGroupSignatureScheme> CredentialBundle<S> {
         self.credential.revocation_handle = revocation_handle;
         self.issuer.revocation_registry = revocation_registry;
     }
+
+    /// Check the credential is usable with the issuer information.
+    ///
+    /// The claims must match the schema and its validators, the signature
+    /// must be from one of the issuer's accepted keys, and the revocation
+    /// handle must verify against the issuer's revocation registry.
+    ///
+    /// The issuer's key proof isn't checked here, check it once with
+    /// [`IssuerPublic::verify_key_proof`](crate::issuer::IssuerPublic::verify_key_proof)
+    /// before trusting a new issuer.
+    pub fn verify(&self) -> CredxResult<()> {
+        let (claims, revocation_index) =
+            check_claims(&self.issuer.schema, &self.credential.claims)?;
+        if claims != self.credential.claims {
+            return Err(Error::InvalidClaimData("claim is not normalized"));
+        }
+        if revocation_index != self.credential.revocation_index {
+            return Err(Error::InvalidCredential(
+                "revocation index doesn't match the schema",
+            ));
+        }
+        self.issuer
+            .key_version_of(&self.credential)
+            .map_err(|_| Error::InvalidCredential("signature is not valid for the issuer"))?;
+        if !self.credential.revocation_handle.verify(
+            Element(claims[revocation_index].to_scalar()),
+            self.issuer.revocation_verifying_key,
+            self.issuer.revocation_registry,
+        ) {
+            return Err(Error::InvalidCredential(
+                "revocation handle is not valid for the revocation registry",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A credential that refers to its issuer by key id.
+///
+/// Resolve it with an [`IssuerKeyRegistry`] to get the [`CredentialBundle`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CompactCredentialBundle<S: ShortGroupSignatureScheme> {
+    /// The key id of the issuer that gave this credential
+    pub issuer_key_id: IssuerKeyId,
+    /// The issuer's revocation registry for the revocation handle
+    pub revocation_registry: Accumulator,
+    /// The signed credential
+    #[serde(bound(
+        serialize = "Credential<S>: Serialize",
+        deserialize = "Credential<S>: Deserialize<'de>"
+    ))]
+    pub credential: Credential<S>,
+}
+
+impl<S: ShortGroupSignatureScheme> From<&CredentialBundle<S>> for CompactCredentialBundle<S> {
+    fn from(bundle: &CredentialBundle<S>) -> Self {
+        Self {
+            issuer_key_id: bundle.issuer.key_id(),
+            revocation_registry: bundle.issuer.revocation_registry,
+            credential: bundle.credential.clone(),
+        }
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> CompactCredentialBundle<S> {
+    /// Look up the issuer and return the full bundle
+    pub fn resolve(&self, registry: &IssuerKeyRegistry<S>) -> CredxResult<CredentialBundle<S>> {
+        let mut issuer = registry.get(&self.issuer_key_id)?.clone();
+        issuer.revocation_registry = self.revocation_registry;
+        Ok(CredentialBundle {
+            issuer,
+            credential: self.credential.clone(),
+        })
+    }
 }
@@ -1,12 +1,23 @@
 use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
-use crate::{claim::ClaimData, issuer::IssuerPublic};
+use crate::knox::short_group_sig_core::BlindSignatureFormat;
+use crate::{
+    claim::{AbsentClaim, ClaimData},
+    error::Error,
+    issuer::IssuerPublic,
+    presentation::PresentationSchema,
+    statement::Statements,
+    CredxResult,
+};
+use blsful::inner_types::{Field, Scalar};
 use rand_core::RngCore;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 
 /// A credential offer from the issuer to the holder
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct CredentialOffer<S: ShortGroupSignatureScheme> {
-    /// The claims to be signed
+    /// The claims to be signed in schema order.
+    /// Claims in `blind_claim_labels` are supplied by the holder and omitted.
     pub claims: Vec<ClaimData>,
     /// The issuer's id
     #[serde(bound(
@@ -16,17 +27,135 @@ pub struct CredentialOffer<S: ShortGroupSignatureScheme> {
     pub issuer: IssuerPublic<S>,
     /// The credential offer id
     pub offer_id: [u8; 16],
+    /// The nonce the holder's blind signing request must use
+    pub nonce: Scalar,
+    /// The labels of the claims the holder blinds
+    #[serde(default)]
+    pub blind_claim_labels: Vec<String>,
+    /// The presentation the holder must include to prove predicates about
+    /// the blind claims
+    #[serde(
+        default = "Option::default",
+        bound(
+            serialize = "PresentationSchema<S>: Serialize",
+            deserialize = "PresentationSchema<S>: Deserialize<'de>"
+        )
+    )]
+    pub presentation_schema: Option<PresentationSchema<S>>,
+    /// The format of the commitment proof in the holder's blind signing request
+    #[serde(default)]
+    pub blind_signature_format: BlindSignatureFormat,
 }
 
 impl<S: ShortGroupSignatureScheme> CredentialOffer<S> {
     /// Create a new offer
     pub fn new(claims: &[ClaimData], issuer: IssuerPublic<S>) -> Self {
+        let mut rng = rand::thread_rng();
         let mut offer_id = [0u8; 16];
-        rand::thread_rng().fill_bytes(&mut offer_id);
+        rng.fill_bytes(&mut offer_id);
         Self {
             claims: claims.to_vec(),
             issuer,
             offer_id,
+            nonce: Scalar::random(&mut rng),
+            blind_claim_labels: Vec::new(),
+            presentation_schema: None,
+            blind_signature_format: BlindSignatureFormat::default(),
         }
     }
+
+    /// Create a new offer where the holder blindly supplies the claims in
+    /// `blind_claim_labels` like a link secret.
+    ///
+    /// Optional claims that are neither in `claims` nor blinded are signed as absent.
+    pub fn new_blind(
+        claims: &BTreeMap<String, ClaimData>,
+        blind_claim_labels: &[&str],
+        issuer: IssuerPublic<S>,
+    ) -> CredxResult<Self> {
+        for label in blind_claim_labels {
+            if !issuer.schema.blind_claims.contains(*label) {
+                return Err(Error::InvalidClaimData("claim is not blindable"));
+            }
+            if claims.contains_key(*label) {
+                return Err(Error::InvalidClaimData("duplicate claim detected"));
+            }
+        }
+        if claims
+            .keys()
+            .any(|label| !issuer.schema.claim_indices.contains(label))
+        {
+            return Err(Error::InvalidClaimData("claim not found in schema"));
+        }
+        let mut known = Vec::with_capacity(claims.len());
+        for c in &issuer.schema.claims {
+            if blind_claim_labels.contains(&c.label.as_str()) {
+                continue;
+            }
+            match claims.get(&c.label) {
+                Some(claim) => known.push(claim.clone()),
+                None if c.optional => known.push(AbsentClaim.into()),
+                None => return Err(Error::InvalidClaimData("claim missing")),
+            }
+        }
+        let mut offer = Self::new(&known, issuer);
+        offer.blind_claim_labels = blind_claim_labels.iter().map(|l| l.to_string()).collect();
+        Ok(offer)
+    }
+
+    /// Require the holder to prove the statements in `schema` about the blind claims.
+    ///
+    /// `schema` must contain a blind claims statement for this issuer and
+    /// the offered blind claims.
+    pub fn with_presentation_schema(mut self, schema: PresentationSchema<S>) -> CredxResult<Self> {
+        let labels = self.blind_claim_labels.iter().collect::<BTreeSet<_>>();
+        let mut found = false;
+        for statement in schema.statements.values() {
+            if let Statements::BlindClaims(bs) = statement {
+                if bs.issuer.id != self.issuer.id
+                    || bs.claims.iter().collect::<BTreeSet<_>>() != labels
+                {
+                    return Err(Error::InvalidPresentationData(format!(
+                        "blind claims statement '{}' doesn't match the offer",
+                        bs.id
+                    )));
+                }
+                found = true;
+            }
+        }
+        if !found {
+            return Err(Error::InvalidPresentationData(
+                "presentation schema doesn't have a blind claims statement".to_string(),
+            ));
+        }
+        self.presentation_schema = Some(schema);
+        Ok(self)
+    }
+
+    /// Require the holder's blind signing request to use `format`
+    pub fn with_blind_signature_format(mut self, format: BlindSignatureFormat) -> Self {
+        self.blind_signature_format = format;
+        self
+    }
+
+    /// The offered claims keyed by label
+    pub fn known_claims(&self) -> CredxResult<BTreeMap<String, ClaimData>> {
+        let labels = self
+            .issuer
+            .schema
+            .claims
+            .iter()
+            .filter(|c| !self.blind_claim_labels.contains(&c.label))
+            .map(|c| c.label.clone())
+            .collect::<Vec<_>>();
+        if labels.len() != self.claims.len() {
+            return Err(Error::InvalidClaimData(
+                "offer claims don't match the schema",
+            ));
+        }
+        Ok(labels
+            .into_iter()
+            .zip(self.claims.iter().cloned())
+            .collect())
+    }
 }
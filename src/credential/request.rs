@@ -0,0 +1,106 @@
+use crate::blind::BlindCredentialRequest;
+use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use crate::{
+    claim::ClaimData, credential::CredentialOffer, error::Error,
+    presentation::PresentationCredential, CredxResult,
+};
+use blsful::inner_types::Scalar;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The holder's answer to a [`CredentialOffer`]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CredentialRequest<S: ShortGroupSignatureScheme> {
+    /// The id of the offer being answered
+    pub offer_id: [u8; 16],
+    /// The blind signing request with the proof of knowledge of the blind claims
+    /// bound to the offer nonce, if the offer has blind claims
+    #[serde(bound(
+        serialize = "BlindCredentialRequest<S>: Serialize",
+        deserialize = "BlindCredentialRequest<S>: Deserialize<'de>"
+    ))]
+    pub blind_request: Option<BlindCredentialRequest<S>>,
+}
+
+impl<S: ShortGroupSignatureScheme> CredentialRequest<S> {
+    /// Answer `offer` with the holder's `blind_claims`.
+    ///
+    /// The returned state is kept by the holder to finish the credential
+    /// when the issuer responds.
+    pub fn new(
+        offer: &CredentialOffer<S>,
+        blind_claims: &BTreeMap<String, ClaimData>,
+    ) -> CredxResult<(Self, CredentialRequestState<S>)> {
+        Self::new_with_credentials(offer, blind_claims, &IndexMap::new())
+    }
+
+    /// Answer `offer` with the holder's `blind_claims` and prove the offer's
+    /// presentation schema using the holder's existing `credentials`.
+    pub fn new_with_credentials(
+        offer: &CredentialOffer<S>,
+        blind_claims: &BTreeMap<String, ClaimData>,
+        credentials: &IndexMap<String, PresentationCredential<S>>,
+    ) -> CredxResult<(Self, CredentialRequestState<S>)> {
+        if blind_claims.len() != offer.blind_claim_labels.len()
+            || offer
+                .blind_claim_labels
+                .iter()
+                .any(|l| !blind_claims.contains_key(l))
+        {
+            return Err(Error::InvalidClaimData(
+                "blind claims don't match the offer",
+            ));
+        }
+        // Check the offer is consistent with the schema before committing to it
+        offer.known_claims()?;
+        let (blind_request, blinder) = if blind_claims.is_empty() {
+            (None, None)
+        } else {
+            let (request, blinder) = match &offer.presentation_schema {
+                None => BlindCredentialRequest::new_with_format(
+                    &offer.issuer,
+                    blind_claims,
+                    offer.nonce,
+                    offer.blind_signature_format,
+                )?,
+                Some(schema) => BlindCredentialRequest::new_with_presentation_and_format(
+                    &offer.issuer,
+                    blind_claims,
+                    offer.nonce,
+                    credentials,
+                    schema,
+                    offer.blind_signature_format,
+                )?,
+            };
+            (Some(request), Some(blinder))
+        };
+        Ok((
+            Self {
+                offer_id: offer.offer_id,
+                blind_request,
+            },
+            CredentialRequestState {
+                offer: offer.clone(),
+                blind_claims: blind_claims.clone(),
+                blinder,
+            },
+        ))
+    }
+}
+
+/// The holder's private state between sending a [`CredentialRequest`]
+/// and receiving the issuer's response
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CredentialRequestState<S: ShortGroupSignatureScheme> {
+    /// The offer that was answered
+    #[serde(bound(
+        serialize = "CredentialOffer<S>: Serialize",
+        deserialize = "CredentialOffer<S>: Deserialize<'de>"
+    ))]
+    pub offer: CredentialOffer<S>,
+    /// The blind claims
+    pub blind_claims: BTreeMap<String, ClaimData>,
+    /// The blinder for the blind claims
+    pub blinder: Option<Scalar>,
+}
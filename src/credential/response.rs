@@ -0,0 +1,92 @@
+use crate::blind::BlindCredentialBundle;
+use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use crate::{
+    credential::{CredentialBundle, CredentialRequestState},
+    error::Error,
+    CredxResult,
+};
+use serde::{Deserialize, Serialize};
+
+/// The issuer's answer to a [`super::CredentialRequest`]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CredentialResponse<S: ShortGroupSignatureScheme> {
+    /// The id of the offer that was requested
+    pub offer_id: [u8; 16],
+    /// The issued credential
+    #[serde(bound(
+        serialize = "IssuedCredential<S>: Serialize",
+        deserialize = "IssuedCredential<S>: Deserialize<'de>"
+    ))]
+    pub credential: IssuedCredential<S>,
+}
+
+/// A credential issued in response to a request
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum IssuedCredential<S: ShortGroupSignatureScheme> {
+    /// A credential where the issuer knew every claim
+    #[serde(bound(
+        serialize = "CredentialBundle<S>: Serialize",
+        deserialize = "CredentialBundle<S>: Deserialize<'de>"
+    ))]
+    Credential(CredentialBundle<S>),
+    /// A credential with blind claims
+    #[serde(bound(
+        serialize = "BlindCredentialBundle<S>: Serialize",
+        deserialize = "BlindCredentialBundle<S>: Deserialize<'de>"
+    ))]
+    Blind(BlindCredentialBundle<S>),
+}
+
+impl<S: ShortGroupSignatureScheme> CredentialResponse<S> {
+    /// Finish the credential with the state from the request.
+    ///
+    /// The credential must be for the requested offer, from the offering issuer,
+    /// contain the offered claims and verify.
+    pub fn to_credential(
+        self,
+        state: &CredentialRequestState<S>,
+    ) -> CredxResult<CredentialBundle<S>> {
+        let offer = &state.offer;
+        if self.offer_id != offer.offer_id {
+            return Err(Error::InvalidCredential(
+                "response is not for the requested offer",
+            ));
+        }
+        let bundle = match (self.credential, state.blinder) {
+            (IssuedCredential::Credential(bundle), None) => {
+                bundle.verify()?;
+                bundle
+            }
+            (IssuedCredential::Blind(bundle), Some(blinder)) => {
+                bundle.to_unblinded(&state.blind_claims, blinder)?
+            }
+            (_, _) => {
+                return Err(Error::InvalidCredential(
+                    "response doesn't match the requested blinding",
+                ))
+            }
+        };
+        if bundle.issuer.id != offer.issuer.id || bundle.issuer.schema.id != offer.issuer.schema.id
+        {
+            return Err(Error::InvalidCredential(
+                "credential is not from the offering issuer",
+            ));
+        }
+        let known = offer.known_claims()?;
+        for (label, claim) in &known {
+            let index = bundle
+                .issuer
+                .schema
+                .get_claim_index(label)
+                .ok_or(Error::InvalidClaimData("claim not found in schema"))?;
+            if bundle.issuer.schema.claims[index].normalize(claim)
+                != bundle.credential.claims[index]
+            {
+                return Err(Error::InvalidCredential(
+                    "credential claims don't match the offer",
+                ));
+            }
+        }
+        Ok(bundle)
+    }
+}
@@ -3,6 +3,7 @@ use crate::error::Error;
 use crate::{random_string, utils::*, CredxResult};
 use indexmap::IndexSet;
 use log::debug;
+use rand_core::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
 use uint_zigzag::Uint;
 
@@ -31,6 +32,12 @@ pub struct CredentialSchema {
     pub claim_indices: IndexSet<String>,
     /// The claims that can be signed
     pub claims: Vec<ClaimSchema>,
+    /// The number claim with the time the credential becomes valid
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub valid_from: Option<String>,
+    /// The number claim with the time the credential expires
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub valid_until: Option<String>,
 }
 
 impl CredentialSchema {
@@ -41,6 +48,32 @@ impl CredentialSchema {
         blind_claims: &[&str],
         claims: &[ClaimSchema],
     ) -> CredxResult<Self> {
+        Self::new_with_rng(label, description, blind_claims, claims, rand::thread_rng())
+    }
+
+    /// Create a new credential schema with a random id generated from `rng`
+    pub fn new_with_rng(
+        label: Option<&str>,
+        description: Option<&str>,
+        blind_claims: &[&str],
+        claims: &[ClaimSchema],
+        rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<Self> {
+        let id = random_string(16, rng);
+        Self::new_with_id(&id, label, description, blind_claims, claims)
+    }
+
+    /// Create a new credential schema with the specified id
+    pub fn new_with_id(
+        id: &str,
+        label: Option<&str>,
+        description: Option<&str>,
+        blind_claims: &[&str],
+        claims: &[ClaimSchema],
+    ) -> CredxResult<Self> {
+        if id.is_empty() {
+            return Err(Error::InvalidClaimData("schema id cannot be empty"));
+        }
         let claims = claims.to_vec();
         if claims.is_empty() {
             return Err(Error::InvalidClaimData(
@@ -48,12 +81,17 @@ impl CredentialSchema {
             ));
         }
 
-        let id = random_string(16, rand::thread_rng());
+        let id = id.to_string();
         let mut claim_indices = IndexSet::new();
         for claim in &claims {
             if !claim_indices.insert(claim.label.to_string()) {
                 return Err(Error::InvalidClaimData("duplicate claim detected"));
             }
+            if claim.optional && claim.claim_type == ClaimType::Revocation {
+                return Err(Error::InvalidClaimData(
+                    "revocation claims cannot be optional",
+                ));
+            }
         }
         for blind_claim in blind_claims {
             if !claim_indices.contains(&blind_claim.to_string()) {
@@ -70,6 +108,8 @@ impl CredentialSchema {
             claim_indices,
             label: label.map(|l| l.to_string()),
             description: description.map(|d| d.to_string()),
+            valid_from: None,
+            valid_until: None,
         };
         debug!(
             "Credential Schema: {}",
@@ -77,6 +117,110 @@ impl CredentialSchema {
         );
         Ok(schema)
     }
+    /// Create a new credential schema from nested claim schemas.
+    ///
+    /// Nested objects are flattened depth first in declaration order and each
+    /// claim is labeled with its path, e.g. `address.street`.
+    /// `blind_claims` are claim paths.
+    pub fn new_nested(
+        label: Option<&str>,
+        description: Option<&str>,
+        blind_claims: &[&str],
+        claims: &[NestedClaimSchema],
+    ) -> CredxResult<Self> {
+        let mut flattened = Vec::with_capacity(claims.len());
+        for claim in claims {
+            claim.flatten_into("", &mut flattened)?;
+        }
+        let blind_claims = blind_claims
+            .iter()
+            .map(|b| normalize_claim_path(b))
+            .collect::<Vec<_>>();
+        Self::new(label, description, &blind_claims, &flattened)
+    }
+
+    /// Declare the number claims that hold when the credential becomes valid
+    /// and when it expires.
+    ///
+    /// The claims must be required, known to the issuer and hold seconds since
+    /// the Unix epoch like [`NumberClaim::parse_rfc3339_datetime`].
+    /// Credentials that have already expired aren't issued.
+    pub fn with_validity_period(
+        mut self,
+        valid_from: Option<&str>,
+        valid_until: Option<&str>,
+    ) -> CredxResult<Self> {
+        let check = |path: Option<&str>| -> CredxResult<Option<String>> {
+            let path = match path {
+                Some(p) => p,
+                None => return Ok(None),
+            };
+            let index = self.get_claim_index(path).ok_or(Error::InvalidClaimData(
+                "validity claim not found in schema",
+            ))?;
+            let claim = &self.claims[index];
+            if claim.claim_type != ClaimType::Number || claim.optional {
+                return Err(Error::InvalidClaimData(
+                    "validity claims must be required number claims",
+                ));
+            }
+            if self.blind_claims.contains(&claim.label) {
+                return Err(Error::InvalidClaimData(
+                    "validity claims cannot be blindly signed",
+                ));
+            }
+            Ok(Some(claim.label.clone()))
+        };
+        let valid_from = check(valid_from)?;
+        let valid_until = check(valid_until)?;
+        if valid_from.is_some() && valid_from == valid_until {
+            return Err(Error::InvalidClaimData(
+                "valid from and valid until must be different claims",
+            ));
+        }
+        self.valid_from = valid_from;
+        self.valid_until = valid_until;
+        Ok(self)
+    }
+
+    /// Get the claim index for the claim label or path
+    pub fn get_claim_index(&self, path: &str) -> Option<usize> {
+        self.claim_indices.get_index_of(normalize_claim_path(path))
+    }
+
+    /// Check the credential hasn't expired at `now`
+    pub(crate) fn check_not_expired<'a>(
+        &self,
+        claim: impl Fn(&str) -> Option<&'a ClaimData>,
+        now: isize,
+    ) -> CredxResult<()> {
+        if let Some(ClaimData::Number(until)) = self.valid_until.as_deref().and_then(claim) {
+            if until.value < now {
+                return Err(Error::InvalidClaimData("credential has already expired"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the credential doesn't expire before it becomes valid
+    pub(crate) fn check_validity_period<'a>(
+        &self,
+        claim: impl Fn(&str) -> Option<&'a ClaimData>,
+    ) -> CredxResult<()> {
+        let value = |label: &Option<String>| match label.as_deref().and_then(&claim) {
+            Some(ClaimData::Number(n)) => Some(n.value),
+            _ => None,
+        };
+        if let (Some(from), Some(until)) = (value(&self.valid_from), value(&self.valid_until)) {
+            if until < from {
+                return Err(Error::InvalidClaimData(
+                    "credential expires before it becomes valid",
+                ));
+            }
+        }
+        Ok(())
+    }
+
     /// Add data to the transcript
     pub fn add_challenge_contribution(&self, transcript: &mut merlin::Transcript) {
         let label = self
@@ -118,6 +262,12 @@ impl CredentialSchema {
             transcript.append_message(b"claim indices index", &Uint::from(index).to_vec());
         }
         transcript.append_message(b"claims length", &Uint::from(self.claims.len()).to_vec());
+        if let Some(valid_from) = &self.valid_from {
+            transcript.append_message(b"schema valid from", valid_from.as_bytes());
+        }
+        if let Some(valid_until) = &self.valid_until {
+            transcript.append_message(b"schema valid until", valid_until.as_bytes());
+        }
     }
 }
 
@@ -130,6 +280,12 @@ pub struct ClaimSchema {
     pub label: String,
     /// Can the claim be represented as printable characters
     pub print_friendly: bool,
+    /// Can the claim be omitted when signing. Omitted claims are signed as [`AbsentClaim`]
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub optional: bool,
+    /// How hashed claims are normalized before validating and signing
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub normalization: Option<ClaimNormalization>,
     /// The claim data validators
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub validators: Vec<ClaimValidator>,
@@ -139,10 +295,22 @@ impl ClaimSchema {
     /// [`Some(true)`] if the claim is the right type and meets the validator requirements
     /// [`Some(false)`] if the claim is the right type but doesn't meet the requirements
     /// [`None`] if the claim is the incorrect type
+    ///
+    /// An absent claim is only valid if this claim is optional
     pub fn is_valid(&self, claim: &ClaimData) -> Option<bool> {
+        if claim.is_absent() {
+            return Some(self.optional);
+        }
+        let claim = self.normalize(claim);
         let mut result = true;
         for v in &self.validators {
-            match v.is_valid(claim) {
+            let valid = match (v, &self.normalization) {
+                (ClaimValidator::AnyOne(claims), Some(n)) => {
+                    Some(claims.iter().any(|c| n.normalize(c) == claim))
+                }
+                (_, _) => v.is_valid(&claim),
+            };
+            match valid {
                 Some(b) => result &= b,
                 None => return None,
             }
@@ -150,11 +318,32 @@ impl ClaimSchema {
         Some(result)
     }
 
+    /// Apply this claim's normalization to `claim`
+    pub fn normalize(&self, claim: &ClaimData) -> ClaimData {
+        match &self.normalization {
+            Some(n) => n.normalize(claim),
+            None => claim.clone(),
+        }
+    }
+
+    /// Convert text to [`ClaimData`] and apply this claim's normalization
+    pub fn claim_from_text(&self, s: &str) -> CredxResult<ClaimData> {
+        Ok(self.normalize(&ClaimData::from_text(s)?))
+    }
+
     /// Add data to the transcript
     pub fn add_challenge_contribution(&self, transcript: &mut merlin::Transcript) {
         transcript.append_message(b"claim label", self.label.as_bytes());
         transcript.append_message(b"claim type", &[self.claim_type as u8]);
         transcript.append_message(b"claim print friendly", &[u8::from(self.print_friendly)]);
+        transcript.append_message(b"claim optional", &[u8::from(self.optional)]);
+        transcript.append_message(
+            b"claim normalization",
+            &[u8::from(self.normalization.is_some())],
+        );
+        if let Some(n) = &self.normalization {
+            n.add_challenge_contribution(transcript);
+        }
         transcript.append_message(
             b"claim validators length",
             &Uint::from(self.validators.len()).to_vec(),
@@ -166,6 +355,89 @@ impl ClaimSchema {
     }
 }
 
+fn is_false(b: &bool) -> bool {
+    !*b
+}
+
+/// A claim schema that can contain nested objects
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum NestedClaimSchema {
+    /// A single claim
+    Claim(ClaimSchema),
+    /// An object of claims
+    Object {
+        /// The object label
+        label: String,
+        /// The claims in this object
+        claims: Vec<NestedClaimSchema>,
+    },
+}
+
+impl From<ClaimSchema> for NestedClaimSchema {
+    fn from(c: ClaimSchema) -> Self {
+        Self::Claim(c)
+    }
+}
+
+impl NestedClaimSchema {
+    /// An object with the latitude and longitude number claims
+    /// of a [`GeolocationClaim`]
+    pub fn geolocation(label: &str, print_friendly: bool, optional: bool) -> Self {
+        let coordinate = |label: &str, min: isize, max: isize| -> NestedClaimSchema {
+            ClaimSchema {
+                claim_type: ClaimType::Number,
+                label: label.to_string(),
+                print_friendly,
+                optional,
+                normalization: None,
+                validators: vec![ClaimValidator::Range {
+                    min: Some(min),
+                    max: Some(max),
+                }],
+            }
+            .into()
+        };
+        Self::Object {
+            label: label.to_string(),
+            claims: vec![
+                coordinate(LATITUDE_LABEL, MIN_LATITUDE, MAX_LATITUDE),
+                coordinate(LONGITUDE_LABEL, MIN_LONGITUDE, MAX_LONGITUDE),
+            ],
+        }
+    }
+
+    fn flatten_into(&self, path: &str, claims: &mut Vec<ClaimSchema>) -> CredxResult<()> {
+        let label = match self {
+            Self::Claim(c) => &c.label,
+            Self::Object { label, .. } => label,
+        };
+        if label.is_empty() || label.contains(CLAIM_PATH_SEPARATOR) {
+            return Err(Error::InvalidClaimData(
+                "nested claim label cannot be empty or contain the path separator",
+            ));
+        }
+        let path = join_claim_path(path, label);
+        match self {
+            Self::Claim(c) => {
+                let mut c = c.clone();
+                c.label = path;
+                claims.push(c);
+            }
+            Self::Object { claims: inner, .. } => {
+                if inner.is_empty() {
+                    return Err(Error::InvalidClaimData(
+                        "cannot create a nested object with an empty claims list",
+                    ));
+                }
+                for c in inner {
+                    c.flatten_into(&path, claims)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 #[test]
 fn test_serialize() {
     let string = r#"{"id":"63e8b522-3ef6-4c45-92f1-47cad2449523","label":"FinclusiveKYCSchema","description":"","blind_claims":[],"claim_indices":["credential_id","first_name","last_name","address1","address2","city","state","postal_cost","iso_country_code","date_of_birth","phone_number","phone_number_type","email_address","tax_id_number","document_id","document_url","document_identification_number","document_type","document_file_name","document_content","document_iso_country_code"],"claims":[{"claim_type":"Revocation","label":"credential_id","print_friendly":false},{"claim_type":"Hashed","label":"first_name","print_friendly":true,"validators":[{"Length":{"max":64}}]},{"claim_type":"Hashed","label":"last_name","print_friendly":true,"validators":[{"Length":{"max":64}}]},{"claim_type":"Hashed","label":"address1","print_friendly":true},{"claim_type":"Hashed","label":"address2","print_friendly":true},{"claim_type":"Hashed","label":"city","print_friendly":true},{"claim_type":"Hashed","label":"state","print_friendly":true},{"claim_type":"Hashed","label":"postal_code","print_friendly":true},{"claim_type":"Hashed","label":"iso_country_code","print_friendly":true},{"claim_type":"Number","label":"date_of_birth","print_friendly":true,"validators":[{"Range":{"min":0,"max":65000}}]},{"claim_type":"Hashed","label":"phone_number","print_friendly":true,"validators":[{"Regex":"\\d{10,15}"}]},{"claim_type":"Number","label":"phone_number_type","print_friendly":true},{"claim_type":"Hashed","label":"email_address","print_friendly":true},{"claim_type":"Hashed","label":"tax_id_number","print_friendly":true},{"claim_type":"Hashed","label":"document_id","print_friendly":true},{"claim_type":"Hashed","label":"document_url","print_friendly":true},{"claim_type":"Hashed","label":"document_identification_number","print_friendly":true},{"claim_type":"Hashed","label":"document_type","print_friendly":true},{"claim_type":"Hashed","label":"document_file_name","print_friendly":true},{"claim_type":"Hashed","label":"document_content","print_friendly":false},{"claim_type":"Number","label":"document_iso_country_code","print_friendly":true}]}"#;
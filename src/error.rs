@@ -1,3 +1,6 @@
+use crate::jwp::JwpError;
+use crate::w3c::W3cError;
+
 /// Errors created by this library
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Error {
@@ -15,14 +18,22 @@ pub enum Error {
     InvalidClaimData(&'static str),
     /// Invalid public key
     InvalidPublicKey,
+    /// An issuer that hasn't published a proof that its public keys are well-formed
+    MissingKeyProof,
     /// Invalid data for creating a signature proof
     InvalidSignatureProofData,
+    /// A credential that doesn't verify for its issuer
+    InvalidCredential(&'static str),
     /// Invalid data for creating a presentation
     InvalidPresentationData(String),
     /// Invalid bulletproof range
     InvalidBulletproofRange,
     /// Invalid binary or text data
     DeserializationError,
+    /// Invalid W3C credential or presentation
+    InvalidW3cData(W3cError),
+    /// Invalid JSON Web Proof
+    InvalidJwp(JwpError),
     /// A generic error message
     General(&'static str),
 }
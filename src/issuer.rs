@@ -1,36 +1,92 @@
+mod export;
+mod key_proof;
+mod offers;
+mod registry;
+mod signer;
+mod threshold;
+
+pub use export::*;
+pub use offers::*;
+pub use registry::*;
+pub use signer::*;
+pub use threshold::*;
+
 use super::{credential::CredentialSchema, error::Error, revocation_registry::RevocationRegistry};
 use crate::blind::{BlindCredential, BlindCredentialBundle, BlindCredentialRequest};
-use crate::claim::{Claim, ClaimData, RevocationClaim};
+use crate::claim::{AbsentClaim, Claim, ClaimData, NestedClaimData, RevocationClaim};
 use crate::credential::{Credential, CredentialBundle};
 use crate::knox::{
     accumulator::vb20::{self, Accumulator, Element, MembershipWitness},
-    short_group_sig_core::short_group_traits::{
-        PublicKey as _, SecretKey as _, ShortGroupSignatureScheme, Signature,
+    short_group_sig_core::{
+        short_group_traits::{PublicKey as _, ShortGroupSignatureScheme, Signature as _},
+        KeyCorrectnessProof,
     },
     Knox,
 };
+use crate::presentation::PresentationSchema;
 use crate::{random_string, CredxResult};
 use blsful::{inner_types::*, *};
 use log::debug;
+use rand_chacha::ChaChaRng;
+use rand_core::{CryptoRng, RngCore, SeedableRng};
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use sha3::{
+    digest::{ExtendableOutput, Update},
+    Shake256,
+};
+use std::collections::{BTreeMap, HashSet};
 use std::num::NonZeroUsize;
+use uint_zigzag::Uint;
+
+/// The minimum length of the master seed for [`Issuer::from_seed`]
+pub const MIN_ISSUER_SEED_LENGTH: usize = 32;
 
-/// An issuer of a credential
+const ISSUER_SEED_SALT: &[u8] = b"credx issuer key derivation";
+
+/// An issuer of a credential.
+///
+/// The secret keys are held by the issuer's [`IssuerSigner`], by default
+/// an [`InMemorySigner`].
 #[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct Issuer<S: ShortGroupSignatureScheme> {
+pub struct Issuer<S: ShortGroupSignatureScheme, K: IssuerSigner<S> = InMemorySigner<S>> {
     /// The issuer's unique id
     pub id: String,
     /// The schema for this issuer
     pub schema: CredentialSchema,
-    /// The credential signing key for this issuer
-    pub signing_key: S::SecretKey,
-    /// The revocation update key for this issuer
-    pub revocation_key: vb20::SecretKey,
-    /// The verifiable decryption key for this issuer
-    pub verifiable_decryption_key: SecretKey<Bls12381G2Impl>,
+    /// The signer holding the secret keys for this issuer
+    #[serde(flatten)]
+    pub signer: K,
     /// The revocation registry for this issuer
     pub revocation_registry: RevocationRegistry,
+    /// The version of the current signing key
+    #[serde(default)]
+    pub key_version: u32,
+    /// The verifying keys still accepted after a rotation
+    #[serde(
+        default = "Vec::new",
+        bound(
+            serialize = "IssuerKeyVersion<S>: Serialize",
+            deserialize = "IssuerKeyVersion<S>: Deserialize<'de>"
+        )
+    )]
+    pub previous_keys: Vec<IssuerKeyVersion<S>>,
+    /// The proof that the public keys are well-formed
+    #[serde(default)]
+    pub key_proof: Option<KeyCorrectnessProof>,
+}
+
+/// A verifying key that was replaced by a key rotation
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct IssuerKeyVersion<S: ShortGroupSignatureScheme> {
+    /// The version of the key
+    pub key_version: u32,
+    /// The credential verifying key
+    pub verifying_key: S::PublicKey,
+    /// The proof that this verifying key and the issuer's other public keys
+    /// are well-formed, from before the rotation
+    #[serde(default)]
+    pub key_proof: Option<KeyCorrectnessProof>,
 }
 
 /// The public data for an issuer
@@ -48,16 +104,32 @@ pub struct IssuerPublic<S: ShortGroupSignatureScheme> {
     pub verifiable_encryption_key: PublicKey<Bls12381G2Impl>,
     /// The revocation registry for this issuer
     pub revocation_registry: Accumulator,
+    /// The version of the credential verifying key
+    #[serde(default)]
+    pub key_version: u32,
+    /// The verifying keys from before a rotation that are still accepted
+    #[serde(
+        default = "Vec::new",
+        bound(
+            serialize = "IssuerKeyVersion<S>: Serialize",
+            deserialize = "IssuerKeyVersion<S>: Deserialize<'de>"
+        )
+    )]
+    pub previous_keys: Vec<IssuerKeyVersion<S>>,
+    /// The issuer's proof that its public keys are well-formed.
+    /// Check it with [`IssuerPublic::verify_key_proof`].
+    #[serde(default)]
+    pub key_proof: Option<KeyCorrectnessProof>,
 }
 
-impl<S: ShortGroupSignatureScheme> From<&Issuer<S>> for IssuerPublic<S> {
-    fn from(i: &Issuer<S>) -> Self {
+impl<S: ShortGroupSignatureScheme, K: IssuerSigner<S>> From<&Issuer<S, K>> for IssuerPublic<S> {
+    fn from(i: &Issuer<S, K>) -> Self {
         i.get_public()
     }
 }
 
-impl<S: ShortGroupSignatureScheme> From<&mut Issuer<S>> for IssuerPublic<S> {
-    fn from(i: &mut Issuer<S>) -> Self {
+impl<S: ShortGroupSignatureScheme, K: IssuerSigner<S>> From<&mut Issuer<S, K>> for IssuerPublic<S> {
+    fn from(i: &mut Issuer<S, K>) -> Self {
         i.get_public()
     }
 }
@@ -65,78 +137,342 @@ impl<S: ShortGroupSignatureScheme> From<&mut Issuer<S>> for IssuerPublic<S> {
 impl<S: ShortGroupSignatureScheme> Issuer<S> {
     /// Create a new Issuer
     pub fn new(schema: &CredentialSchema) -> (IssuerPublic<S>, Self) {
-        let id = random_string(16, rand::thread_rng());
+        Self::new_with_rng(schema, rand::thread_rng()).unwrap()
+    }
+
+    /// Create a new Issuer with a random id and keys generated from `rng`
+    pub fn new_with_rng(
+        schema: &CredentialSchema,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<(IssuerPublic<S>, Self)> {
+        let id = random_string(16, &mut rng);
+        Self::new_with_id_and_rng(&id, schema, rng)
+    }
+
+    /// Create a new Issuer with the specified id, e.g. a DID, and keys generated from `rng`
+    pub fn new_with_id_and_rng(
+        id: &str,
+        schema: &CredentialSchema,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<(IssuerPublic<S>, Self)> {
         let (verifying_key, signing_key) = S::new_keys(
-            NonZeroUsize::new(schema.claims.len()).expect("non-zero"),
-            rand::thread_rng(),
-        )
-        .unwrap();
-        let (pubkkey, seckey) = Knox::new_bls381g1_keys(rand::thread_rng());
-        let revocation_verifying_key = vb20::PublicKey(pubkkey.0);
+            NonZeroUsize::new(schema.claims.len()).ok_or(Error::InvalidCredentialSchema)?,
+            &mut rng,
+        )?;
+        let revocation_keys = Knox::new_bls381g1_keys(&mut rng);
+        let verifiable_encryption_keys = Knox::new_bls381g2_keys(&mut rng);
+        let revocation_registry = RevocationRegistry::new(&mut rng);
+        Ok(Self::from_keys(
+            id,
+            schema,
+            (verifying_key, signing_key),
+            revocation_keys,
+            verifiable_encryption_keys,
+            revocation_registry,
+            rng,
+        ))
+    }
+
+    /// Create a new Issuer with the specified id and keys derived from a master seed.
+    ///
+    /// Each key is generated from its own domain separated seed so the same
+    /// master seed always reproduces the same issuer.
+    /// The master seed must be at least [`MIN_ISSUER_SEED_LENGTH`] bytes.
+    pub fn from_seed(
+        id: &str,
+        schema: &CredentialSchema,
+        seed: &[u8],
+    ) -> CredxResult<(IssuerPublic<S>, Self)> {
+        if seed.len() < MIN_ISSUER_SEED_LENGTH {
+            return Err(Error::General("issuer seed is too short"));
+        }
+        let derive_rng = |info: &[u8]| {
+            let mut okm = [0u8; 32];
+            Shake256::default()
+                .chain(ISSUER_SEED_SALT)
+                .chain(Uint::from(seed.len()).to_vec())
+                .chain(seed)
+                .chain(Uint::from(info.len()).to_vec())
+                .chain(info)
+                .finalize_xof_into(&mut okm);
+            ChaChaRng::from_seed(okm)
+        };
+        let signing_keys = S::new_keys(
+            NonZeroUsize::new(schema.claims.len()).ok_or(Error::InvalidCredentialSchema)?,
+            derive_rng(b"signing key"),
+        )?;
+        let revocation_keys = Knox::new_bls381g1_keys(derive_rng(b"revocation key"));
+        let verifiable_encryption_keys =
+            Knox::new_bls381g2_keys(derive_rng(b"verifiable encryption key"));
+        let revocation_registry = RevocationRegistry::new(derive_rng(b"revocation registry"));
+        Ok(Self::from_keys(
+            id,
+            schema,
+            signing_keys,
+            revocation_keys,
+            verifiable_encryption_keys,
+            revocation_registry,
+            derive_rng(b"key proof"),
+        ))
+    }
+
+    fn from_keys(
+        id: &str,
+        schema: &CredentialSchema,
+        signing_keys: (S::PublicKey, S::SecretKey),
+        revocation_keys: (PublicKey<Bls12381G1Impl>, SecretKey<Bls12381G1Impl>),
+        verifiable_encryption_keys: (PublicKey<Bls12381G2Impl>, SecretKey<Bls12381G2Impl>),
+        revocation_registry: RevocationRegistry,
+        rng: impl RngCore + CryptoRng,
+    ) -> (IssuerPublic<S>, Self) {
+        let (_, signing_key) = signing_keys;
+        let (_, seckey) = revocation_keys;
         let revocation_key = vb20::SecretKey(seckey.0);
-        let (verifiable_encryption_key, verifiable_decryption_key) =
-            Knox::new_bls381g2_keys(rand::thread_rng());
-        let revocation_registry = RevocationRegistry::new(rand::thread_rng());
-        let issuer_public = IssuerPublic {
-            id: id.clone(),
+        let (_, verifiable_decryption_key) = verifiable_encryption_keys;
+        let signer = InMemorySigner {
+            signing_key,
+            revocation_key,
+            verifiable_decryption_key,
+        };
+        let key_proof = signer
+            .create_key_proof(id, rng)
+            .expect("generated keys are well-formed");
+        let issuer = Issuer {
+            id: id.to_string(),
             schema: schema.clone(),
-            verifying_key,
-            revocation_verifying_key,
-            verifiable_encryption_key,
-            revocation_registry: revocation_registry.value,
+            signer,
+            revocation_registry,
+            key_version: 0,
+            previous_keys: Vec::new(),
+            key_proof: Some(key_proof),
         };
+        let issuer_public = issuer.get_public();
         debug!(
             "Credential Definition: {:}",
             serde_json::to_string_pretty(&issuer_public).unwrap()
         );
-        let issuer = Issuer {
-            id,
-            schema: schema.clone(),
+        (issuer_public, issuer)
+    }
+
+    /// Replace the credential signing key with a new one.
+    ///
+    /// The id, schema and revocation keys stay the same so existing revocation
+    /// handles keep working. The old verifying key is still accepted until
+    /// [`Issuer::end_grace_period`] is called for its version.
+    pub fn rotate_keys(
+        &mut self,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<IssuerPublic<S>> {
+        let (_, signing_key) = S::new_keys(
+            NonZeroUsize::new(self.schema.claims.len()).expect("non-zero"),
+            &mut rng,
+        )?;
+        let signer = InMemorySigner {
             signing_key,
-            revocation_key,
-            verifiable_decryption_key,
-            revocation_registry,
+            ..self.signer.clone()
         };
-        (issuer_public, issuer)
+        let key_proof = signer.create_key_proof(&self.id, rng)?;
+        self.replace_signer(signer, key_proof)
     }
+}
 
-    /// Sign the claims into a credential
+impl<S: ShortGroupSignatureScheme, K: IssuerSigner<S>> Issuer<S, K> {
+    /// Create a new Issuer with the specified id that signs with `signer`.
+    ///
+    /// The signer proves its public keys are well-formed for this issuer.
+    pub fn new_with_signer(
+        id: &str,
+        schema: &CredentialSchema,
+        signer: K,
+        rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<(IssuerPublic<S>, Self)> {
+        let key_proof = signer.key_proof(id)?;
+        let issuer = Self {
+            id: id.to_string(),
+            schema: schema.clone(),
+            signer,
+            revocation_registry: RevocationRegistry::new(rng),
+            key_version: 0,
+            previous_keys: Vec::new(),
+            key_proof: Some(key_proof),
+        };
+        let issuer_public = issuer.get_public();
+        issuer_public.verify_key_proof()?;
+        Ok((issuer_public, issuer))
+    }
+
+    /// Sign the claims into a credential.
+    ///
+    /// Claims are normalized according to the schema before being validated and signed.
     pub fn sign_credential(&mut self, claims: &[ClaimData]) -> CredxResult<CredentialBundle<S>> {
-        // Check if claim data matches schema and validators
-        if claims.len() != self.schema.claims.len() {
-            return Err(Error::InvalidClaimData("claims.len != schema.claims.len"));
+        self.issuance().sign_credential(claims)
+    }
+
+    /// Sign many credentials at once.
+    ///
+    /// The claims are validated and the signatures and revocation handles
+    /// are created in parallel. The revocation registry is updated once with
+    /// every issued revocation id. Each item gets its own result so an invalid
+    /// item doesn't stop the rest of the batch.
+    pub fn sign_credentials_batch(
+        &mut self,
+        batch: &[Vec<ClaimData>],
+    ) -> Vec<CredxResult<CredentialBundle<S>>>
+    where
+        K: Sync,
+        S::Signature: Send,
+    {
+        self.issuance().sign_credentials_batch(batch)
+    }
+
+    /// Sign the claims into a credential where each claim is keyed by its label or path.
+    ///
+    /// Optional claims that are not provided are signed as absent.
+    pub fn sign_credential_from_paths(
+        &mut self,
+        claims: &BTreeMap<String, ClaimData>,
+    ) -> CredxResult<CredentialBundle<S>> {
+        self.issuance().sign_credential_from_paths(claims)
+    }
+
+    /// Sign nested claims into a credential
+    pub fn sign_nested_credential(
+        &mut self,
+        claims: &NestedClaimData,
+    ) -> CredxResult<CredentialBundle<S>> {
+        self.sign_credential_from_paths(&claims.flatten()?)
+    }
+
+    /// Blind sign a credential where only a subset of the claims are known.
+    ///
+    /// Optional claims that are neither known nor blinded are signed as absent.
+    /// A presentation in the request isn't checked, use
+    /// [`Self::blind_sign_credential_with_schema`] to require one.
+    pub fn blind_sign_credential(
+        &mut self,
+        request: &BlindCredentialRequest<S>,
+        claims: &BTreeMap<String, ClaimData>,
+    ) -> CredxResult<BlindCredentialBundle<S>> {
+        self.issuance().blind_sign_credential(request, claims, None)
+    }
+
+    /// Blind sign a credential like [`Self::blind_sign_credential`] only if
+    /// the request has a presentation of the issuer's `schema` that proves
+    /// its predicates about the blinded claims.
+    pub fn blind_sign_credential_with_schema(
+        &mut self,
+        request: &BlindCredentialRequest<S>,
+        claims: &BTreeMap<String, ClaimData>,
+        schema: &PresentationSchema<S>,
+    ) -> CredxResult<BlindCredentialBundle<S>> {
+        self.issuance()
+            .blind_sign_credential(request, claims, Some(schema))
+    }
+
+    /// Update a revocation handle
+    pub fn update_revocation_handle(
+        &self,
+        claim: RevocationClaim,
+    ) -> CredxResult<MembershipWitness> {
+        update_revocation_handle(&self.revocation_registry, &self.signer, claim)
+    }
+
+    /// Revoke a credential and update this issue's revocation registry
+    /// A list of all revoked claims should be kept externally.
+    pub fn revoke_credentials(&mut self, claims: &[RevocationClaim]) -> CredxResult<()> {
+        revoke_credentials(&mut self.revocation_registry, &self.signer, claims)
+    }
+
+    /// Switch to a signer with a new credential signing key.
+    ///
+    /// The new signer must use the same revocation and verifiable encryption
+    /// keys and prove its keys are well-formed. The old verifying key is still
+    /// accepted until [`Issuer::end_grace_period`] is called for its version.
+    pub fn rotate_signer(&mut self, signer: K) -> CredxResult<IssuerPublic<S>> {
+        if signer.revocation_verifying_key() != self.signer.revocation_verifying_key()
+            || signer.verifiable_encryption_key() != self.signer.verifiable_encryption_key()
+        {
+            return Err(Error::InvalidPublicKey);
         }
-        let mut revocation_element_index = None;
-        let mut revocation_claim = None;
-        for (i, (c, t)) in claims.iter().zip(&self.schema.claims).enumerate() {
-            if !c.is_type(t.claim_type) {
-                return Err(Error::InvalidClaimData("claim is not the correct type"));
-            }
-            match t.is_valid(c) {
-                Some(b) => {
-                    if !b {
-                        return Err(Error::InvalidClaimData("claim is not valid"));
-                    }
-                }
-                None => {
-                    return Err(Error::InvalidClaimData(
-                        "claim is not correct type to validate",
-                    ))
-                }
-            };
-            if let ClaimData::Revocation(rc) = c {
-                if revocation_claim.is_some() {
-                    return Err(Error::InvalidClaimData("multiple revocation claims found"));
-                }
-                revocation_element_index = Some(i);
-                revocation_claim = Some(rc);
-            }
+        let key_proof = signer.key_proof(&self.id)?;
+        self.replace_signer(signer, key_proof)
+    }
+
+    /// Stop accepting credentials signed with the key `key_version`
+    pub fn end_grace_period(&mut self, key_version: u32) -> CredxResult<IssuerPublic<S>> {
+        end_grace_period(&mut self.previous_keys, key_version)?;
+        Ok(self.get_public())
+    }
+
+    /// Sign the claims of a credential issued with the current or a previous key
+    /// with the current key.
+    ///
+    /// The revocation id is kept so the credential must not have been revoked.
+    pub fn reissue_credential(
+        &mut self,
+        credential: &Credential<S>,
+    ) -> CredxResult<CredentialBundle<S>> {
+        self.get_public().key_version_of(credential)?;
+        self.sign_credential(&credential.claims)
+    }
+
+    fn replace_signer(
+        &mut self,
+        signer: K,
+        key_proof: KeyCorrectnessProof,
+    ) -> CredxResult<IssuerPublic<S>> {
+        let key_version = self
+            .key_version
+            .checked_add(1)
+            .ok_or(Error::General("issuer key version overflow"))?;
+        let previous = std::mem::replace(&mut self.signer, signer);
+        self.previous_keys.push(IssuerKeyVersion {
+            key_version: self.key_version,
+            verifying_key: previous.verifying_key(),
+            key_proof: self.key_proof.replace(key_proof),
+        });
+        self.key_version = key_version;
+        Ok(self.get_public())
+    }
+
+    fn issuance(&mut self) -> Issuance<'_, S, &K> {
+        Issuance {
+            public: self.get_public(),
+            revocation_registry: &mut self.revocation_registry,
+            signer: &self.signer,
         }
-        let revocation_element_index = revocation_element_index.ok_or(Error::InvalidClaimData(
-            "revocation element index not found",
-        ))?;
-        let revocation_claim =
-            revocation_claim.ok_or(Error::InvalidClaimData("revocation claim not found"))?;
+    }
+
+    fn get_public(&self) -> IssuerPublic<S> {
+        IssuerPublic {
+            id: self.id.clone(),
+            schema: self.schema.clone(),
+            verifying_key: self.signer.verifying_key(),
+            revocation_verifying_key: self.signer.revocation_verifying_key(),
+            verifiable_encryption_key: self.signer.verifiable_encryption_key(),
+            revocation_registry: self.revocation_registry.value,
+            key_version: self.key_version,
+            previous_keys: self.previous_keys.clone(),
+            key_proof: self.key_proof.clone(),
+        }
+    }
+}
+
+/// The issuance steps of an [`Issuer`] with a borrowed signer
+struct Issuance<'a, S: ShortGroupSignatureScheme, K: IssuerSigner<S>> {
+    public: IssuerPublic<S>,
+    revocation_registry: &'a mut RevocationRegistry,
+    signer: K,
+}
+
+impl<S: ShortGroupSignatureScheme, K: IssuerSigner<S>> Issuance<'_, S, K> {
+    fn sign_credential(&mut self, claims: &[ClaimData]) -> CredxResult<CredentialBundle<S>> {
+        let (claims, revocation_element_index) = check_claims(&self.public.schema, claims)?;
+        check_not_expired(&self.public.schema, &claims)?;
+        let revocation_claim = match &claims[revocation_element_index] {
+            ClaimData::Revocation(rc) => rc,
+            _ => return Err(Error::InvalidClaimData("revocation claim not found")),
+        };
 
         // This data has already been revoked
         if !self
@@ -153,21 +489,18 @@ impl<S: ShortGroupSignatureScheme> Issuer<S> {
 
         let attributes: Vec<Scalar> = claims.iter().map(|c| c.to_scalar()).collect();
         let revocation_id = Element(attributes[revocation_element_index]);
-        let witness = MembershipWitness::new(
-            revocation_id,
-            self.revocation_registry.value,
-            &self.revocation_key,
-        );
+        let witness = self
+            .signer
+            .membership_witness(revocation_id, self.revocation_registry.value)?;
+        let signature = self.signer.sign(&attributes)?;
         self.revocation_registry
             .active
             .insert(revocation_claim.value.clone());
         self.revocation_registry
             .elements
             .insert(revocation_claim.value.clone());
-        let signature = S::Signature::create(&self.signing_key, &attributes)
-            .map_err(|_| Error::InvalidSigningOperation)?;
         let credential_bundle = CredentialBundle {
-            issuer: IssuerPublic::from(self),
+            issuer: self.public.clone(),
             credential: Credential {
                 claims: claims.to_vec(),
                 signature,
@@ -182,13 +515,152 @@ impl<S: ShortGroupSignatureScheme> Issuer<S> {
         Ok(credential_bundle)
     }
 
-    /// Blind sign a credential where only a subset of the claims are known
-    pub fn blind_sign_credential(
+    fn sign_credentials_batch(
+        &mut self,
+        batch: &[Vec<ClaimData>],
+    ) -> Vec<CredxResult<CredentialBundle<S>>>
+    where
+        K: Sync,
+        S::Signature: Send,
+    {
+        let schema = &self.public.schema;
+        let checked = batch
+            .par_iter()
+            .map(|claims| {
+                let checked = check_claims(schema, claims)?;
+                check_not_expired(schema, &checked.0)?;
+                Ok(checked)
+            })
+            .collect::<Vec<CredxResult<_>>>();
+
+        // Allocate the revocation ids in order so the first use of an id wins
+        let mut allocated = HashSet::with_capacity(checked.len());
+        let checked = checked
+            .into_iter()
+            .map(|result| {
+                let (claims, revocation_element_index) = result?;
+                let revocation_claim = match &claims[revocation_element_index] {
+                    ClaimData::Revocation(rc) => rc,
+                    _ => return Err(Error::InvalidClaimData("revocation claim not found")),
+                };
+                // This data has already been revoked
+                if !self
+                    .revocation_registry
+                    .active
+                    .contains(&revocation_claim.value)
+                    && self
+                        .revocation_registry
+                        .elements
+                        .contains(&revocation_claim.value)
+                {
+                    return Err(Error::InvalidClaimData("This claim is already revoked"));
+                }
+                if !allocated.insert(revocation_claim.value.clone()) {
+                    return Err(Error::InvalidClaimData(
+                        "revocation claim is used more than once in the batch",
+                    ));
+                }
+                Ok((claims, revocation_element_index))
+            })
+            .collect::<Vec<_>>();
+
+        let accumulator = self.revocation_registry.value;
+        let signer = &self.signer;
+        let signed = checked
+            .into_par_iter()
+            .map(|result| {
+                let (claims, revocation_element_index) = result?;
+                let attributes: Vec<Scalar> = claims.iter().map(|c| c.to_scalar()).collect();
+                let revocation_id = Element(attributes[revocation_element_index]);
+                let witness = signer.membership_witness(revocation_id, accumulator)?;
+                let signature = signer.sign(&attributes)?;
+                Ok((claims, revocation_element_index, signature, witness))
+            })
+            .collect::<Vec<CredxResult<_>>>();
+
+        let issued = signed
+            .iter()
+            .flatten()
+            .filter_map(|(claims, index, _, _)| match &claims[*index] {
+                ClaimData::Revocation(rc) => Some(rc.value.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        self.revocation_registry.add(&issued);
+
+        signed
+            .into_iter()
+            .map(|result| {
+                let (claims, revocation_index, signature, revocation_handle) = result?;
+                Ok(CredentialBundle {
+                    issuer: self.public.clone(),
+                    credential: Credential {
+                        claims,
+                        signature,
+                        revocation_handle,
+                        revocation_index,
+                    },
+                })
+            })
+            .collect()
+    }
+
+    fn sign_credential_from_paths(
+        &mut self,
+        claims: &BTreeMap<String, ClaimData>,
+    ) -> CredxResult<CredentialBundle<S>> {
+        let mut ordered = vec![None; self.public.schema.claims.len()];
+        for (path, claim) in claims {
+            let index = self
+                .public
+                .schema
+                .get_claim_index(path)
+                .ok_or(Error::InvalidClaimData("claim not found in schema"))?;
+            if ordered[index].replace(claim.clone()).is_some() {
+                return Err(Error::InvalidClaimData("duplicate claim detected"));
+            }
+        }
+        let ordered = ordered
+            .into_iter()
+            .zip(&self.public.schema.claims)
+            .map(|(c, t)| match c {
+                Some(c) => Some(c),
+                None if t.optional => Some(AbsentClaim.into()),
+                None => None,
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or(Error::InvalidClaimData("claim missing"))?;
+        self.sign_credential(&ordered)
+    }
+
+    fn blind_sign_credential(
         &mut self,
         request: &BlindCredentialRequest<S>,
         claims: &BTreeMap<String, ClaimData>,
+        required: Option<&PresentationSchema<S>>,
     ) -> CredxResult<BlindCredentialBundle<S>> {
-        if request.blind_claim_labels.len() + claims.len() != self.schema.claims.len() {
+        if let Some(schema) = required {
+            request.verify_presentation(&self.public, schema)?;
+        }
+        let mut claims = claims
+            .iter()
+            .map(|(label, c)| {
+                let c = match self.public.schema.claim_indices.get_index_of(label) {
+                    Some(index) => self.public.schema.claims[index].normalize(c),
+                    None => c.clone(),
+                };
+                (label.clone(), c)
+            })
+            .collect::<BTreeMap<_, _>>();
+        for t in &self.public.schema.claims {
+            if t.optional
+                && !claims.contains_key(&t.label)
+                && !request.blind_claim_labels.contains(&t.label)
+            {
+                claims.insert(t.label.clone(), AbsentClaim.into());
+            }
+        }
+        if request.blind_claim_labels.len() + claims.len() != self.public.schema.claims.len() {
             return Err(Error::InvalidClaimData(
                 "blind_claims.len + known_claims.len != schema.claims.len",
             ));
@@ -197,16 +669,25 @@ impl<S: ShortGroupSignatureScheme> Issuer<S> {
         let mut messages = Vec::with_capacity(claims.len());
         let mut revocation_label = None;
         let mut revocation_claim = None;
-        for (label, c) in claims {
+        for (label, c) in &claims {
             let index = self
+                .public
                 .schema
                 .claim_indices
                 .get_index_of(label)
                 .ok_or(Error::InvalidClaimData("claim not found in schema"))?;
-            let t = &self.schema.claims[index];
+            let t = &self.public.schema.claims[index];
 
-            if !c.is_type(t.claim_type) {
+            if c.is_absent() {
+                if !t.optional {
+                    return Err(Error::InvalidClaimData("claim is not optional"));
+                }
+            } else if !c.is_type(t.claim_type) {
                 return Err(Error::InvalidClaimData("claim is not the correct type"));
+            } else if c.to_scalar() == AbsentClaim::scalar() {
+                return Err(Error::InvalidClaimData(
+                    "claim value is reserved for absent claims",
+                ));
             }
             match t.is_valid(c) {
                 Some(b) => {
@@ -221,7 +702,8 @@ impl<S: ShortGroupSignatureScheme> Issuer<S> {
                 }
             };
             messages.push((
-                self.schema
+                self.public
+                    .schema
                     .claim_indices
                     .get_index_of(label)
                     .ok_or(Error::InvalidClaimData("claim does not exist in schema"))?,
@@ -232,6 +714,12 @@ impl<S: ShortGroupSignatureScheme> Issuer<S> {
                 revocation_claim = Some(rc);
             }
         }
+        self.public
+            .schema
+            .check_validity_period(|label| claims.get(label))?;
+        self.public
+            .schema
+            .check_not_expired(|label| claims.get(label), unix_time())?;
         let revocation_label =
             revocation_label.ok_or(Error::InvalidClaimData("revocation label not found"))?;
         let revocation_claim =
@@ -251,29 +739,22 @@ impl<S: ShortGroupSignatureScheme> Issuer<S> {
         }
 
         let revocation_id = Element(revocation_claim.to_scalar());
-        let witness = MembershipWitness::new(
-            revocation_id,
-            self.revocation_registry.value,
-            &self.revocation_key,
-        );
+        let witness = self
+            .signer
+            .membership_witness(revocation_id, self.revocation_registry.value)?;
+        let signature =
+            self.signer
+                .blind_sign(&request.blind_signature_context, &messages, request.nonce)?;
         self.revocation_registry
             .active
             .insert(revocation_claim.value.clone());
         self.revocation_registry
             .elements
             .insert(revocation_claim.value.clone());
-
-        let signature = S::blind_sign(
-            &request.blind_signature_context,
-            &self.signing_key,
-            &messages,
-            request.nonce,
-        )
-        .map_err(|_| Error::InvalidSigningOperation)?;
         let blind_credential_bundle = BlindCredentialBundle {
-            issuer: IssuerPublic::from(self),
+            issuer: self.public.clone(),
             credential: BlindCredential {
-                claims: claims.clone(),
+                claims,
                 signature,
                 revocation_handle: witness,
                 revocation_label,
@@ -285,44 +766,108 @@ impl<S: ShortGroupSignatureScheme> Issuer<S> {
         );
         Ok(blind_credential_bundle)
     }
+}
 
-    /// Update a revocation handle
-    pub fn update_revocation_handle(
-        &self,
-        claim: RevocationClaim,
-    ) -> CredxResult<MembershipWitness> {
-        if !self.revocation_registry.active.contains(&claim.value) {
-            return Err(Error::InvalidRevocationRegistryRevokeOperation);
+/// Normalize and validate the claims against the schema returning
+/// the normalized claims and the index of the revocation claim
+pub(crate) fn check_claims(
+    schema: &CredentialSchema,
+    claims: &[ClaimData],
+) -> CredxResult<(Vec<ClaimData>, usize)> {
+    // Check if claim data matches schema and validators
+    if claims.len() != schema.claims.len() {
+        return Err(Error::InvalidClaimData("claims.len != schema.claims.len"));
+    }
+    let claims = claims
+        .iter()
+        .zip(&schema.claims)
+        .map(|(c, t)| t.normalize(c))
+        .collect::<Vec<_>>();
+    let mut revocation_element_index = None;
+    let mut revocation_claim = None;
+    for (i, (c, t)) in claims.iter().zip(&schema.claims).enumerate() {
+        if c.is_absent() {
+            if !t.optional {
+                return Err(Error::InvalidClaimData("claim is not optional"));
+            }
+        } else if !c.is_type(t.claim_type) {
+            return Err(Error::InvalidClaimData("claim is not the correct type"));
+        } else if c.to_scalar() == AbsentClaim::scalar() {
+            return Err(Error::InvalidClaimData(
+                "claim value is reserved for absent claims",
+            ));
+        }
+        match t.is_valid(c) {
+            Some(b) => {
+                if !b {
+                    return Err(Error::InvalidClaimData("claim is not valid"));
+                }
+            }
+            None => {
+                return Err(Error::InvalidClaimData(
+                    "claim is not correct type to validate",
+                ))
+            }
+        };
+        if let ClaimData::Revocation(rc) = c {
+            if revocation_claim.is_some() {
+                return Err(Error::InvalidClaimData("multiple revocation claims found"));
+            }
+            revocation_element_index = Some(i);
+            revocation_claim = Some(rc);
         }
-
-        Ok(MembershipWitness::new(
-            Element(claim.to_scalar()),
-            self.revocation_registry.value,
-            &self.revocation_key,
-        ))
     }
+    let revocation_element_index = revocation_element_index.ok_or(Error::InvalidClaimData(
+        "revocation element index not found",
+    ))?;
+    schema.check_validity_period(|label| schema.get_claim_index(label).map(|i| &claims[i]))?;
+    Ok((claims, revocation_element_index))
+}
 
-    /// Revoke a credential and update this issue's revocation registry
-    /// A list of all revoked claims should be kept externally.
-    pub fn revoke_credentials(&mut self, claims: &[RevocationClaim]) -> CredxResult<()> {
-        let c: Vec<_> = claims.iter().map(|c| c.value.clone()).collect();
-        self.revocation_registry.revoke(&self.revocation_key, &c)
-    }
+/// Check a credential with the normalized `claims` hasn't already expired
+fn check_not_expired(schema: &CredentialSchema, claims: &[ClaimData]) -> CredxResult<()> {
+    schema.check_not_expired(
+        |label| schema.get_claim_index(label).map(|i| &claims[i]),
+        unix_time(),
+    )
+}
 
-    fn get_public(&self) -> IssuerPublic<S> {
-        let verifying_key = self.signing_key.public_key();
-        let revocation_verifying_key = vb20::PublicKey::from(&self.revocation_key);
-        let verifiable_encryption_key =
-            PublicKey::<Bls12381G2Impl>::from(&self.verifiable_decryption_key);
-        IssuerPublic {
-            id: self.id.clone(),
-            schema: self.schema.clone(),
-            verifying_key,
-            revocation_verifying_key,
-            verifiable_encryption_key,
-            revocation_registry: self.revocation_registry.value,
-        }
+fn unix_time() -> isize {
+    chrono::Utc::now().timestamp() as isize
+}
+
+fn end_grace_period<S: ShortGroupSignatureScheme>(
+    previous_keys: &mut Vec<IssuerKeyVersion<S>>,
+    key_version: u32,
+) -> CredxResult<()> {
+    let index = previous_keys
+        .iter()
+        .position(|k| k.key_version == key_version)
+        .ok_or(Error::General("no previous key with this version"))?;
+    previous_keys.remove(index);
+    Ok(())
+}
+
+fn update_revocation_handle<S: ShortGroupSignatureScheme>(
+    revocation_registry: &RevocationRegistry,
+    signer: &impl IssuerSigner<S>,
+    claim: RevocationClaim,
+) -> CredxResult<MembershipWitness> {
+    if !revocation_registry.active.contains(&claim.value) {
+        return Err(Error::InvalidRevocationRegistryRevokeOperation);
     }
+    signer.membership_witness(Element(claim.to_scalar()), revocation_registry.value)
+}
+
+fn revoke_credentials<S: ShortGroupSignatureScheme>(
+    revocation_registry: &mut RevocationRegistry,
+    signer: &impl IssuerSigner<S>,
+    claims: &[RevocationClaim],
+) -> CredxResult<()> {
+    let c: Vec<_> = claims.iter().map(|c| c.value.clone()).collect();
+    revocation_registry.revoke_with(&c, |value, removals| {
+        signer.remove_elements(value, removals)
+    })
 }
 
 impl<S: ShortGroupSignatureScheme> IssuerPublic<S> {
@@ -345,6 +890,59 @@ impl<S: ShortGroupSignatureScheme> IssuerPublic<S> {
             b"issuer verifiable encryption key",
             self.verifiable_encryption_key.0.to_bytes().as_ref(),
         );
+        // Issuers that never rotated their keys keep the original transcript
+        if self.key_version != 0 || !self.previous_keys.is_empty() {
+            transcript.append_u64(b"issuer key version", self.key_version as u64);
+            transcript.append_u64(
+                b"issuer previous keys length",
+                self.previous_keys.len() as u64,
+            );
+            for previous in &self.previous_keys {
+                transcript.append_u64(b"issuer previous key version", previous.key_version as u64);
+                transcript.append_message(
+                    b"issuer previous verifying key",
+                    previous.verifying_key.to_bytes().as_slice(),
+                );
+            }
+        }
         self.schema.add_challenge_contribution(transcript);
     }
+
+    /// The verifying key for `key_version` if it is still accepted
+    pub fn verifying_key_for(&self, key_version: u32) -> CredxResult<&S::PublicKey> {
+        if key_version == self.key_version {
+            return Ok(&self.verifying_key);
+        }
+        self.previous_keys
+            .iter()
+            .find(|k| k.key_version == key_version)
+            .map(|k| &k.verifying_key)
+            .ok_or(Error::InvalidPublicKey)
+    }
+
+    /// The version of the accepted key that signed `messages`
+    pub fn key_version_for_signature(
+        &self,
+        signature: &S::Signature,
+        messages: &[Scalar],
+    ) -> CredxResult<u32> {
+        if signature.verify(&self.verifying_key, messages).is_ok() {
+            return Ok(self.key_version);
+        }
+        self.previous_keys
+            .iter()
+            .find(|k| signature.verify(&k.verifying_key, messages).is_ok())
+            .map(|k| k.key_version)
+            .ok_or(Error::InvalidSignatureProofData)
+    }
+
+    /// The version of the accepted key that signed `credential`
+    pub fn key_version_of(&self, credential: &Credential<S>) -> CredxResult<u32> {
+        let messages = credential
+            .claims
+            .iter()
+            .map(|c| c.to_scalar())
+            .collect::<Vec<_>>();
+        self.key_version_for_signature(&credential.signature, &messages)
+    }
 }
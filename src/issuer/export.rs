@@ -0,0 +1,249 @@
+use super::{Issuer, IssuerPublic};
+use crate::error::Error;
+use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use crate::CredxResult;
+use aes_gcm::aead::{Aead, Payload};
+use aes_gcm::{AeadCore, Aes256Gcm, Key, KeyInit, Nonce};
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+/// The current encrypted issuer format version
+pub const ENCRYPTED_ISSUER_VERSION: u8 = 1;
+/// The length of the key used to wrap an exported issuer
+pub const ISSUER_WRAPPING_KEY_LENGTH: usize = 32;
+
+const ARGON2_SALT_LENGTH: usize = 16;
+
+/// How the wrapping key for an exported issuer is derived
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub enum IssuerWrappingKdf {
+    /// The caller supplied the wrapping key directly
+    Key,
+    /// The wrapping key is derived from a password with Argon2id
+    Argon2id {
+        /// The memory cost in KiB
+        memory_kib: u32,
+        /// The number of passes
+        iterations: u32,
+        /// The degree of parallelism
+        parallelism: u32,
+        /// The random salt
+        salt: Vec<u8>,
+    },
+}
+
+/// The Argon2id cost parameters for password wrapped issuers
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Argon2Params {
+    /// The memory cost in KiB
+    pub memory_kib: u32,
+    /// The number of passes
+    pub iterations: u32,
+    /// The degree of parallelism
+    pub parallelism: u32,
+}
+
+impl Default for Argon2Params {
+    /// The OWASP recommended minimum of 19 MiB, 2 passes and 1 lane
+    fn default() -> Self {
+        Self {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+/// An issuer with its secret keys encrypted with AES-256-GCM.
+///
+/// The public parts are stored in the clear and authenticated
+/// so they can be inspected without the password or key.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EncryptedIssuer<S: ShortGroupSignatureScheme> {
+    /// The format version
+    pub version: u8,
+    /// The public parts of the issuer
+    #[serde(bound(
+        serialize = "IssuerPublic<S>: Serialize",
+        deserialize = "IssuerPublic<S>: Deserialize<'de>"
+    ))]
+    pub public: IssuerPublic<S>,
+    /// How the wrapping key is derived
+    pub kdf: IssuerWrappingKdf,
+    /// The AES-GCM nonce
+    pub nonce: Vec<u8>,
+    /// The encrypted issuer
+    pub ciphertext: Vec<u8>,
+}
+
+impl<S: ShortGroupSignatureScheme> Issuer<S> {
+    /// Export this issuer encrypted with a key derived from `password`
+    /// using the default Argon2id parameters
+    pub fn export_with_password(
+        &self,
+        password: &[u8],
+        rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<EncryptedIssuer<S>> {
+        self.export_with_password_and_params(password, Argon2Params::default(), rng)
+    }
+
+    /// Export this issuer encrypted with a key derived from `password`
+    pub fn export_with_password_and_params(
+        &self,
+        password: &[u8],
+        params: Argon2Params,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<EncryptedIssuer<S>> {
+        let mut salt = vec![0u8; ARGON2_SALT_LENGTH];
+        rng.fill_bytes(&mut salt);
+        let kdf = IssuerWrappingKdf::Argon2id {
+            memory_kib: params.memory_kib,
+            iterations: params.iterations,
+            parallelism: params.parallelism,
+            salt,
+        };
+        let key = kdf.derive_key(password)?;
+        self.export(&key, kdf, rng)
+    }
+
+    /// Export this issuer encrypted with a 32-byte wrapping key
+    pub fn export_with_key(
+        &self,
+        key: &[u8; ISSUER_WRAPPING_KEY_LENGTH],
+        rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<EncryptedIssuer<S>> {
+        self.export(key, IssuerWrappingKdf::Key, rng)
+    }
+
+    fn export(
+        &self,
+        key: &[u8; ISSUER_WRAPPING_KEY_LENGTH],
+        kdf: IssuerWrappingKdf,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<EncryptedIssuer<S>> {
+        let public = self.get_public();
+        let aad = associated_data(ENCRYPTED_ISSUER_VERSION, &public, &kdf)?;
+        let plaintext = Zeroizing::new(
+            serde_json::to_vec(self).map_err(|_| Error::General("unable to serialize issuer"))?,
+        );
+        let nonce = Aes256Gcm::generate_nonce(&mut rng);
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+        let ciphertext = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: &plaintext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| Error::General("unable to encrypt issuer"))?;
+        Ok(EncryptedIssuer {
+            version: ENCRYPTED_ISSUER_VERSION,
+            public,
+            kdf,
+            nonce: nonce.to_vec(),
+            ciphertext,
+        })
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> EncryptedIssuer<S> {
+    /// Decrypt the issuer with a password
+    pub fn decrypt_with_password(&self, password: &[u8]) -> CredxResult<Issuer<S>> {
+        if self.kdf == IssuerWrappingKdf::Key {
+            return Err(Error::General("issuer is not password encrypted"));
+        }
+        let key = self.kdf.derive_key(password)?;
+        self.decrypt(&key)
+    }
+
+    /// Decrypt the issuer with a 32-byte wrapping key
+    pub fn decrypt_with_key(
+        &self,
+        key: &[u8; ISSUER_WRAPPING_KEY_LENGTH],
+    ) -> CredxResult<Issuer<S>> {
+        if self.kdf != IssuerWrappingKdf::Key {
+            return Err(Error::General("issuer is not key encrypted"));
+        }
+        self.decrypt(key)
+    }
+
+    /// Decrypt the issuer and check the secret keys match the public parts
+    fn decrypt(&self, key: &[u8; ISSUER_WRAPPING_KEY_LENGTH]) -> CredxResult<Issuer<S>> {
+        if self.version != ENCRYPTED_ISSUER_VERSION {
+            return Err(Error::General("unsupported encrypted issuer version"));
+        }
+        if self.nonce.len() != 12 {
+            return Err(Error::DeserializationError);
+        }
+        let aad = associated_data(self.version, &self.public, &self.kdf)?;
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+        let plaintext = Zeroizing::new(
+            cipher
+                .decrypt(
+                    Nonce::from_slice(&self.nonce),
+                    Payload {
+                        msg: &self.ciphertext,
+                        aad: &aad,
+                    },
+                )
+                .map_err(|_| Error::General("unable to decrypt issuer"))?,
+        );
+        let issuer: Issuer<S> =
+            serde_json::from_slice(&plaintext).map_err(|_| Error::DeserializationError)?;
+        let expected = serde_json::to_vec(&self.public).map_err(|_| Error::DeserializationError)?;
+        let actual =
+            serde_json::to_vec(&issuer.get_public()).map_err(|_| Error::DeserializationError)?;
+        if expected != actual {
+            return Err(Error::InvalidPublicKey);
+        }
+        Ok(issuer)
+    }
+}
+
+impl IssuerWrappingKdf {
+    fn derive_key(
+        &self,
+        password: &[u8],
+    ) -> CredxResult<Zeroizing<[u8; ISSUER_WRAPPING_KEY_LENGTH]>> {
+        match self {
+            Self::Key => Err(Error::General("no key derivation for a wrapping key")),
+            Self::Argon2id {
+                memory_kib,
+                iterations,
+                parallelism,
+                salt,
+            } => {
+                let params = argon2::Params::new(
+                    *memory_kib,
+                    *iterations,
+                    *parallelism,
+                    Some(ISSUER_WRAPPING_KEY_LENGTH),
+                )
+                .map_err(|_| Error::General("invalid argon2 parameters"))?;
+                let argon2 = argon2::Argon2::new(
+                    argon2::Algorithm::Argon2id,
+                    argon2::Version::V0x13,
+                    params,
+                );
+                let mut key = Zeroizing::new([0u8; ISSUER_WRAPPING_KEY_LENGTH]);
+                argon2
+                    .hash_password_into(password, salt, key.as_mut())
+                    .map_err(|_| Error::General("unable to derive the issuer wrapping key"))?;
+                Ok(key)
+            }
+        }
+    }
+}
+
+fn associated_data<S: ShortGroupSignatureScheme>(
+    version: u8,
+    public: &IssuerPublic<S>,
+    kdf: &IssuerWrappingKdf,
+) -> CredxResult<Vec<u8>> {
+    let mut aad = vec![version];
+    aad.append(&mut serde_json::to_vec(public).map_err(|_| Error::DeserializationError)?);
+    aad.append(&mut serde_json::to_vec(kdf).map_err(|_| Error::DeserializationError)?);
+    Ok(aad)
+}
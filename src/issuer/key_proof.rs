@@ -0,0 +1,121 @@
+use super::{InMemorySigner, Issuer, IssuerPublic};
+use crate::error::Error;
+use crate::knox::accumulator::vb20;
+use crate::knox::short_group_sig_core::short_group_traits::{
+    PublicKey as _, SecretKey as _, ShortGroupSignatureScheme,
+};
+use crate::knox::short_group_sig_core::{KeyCorrectnessProof, KeyRelation};
+use crate::presentation::PresentationSchema;
+use crate::statement::Statements;
+use crate::CredxResult;
+use blsful::inner_types::{G1Projective, G2Projective};
+use blsful::{Bls12381G2Impl, PublicKey};
+use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
+
+impl<S: ShortGroupSignatureScheme> Issuer<S> {
+    /// Create a new proof that the issuer's public keys were computed
+    /// correctly from its secret keys.
+    ///
+    /// New and rotated issuers already have a proof. This is only needed for
+    /// issuers created before proofs were published.
+    pub fn prove_keys(&mut self, rng: impl RngCore + CryptoRng) -> CredxResult<IssuerPublic<S>> {
+        self.key_proof = Some(self.signer.create_key_proof(&self.id, rng)?);
+        Ok(self.get_public())
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> InMemorySigner<S> {
+    pub(crate) fn create_key_proof(
+        &self,
+        issuer_id: &str,
+        rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<KeyCorrectnessProof> {
+        let relations = key_relations::<S>(
+            &self.signing_key.public_key(),
+            &vb20::PublicKey::from(&self.revocation_key),
+            &PublicKey::<Bls12381G2Impl>::from(&self.verifiable_decryption_key),
+        )?;
+        let mut secrets = self.signing_key.key_secrets();
+        secrets.push(self.revocation_key.0);
+        secrets.push(self.verifiable_decryption_key.0);
+        KeyCorrectnessProof::new(
+            &relations,
+            &secrets,
+            &mut key_proof_transcript(issuer_id),
+            rng,
+        )
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> IssuerPublic<S> {
+    /// Check the issuer's proof that its credential verifying key, revocation
+    /// verifying key and verifiable encryption key are well-formed.
+    ///
+    /// The verifying keys from before a rotation that are still accepted
+    /// must have their own proofs too.
+    /// Holders and verifiers should check this before accepting credentials
+    /// or presentation schemas from an issuer. Errors with
+    /// [`Error::MissingKeyProof`] if a key has no proof.
+    pub fn verify_key_proof(&self) -> CredxResult<()> {
+        self.verify_verifying_key_proof(&self.verifying_key, self.key_proof.as_ref())?;
+        for previous in &self.previous_keys {
+            self.verify_verifying_key_proof(&previous.verifying_key, previous.key_proof.as_ref())?;
+        }
+        Ok(())
+    }
+
+    fn verify_verifying_key_proof(
+        &self,
+        verifying_key: &S::PublicKey,
+        key_proof: Option<&KeyCorrectnessProof>,
+    ) -> CredxResult<()> {
+        let proof = key_proof.ok_or(Error::MissingKeyProof)?;
+        let relations = key_relations::<S>(
+            verifying_key,
+            &self.revocation_verifying_key,
+            &self.verifiable_encryption_key,
+        )?;
+        proof.verify(&relations, &mut key_proof_transcript(&self.id))
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> PresentationSchema<S> {
+    /// Check the key proof of every issuer in the schema's statements
+    pub fn verify_issuer_key_proofs(&self) -> CredxResult<()> {
+        for statement in self.statements.values() {
+            match statement {
+                Statements::Signature(s) => s.issuer.verify_key_proof()?,
+                Statements::BlindClaims(s) => s.issuer.verify_key_proof()?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+fn key_relations<S: ShortGroupSignatureScheme>(
+    verifying_key: &S::PublicKey,
+    revocation_verifying_key: &vb20::PublicKey,
+    verifiable_encryption_key: &PublicKey<Bls12381G2Impl>,
+) -> CredxResult<Vec<KeyRelation>> {
+    let mut relations = verifying_key.key_relations()?;
+    let offset = relations.iter().map(|r| r.secret() + 1).max().unwrap_or(0);
+    relations.push(KeyRelation::G2 {
+        secret: offset,
+        base: G2Projective::GENERATOR,
+        public: revocation_verifying_key.0,
+    });
+    relations.push(KeyRelation::G1 {
+        secret: offset + 1,
+        base: G1Projective::GENERATOR,
+        public: verifiable_encryption_key.0,
+    });
+    Ok(relations)
+}
+
+fn key_proof_transcript(issuer_id: &str) -> Transcript {
+    let mut transcript = Transcript::new(b"credx issuer key proof");
+    transcript.append_message(b"issuer id", issuer_id.as_bytes());
+    transcript
+}
@@ -0,0 +1,234 @@
+use super::{Issuer, IssuerSigner};
+use crate::blind::{BlindCredentialBundle, BlindCredentialRequest};
+use crate::claim::ClaimData;
+use crate::credential::{
+    CredentialBundle, CredentialOffer, CredentialRequest, CredentialResponse, CredentialSchema,
+    IssuedCredential,
+};
+use crate::error::Error;
+use crate::knox::short_group_sig_core::short_group_traits::{
+    BlindSignatureContext as _, ShortGroupSignatureScheme,
+};
+use crate::presentation::PresentationSchema;
+use crate::utils::{deserialize_map_entries, serialize_map_entries};
+use crate::CredxResult;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// How long an offer can be answered in seconds if not set
+pub const DEFAULT_OFFER_LIFETIME: u64 = 24 * 60 * 60;
+
+/// The signing steps [`OfferTracker::issue`] needs from an issuer
+pub trait OfferIssuer<S: ShortGroupSignatureScheme> {
+    /// The issuer's unique id
+    fn id(&self) -> &str;
+    /// The schema for this issuer
+    fn schema(&self) -> &CredentialSchema;
+    /// Sign the claims into a credential where each claim is keyed by its label or path
+    fn sign_credential_from_paths(
+        &mut self,
+        claims: &BTreeMap<String, ClaimData>,
+    ) -> CredxResult<CredentialBundle<S>>;
+    /// Blind sign a credential where only a subset of the claims are known,
+    /// checking the request's presentation of `schema` if given
+    fn blind_sign_credential(
+        &mut self,
+        request: &BlindCredentialRequest<S>,
+        claims: &BTreeMap<String, ClaimData>,
+        schema: Option<&PresentationSchema<S>>,
+    ) -> CredxResult<BlindCredentialBundle<S>>;
+}
+
+impl<S: ShortGroupSignatureScheme, K: IssuerSigner<S>> OfferIssuer<S> for Issuer<S, K> {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn schema(&self) -> &CredentialSchema {
+        &self.schema
+    }
+
+    fn sign_credential_from_paths(
+        &mut self,
+        claims: &BTreeMap<String, ClaimData>,
+    ) -> CredxResult<CredentialBundle<S>> {
+        Issuer::sign_credential_from_paths(self, claims)
+    }
+
+    fn blind_sign_credential(
+        &mut self,
+        request: &BlindCredentialRequest<S>,
+        claims: &BTreeMap<String, ClaimData>,
+        schema: Option<&PresentationSchema<S>>,
+    ) -> CredxResult<BlindCredentialBundle<S>> {
+        match schema {
+            Some(schema) => self.blind_sign_credential_with_schema(request, claims, schema),
+            None => Issuer::blind_sign_credential(self, request, claims),
+        }
+    }
+}
+
+/// An offer waiting for a request
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PendingOffer<S: ShortGroupSignatureScheme> {
+    /// The offer sent to the holder
+    #[serde(bound(
+        serialize = "CredentialOffer<S>: Serialize",
+        deserialize = "CredentialOffer<S>: Deserialize<'de>"
+    ))]
+    pub offer: CredentialOffer<S>,
+    /// When the offer expires in seconds since the Unix epoch
+    pub expires: i64,
+}
+
+/// The issuer's record of outstanding credential offers.
+///
+/// Each offer can be answered once before it expires. Answered offer ids
+/// are remembered until their offer expires so replayed requests are rejected.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OfferTracker<S: ShortGroupSignatureScheme> {
+    /// The offers waiting for a request
+    #[serde(bound(
+        serialize = "PendingOffer<S>: Serialize",
+        deserialize = "PendingOffer<S>: Deserialize<'de>"
+    ))]
+    pub pending: Vec<PendingOffer<S>>,
+    /// The ids of offers that have been answered or cancelled
+    /// and when their offer expires
+    #[serde(
+        serialize_with = "serialize_map_entries",
+        deserialize_with = "deserialize_map_entries"
+    )]
+    pub answered: BTreeMap<[u8; 16], i64>,
+    /// How long new offers can be answered in seconds
+    pub offer_lifetime: u64,
+}
+
+impl<S: ShortGroupSignatureScheme> Default for OfferTracker<S> {
+    fn default() -> Self {
+        Self {
+            pending: Vec::new(),
+            answered: BTreeMap::new(),
+            offer_lifetime: DEFAULT_OFFER_LIFETIME,
+        }
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> OfferTracker<S> {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an offer sent to a holder that expires after the tracker's
+    /// offer lifetime
+    pub fn add_offer(&mut self, offer: &CredentialOffer<S>) -> CredxResult<()> {
+        let expires = unix_time().saturating_add(self.offer_lifetime as i64);
+        self.add_offer_until(offer, expires)
+    }
+
+    /// Record an offer sent to a holder that expires at `expires`
+    /// in seconds since the Unix epoch
+    pub fn add_offer_until(&mut self, offer: &CredentialOffer<S>, expires: i64) -> CredxResult<()> {
+        self.prune();
+        if self.answered.contains_key(&offer.offer_id) || self.get_offer(&offer.offer_id).is_some()
+        {
+            return Err(Error::General("credential offer id is already used"));
+        }
+        offer.known_claims()?;
+        self.pending.push(PendingOffer {
+            offer: offer.clone(),
+            expires,
+        });
+        Ok(())
+    }
+
+    /// The pending offer with `offer_id` if it hasn't expired
+    pub fn get_offer(&self, offer_id: &[u8; 16]) -> Option<&CredentialOffer<S>> {
+        let now = unix_time();
+        self.pending
+            .iter()
+            .find(|p| &p.offer.offer_id == offer_id && p.expires >= now)
+            .map(|p| &p.offer)
+    }
+
+    /// Withdraw a pending offer so it can't be answered
+    pub fn cancel_offer(&mut self, offer_id: &[u8; 16]) -> CredxResult<CredentialOffer<S>> {
+        let index = self
+            .pending
+            .iter()
+            .position(|p| &p.offer.offer_id == offer_id)
+            .ok_or(Error::General("unknown credential offer"))?;
+        let pending = self.pending.remove(index);
+        self.answered.insert(*offer_id, pending.expires);
+        Ok(pending.offer)
+    }
+
+    /// Forget the offers that expired and the answered offer ids whose
+    /// offer expired, they can't be answered anymore
+    pub fn prune(&mut self) {
+        let now = unix_time();
+        self.pending.retain(|p| p.expires >= now);
+        self.answered.retain(|_, expires| *expires >= now);
+    }
+
+    /// Issue the credential for a request that answers a pending offer.
+    ///
+    /// The offer stays pending if issuance fails.
+    pub fn issue<I: OfferIssuer<S> + ?Sized>(
+        &mut self,
+        issuer: &mut I,
+        request: &CredentialRequest<S>,
+    ) -> CredxResult<CredentialResponse<S>> {
+        self.prune();
+        if self.answered.contains_key(&request.offer_id) {
+            return Err(Error::General("credential offer was already answered"));
+        }
+        let index = self
+            .pending
+            .iter()
+            .position(|p| p.offer.offer_id == request.offer_id)
+            .ok_or(Error::General("unknown credential offer"))?;
+        let offer = &self.pending[index].offer;
+        if offer.issuer.id != issuer.id() || offer.issuer.schema.id != issuer.schema().id {
+            return Err(Error::General("credential offer is for a different issuer"));
+        }
+        let known_claims = offer.known_claims()?;
+        let credential = match &request.blind_request {
+            None if offer.blind_claim_labels.is_empty() => {
+                IssuedCredential::Credential(issuer.sign_credential_from_paths(&known_claims)?)
+            }
+            Some(blind_request)
+                if blind_request.nonce == offer.nonce
+                    && blind_request.blind_signature_context.format()
+                        == offer.blind_signature_format
+                    && blind_request.blind_claim_labels.len() == offer.blind_claim_labels.len()
+                    && offer
+                        .blind_claim_labels
+                        .iter()
+                        .all(|l| blind_request.blind_claim_labels.contains(l)) =>
+            {
+                IssuedCredential::Blind(issuer.blind_sign_credential(
+                    blind_request,
+                    &known_claims,
+                    offer.presentation_schema.as_ref(),
+                )?)
+            }
+            _ => {
+                return Err(Error::General(
+                    "credential request doesn't answer the offer",
+                ))
+            }
+        };
+        let pending = self.pending.remove(index);
+        self.answered.insert(request.offer_id, pending.expires);
+        Ok(CredentialResponse {
+            offer_id: request.offer_id,
+            credential,
+        })
+    }
+}
+
+fn unix_time() -> i64 {
+    chrono::Utc::now().timestamp()
+}
@@ -0,0 +1,99 @@
+use super::IssuerPublic;
+use crate::error::Error;
+use crate::knox::short_group_sig_core::short_group_traits::{
+    PublicKey as _, ShortGroupSignatureScheme,
+};
+use crate::CredxResult;
+use blsful::inner_types::GroupEncoding;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The id of an issuer's public information
+pub type IssuerKeyId = [u8; 32];
+
+impl<S: ShortGroupSignatureScheme> IssuerPublic<S> {
+    /// The id that refers to this issuer's public information.
+    ///
+    /// Covers everything but the revocation registry which changes
+    /// whenever a credential is revoked.
+    pub fn key_id(&self) -> IssuerKeyId {
+        let mut transcript = merlin::Transcript::new(b"credx issuer key id");
+        transcript.append_message(b"issuer id", self.id.as_bytes());
+        transcript.append_message(
+            b"issuer verifying key",
+            self.verifying_key.to_bytes().as_slice(),
+        );
+        transcript.append_message(
+            b"issuer revocation verifying key",
+            self.revocation_verifying_key.to_bytes().as_slice(),
+        );
+        transcript.append_message(
+            b"issuer verifiable encryption key",
+            self.verifiable_encryption_key.0.to_bytes().as_ref(),
+        );
+        transcript.append_u64(b"issuer key version", self.key_version as u64);
+        transcript.append_u64(
+            b"issuer previous keys length",
+            self.previous_keys.len() as u64,
+        );
+        for previous in &self.previous_keys {
+            transcript.append_u64(b"issuer previous key version", previous.key_version as u64);
+            transcript.append_message(
+                b"issuer previous verifying key",
+                previous.verifying_key.to_bytes().as_slice(),
+            );
+        }
+        self.schema.add_challenge_contribution(&mut transcript);
+        let mut key_id = [0u8; 32];
+        transcript.challenge_bytes(b"issuer key id", &mut key_id);
+        key_id
+    }
+}
+
+/// Issuer public information by key id so credentials and presentation
+/// schemas can refer to an issuer instead of carrying its keys.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct IssuerKeyRegistry<S: ShortGroupSignatureScheme> {
+    /// The issuers by their hex encoded key id
+    #[serde(bound(
+        serialize = "IssuerPublic<S>: Serialize",
+        deserialize = "IssuerPublic<S>: Deserialize<'de>"
+    ))]
+    pub issuers: BTreeMap<String, IssuerPublic<S>>,
+}
+
+impl<S: ShortGroupSignatureScheme> Default for IssuerKeyRegistry<S> {
+    fn default() -> Self {
+        Self {
+            issuers: BTreeMap::new(),
+        }
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> IssuerKeyRegistry<S> {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an issuer's public information and return its key id.
+    ///
+    /// Adding an issuer again replaces its revocation registry.
+    pub fn add(&mut self, issuer: &IssuerPublic<S>) -> IssuerKeyId {
+        let key_id = issuer.key_id();
+        self.issuers.insert(hex::encode(key_id), issuer.clone());
+        key_id
+    }
+
+    /// The issuer with `key_id`
+    pub fn get(&self, key_id: &IssuerKeyId) -> CredxResult<&IssuerPublic<S>> {
+        self.issuers
+            .get(&hex::encode(key_id))
+            .ok_or(Error::General("unknown issuer key id"))
+    }
+
+    /// Remove the issuer with `key_id`
+    pub fn remove(&mut self, key_id: &IssuerKeyId) -> Option<IssuerPublic<S>> {
+        self.issuers.remove(&hex::encode(key_id))
+    }
+}
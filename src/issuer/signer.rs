@@ -0,0 +1,505 @@
+use crate::error::Error;
+use crate::knox::{
+    accumulator::vb20::{self, Accumulator, Element, MembershipWitness},
+    short_group_sig_core::{
+        short_group_traits::{SecretKey as _, ShortGroupSignatureScheme, Signature as _},
+        KeyCorrectnessProof,
+    },
+};
+use crate::CredxResult;
+use blsful::{inner_types::Scalar, Bls12381G2Impl, PublicKey, SecretKey};
+use serde::{Deserialize, Serialize};
+
+/// The operations an issuer needs from its secret keys.
+///
+/// [`Issuer`](super::Issuer) uses an [`InMemorySigner`] by default.
+/// Implement this trait to keep the keys somewhere else like an HSM,
+/// KMS or separate process and create the issuer with
+/// [`Issuer::new_with_signer`](super::Issuer::new_with_signer).
+pub trait IssuerSigner<S: ShortGroupSignatureScheme> {
+    /// The credential verifying key
+    fn verifying_key(&self) -> S::PublicKey;
+    /// The revocation verifying key
+    fn revocation_verifying_key(&self) -> vb20::PublicKey;
+    /// The verifiable encryption key
+    fn verifiable_encryption_key(&self) -> PublicKey<Bls12381G2Impl>;
+    /// Prove the public keys are well-formed for the issuer `issuer_id`
+    fn key_proof(&self, issuer_id: &str) -> CredxResult<KeyCorrectnessProof>;
+    /// Sign the credential messages
+    fn sign(&self, messages: &[Scalar]) -> CredxResult<S::Signature>;
+    /// Complete a blind signature with the known messages
+    fn blind_sign(
+        &self,
+        context: &S::BlindSignatureContext,
+        messages: &[(usize, Scalar)],
+        nonce: Scalar,
+    ) -> CredxResult<S::BlindSignature>;
+    /// Create a membership witness for `element` in `accumulator`
+    fn membership_witness(
+        &self,
+        element: Element,
+        accumulator: Accumulator,
+    ) -> CredxResult<MembershipWitness>;
+    /// Remove `elements` from `accumulator` and return the new value
+    fn remove_elements(
+        &self,
+        accumulator: Accumulator,
+        elements: &[Element],
+    ) -> CredxResult<Accumulator>;
+}
+
+impl<S: ShortGroupSignatureScheme, K: IssuerSigner<S> + ?Sized> IssuerSigner<S> for &K {
+    fn verifying_key(&self) -> S::PublicKey {
+        (*self).verifying_key()
+    }
+
+    fn revocation_verifying_key(&self) -> vb20::PublicKey {
+        (*self).revocation_verifying_key()
+    }
+
+    fn verifiable_encryption_key(&self) -> PublicKey<Bls12381G2Impl> {
+        (*self).verifiable_encryption_key()
+    }
+
+    fn key_proof(&self, issuer_id: &str) -> CredxResult<KeyCorrectnessProof> {
+        (*self).key_proof(issuer_id)
+    }
+
+    fn sign(&self, messages: &[Scalar]) -> CredxResult<S::Signature> {
+        (*self).sign(messages)
+    }
+
+    fn blind_sign(
+        &self,
+        context: &S::BlindSignatureContext,
+        messages: &[(usize, Scalar)],
+        nonce: Scalar,
+    ) -> CredxResult<S::BlindSignature> {
+        (*self).blind_sign(context, messages, nonce)
+    }
+
+    fn membership_witness(
+        &self,
+        element: Element,
+        accumulator: Accumulator,
+    ) -> CredxResult<MembershipWitness> {
+        (*self).membership_witness(element, accumulator)
+    }
+
+    fn remove_elements(
+        &self,
+        accumulator: Accumulator,
+        elements: &[Element],
+    ) -> CredxResult<Accumulator> {
+        (*self).remove_elements(accumulator, elements)
+    }
+}
+
+/// A signer that holds the issuer secret keys in memory
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct InMemorySigner<S: ShortGroupSignatureScheme> {
+    /// The credential signing key
+    pub signing_key: S::SecretKey,
+    /// The revocation update key
+    pub revocation_key: vb20::SecretKey,
+    /// The verifiable decryption key
+    pub verifiable_decryption_key: SecretKey<Bls12381G2Impl>,
+}
+
+impl<S: ShortGroupSignatureScheme> IssuerSigner<S> for InMemorySigner<S> {
+    fn verifying_key(&self) -> S::PublicKey {
+        self.signing_key.public_key()
+    }
+
+    fn revocation_verifying_key(&self) -> vb20::PublicKey {
+        vb20::PublicKey::from(&self.revocation_key)
+    }
+
+    fn verifiable_encryption_key(&self) -> PublicKey<Bls12381G2Impl> {
+        PublicKey::from(&self.verifiable_decryption_key)
+    }
+
+    fn key_proof(&self, issuer_id: &str) -> CredxResult<KeyCorrectnessProof> {
+        self.create_key_proof(issuer_id, rand::thread_rng())
+    }
+
+    fn sign(&self, messages: &[Scalar]) -> CredxResult<S::Signature> {
+        S::Signature::create(&self.signing_key, messages)
+            .map_err(|_| Error::InvalidSigningOperation)
+    }
+
+    fn blind_sign(
+        &self,
+        context: &S::BlindSignatureContext,
+        messages: &[(usize, Scalar)],
+        nonce: Scalar,
+    ) -> CredxResult<S::BlindSignature> {
+        S::blind_sign(context, &self.signing_key, messages, nonce)
+            .map_err(|_| Error::InvalidSigningOperation)
+    }
+
+    fn membership_witness(
+        &self,
+        element: Element,
+        accumulator: Accumulator,
+    ) -> CredxResult<MembershipWitness> {
+        Ok(MembershipWitness::new(
+            element,
+            accumulator,
+            &self.revocation_key,
+        ))
+    }
+
+    fn remove_elements(
+        &self,
+        accumulator: Accumulator,
+        elements: &[Element],
+    ) -> CredxResult<Accumulator> {
+        let mut accumulator = accumulator;
+        accumulator.remove_elements_assign(&self.revocation_key, elements);
+        Ok(accumulator)
+    }
+}
+
+#[cfg(unix)]
+pub use unix_socket::*;
+
+#[cfg(unix)]
+mod unix_socket {
+    use super::IssuerSigner;
+    use crate::error::Error;
+    use crate::knox::{
+        accumulator::vb20::{self, Accumulator, Element, MembershipWitness},
+        short_group_sig_core::{
+            short_group_traits::ShortGroupSignatureScheme, KeyCorrectnessProof,
+        },
+    };
+    use crate::CredxResult;
+    use blsful::{inner_types::Scalar, Bls12381G2Impl, PublicKey};
+    use log::warn;
+    use serde::{Deserialize, Serialize};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::marker::PhantomData;
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+
+    /// The longest line of JSON a [`UnixSocketSignerServer`] or
+    /// [`UnixSocketSigner`] reads before dropping the connection
+    pub const MAX_REQUEST_BYTES: usize = 4 * 1024 * 1024;
+
+    /// How long a [`UnixSocketSignerServer`] or [`UnixSocketSigner`] waits
+    /// to read or write a line before dropping the connection
+    pub const SIGNER_TIMEOUT: Duration = Duration::from_secs(30);
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(bound(serialize = "", deserialize = ""))]
+    enum SignerRequest<S: ShortGroupSignatureScheme> {
+        PublicKeys,
+        KeyProof(String),
+        Sign(Vec<Scalar>),
+        BlindSign {
+            context: S::BlindSignatureContext,
+            messages: Vec<(usize, Scalar)>,
+            nonce: Scalar,
+        },
+        MembershipWitness {
+            element: Element,
+            accumulator: Accumulator,
+        },
+        RemoveElements {
+            accumulator: Accumulator,
+            elements: Vec<Element>,
+        },
+    }
+
+    #[allow(clippy::large_enum_variant)]
+    #[derive(Deserialize, Serialize)]
+    #[serde(bound(serialize = "", deserialize = ""))]
+    enum SignerResponse<S: ShortGroupSignatureScheme> {
+        PublicKeys {
+            verifying_key: S::PublicKey,
+            revocation_verifying_key: vb20::PublicKey,
+            verifiable_encryption_key: PublicKey<Bls12381G2Impl>,
+        },
+        KeyProof(KeyCorrectnessProof),
+        Signature(S::Signature),
+        BlindSignature(S::BlindSignature),
+        MembershipWitness(MembershipWitness),
+        Accumulator(Accumulator),
+        Error,
+    }
+
+    /// A signer in another process reached over a Unix domain socket.
+    ///
+    /// Each operation opens a connection and exchanges one line of JSON
+    /// in each direction with a [`UnixSocketSignerServer`]. The public
+    /// keys are fetched once when connecting.
+    #[derive(Clone, Debug)]
+    pub struct UnixSocketSigner<S: ShortGroupSignatureScheme> {
+        path: PathBuf,
+        timeout: Duration,
+        verifying_key: S::PublicKey,
+        revocation_verifying_key: vb20::PublicKey,
+        verifiable_encryption_key: PublicKey<Bls12381G2Impl>,
+    }
+
+    impl<S: ShortGroupSignatureScheme> UnixSocketSigner<S> {
+        /// Connect to the signer at `path` and fetch its public keys
+        pub fn connect<P: AsRef<Path>>(path: P) -> CredxResult<Self> {
+            Self::connect_with_timeout(path, SIGNER_TIMEOUT)
+        }
+
+        /// Connect to the signer at `path` waiting at most `timeout`
+        /// for each read or write
+        pub fn connect_with_timeout<P: AsRef<Path>>(
+            path: P,
+            timeout: Duration,
+        ) -> CredxResult<Self> {
+            let path = path.as_ref().to_path_buf();
+            match call::<S>(&path, timeout, &SignerRequest::PublicKeys)? {
+                SignerResponse::PublicKeys {
+                    verifying_key,
+                    revocation_verifying_key,
+                    verifiable_encryption_key,
+                } => Ok(Self {
+                    path,
+                    timeout,
+                    verifying_key,
+                    revocation_verifying_key,
+                    verifiable_encryption_key,
+                }),
+                _ => Err(Error::General("unexpected signer response")),
+            }
+        }
+
+        fn call(&self, request: &SignerRequest<S>) -> CredxResult<SignerResponse<S>> {
+            call(&self.path, self.timeout, request)
+        }
+    }
+
+    impl<S: ShortGroupSignatureScheme> IssuerSigner<S> for UnixSocketSigner<S> {
+        fn verifying_key(&self) -> S::PublicKey {
+            self.verifying_key.clone()
+        }
+
+        fn revocation_verifying_key(&self) -> vb20::PublicKey {
+            self.revocation_verifying_key
+        }
+
+        fn verifiable_encryption_key(&self) -> PublicKey<Bls12381G2Impl> {
+            self.verifiable_encryption_key
+        }
+
+        fn key_proof(&self, issuer_id: &str) -> CredxResult<KeyCorrectnessProof> {
+            match self.call(&SignerRequest::KeyProof(issuer_id.to_string()))? {
+                SignerResponse::KeyProof(proof) => Ok(proof),
+                _ => Err(Error::General("unexpected signer response")),
+            }
+        }
+
+        fn sign(&self, messages: &[Scalar]) -> CredxResult<S::Signature> {
+            match self.call(&SignerRequest::Sign(messages.to_vec()))? {
+                SignerResponse::Signature(signature) => Ok(signature),
+                _ => Err(Error::InvalidSigningOperation),
+            }
+        }
+
+        fn blind_sign(
+            &self,
+            context: &S::BlindSignatureContext,
+            messages: &[(usize, Scalar)],
+            nonce: Scalar,
+        ) -> CredxResult<S::BlindSignature> {
+            let request = SignerRequest::BlindSign {
+                context: context.clone(),
+                messages: messages.to_vec(),
+                nonce,
+            };
+            match self.call(&request)? {
+                SignerResponse::BlindSignature(signature) => Ok(signature),
+                _ => Err(Error::InvalidSigningOperation),
+            }
+        }
+
+        fn membership_witness(
+            &self,
+            element: Element,
+            accumulator: Accumulator,
+        ) -> CredxResult<MembershipWitness> {
+            let request = SignerRequest::MembershipWitness {
+                element,
+                accumulator,
+            };
+            match self.call(&request)? {
+                SignerResponse::MembershipWitness(witness) => Ok(witness),
+                _ => Err(Error::General("unexpected signer response")),
+            }
+        }
+
+        fn remove_elements(
+            &self,
+            accumulator: Accumulator,
+            elements: &[Element],
+        ) -> CredxResult<Accumulator> {
+            let request = SignerRequest::RemoveElements {
+                accumulator,
+                elements: elements.to_vec(),
+            };
+            match self.call(&request)? {
+                SignerResponse::Accumulator(accumulator) => Ok(accumulator),
+                _ => Err(Error::InvalidRevocationRegistryRevokeOperation),
+            }
+        }
+    }
+
+    /// Answers requests from [`UnixSocketSigner`]s with another signer.
+    ///
+    /// Each connection is answered on its own thread.
+    #[derive(Debug)]
+    pub struct UnixSocketSignerServer<S: ShortGroupSignatureScheme, K: IssuerSigner<S>> {
+        listener: UnixListener,
+        signer: K,
+        timeout: Duration,
+        _scheme: PhantomData<fn() -> S>,
+    }
+
+    impl<S: ShortGroupSignatureScheme, K: IssuerSigner<S>> UnixSocketSignerServer<S, K> {
+        /// Bind a new socket at `path` that answers with `signer`
+        pub fn bind<P: AsRef<Path>>(path: P, signer: K) -> CredxResult<Self> {
+            let listener = UnixListener::bind(path)
+                .map_err(|_| Error::General("unable to bind the signer socket"))?;
+            Ok(Self::new(listener, signer))
+        }
+
+        /// Answer connections to `listener` with `signer`
+        pub fn new(listener: UnixListener, signer: K) -> Self {
+            Self {
+                listener,
+                signer,
+                timeout: SIGNER_TIMEOUT,
+                _scheme: PhantomData,
+            }
+        }
+
+        /// Wait at most `timeout` for each read or write instead of [`SIGNER_TIMEOUT`]
+        pub fn set_timeout(&mut self, timeout: Duration) {
+            self.timeout = timeout;
+        }
+
+        /// Answer connections until the listener fails.
+        ///
+        /// Connections that fail are logged and dropped without affecting the others.
+        pub fn serve(&self) -> CredxResult<()>
+        where
+            K: Sync,
+        {
+            std::thread::scope(|scope| {
+                for stream in self.listener.incoming() {
+                    let stream = stream
+                        .map_err(|_| Error::General("unable to accept a signer connection"))?;
+                    scope.spawn(move || {
+                        if let Err(e) = self.handle(stream) {
+                            warn!("Signer connection failed: {:?}", e);
+                        }
+                    });
+                }
+                Ok(())
+            })
+        }
+
+        /// Answer the next connection
+        pub fn serve_one(&self) -> CredxResult<()> {
+            let (stream, _) = self
+                .listener
+                .accept()
+                .map_err(|_| Error::General("unable to accept a signer connection"))?;
+            self.handle(stream)
+        }
+
+        fn handle(&self, stream: UnixStream) -> CredxResult<()> {
+            let mut stream = stream;
+            set_timeouts(&stream, self.timeout)?;
+            let request = read_line::<SignerRequest<S>>(&stream)?;
+            let response = self.respond(request).unwrap_or_else(|e| {
+                warn!("Signer request failed: {:?}", e);
+                SignerResponse::Error
+            });
+            write_line(&mut stream, &response)
+        }
+
+        fn respond(&self, request: SignerRequest<S>) -> CredxResult<SignerResponse<S>> {
+            Ok(match request {
+                SignerRequest::PublicKeys => SignerResponse::PublicKeys {
+                    verifying_key: self.signer.verifying_key(),
+                    revocation_verifying_key: self.signer.revocation_verifying_key(),
+                    verifiable_encryption_key: self.signer.verifiable_encryption_key(),
+                },
+                SignerRequest::KeyProof(issuer_id) => {
+                    SignerResponse::KeyProof(self.signer.key_proof(&issuer_id)?)
+                }
+                SignerRequest::Sign(messages) => {
+                    SignerResponse::Signature(self.signer.sign(&messages)?)
+                }
+                SignerRequest::BlindSign {
+                    context,
+                    messages,
+                    nonce,
+                } => SignerResponse::BlindSignature(
+                    self.signer.blind_sign(&context, &messages, nonce)?,
+                ),
+                SignerRequest::MembershipWitness {
+                    element,
+                    accumulator,
+                } => SignerResponse::MembershipWitness(
+                    self.signer.membership_witness(element, accumulator)?,
+                ),
+                SignerRequest::RemoveElements {
+                    accumulator,
+                    elements,
+                } => SignerResponse::Accumulator(
+                    self.signer.remove_elements(accumulator, &elements)?,
+                ),
+            })
+        }
+    }
+
+    fn call<S: ShortGroupSignatureScheme>(
+        path: &Path,
+        timeout: Duration,
+        request: &SignerRequest<S>,
+    ) -> CredxResult<SignerResponse<S>> {
+        let mut stream = UnixStream::connect(path)
+            .map_err(|_| Error::General("unable to connect to the signer"))?;
+        set_timeouts(&stream, timeout)?;
+        write_line(&mut stream, request)?;
+        read_line(&stream)
+    }
+
+    fn set_timeouts(stream: &UnixStream, timeout: Duration) -> CredxResult<()> {
+        stream
+            .set_read_timeout(Some(timeout))
+            .and_then(|_| stream.set_write_timeout(Some(timeout)))
+            .map_err(|_| Error::General("unable to set the signer socket timeout"))
+    }
+
+    fn write_line<T: Serialize>(stream: &mut UnixStream, value: &T) -> CredxResult<()> {
+        let mut line =
+            serde_json::to_vec(value).map_err(|_| Error::General("unable to serialize"))?;
+        line.push(b'\n');
+        stream
+            .write_all(&line)
+            .map_err(|_| Error::General("unable to write to the signer socket"))
+    }
+
+    fn read_line<T: for<'de> Deserialize<'de>>(stream: &UnixStream) -> CredxResult<T> {
+        let mut line = Vec::new();
+        BufReader::new(stream.take(MAX_REQUEST_BYTES as u64 + 1))
+            .read_until(b'\n', &mut line)
+            .map_err(|_| Error::General("unable to read from the signer socket"))?;
+        if line.len() > MAX_REQUEST_BYTES {
+            return Err(Error::General("the signer request is too long"));
+        }
+        serde_json::from_slice(&line).map_err(|_| Error::DeserializationError)
+    }
+}
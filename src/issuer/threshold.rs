@@ -0,0 +1,567 @@
+use super::{check_claims, Issuer, IssuerPublic};
+use crate::claim::{ClaimData, RevocationClaim};
+use crate::credential::{Credential, CredentialBundle, CredentialSchema};
+use crate::error::Error;
+use crate::knox::{
+    accumulator::vb20::{self, Accumulator, Element, MembershipWitness},
+    short_group_sig_core::KeyCorrectnessProof,
+    threshold::{
+        check_threshold, combine_inversion, deal_inversion_nonces, inversion_participants,
+        partial_inversion, receive_inversion_nonces, split_scalar, InversionNonce, NonceShares,
+        PartialInversion, ThresholdSignatureScheme,
+    },
+};
+use crate::revocation_registry::RevocationRegistry;
+use crate::CredxResult;
+use blsful::inner_types::{Field, G1Projective, Scalar};
+use blsful::{Bls12381G2Impl, PublicKey};
+use log::debug;
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Splits an issuer into threshold signers.
+///
+/// The credential signing key and the revocation key are shared so only
+/// schemes that implement [`ThresholdSignatureScheme`] can be split.
+/// Revocation handles and revocations need the revocation key so the
+/// signers compute them together with a multiparty inversion, which needs
+/// `2 * threshold - 1` signers and single use nonces the signers deal each
+/// other in advance.
+///
+/// The dealer holds the complete issuer so it is consumed by
+/// [`ThresholdDealer::split`] and nothing needs it afterwards.
+#[derive(Debug)]
+pub struct ThresholdDealer<S: ThresholdSignatureScheme> {
+    issuer: Issuer<S>,
+    threshold: usize,
+    limit: usize,
+}
+
+impl<S: ThresholdSignatureScheme> ThresholdDealer<S> {
+    /// Create a dealer where any `threshold` of `limit` signers can issue
+    pub fn new(issuer: Issuer<S>, threshold: usize, limit: usize) -> CredxResult<Self> {
+        check_threshold(threshold, limit)?;
+        if inversion_participants(threshold).max(S::signing_participants(threshold)) > limit {
+            return Err(Error::General(
+                "the threshold needs more signers than the limit",
+            ));
+        }
+        if !issuer.previous_keys.is_empty() {
+            return Err(Error::General(
+                "end the key rotation grace period before splitting the issuer",
+            ));
+        }
+        Ok(Self {
+            issuer,
+            threshold,
+            limit,
+        })
+    }
+
+    /// Create the coordinator and the `limit` signers.
+    ///
+    /// The coordinator gets the revocation registry and the issuer's key
+    /// proof, the signers get the shares of the credential signing key and
+    /// the revocation key.
+    pub fn split(
+        self,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<(ThresholdIssuer<S>, Vec<ThresholdSigner<S>>)> {
+        let public = self.issuer.get_public();
+        let key_proof = match public.key_proof {
+            Some(key_proof) => key_proof,
+            None => self
+                .issuer
+                .signer
+                .create_key_proof(&self.issuer.id, &mut rng)?,
+        };
+        let shares = S::split_secret_key(
+            &self.issuer.signer.signing_key,
+            self.threshold,
+            self.limit,
+            &mut rng,
+        )?;
+        let revocation_shares = split_scalar(
+            self.issuer.signer.revocation_key.0,
+            self.threshold,
+            self.limit,
+            rng,
+        )?;
+        let signers = shares
+            .into_iter()
+            .zip(revocation_shares)
+            .enumerate()
+            .map(|(i, (signing_key, revocation_key))| ThresholdSigner {
+                id: public.id.clone(),
+                schema: public.schema.clone(),
+                index: (i + 1) as u32,
+                threshold: self.threshold,
+                limit: self.limit,
+                signing_key,
+                revocation_key,
+                nonces: BTreeMap::new(),
+            })
+            .collect();
+        let issuer = ThresholdIssuer {
+            id: public.id,
+            schema: public.schema,
+            threshold: self.threshold,
+            limit: self.limit,
+            verifying_key: public.verifying_key,
+            revocation_verifying_key: public.revocation_verifying_key,
+            verifiable_encryption_key: public.verifiable_encryption_key,
+            revocation_registry: self.issuer.revocation_registry.clone(),
+            key_version: public.key_version,
+            key_proof,
+            nonces: Vec::new(),
+            next_nonce: 0,
+        };
+        Ok((issuer, signers))
+    }
+}
+
+/// Coordinates threshold issuance and manages the revocation registry.
+///
+/// The coordinator holds no share of the credential signing key or the
+/// revocation key. It checks requests, hands out the signers' nonces and
+/// combines the signers' parts.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ThresholdIssuer<S: ThresholdSignatureScheme> {
+    /// The issuer's unique id
+    pub id: String,
+    /// The schema for this issuer
+    pub schema: CredentialSchema,
+    /// The number of shares that recover a key
+    pub threshold: usize,
+    /// The number of signers
+    pub limit: usize,
+    /// The credential verifying key for this issuer
+    pub verifying_key: S::PublicKey,
+    /// The revocation registry verifying key for this issuer
+    pub revocation_verifying_key: vb20::PublicKey,
+    /// The verifiable encryption key for this issuer
+    pub verifiable_encryption_key: PublicKey<Bls12381G2Impl>,
+    /// The revocation registry for this issuer
+    pub revocation_registry: RevocationRegistry,
+    /// The version of the credential verifying key
+    pub key_version: u32,
+    /// The dealer's proof that the public keys are well-formed
+    pub key_proof: KeyCorrectnessProof,
+    /// The ids of the nonces the signers hold that haven't been used
+    pub nonces: Vec<u64>,
+    /// The id of the next nonce
+    pub next_nonce: u64,
+}
+
+/// A request for each signer to deal nonces to the others
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ThresholdNonceRequest {
+    /// The ids of the new nonces
+    pub ids: Vec<u64>,
+}
+
+/// A request for signers to sign a credential
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ThresholdSigningRequest {
+    /// The normalized claims to sign
+    pub claims: Vec<ClaimData>,
+    /// The revocation registry value for the revocation handle
+    pub revocation_registry: Accumulator,
+    /// The ids of the nonces for the signature and the revocation handle
+    pub nonces: Vec<u64>,
+}
+
+/// A signer's part of a credential
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ThresholdPartialCredential<S: ThresholdSignatureScheme> {
+    /// The signer index
+    pub index: u32,
+    /// The part of the signature
+    pub signature: S::PartialSignature,
+    /// The part of the revocation handle
+    pub revocation_handle: PartialInversion,
+}
+
+/// A request for signers to compute revocation handles for the current
+/// revocation registry
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ThresholdWitnessRequest {
+    /// The revocation claim values
+    pub claims: Vec<String>,
+    /// The revocation registry value
+    pub revocation_registry: Accumulator,
+    /// The ids of the nonces, one for each claim
+    pub nonces: Vec<u64>,
+}
+
+/// A signer's parts of revocation handles
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ThresholdPartialWitnesses {
+    /// The signer index
+    pub index: u32,
+    /// The part of each revocation handle
+    pub witnesses: Vec<PartialInversion>,
+}
+
+impl<S: ThresholdSignatureScheme> ThresholdIssuer<S> {
+    /// The public data for this issuer
+    pub fn get_public(&self) -> IssuerPublic<S> {
+        IssuerPublic {
+            id: self.id.clone(),
+            schema: self.schema.clone(),
+            verifying_key: self.verifying_key.clone(),
+            revocation_verifying_key: self.revocation_verifying_key,
+            verifiable_encryption_key: self.verifiable_encryption_key,
+            revocation_registry: self.revocation_registry.value,
+            key_version: self.key_version,
+            previous_keys: Vec::new(),
+            key_proof: Some(self.key_proof.clone()),
+        }
+    }
+
+    /// The number of signers needed to combine a credential, a revocation
+    /// handle or a revocation
+    pub fn signing_participants(&self) -> usize {
+        inversion_participants(self.threshold).max(S::signing_participants(self.threshold))
+    }
+
+    /// Create a request for `count` new nonces.
+    ///
+    /// Each signer deals shares with [`ThresholdSigner::deal_nonces`] and
+    /// receives the shares addressed to it from at least a threshold of
+    /// signers with [`ThresholdSigner::receive_nonces`]. Each credential
+    /// uses `S::SIGNING_NONCES + 1` nonces and each revocation handle or
+    /// revoked claim uses one.
+    pub fn nonce_request(&mut self, count: usize) -> ThresholdNonceRequest {
+        let ids = (self.next_nonce..self.next_nonce + count as u64).collect::<Vec<_>>();
+        self.next_nonce += count as u64;
+        self.nonces.extend_from_slice(&ids);
+        ThresholdNonceRequest { ids }
+    }
+
+    /// Check the claims and create a request for signers to sign them
+    pub fn signing_request(
+        &mut self,
+        claims: &[ClaimData],
+    ) -> CredxResult<ThresholdSigningRequest> {
+        let (claims, revocation_index) = check_claims(&self.schema, claims)?;
+        self.check_not_revoked(&claims[revocation_index])?;
+        Ok(ThresholdSigningRequest {
+            claims,
+            revocation_registry: self.revocation_registry.value,
+            nonces: self.take_nonces(S::SIGNING_NONCES + 1)?,
+        })
+    }
+
+    /// Combine the signers' parts into a credential
+    pub fn combine_credential(
+        &mut self,
+        request: &ThresholdSigningRequest,
+        partials: &[ThresholdPartialCredential<S>],
+    ) -> CredxResult<CredentialBundle<S>> {
+        let (claims, revocation_index) = check_claims(&self.schema, &request.claims)?;
+        let revocation_claim = self.check_not_revoked(&claims[revocation_index])?.clone();
+        self.check_registry(request.revocation_registry)?;
+        let attributes = claims.iter().map(|c| c.to_scalar()).collect::<Vec<_>>();
+        let signatures = partials
+            .iter()
+            .map(|p| p.signature.clone())
+            .collect::<Vec<_>>();
+        let signature = S::combine_signatures(
+            &self.verifying_key,
+            &attributes,
+            &request_id(&request.nonces),
+            &signatures,
+        )?;
+        let handles = partials
+            .iter()
+            .map(|p| p.revocation_handle.clone())
+            .collect::<Vec<_>>();
+        let revocation_handle =
+            self.combine_witness(Element(attributes[revocation_index]), &handles)?;
+        self.revocation_registry
+            .active
+            .insert(revocation_claim.value.clone());
+        self.revocation_registry
+            .elements
+            .insert(revocation_claim.value);
+        let credential_bundle = CredentialBundle {
+            issuer: self.get_public(),
+            credential: Credential {
+                claims,
+                signature,
+                revocation_handle,
+                revocation_index,
+            },
+        };
+        debug!(
+            "Threshold Signed Credential: {}",
+            serde_json::to_string_pretty(&credential_bundle).unwrap()
+        );
+        Ok(credential_bundle)
+    }
+
+    /// Create a request for signers to compute the revocation handles of
+    /// active claims.
+    ///
+    /// Combine the parts with [`ThresholdIssuer::combine_witnesses`] to
+    /// update revocation handles or with [`ThresholdIssuer::combine_revocation`]
+    /// to revoke the claims.
+    pub fn witness_request(
+        &mut self,
+        claims: &[RevocationClaim],
+    ) -> CredxResult<ThresholdWitnessRequest> {
+        let claims = claims.iter().map(|c| c.value.clone()).collect::<Vec<_>>();
+        for (i, claim) in claims.iter().enumerate() {
+            if !self.revocation_registry.active.contains(claim) {
+                return Err(Error::InvalidRevocationRegistryRevokeOperation);
+            }
+            if claims[..i].contains(claim) {
+                return Err(Error::InvalidClaimData("duplicate revocation claim"));
+            }
+        }
+        Ok(ThresholdWitnessRequest {
+            revocation_registry: self.revocation_registry.value,
+            nonces: self.take_nonces(claims.len())?,
+            claims,
+        })
+    }
+
+    /// Combine the signers' parts into the revocation handles of the claims
+    pub fn combine_witnesses(
+        &self,
+        request: &ThresholdWitnessRequest,
+        partials: &[ThresholdPartialWitnesses],
+    ) -> CredxResult<Vec<MembershipWitness>> {
+        self.check_registry(request.revocation_registry)?;
+        if partials
+            .iter()
+            .any(|p| p.witnesses.len() != request.claims.len())
+        {
+            return Err(Error::InvalidSigningOperation);
+        }
+        request
+            .claims
+            .iter()
+            .enumerate()
+            .map(|(i, claim)| {
+                let parts = partials
+                    .iter()
+                    .map(|p| p.witnesses[i].clone())
+                    .collect::<Vec<_>>();
+                self.combine_witness(Element::hash(claim.as_bytes()), &parts)
+            })
+            .collect()
+    }
+
+    /// Combine the signers' parts to revoke the claims.
+    ///
+    /// The new registry value is `V / ∏(y_i + k)`, which is the sum of the
+    /// revocation handles `V / (y_i + k)` with partial fraction coefficients
+    /// `1 / ∏_{j≠i}(y_j - y_i)`.
+    pub fn combine_revocation(
+        &mut self,
+        request: &ThresholdWitnessRequest,
+        partials: &[ThresholdPartialWitnesses],
+    ) -> CredxResult<()> {
+        let witnesses = self.combine_witnesses(request, partials)?;
+        self.revocation_registry
+            .revoke_with(&request.claims, |_, removals| {
+                let mut value = G1Projective::IDENTITY;
+                for (i, (witness, y)) in witnesses.iter().zip(removals).enumerate() {
+                    let denominator = removals
+                        .iter()
+                        .enumerate()
+                        .filter(|(j, _)| *j != i)
+                        .fold(Scalar::ONE, |acc, (_, y_j)| acc * (y_j.0 - y.0));
+                    let coefficient = Option::<Scalar>::from(denominator.invert())
+                        .ok_or(Error::InvalidRevocationRegistryRevokeOperation)?;
+                    value += witness.0 * coefficient;
+                }
+                Ok(Accumulator(value))
+            })
+    }
+
+    fn take_nonces(&mut self, count: usize) -> CredxResult<Vec<u64>> {
+        if self.nonces.len() < count {
+            return Err(Error::General("not enough threshold nonces"));
+        }
+        Ok(self.nonces.drain(..count).collect())
+    }
+
+    fn check_registry(&self, revocation_registry: Accumulator) -> CredxResult<()> {
+        if revocation_registry != self.revocation_registry.value {
+            return Err(Error::General("the revocation registry changed"));
+        }
+        Ok(())
+    }
+
+    fn combine_witness(
+        &self,
+        element: Element,
+        partials: &[PartialInversion],
+    ) -> CredxResult<MembershipWitness> {
+        let accumulator = self.revocation_registry.value;
+        let witness = MembershipWitness(combine_inversion(partials)?);
+        if witness.verify(element, self.revocation_verifying_key, accumulator) {
+            Ok(witness)
+        } else {
+            Err(Error::InvalidSigningOperation)
+        }
+    }
+
+    fn check_not_revoked<'a>(&self, claim: &'a ClaimData) -> CredxResult<&'a RevocationClaim> {
+        let claim = match claim {
+            ClaimData::Revocation(rc) => rc,
+            _ => return Err(Error::InvalidClaimData("revocation claim not found")),
+        };
+        // This data has already been revoked
+        if !self.revocation_registry.active.contains(&claim.value)
+            && self.revocation_registry.elements.contains(&claim.value)
+        {
+            return Err(Error::InvalidClaimData("This claim is already revoked"));
+        }
+        Ok(claim)
+    }
+}
+
+/// One of the signers for a [`ThresholdIssuer`]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ThresholdSigner<S: ThresholdSignatureScheme> {
+    /// The issuer's unique id
+    pub id: String,
+    /// The schema for this issuer
+    pub schema: CredentialSchema,
+    /// The signer index
+    pub index: u32,
+    /// The number of shares that recover a key
+    pub threshold: usize,
+    /// The number of signers
+    pub limit: usize,
+    /// The share of the credential signing key
+    pub signing_key: S::SecretKeyShare,
+    /// The share of the revocation key
+    pub(crate) revocation_key: Scalar,
+    /// The unused nonces by id
+    pub(crate) nonces: BTreeMap<u64, InversionNonce>,
+}
+
+impl<S: ThresholdSignatureScheme> ThresholdSigner<S> {
+    /// Deal shares of the requested nonces to every signer.
+    ///
+    /// The shares at position `i` belong to signer `i + 1` and must be sent
+    /// to it privately.
+    pub fn deal_nonces(
+        &self,
+        request: &ThresholdNonceRequest,
+        rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<Vec<NonceShares>> {
+        deal_inversion_nonces(
+            self.index,
+            request.ids.len(),
+            self.threshold,
+            self.limit,
+            rng,
+        )
+    }
+
+    /// Store the requested nonces from the shares other signers dealt
+    /// to this signer
+    pub fn receive_nonces(
+        &mut self,
+        request: &ThresholdNonceRequest,
+        shares: &[NonceShares],
+    ) -> CredxResult<()> {
+        let nonces = receive_inversion_nonces(self.index, self.threshold, shares)?;
+        if nonces.len() != request.ids.len()
+            || request.ids.iter().any(|id| self.nonces.contains_key(id))
+        {
+            return Err(Error::General("invalid nonce shares"));
+        }
+        self.nonces.extend(request.ids.iter().copied().zip(nonces));
+        Ok(())
+    }
+
+    /// Check the claims and create this signer's part of the credential
+    pub fn partial_sign_credential(
+        &mut self,
+        request: &ThresholdSigningRequest,
+    ) -> CredxResult<ThresholdPartialCredential<S>> {
+        let (claims, revocation_index) = check_claims(&self.schema, &request.claims)?;
+        if request.nonces.len() != S::SIGNING_NONCES + 1 {
+            return Err(Error::General("invalid signing nonces"));
+        }
+        let mut nonces = self.take_nonces(&request.nonces)?;
+        let revocation_nonce = nonces.pop().expect("one nonce for the revocation handle");
+        let attributes = claims.iter().map(|c| c.to_scalar()).collect::<Vec<_>>();
+        let signature = S::partial_sign(
+            &self.signing_key,
+            &attributes,
+            &request_id(&request.nonces),
+            nonces,
+        )?;
+        let revocation_handle = partial_inversion(
+            self.revocation_key,
+            attributes[revocation_index],
+            request.revocation_registry.0,
+            revocation_nonce,
+        );
+        Ok(ThresholdPartialCredential {
+            index: self.index,
+            signature,
+            revocation_handle,
+        })
+    }
+
+    /// Create this signer's parts of the requested revocation handles
+    pub fn partial_witnesses(
+        &mut self,
+        request: &ThresholdWitnessRequest,
+    ) -> CredxResult<ThresholdPartialWitnesses> {
+        if request.nonces.len() != request.claims.len() {
+            return Err(Error::General("invalid witness nonces"));
+        }
+        let nonces = self.take_nonces(&request.nonces)?;
+        let witnesses = request
+            .claims
+            .iter()
+            .zip(nonces)
+            .map(|(claim, nonce)| {
+                partial_inversion(
+                    self.revocation_key,
+                    Element::hash(claim.as_bytes()).0,
+                    request.revocation_registry.0,
+                    nonce,
+                )
+            })
+            .collect();
+        Ok(ThresholdPartialWitnesses {
+            index: self.index,
+            witnesses,
+        })
+    }
+
+    /// Remove the nonces so they are never used again
+    fn take_nonces(&mut self, ids: &[u64]) -> CredxResult<Vec<InversionNonce>> {
+        if ids.iter().any(|id| !self.nonces.contains_key(id)) {
+            return Err(Error::General("unknown or used threshold nonce"));
+        }
+        Ok(ids
+            .iter()
+            .map(|id| self.nonces.remove(id).expect("checked nonce"))
+            .collect())
+    }
+}
+
+impl<S: ThresholdSignatureScheme> From<&ThresholdIssuer<S>> for IssuerPublic<S> {
+    fn from(issuer: &ThresholdIssuer<S>) -> Self {
+        issuer.get_public()
+    }
+}
+
+/// The signing request id for schemes that derive signature values from it,
+/// nonce ids are never reused so it is unique
+fn request_id(nonces: &[u64]) -> Vec<u8> {
+    nonces.iter().flat_map(|id| id.to_be_bytes()).collect()
+}
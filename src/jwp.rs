@@ -0,0 +1,130 @@
+mod bbs;
+mod error;
+mod issued;
+mod presented;
+
+pub use bbs::*;
+pub use error::*;
+pub use issued::*;
+pub use presented::*;
+
+use crate::claim::ClaimData;
+use crate::credential::{ClaimSchema, CredentialSchema};
+use crate::error::Error;
+use crate::CredxResult;
+use base64::Engine;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// The private JWP algorithm for credx BBS signatures and presentations.
+///
+/// The proofs are credx encodings rather than the octets of the
+/// registered [`BBS_ALGORITHM`] so they use their own name. They carry the
+/// revocation handle and the proofs of every presentation statement.
+pub const CREDX_BBS_ALGORITHM: &str = "credx-BBS";
+
+/// The protected header created by the issuer
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct JwpIssuerHeader {
+    /// The algorithm, [`CREDX_BBS_ALGORITHM`] or [`BBS_ALGORITHM`]
+    pub alg: String,
+    /// The hex encoded issuer key id, or any key id for [`BBS_ALGORITHM`]
+    pub kid: String,
+    /// The claim labels in payload order
+    pub claims: Vec<String>,
+}
+
+/// The protected header created by the holder
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct JwpPresentationHeader {
+    /// The algorithm, [`CREDX_BBS_ALGORITHM`] or [`BBS_ALGORITHM`]
+    pub alg: String,
+    /// The base64url encoded presentation nonce
+    pub nonce: String,
+}
+
+fn encode_part(bytes: &[u8]) -> String {
+    base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn decode_part(part: &str) -> CredxResult<Vec<u8>> {
+    Ok(base64::prelude::BASE64_URL_SAFE_NO_PAD
+        .decode(part)
+        .map_err(|_| JwpError::InvalidSerialization)?)
+}
+
+fn encode_header<T: Serialize>(header: &T) -> CredxResult<String> {
+    let bytes = serde_json::to_vec(header)
+        .map_err(|_| Error::General("unable to serialize the protected header"))?;
+    Ok(encode_part(&bytes))
+}
+
+fn decode_header<T: DeserializeOwned>(part: &str) -> CredxResult<T> {
+    Ok(serde_json::from_slice(&decode_part(part)?).map_err(|_| JwpError::InvalidHeader)?)
+}
+
+fn encode_proof<T: Serialize>(proof: &T) -> CredxResult<Vec<u8>> {
+    serde_bare::to_vec(proof).map_err(|_| Error::General("unable to serialize the proof"))
+}
+
+fn decode_proof<T: DeserializeOwned>(proof: &[u8]) -> CredxResult<T> {
+    Ok(serde_bare::from_slice(proof).map_err(|_| JwpError::InvalidProof)?)
+}
+
+/// Payloads are separated by `~` and undisclosed payloads are empty
+fn encode_payloads<'a>(payloads: impl Iterator<Item = Option<&'a Vec<u8>>>) -> String {
+    payloads
+        .map(|p| p.map(|p| encode_part(p)).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("~")
+}
+
+fn decode_payloads(part: &str) -> CredxResult<Vec<Option<Vec<u8>>>> {
+    part.split('~')
+        .map(|p| {
+            if p.is_empty() {
+                Ok(None)
+            } else {
+                decode_part(p).map(Some)
+            }
+        })
+        .collect()
+}
+
+fn check_algorithm(alg: &str) -> CredxResult<()> {
+    if alg == CREDX_BBS_ALGORITHM {
+        Ok(())
+    } else {
+        Err(JwpError::UnsupportedAlgorithm(alg.to_string()).into())
+    }
+}
+
+/// The claim names aren't signed so they must be the issuer schema's labels
+fn check_claims(header: &JwpIssuerHeader, schema: &CredentialSchema) -> CredxResult<()> {
+    if header
+        .claims
+        .iter()
+        .eq(schema.claims.iter().map(|c| &c.label))
+    {
+        Ok(())
+    } else {
+        Err(JwpError::ClaimsMismatch.into())
+    }
+}
+
+/// A claim's payload is its text form
+fn claim_to_payload(claim: &ClaimData) -> Vec<u8> {
+    claim.to_text().into_bytes()
+}
+
+fn claim_from_payload(claim_schema: &ClaimSchema, payload: &[u8]) -> CredxResult<ClaimData> {
+    let invalid = || JwpError::InvalidPayload(claim_schema.label.clone());
+    let text = std::str::from_utf8(payload)
+        .ok()
+        .filter(|t| t.get(0..4).is_some())
+        .ok_or_else(invalid)?;
+    let claim = ClaimData::from_text(text).map_err(|_| invalid())?;
+    if !matches!(claim, ClaimData::Absent(_)) && !claim.is_type(claim_schema.claim_type) {
+        return Err(invalid().into());
+    }
+    Ok(claim)
+}
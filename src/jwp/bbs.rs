@@ -0,0 +1,294 @@
+use super::*;
+use crate::knox::bbs::BbsCiphersuite;
+use rand_core::{CryptoRng, RngCore};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The registered JWP algorithm for BBS with the BLS12-381-SHA-256 ciphersuite
+pub const BBS_ALGORITHM: &str = "BBS";
+
+/// The ciphersuite of the registered `BBS` algorithm
+const BBS_CIPHERSUITE: BbsCiphersuite = BbsCiphersuite::Bls12381Sha256;
+
+/// A credential in the JWP issued form with the registered `BBS` algorithm.
+///
+/// The proof is the BBS signature over the payloads with the protected
+/// header octets as the BBS header, so other JWP implementations can verify
+/// it with the issuer's BBS public key. The header is kept as the signed
+/// octets. Unlike [`IssuedJwp`] it uses [`BbsCiphersuite`] keys rather
+/// than an [`Issuer`](crate::issuer::Issuer)'s keys and has no revocation handle.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BbsIssuedJwp {
+    /// The issuer's protected header octets
+    pub header: Vec<u8>,
+    /// The claim payloads
+    pub payloads: Vec<Vec<u8>>,
+    /// The BBS signature
+    pub proof: Vec<u8>,
+}
+
+/// A credential in the JWP presented form with the registered `BBS` algorithm.
+///
+/// The proof is the BBS proof of the disclosed payloads with the issuer
+/// header octets as the BBS header and the presentation header octets as
+/// the BBS presentation header.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BbsPresentedJwp {
+    /// The holder's protected header octets
+    pub presentation_header: Vec<u8>,
+    /// The issuer's protected header octets
+    pub issuer_header: Vec<u8>,
+    /// The disclosed claim payloads
+    pub payloads: Vec<Option<Vec<u8>>>,
+    /// The BBS proof
+    pub proof: Vec<u8>,
+}
+
+impl BbsIssuedJwp {
+    /// Sign `claims` of `schema` with a BBS secret and public key.
+    ///
+    /// `kid` identifies the public key for the holder and verifier.
+    pub fn sign(
+        schema: &CredentialSchema,
+        claims: &[ClaimData],
+        kid: &str,
+        sk: &[u8],
+        pk: &[u8],
+    ) -> CredxResult<Self> {
+        if schema.claims.len() != claims.len() {
+            return Err(Error::InvalidClaimData(
+                "claims don't match the credential schema",
+            ));
+        }
+        let header = serde_json::to_vec(&JwpIssuerHeader {
+            alg: BBS_ALGORITHM.to_string(),
+            kid: kid.to_string(),
+            claims: schema.claims.iter().map(|c| c.label.clone()).collect(),
+        })
+        .map_err(|_| Error::General("unable to serialize the protected header"))?;
+        let payloads = claims.iter().map(claim_to_payload).collect::<Vec<_>>();
+        let proof = BBS_CIPHERSUITE.sign(sk, pk, &header, &payloads)?;
+        Ok(Self {
+            header,
+            payloads,
+            proof: proof.to_vec(),
+        })
+    }
+
+    /// The issuer's protected header
+    pub fn header(&self) -> CredxResult<JwpIssuerHeader> {
+        let header: JwpIssuerHeader = read_header(&self.header)?;
+        check_bbs_algorithm(&header.alg)?;
+        Ok(header)
+    }
+
+    /// Verify the signature with the issuer's BBS public key
+    pub fn verify(&self, pk: &[u8]) -> CredxResult<()> {
+        let header = self.header()?;
+        if header.claims.len() != self.payloads.len() {
+            return Err(JwpError::ClaimsMismatch.into());
+        }
+        BBS_CIPHERSUITE.verify(pk, &self.proof, &self.header, &self.payloads)
+    }
+
+    /// Read the claims with the credential schema
+    pub fn claims(&self, schema: &CredentialSchema) -> CredxResult<Vec<ClaimData>> {
+        check_claims(&self.header()?, schema)?;
+        if self.payloads.len() != schema.claims.len() {
+            return Err(JwpError::ClaimsMismatch.into());
+        }
+        schema
+            .claims
+            .iter()
+            .zip(&self.payloads)
+            .map(|(claim_schema, payload)| claim_from_payload(claim_schema, payload))
+            .collect()
+    }
+
+    /// Disclose the claims with the labels in `disclosed` to a verifier
+    /// that sent `nonce`
+    pub fn present(
+        &self,
+        pk: &[u8],
+        disclosed: &BTreeSet<String>,
+        nonce: &[u8],
+        rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<BbsPresentedJwp> {
+        let header = self.header()?;
+        if header.claims.len() != self.payloads.len() {
+            return Err(JwpError::ClaimsMismatch.into());
+        }
+        if let Some(label) = disclosed.iter().find(|l| !header.claims.contains(*l)) {
+            return Err(JwpError::MissingPayload(label.clone()).into());
+        }
+        let disclosed_indexes = header
+            .claims
+            .iter()
+            .enumerate()
+            .filter(|(_, label)| disclosed.contains(*label))
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        let presentation_header = serde_json::to_vec(&JwpPresentationHeader {
+            alg: BBS_ALGORITHM.to_string(),
+            nonce: encode_part(nonce),
+        })
+        .map_err(|_| Error::General("unable to serialize the protected header"))?;
+        let proof = BBS_CIPHERSUITE.proof_gen(
+            pk,
+            &self.proof,
+            &self.header,
+            &presentation_header,
+            &self.payloads,
+            &disclosed_indexes,
+            rng,
+        )?;
+        Ok(BbsPresentedJwp {
+            presentation_header,
+            issuer_header: self.header.clone(),
+            payloads: header
+                .claims
+                .iter()
+                .zip(&self.payloads)
+                .map(|(label, p)| disclosed.contains(label).then(|| p.clone()))
+                .collect(),
+            proof,
+        })
+    }
+
+    /// The compact serialization `header.payloads.proof`
+    pub fn to_compact(&self) -> String {
+        format!(
+            "{}.{}.{}",
+            encode_part(&self.header),
+            encode_payloads(self.payloads.iter().map(Some)),
+            encode_part(&self.proof)
+        )
+    }
+
+    /// Read the compact serialization
+    pub fn from_compact(jwp: &str) -> CredxResult<Self> {
+        let parts = jwp.split('.').collect::<Vec<_>>();
+        if parts.len() != 3 {
+            return Err(JwpError::InvalidSerialization.into());
+        }
+        let header = decode_part(parts[0])?;
+        let claims = read_header::<JwpIssuerHeader>(&header)?.claims;
+        let payloads = decode_payloads(parts[1])?
+            .into_iter()
+            .enumerate()
+            .map(|(i, p)| {
+                p.ok_or_else(|| {
+                    JwpError::MissingPayload(claims.get(i).cloned().unwrap_or_default())
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            header,
+            payloads,
+            proof: decode_part(parts[2])?,
+        })
+    }
+}
+
+impl BbsPresentedJwp {
+    /// The issuer's protected header
+    pub fn issuer_header(&self) -> CredxResult<JwpIssuerHeader> {
+        let header: JwpIssuerHeader = read_header(&self.issuer_header)?;
+        check_bbs_algorithm(&header.alg)?;
+        Ok(header)
+    }
+
+    /// The holder's protected header
+    pub fn presentation_header(&self) -> CredxResult<JwpPresentationHeader> {
+        let header: JwpPresentationHeader = read_header(&self.presentation_header)?;
+        check_bbs_algorithm(&header.alg)?;
+        Ok(header)
+    }
+
+    /// Verify the proof with the issuer's BBS public key and the
+    /// verifier's `nonce`
+    pub fn verify(&self, pk: &[u8], nonce: &[u8]) -> CredxResult<()> {
+        let issuer_header = self.issuer_header()?;
+        if self.presentation_header()?.nonce != encode_part(nonce) {
+            return Err(JwpError::NonceMismatch.into());
+        }
+        if issuer_header.claims.len() != self.payloads.len() {
+            return Err(JwpError::ClaimsMismatch.into());
+        }
+        let disclosed = self
+            .payloads
+            .iter()
+            .enumerate()
+            .filter_map(|(i, p)| p.as_ref().map(|p| (i, p)))
+            .collect::<Vec<_>>();
+        BBS_CIPHERSUITE.proof_verify(
+            pk,
+            &self.proof,
+            &self.issuer_header,
+            &self.presentation_header,
+            self.payloads.len(),
+            &disclosed,
+        )
+    }
+
+    /// Read the disclosed claims by label with the credential schema
+    pub fn disclosed_claims(
+        &self,
+        schema: &CredentialSchema,
+    ) -> CredxResult<BTreeMap<String, ClaimData>> {
+        check_claims(&self.issuer_header()?, schema)?;
+        if self.payloads.len() != schema.claims.len() {
+            return Err(JwpError::ClaimsMismatch.into());
+        }
+        let mut claims = BTreeMap::new();
+        for (claim_schema, payload) in schema.claims.iter().zip(&self.payloads) {
+            if let Some(payload) = payload {
+                claims.insert(
+                    claim_schema.label.clone(),
+                    claim_from_payload(claim_schema, payload)?,
+                );
+            }
+        }
+        Ok(claims)
+    }
+
+    /// The compact serialization
+    /// `presentation header.issuer header.payloads.proof`
+    pub fn to_compact(&self) -> String {
+        format!(
+            "{}.{}.{}.{}",
+            encode_part(&self.presentation_header),
+            encode_part(&self.issuer_header),
+            encode_payloads(self.payloads.iter().map(Option::as_ref)),
+            encode_part(&self.proof)
+        )
+    }
+
+    /// Read the compact serialization
+    pub fn from_compact(jwp: &str) -> CredxResult<Self> {
+        let parts = jwp.split('.').collect::<Vec<_>>();
+        if parts.len() != 4 {
+            return Err(JwpError::InvalidSerialization.into());
+        }
+        let jwp = Self {
+            presentation_header: decode_part(parts[0])?,
+            issuer_header: decode_part(parts[1])?,
+            payloads: decode_payloads(parts[2])?,
+            proof: decode_part(parts[3])?,
+        };
+        jwp.issuer_header()?;
+        jwp.presentation_header()?;
+        Ok(jwp)
+    }
+}
+
+fn read_header<T: DeserializeOwned>(octets: &[u8]) -> CredxResult<T> {
+    Ok(serde_json::from_slice(octets).map_err(|_| JwpError::InvalidHeader)?)
+}
+
+fn check_bbs_algorithm(alg: &str) -> CredxResult<()> {
+    if alg == BBS_ALGORITHM {
+        Ok(())
+    } else {
+        Err(JwpError::UnsupportedAlgorithm(alg.to_string()).into())
+    }
+}
@@ -0,0 +1,32 @@
+use crate::error::Error;
+
+/// Reasons JSON Web Proofs can't be read as credx credentials and presentations
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum JwpError {
+    /// The compact serialization doesn't have the expected base64url parts
+    InvalidSerialization,
+    /// A protected header isn't the expected JSON object
+    InvalidHeader,
+    /// The algorithm isn't the expected BBS algorithm
+    UnsupportedAlgorithm(String),
+    /// The key id isn't the issuer's key id
+    IssuerMismatch(String),
+    /// The claim names aren't the issuer schema's claim labels
+    ClaimsMismatch,
+    /// The payload is missing for the claim with this label
+    MissingPayload(String),
+    /// The payload isn't a claim of the type in the schema for this label
+    InvalidPayload(String),
+    /// The proof isn't a credx signature or presentation proof
+    InvalidProof,
+    /// The presentation header's nonce isn't the verifier's nonce
+    NonceMismatch,
+    /// The presentation schema doesn't have exactly one signature statement
+    UnsupportedPresentation,
+}
+
+impl From<JwpError> for Error {
+    fn from(e: JwpError) -> Self {
+        Self::InvalidJwp(e)
+    }
+}
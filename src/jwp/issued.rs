@@ -0,0 +1,143 @@
+use super::*;
+use crate::credential::{Credential, CredentialBundle, MembershipCredential};
+use crate::issuer::IssuerKeyRegistry;
+use crate::knox::accumulator::vb20::Accumulator;
+use crate::knox::bbs::{BbsScheme, Signature};
+
+/// A BBS credential in the JWP issued form.
+///
+/// The payloads are the claims in schema order and the proof holds the
+/// signature and revocation handle. The signature only covers the claims
+/// so the header's key id and claim names are checked against the issuer
+/// when reading the credential back.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssuedJwp {
+    /// The issuer's protected header
+    pub header: JwpIssuerHeader,
+    /// The claim payloads
+    pub payloads: Vec<Vec<u8>>,
+    /// The credx signature proof
+    pub proof: Vec<u8>,
+}
+
+/// The credx data in an [`IssuedJwp`] proof
+#[derive(Deserialize, Serialize)]
+struct IssuedProof {
+    signature: Signature,
+    revocation_handle: MembershipCredential,
+    revocation_index: usize,
+    revocation_registry: Accumulator,
+}
+
+impl TryFrom<&CredentialBundle<BbsScheme>> for IssuedJwp {
+    type Error = Error;
+
+    fn try_from(bundle: &CredentialBundle<BbsScheme>) -> CredxResult<Self> {
+        let schema = &bundle.issuer.schema;
+        if schema.claims.len() != bundle.credential.claims.len() {
+            return Err(Error::InvalidClaimData(
+                "claims don't match the credential schema",
+            ));
+        }
+        Ok(Self {
+            header: JwpIssuerHeader {
+                alg: CREDX_BBS_ALGORITHM.to_string(),
+                kid: hex::encode(bundle.issuer.key_id()),
+                claims: schema.claims.iter().map(|c| c.label.clone()).collect(),
+            },
+            payloads: bundle
+                .credential
+                .claims
+                .iter()
+                .map(claim_to_payload)
+                .collect(),
+            proof: encode_proof(&IssuedProof {
+                signature: bundle.credential.signature,
+                revocation_handle: bundle.credential.revocation_handle,
+                revocation_index: bundle.credential.revocation_index,
+                revocation_registry: bundle.issuer.revocation_registry,
+            })?,
+        })
+    }
+}
+
+impl TryFrom<CredentialBundle<BbsScheme>> for IssuedJwp {
+    type Error = Error;
+
+    fn try_from(bundle: CredentialBundle<BbsScheme>) -> CredxResult<Self> {
+        Self::try_from(&bundle)
+    }
+}
+
+impl IssuedJwp {
+    /// The compact serialization `header.payloads.proof`
+    pub fn to_compact(&self) -> CredxResult<String> {
+        Ok(format!(
+            "{}.{}.{}",
+            encode_header(&self.header)?,
+            encode_payloads(self.payloads.iter().map(Some)),
+            encode_part(&self.proof)
+        ))
+    }
+
+    /// Read the compact serialization
+    pub fn from_compact(jwp: &str) -> CredxResult<Self> {
+        let parts = jwp.split('.').collect::<Vec<_>>();
+        if parts.len() != 3 {
+            return Err(JwpError::InvalidSerialization.into());
+        }
+        let header: JwpIssuerHeader = decode_header(parts[0])?;
+        let payloads = decode_payloads(parts[1])?
+            .into_iter()
+            .enumerate()
+            .map(|(i, p)| {
+                p.ok_or_else(|| {
+                    JwpError::MissingPayload(header.claims.get(i).cloned().unwrap_or_default())
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            header,
+            payloads,
+            proof: decode_part(parts[2])?,
+        })
+    }
+
+    /// Get the credential bundle with the issuer from `registry`.
+    ///
+    /// Use [`CredentialBundle::verify`] to check the signature.
+    pub fn to_bundle(
+        &self,
+        registry: &IssuerKeyRegistry<BbsScheme>,
+    ) -> CredxResult<CredentialBundle<BbsScheme>> {
+        check_algorithm(&self.header.alg)?;
+        let key_id = hex::decode(&self.header.kid)
+            .ok()
+            .and_then(|k| k.try_into().ok())
+            .ok_or_else(|| JwpError::IssuerMismatch(self.header.kid.clone()))?;
+        let mut issuer = registry.get(&key_id)?.clone();
+        check_claims(&self.header, &issuer.schema)?;
+        if self.payloads.len() != issuer.schema.claims.len() {
+            return Err(JwpError::ClaimsMismatch.into());
+        }
+        let claims = issuer
+            .schema
+            .claims
+            .iter()
+            .zip(&self.payloads)
+            .map(|(claim_schema, payload)| claim_from_payload(claim_schema, payload))
+            .collect::<CredxResult<Vec<_>>>()?;
+
+        let proof: IssuedProof = decode_proof(&self.proof)?;
+        issuer.revocation_registry = proof.revocation_registry;
+        Ok(CredentialBundle {
+            issuer,
+            credential: Credential {
+                claims,
+                signature: proof.signature,
+                revocation_handle: proof.revocation_handle,
+                revocation_index: proof.revocation_index,
+            },
+        })
+    }
+}
@@ -0,0 +1,154 @@
+use super::*;
+use crate::knox::bbs::BbsScheme;
+use crate::presentation::{Presentation, PresentationProofs, PresentationSchema};
+use crate::statement::{SignatureStatement, Statements};
+use crate::utils::*;
+use blsful::inner_types::Scalar;
+use indexmap::{indexmap, IndexMap};
+
+/// A BBS presentation in the JWP presented form.
+///
+/// A JWP holds the payloads of one issuer so the presentation schema must
+/// have exactly one signature statement. The payloads of the claims that
+/// aren't disclosed are empty and the proof holds the challenge and the
+/// proofs of every statement.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PresentedJwp {
+    /// The issuer's protected header
+    pub issuer_header: JwpIssuerHeader,
+    /// The holder's protected header
+    pub presentation_header: JwpPresentationHeader,
+    /// The disclosed claim payloads
+    pub payloads: Vec<Option<Vec<u8>>>,
+    /// The credx presentation proof
+    pub proof: Vec<u8>,
+}
+
+/// The credx data in a [`PresentedJwp`] proof
+#[derive(Deserialize, Serialize)]
+struct PresentedProof {
+    challenge: Scalar,
+    #[serde(
+        serialize_with = "serialize_indexmap",
+        deserialize_with = "deserialize_indexmap"
+    )]
+    proofs: IndexMap<String, PresentationProofs<BbsScheme>>,
+}
+
+/// The only signature statement in `schema`
+fn signature_statement(
+    schema: &PresentationSchema<BbsScheme>,
+) -> CredxResult<&SignatureStatement<BbsScheme>> {
+    let mut signatures = schema.statements.values().filter_map(|s| match s {
+        Statements::Signature(ss) => Some(ss.as_ref()),
+        _ => None,
+    });
+    match (signatures.next(), signatures.next()) {
+        (Some(ss), None) => Ok(ss),
+        (_, _) => Err(JwpError::UnsupportedPresentation.into()),
+    }
+}
+
+impl PresentedJwp {
+    /// Encode `presentation` created for `schema` and `nonce`
+    pub fn new(
+        presentation: &Presentation<BbsScheme>,
+        schema: &PresentationSchema<BbsScheme>,
+        nonce: &[u8],
+    ) -> CredxResult<Self> {
+        let statement = signature_statement(schema)?;
+        let issuer = &statement.issuer;
+        let disclosed = presentation
+            .disclosed_messages
+            .get(&statement.id)
+            .ok_or_else(|| Error::InvalidPresentationData(statement.id.clone()))?;
+        Ok(Self {
+            issuer_header: JwpIssuerHeader {
+                alg: CREDX_BBS_ALGORITHM.to_string(),
+                kid: hex::encode(issuer.key_id()),
+                claims: issuer
+                    .schema
+                    .claims
+                    .iter()
+                    .map(|c| c.label.clone())
+                    .collect(),
+            },
+            presentation_header: JwpPresentationHeader {
+                alg: CREDX_BBS_ALGORITHM.to_string(),
+                nonce: encode_part(nonce),
+            },
+            payloads: issuer
+                .schema
+                .claims
+                .iter()
+                .map(|c| disclosed.get(&c.label).map(claim_to_payload))
+                .collect(),
+            proof: encode_proof(&PresentedProof {
+                challenge: presentation.challenge,
+                proofs: presentation.proofs.clone(),
+            })?,
+        })
+    }
+
+    /// The compact serialization
+    /// `presentation header.issuer header.payloads.proof`
+    pub fn to_compact(&self) -> CredxResult<String> {
+        Ok(format!(
+            "{}.{}.{}.{}",
+            encode_header(&self.presentation_header)?,
+            encode_header(&self.issuer_header)?,
+            encode_payloads(self.payloads.iter().map(Option::as_ref)),
+            encode_part(&self.proof)
+        ))
+    }
+
+    /// Read the compact serialization
+    pub fn from_compact(jwp: &str) -> CredxResult<Self> {
+        let parts = jwp.split('.').collect::<Vec<_>>();
+        if parts.len() != 4 {
+            return Err(JwpError::InvalidSerialization.into());
+        }
+        Ok(Self {
+            presentation_header: decode_header(parts[0])?,
+            issuer_header: decode_header(parts[1])?,
+            payloads: decode_payloads(parts[2])?,
+            proof: decode_part(parts[3])?,
+        })
+    }
+
+    /// Get the presentation created for `schema`.
+    ///
+    /// Use [`Presentation::verify`] with the nonce to check the proofs.
+    pub fn to_presentation(
+        &self,
+        schema: &PresentationSchema<BbsScheme>,
+    ) -> CredxResult<Presentation<BbsScheme>> {
+        check_algorithm(&self.presentation_header.alg)?;
+        check_algorithm(&self.issuer_header.alg)?;
+        let statement = signature_statement(schema)?;
+        let issuer = &statement.issuer;
+        if self.issuer_header.kid != hex::encode(issuer.key_id()) {
+            return Err(JwpError::IssuerMismatch(self.issuer_header.kid.clone()).into());
+        }
+        check_claims(&self.issuer_header, &issuer.schema)?;
+        if self.payloads.len() != issuer.schema.claims.len() {
+            return Err(JwpError::ClaimsMismatch.into());
+        }
+        let mut disclosed = IndexMap::new();
+        for (claim_schema, payload) in issuer.schema.claims.iter().zip(&self.payloads) {
+            if let Some(payload) = payload {
+                disclosed.insert(
+                    claim_schema.label.clone(),
+                    claim_from_payload(claim_schema, payload)?,
+                );
+            }
+        }
+
+        let PresentedProof { challenge, proofs } = decode_proof(&self.proof)?;
+        Ok(Presentation {
+            proofs,
+            challenge,
+            disclosed_messages: indexmap! { statement.id.clone() => disclosed },
+        })
+    }
+}
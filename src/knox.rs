@@ -7,6 +7,8 @@ pub mod ecc_group;
 pub mod ps;
 /// Operations for short group signatures
 pub mod short_group_sig_core;
+/// Threshold signing with secret shared keys
+pub mod threshold;
 
 use blsful::*;
 use rand_core::{CryptoRng, RngCore};
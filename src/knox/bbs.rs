@@ -1,5 +1,6 @@
 mod blind_signature;
 mod blind_signature_context;
+mod ietf;
 mod msg_gens;
 mod pok_signature;
 mod pok_signature_proof;
@@ -7,9 +8,11 @@ mod public_key;
 mod scheme;
 mod secret_key;
 mod signature;
+mod threshold;
 
 pub use blind_signature::*;
 pub use blind_signature_context::*;
+pub use ietf::*;
 pub use msg_gens::*;
 pub use pok_signature::*;
 pub use pok_signature_proof::*;
@@ -17,3 +20,4 @@ pub use public_key::*;
 pub use scheme::*;
 pub use secret_key::*;
 pub use signature::*;
+pub use threshold::*;
@@ -1,7 +1,8 @@
-use super::PublicKey;
+use super::{BbsCiphersuite, PublicKey};
 use crate::error::Error;
 use crate::knox::bbs::SecretKey;
 use crate::knox::short_group_sig_core::short_group_traits::BlindSignatureContext as BlindSignatureContextTrait;
+use crate::knox::short_group_sig_core::BlindSignatureFormat;
 use crate::CredxResult;
 use blsful::inner_types::{Curve, G1Projective, Scalar};
 use merlin::Transcript;
@@ -19,11 +20,37 @@ pub struct BlindSignatureContext {
     pub challenge: Scalar,
     /// The proofs for the hidden messages
     pub proofs: Vec<Scalar>,
+    /// The format of the proof
+    #[serde(default)]
+    pub format: BlindSignatureFormat,
+}
+
+impl BlindSignatureContext {
+    /// The ciphersuite used for [`BlindSignatureFormat::Cfrg`] proofs
+    pub const CFRG_CIPHERSUITE: BbsCiphersuite = BbsCiphersuite::Bls12381Sha256;
+
+    /// The commitment and proof as `commitment_with_proof` octets
+    /// from the CFRG Blind BBS Signatures draft
+    pub fn commitment_with_proof(&self) -> Vec<u8> {
+        Self::CFRG_CIPHERSUITE.commitment_with_proof_octets(
+            self.commitment,
+            &self.proofs,
+            self.challenge,
+        )
+    }
 }
 
 impl BlindSignatureContextTrait for BlindSignatureContext {
     type SecretKey = SecretKey;
 
+    fn commitment(&self) -> G1Projective {
+        self.commitment
+    }
+
+    fn format(&self) -> BlindSignatureFormat {
+        self.format
+    }
+
     /// Assumes the proof of hidden messages
     /// If other proofs were included, those will need to be verified another way
     fn verify(
@@ -46,6 +73,14 @@ impl BlindSignatureContextTrait for BlindSignatureContext {
                 points.push(pk.y[i]);
             }
         }
+        if self.format == BlindSignatureFormat::Cfrg {
+            return Ok(Self::CFRG_CIPHERSUITE.core_verify_commitment(
+                self.commitment,
+                &self.proofs,
+                self.challenge,
+                &points,
+            ));
+        }
         points.push(self.commitment);
 
         let mut scalars = self.proofs.clone();
@@ -0,0 +1,664 @@
+use crate::error::Error;
+use crate::CredxResult;
+use blsful::inner_types::{
+    multi_miller_loop, Curve, Field, G1Affine, G1Projective, G2Affine, G2Prepared, G2Projective,
+    Group, MillerLoopResult, Scalar,
+};
+use elliptic_curve::group::prime::PrimeCurveAffine;
+use elliptic_curve::hash2curve::{ExpandMsg, ExpandMsgXmd, ExpandMsgXof, Expander};
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sha3::Shake256;
+
+mod blind;
+mod pseudonym;
+
+/// The ciphersuites from the IRTF CFRG BBS signature draft
+/// <https://datatracker.ietf.org/doc/draft-irtf-cfrg-bbs-signatures/>
+///
+/// Keys, signatures and proofs use the draft's octet encodings.
+/// Generators, keys and signatures are tested against the draft's
+/// fixtures. Proofs are tested with the draft's mocked random scalars
+/// against the draft's ProofGen steps, not against its proof fixtures.
+/// Messages are mapped to scalars with the `H2G_HM2S_` API.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum BbsCiphersuite {
+    /// BLS12-381-SHA-256
+    Bls12381Sha256,
+    /// BLS12-381-SHAKE-256
+    Bls12381Shake256,
+}
+
+impl BbsCiphersuite {
+    /// The length of a secret key in bytes
+    pub const SECRET_KEY_BYTES: usize = 32;
+    /// The length of a public key in bytes
+    pub const PUBLIC_KEY_BYTES: usize = 96;
+    /// The length of a signature in bytes
+    pub const SIGNATURE_BYTES: usize = 80;
+
+    const EXPAND_LEN: usize = 48;
+    const POINT_BYTES: usize = 48;
+    const SCALAR_BYTES: usize = 32;
+
+    /// The ciphersuite id
+    pub fn id(&self) -> &'static [u8] {
+        match self {
+            Self::Bls12381Sha256 => b"BBS_BLS12381G1_XMD:SHA-256_SSWU_RO_",
+            Self::Bls12381Shake256 => b"BBS_BLS12381G1_XOF:SHAKE-256_SSWU_RO_",
+        }
+    }
+
+    /// The API id used for domain separation
+    pub fn api_id(&self) -> Vec<u8> {
+        [self.id(), b"H2G_HM2S_"].concat()
+    }
+
+    /// Expand `msg` into `out` with the ciphersuite's `expand_message`
+    pub fn expand_message(&self, msg: &[u8], dst: &[u8], out: &mut [u8]) -> CredxResult<()> {
+        match self {
+            Self::Bls12381Sha256 => expand::<ExpandMsgXmd<Sha256>>(msg, dst, out),
+            Self::Bls12381Shake256 => expand::<ExpandMsgXof<Shake256>>(msg, dst, out),
+        }
+    }
+
+    /// Hash arbitrary data to a scalar
+    pub fn hash_to_scalar(&self, msg: &[u8], dst: &[u8]) -> Scalar {
+        match self {
+            Self::Bls12381Sha256 => Scalar::hash::<ExpandMsgXmd<Sha256>>(msg, dst),
+            Self::Bls12381Shake256 => Scalar::hash::<ExpandMsgXof<Shake256>>(msg, dst),
+        }
+    }
+
+    fn hash_to_curve(&self, msg: &[u8], dst: &[u8]) -> G1Projective {
+        match self {
+            Self::Bls12381Sha256 => G1Projective::hash::<ExpandMsgXmd<Sha256>>(msg, dst),
+            Self::Bls12381Shake256 => G1Projective::hash::<ExpandMsgXof<Shake256>>(msg, dst),
+        }
+    }
+
+    /// The base point `P1`
+    pub fn p1(&self) -> G1Projective {
+        let api_id = self.api_id();
+        self.generators_with_seed(
+            1,
+            &[api_id.as_slice(), b"BP_MESSAGE_GENERATOR_SEED"].concat(),
+            &api_id,
+        )
+        .expect("expand_message to succeed")[0]
+    }
+
+    /// Create `count` generators `(Q_1, H_1, ..., H_{count-1})`
+    pub fn create_generators(&self, count: usize) -> CredxResult<Vec<G1Projective>> {
+        self.generators(count, &self.api_id())
+    }
+
+    fn generators(&self, count: usize, api_id: &[u8]) -> CredxResult<Vec<G1Projective>> {
+        self.generators_with_seed(count, &[api_id, b"MESSAGE_GENERATOR_SEED"].concat(), api_id)
+    }
+
+    fn generators_with_seed(
+        &self,
+        count: usize,
+        generator_seed: &[u8],
+        api_id: &[u8],
+    ) -> CredxResult<Vec<G1Projective>> {
+        let seed_dst = [api_id, b"SIG_GENERATOR_SEED_"].concat();
+        let generator_dst = [api_id, b"SIG_GENERATOR_DST_"].concat();
+        let mut v = [0u8; Self::EXPAND_LEN];
+        self.expand_message(generator_seed, &seed_dst, &mut v)?;
+        let mut generators = Vec::with_capacity(count);
+        for i in 1..=count {
+            let input = [v.as_slice(), &(i as u64).to_be_bytes()].concat();
+            self.expand_message(&input, &seed_dst, &mut v)?;
+            generators.push(self.hash_to_curve(&v, &generator_dst));
+        }
+        Ok(generators)
+    }
+
+    /// Map the messages to scalars
+    pub fn messages_to_scalars<B: AsRef<[u8]>>(&self, messages: &[B]) -> Vec<Scalar> {
+        self.map_messages(messages, &self.api_id())
+    }
+
+    fn map_messages<B: AsRef<[u8]>>(&self, messages: &[B], api_id: &[u8]) -> Vec<Scalar> {
+        let dst = [api_id, b"MAP_MSG_TO_SCALAR_AS_HASH_"].concat();
+        messages
+            .iter()
+            .map(|m| self.hash_to_scalar(m.as_ref(), &dst))
+            .collect()
+    }
+
+    /// Derive a secret key from at least 32 bytes of `key_material`.
+    ///
+    /// `key_dst` defaults to the API id followed by `KEYGEN_DST_`.
+    pub fn key_gen(
+        &self,
+        key_material: &[u8],
+        key_info: &[u8],
+        key_dst: Option<&[u8]>,
+    ) -> CredxResult<[u8; Self::SECRET_KEY_BYTES]> {
+        if key_material.len() < 32 {
+            return Err(Error::General("key material must be at least 32 bytes"));
+        }
+        let key_info_len =
+            u16::try_from(key_info.len()).map_err(|_| Error::General("key info is too long"))?;
+        let default_dst = [self.api_id().as_slice(), b"KEYGEN_DST_"].concat();
+        let key_dst = key_dst.unwrap_or(&default_dst);
+        let derive_input = [key_material, &key_info_len.to_be_bytes(), key_info].concat();
+        let sk = self.hash_to_scalar(&derive_input, key_dst);
+        if sk.is_zero().into() {
+            return Err(Error::General("invalid secret key"));
+        }
+        Ok(sk.to_be_bytes())
+    }
+
+    /// Compute the public key for a secret key
+    pub fn sk_to_pk(&self, sk: &[u8]) -> CredxResult<[u8; Self::PUBLIC_KEY_BYTES]> {
+        let sk = scalar_from_bytes(sk)?;
+        Ok((G2Projective::GENERATOR * sk).to_affine().to_compressed())
+    }
+
+    /// Sign the messages and header
+    pub fn sign<B: AsRef<[u8]>>(
+        &self,
+        sk: &[u8],
+        pk: &[u8],
+        header: &[u8],
+        messages: &[B],
+    ) -> CredxResult<[u8; Self::SIGNATURE_BYTES]> {
+        let sk = scalar_from_bytes(sk)?;
+        if sk.is_zero().into() {
+            return Err(Error::General("invalid secret key"));
+        }
+        let message_scalars = self.messages_to_scalars(messages);
+        let generators = self.create_generators(message_scalars.len() + 1)?;
+        let api_id = self.api_id();
+        let domain = self.calculate_domain(pk, &generators, header, &api_id)?;
+
+        let mut e_input = Vec::with_capacity(Self::SCALAR_BYTES * (message_scalars.len() + 2));
+        e_input.extend_from_slice(&sk.to_be_bytes());
+        for m in &message_scalars {
+            e_input.extend_from_slice(&m.to_be_bytes());
+        }
+        e_input.extend_from_slice(&domain.to_be_bytes());
+        let e = self.hash_to_scalar(&e_input, &[api_id.as_slice(), b"H2S_"].concat());
+
+        let b = self.calculate_b(&generators, domain, &message_scalars);
+        finalize_signature(sk, e, b)
+    }
+
+    /// Verify a signature over the messages and header
+    pub fn verify<B: AsRef<[u8]>>(
+        &self,
+        pk: &[u8],
+        signature: &[u8],
+        header: &[u8],
+        messages: &[B],
+    ) -> CredxResult<()> {
+        let w = public_key_from_bytes(pk)?;
+        let (a, e) = signature_from_bytes(signature)?;
+        let message_scalars = self.messages_to_scalars(messages);
+        let generators = self.create_generators(message_scalars.len() + 1)?;
+        let domain = self.calculate_domain(pk, &generators, header, &self.api_id())?;
+        let b = self.calculate_b(&generators, domain, &message_scalars);
+        core_verify(w, a, e, b)
+    }
+
+    /// Create a proof of knowledge of a signature that reveals the messages
+    /// at `disclosed_indexes` bound to the presentation header `ph`
+    #[allow(clippy::too_many_arguments)]
+    pub fn proof_gen<B: AsRef<[u8]>>(
+        &self,
+        pk: &[u8],
+        signature: &[u8],
+        header: &[u8],
+        ph: &[u8],
+        messages: &[B],
+        disclosed_indexes: &[usize],
+        mut rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<Vec<u8>> {
+        let undisclosed = messages.len().saturating_sub(disclosed_indexes.len());
+        let random_scalars = (0..5 + undisclosed)
+            .map(|_| Scalar::random(&mut rng))
+            .collect::<Vec<_>>();
+        self.proof_gen_with_scalars(
+            pk,
+            signature,
+            header,
+            ph,
+            messages,
+            disclosed_indexes,
+            &random_scalars,
+        )
+    }
+
+    /// Create a proof like [`Self::proof_gen`] with the draft's mocked
+    /// random scalars expanded from `seed`.
+    ///
+    /// This is only for comparing proofs with the draft's fixtures. Anyone
+    /// with the seed can recover the undisclosed messages from the proof.
+    #[allow(clippy::too_many_arguments)]
+    pub fn proof_gen_with_seed<B: AsRef<[u8]>>(
+        &self,
+        pk: &[u8],
+        signature: &[u8],
+        header: &[u8],
+        ph: &[u8],
+        messages: &[B],
+        disclosed_indexes: &[usize],
+        seed: &[u8],
+    ) -> CredxResult<Vec<u8>> {
+        let undisclosed = messages.len().saturating_sub(disclosed_indexes.len());
+        let random_scalars = self.seeded_random_scalars(seed, 5 + undisclosed, &self.api_id())?;
+        self.proof_gen_with_scalars(
+            pk,
+            signature,
+            header,
+            ph,
+            messages,
+            disclosed_indexes,
+            &random_scalars,
+        )
+    }
+
+    /// The draft's `seeded_random_scalars` with the mocked random scalars dst
+    fn seeded_random_scalars(
+        &self,
+        seed: &[u8],
+        count: usize,
+        api_id: &[u8],
+    ) -> CredxResult<Vec<Scalar>> {
+        let dst = [api_id, b"MOCK_RANDOM_SCALARS_DST_"].concat();
+        let mut v = vec![0u8; Self::EXPAND_LEN * count];
+        self.expand_message(seed, &dst, &mut v)?;
+        Ok(v.chunks(Self::EXPAND_LEN)
+            .map(|c| Scalar::from_okm(&<[u8; Self::EXPAND_LEN]>::try_from(c).expect("48 bytes")))
+            .collect())
+    }
+
+    /// Create a proof with the random scalars `(r1, r2, e~, r1~, r3~, m~_j1, ..., m~_jU)`
+    #[allow(clippy::too_many_arguments)]
+    fn proof_gen_with_scalars<B: AsRef<[u8]>>(
+        &self,
+        pk: &[u8],
+        signature: &[u8],
+        header: &[u8],
+        ph: &[u8],
+        messages: &[B],
+        disclosed_indexes: &[usize],
+        random_scalars: &[Scalar],
+    ) -> CredxResult<Vec<u8>> {
+        let message_scalars = self.messages_to_scalars(messages);
+        let disclosed = sorted_indexes(disclosed_indexes, message_scalars.len())?;
+        let generators = self.create_generators(message_scalars.len() + 1)?;
+        let api_id = self.api_id();
+        let domain = self.calculate_domain(pk, &generators, header, &api_id)?;
+        self.core_proof_gen(
+            signature,
+            &generators,
+            domain,
+            &message_scalars,
+            &disclosed,
+            ph,
+            &api_id,
+            random_scalars,
+            None,
+        )
+    }
+
+    /// Create a proof over the message scalars revealing those at the sorted
+    /// indexes `disclosed`.
+    ///
+    /// `nym` is the index of the nym secret in `message_scalars` and the
+    /// pseudonym base point when the proof also shows a pseudonym.
+    #[allow(clippy::too_many_arguments)]
+    fn core_proof_gen(
+        &self,
+        signature: &[u8],
+        generators: &[G1Projective],
+        domain: Scalar,
+        message_scalars: &[Scalar],
+        disclosed: &[usize],
+        ph: &[u8],
+        api_id: &[u8],
+        random_scalars: &[Scalar],
+        nym: Option<(usize, G1Projective)>,
+    ) -> CredxResult<Vec<u8>> {
+        let (a, e) = signature_from_bytes(signature)?;
+        let undisclosed = (0..message_scalars.len())
+            .filter(|i| disclosed.binary_search(i).is_err())
+            .collect::<Vec<_>>();
+        if random_scalars.len() != 5 + undisclosed.len() {
+            return Err(Error::General("invalid number of random scalars"));
+        }
+
+        let (r1, r2, e_t, r1_t, r3_t) = (
+            random_scalars[0],
+            random_scalars[1],
+            random_scalars[2],
+            random_scalars[3],
+            random_scalars[4],
+        );
+        let m_t = &random_scalars[5..];
+
+        let b = self.calculate_b(generators, domain, message_scalars);
+        let d = b * r2;
+        let a_bar = a * (r1 * r2);
+        let b_bar = d * r1 - a_bar * e;
+        let t1 = a_bar * e_t + d * r1_t;
+        let mut t2 = d * r3_t;
+        for (j, m) in undisclosed.iter().zip(m_t) {
+            t2 += generators[j + 1] * m;
+        }
+        let mut points = vec![a_bar, b_bar, d, t1, t2];
+        if let Some((index, op)) = nym {
+            let position = undisclosed
+                .binary_search(&index)
+                .map_err(|_| Error::General("the nym secret can't be disclosed"))?;
+            points.extend([op * message_scalars[index], op, op * m_t[position]]);
+        }
+
+        let disclosed_messages = disclosed
+            .iter()
+            .map(|i| (*i, message_scalars[*i]))
+            .collect::<Vec<_>>();
+        let challenge = self.proof_challenge(&points, domain, &disclosed_messages, ph, api_id);
+
+        let r3 =
+            Option::<Scalar>::from(r2.invert()).ok_or(Error::General("invalid random scalar"))?;
+        let mut proof = Vec::with_capacity(
+            3 * Self::POINT_BYTES + (4 + undisclosed.len()) * Self::SCALAR_BYTES,
+        );
+        for p in [a_bar, b_bar, d] {
+            proof.extend_from_slice(&p.to_affine().to_compressed());
+        }
+        for s in [
+            e_t + e * challenge,
+            r1_t - r1 * challenge,
+            r3_t - r3 * challenge,
+        ] {
+            proof.extend_from_slice(&s.to_be_bytes());
+        }
+        for (j, m) in undisclosed.iter().zip(m_t) {
+            proof.extend_from_slice(&(m + message_scalars[*j] * challenge).to_be_bytes());
+        }
+        proof.extend_from_slice(&challenge.to_be_bytes());
+        Ok(proof)
+    }
+
+    /// Verify a proof that reveals `disclosed_messages` by their index
+    /// out of `message_count` signed messages
+    pub fn proof_verify<B: AsRef<[u8]>>(
+        &self,
+        pk: &[u8],
+        proof: &[u8],
+        header: &[u8],
+        ph: &[u8],
+        message_count: usize,
+        disclosed_messages: &[(usize, B)],
+    ) -> CredxResult<()> {
+        let api_id = self.api_id();
+        let disclosed_scalars = disclosed_messages
+            .iter()
+            .map(|(i, m)| (*i, self.map_messages(&[m], &api_id)[0]))
+            .collect::<Vec<_>>();
+        let generators = self.create_generators(message_count + 1)?;
+        let domain = self.calculate_domain(pk, &generators, header, &api_id)?;
+        self.core_proof_verify(
+            pk,
+            proof,
+            &generators,
+            domain,
+            &disclosed_scalars,
+            ph,
+            &api_id,
+            None,
+        )
+    }
+
+    /// Verify a proof created by [`Self::core_proof_gen`].
+    ///
+    /// `nym` is the index of the nym secret, the pseudonym base point
+    /// and the pseudonym when the proof also shows a pseudonym.
+    #[allow(clippy::too_many_arguments)]
+    fn core_proof_verify(
+        &self,
+        pk: &[u8],
+        proof: &[u8],
+        generators: &[G1Projective],
+        domain: Scalar,
+        disclosed_messages: &[(usize, Scalar)],
+        ph: &[u8],
+        api_id: &[u8],
+        nym: Option<(usize, G1Projective, G1Projective)>,
+    ) -> CredxResult<()> {
+        let w = public_key_from_bytes(pk)?;
+        let message_count = generators.len() - 1;
+        let disclosed_indexes = disclosed_messages
+            .iter()
+            .map(|(i, _)| *i)
+            .collect::<Vec<_>>();
+        let disclosed = sorted_indexes(&disclosed_indexes, message_count)?;
+        let undisclosed = (0..message_count)
+            .filter(|i| disclosed.binary_search(i).is_err())
+            .collect::<Vec<_>>();
+
+        let points_len = 3 * Self::POINT_BYTES;
+        if proof.len() != points_len + (4 + undisclosed.len()) * Self::SCALAR_BYTES {
+            return Err(Error::InvalidSignatureProofData);
+        }
+        let mut points = proof[..points_len]
+            .chunks(Self::POINT_BYTES)
+            .map(point_from_bytes);
+        let a_bar = points.next().expect("3 points")?;
+        let b_bar = points.next().expect("3 points")?;
+        let d = points.next().expect("3 points")?;
+        let scalars = proof[points_len..]
+            .chunks(Self::SCALAR_BYTES)
+            .map(scalar_from_bytes)
+            .collect::<CredxResult<Vec<_>>>()?;
+        let (e_h, r1_h, r3_h) = (scalars[0], scalars[1], scalars[2]);
+        let m_h = &scalars[3..3 + undisclosed.len()];
+        let challenge = scalars[scalars.len() - 1];
+
+        let mut disclosed_scalars = disclosed_messages.to_vec();
+        disclosed_scalars.sort_unstable_by_key(|(i, _)| *i);
+
+        let t1 = b_bar * challenge + a_bar * e_h + d * r1_h;
+        let mut bv = self.p1() + generators[0] * domain;
+        for (i, m) in &disclosed_scalars {
+            bv += generators[i + 1] * m;
+        }
+        let mut t2 = bv * challenge + d * r3_h;
+        for (j, m) in undisclosed.iter().zip(m_h) {
+            t2 += generators[j + 1] * m;
+        }
+        let mut challenge_points = vec![a_bar, b_bar, d, t1, t2];
+        if let Some((index, op, pseudonym)) = nym {
+            let position = undisclosed
+                .binary_search(&index)
+                .map_err(|_| Error::InvalidSignatureProofData)?;
+            if pseudonym.is_identity().into() {
+                return Err(Error::InvalidSignatureProofData);
+            }
+            challenge_points.extend([pseudonym, op, op * m_h[position] - pseudonym * challenge]);
+        }
+        let expected =
+            self.proof_challenge(&challenge_points, domain, &disclosed_scalars, ph, api_id);
+        if expected != challenge {
+            return Err(Error::InvalidSignatureProofData);
+        }
+        if a_bar.is_identity().into() {
+            return Err(Error::InvalidSignatureProofData);
+        }
+        let valid: bool = multi_miller_loop(&[
+            (&a_bar.to_affine(), &G2Prepared::from(w.to_affine())),
+            (
+                &b_bar.to_affine(),
+                &G2Prepared::from(-G2Affine::generator()),
+            ),
+        ])
+        .final_exponentiation()
+        .is_identity()
+        .into();
+        if valid {
+            Ok(())
+        } else {
+            Err(Error::InvalidSignatureProofData)
+        }
+    }
+
+    fn calculate_b(
+        &self,
+        generators: &[G1Projective],
+        domain: Scalar,
+        msgs: &[Scalar],
+    ) -> G1Projective {
+        self.p1() + generators[0] * domain + G1Projective::sum_of_products(&generators[1..], msgs)
+    }
+
+    fn calculate_domain(
+        &self,
+        pk: &[u8],
+        generators: &[G1Projective],
+        header: &[u8],
+        api_id: &[u8],
+    ) -> CredxResult<Scalar> {
+        public_key_from_bytes(pk)?;
+        let mut input = Vec::with_capacity(
+            pk.len() + 8 + Self::POINT_BYTES * generators.len() + api_id.len() + 8 + header.len(),
+        );
+        input.extend_from_slice(pk);
+        input.extend_from_slice(&((generators.len() - 1) as u64).to_be_bytes());
+        for g in generators {
+            input.extend_from_slice(&g.to_affine().to_compressed());
+        }
+        input.extend_from_slice(api_id);
+        input.extend_from_slice(&(header.len() as u64).to_be_bytes());
+        input.extend_from_slice(header);
+        Ok(self.hash_to_scalar(&input, &[api_id, b"H2S_"].concat()))
+    }
+
+    fn proof_challenge(
+        &self,
+        points: &[G1Projective],
+        domain: Scalar,
+        disclosed: &[(usize, Scalar)],
+        ph: &[u8],
+        api_id: &[u8],
+    ) -> Scalar {
+        let mut input = Vec::with_capacity(
+            8 + disclosed.len() * (8 + Self::SCALAR_BYTES)
+                + points.len() * Self::POINT_BYTES
+                + Self::SCALAR_BYTES
+                + 8
+                + ph.len(),
+        );
+        input.extend_from_slice(&(disclosed.len() as u64).to_be_bytes());
+        for (i, m) in disclosed {
+            input.extend_from_slice(&(*i as u64).to_be_bytes());
+            input.extend_from_slice(&m.to_be_bytes());
+        }
+        for p in points {
+            input.extend_from_slice(&p.to_affine().to_compressed());
+        }
+        input.extend_from_slice(&domain.to_be_bytes());
+        input.extend_from_slice(&(ph.len() as u64).to_be_bytes());
+        input.extend_from_slice(ph);
+        self.hash_to_scalar(&input, &[api_id, b"H2S_"].concat())
+    }
+}
+
+fn finalize_signature(
+    sk: Scalar,
+    e: Scalar,
+    b: G1Projective,
+) -> CredxResult<[u8; BbsCiphersuite::SIGNATURE_BYTES]> {
+    let ske =
+        Option::<Scalar>::from((sk + e).invert()).ok_or(Error::General("invalid signature"))?;
+    let a = b * ske;
+    if a.is_identity().into() {
+        return Err(Error::General("invalid signature"));
+    }
+    let mut signature = [0u8; BbsCiphersuite::SIGNATURE_BYTES];
+    signature[..BbsCiphersuite::POINT_BYTES].copy_from_slice(&a.to_affine().to_compressed());
+    signature[BbsCiphersuite::POINT_BYTES..].copy_from_slice(&e.to_be_bytes());
+    Ok(signature)
+}
+
+fn core_verify(w: G2Projective, a: G1Projective, e: Scalar, b: G1Projective) -> CredxResult<()> {
+    let valid: bool = multi_miller_loop(&[
+        (
+            &a.to_affine(),
+            &G2Prepared::from((w + G2Projective::GENERATOR * e).to_affine()),
+        ),
+        (&b.to_affine(), &G2Prepared::from(-G2Affine::generator())),
+    ])
+    .final_exponentiation()
+    .is_identity()
+    .into();
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::General("invalid signature"))
+    }
+}
+
+fn expand<X>(msg: &[u8], dst: &[u8], out: &mut [u8]) -> CredxResult<()>
+where
+    X: for<'a> ExpandMsg<'a>,
+{
+    let dst = [dst];
+    let mut expander = X::expand_message(&[msg], &dst, out.len())
+        .map_err(|_| Error::General("unable to expand message"))?;
+    expander.fill_bytes(out);
+    Ok(())
+}
+
+fn sorted_indexes(indexes: &[usize], count: usize) -> CredxResult<Vec<usize>> {
+    let mut sorted = indexes.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    if sorted.len() != indexes.len() || sorted.last().is_some_and(|i| *i >= count) {
+        return Err(Error::General("invalid disclosed indexes"));
+    }
+    Ok(sorted)
+}
+
+fn scalar_from_bytes(bytes: &[u8]) -> CredxResult<Scalar> {
+    let bytes = <[u8; 32]>::try_from(bytes).map_err(|_| Error::General("invalid scalar"))?;
+    Option::<Scalar>::from(Scalar::from_be_bytes(&bytes)).ok_or(Error::General("invalid scalar"))
+}
+
+fn point_from_bytes(bytes: &[u8]) -> CredxResult<G1Projective> {
+    let bytes = <[u8; 48]>::try_from(bytes).map_err(|_| Error::InvalidSignatureProofData)?;
+    let point = Option::<G1Affine>::from(G1Affine::from_compressed(&bytes))
+        .ok_or(Error::InvalidSignatureProofData)?;
+    Ok(G1Projective::from(point))
+}
+
+fn public_key_from_bytes(bytes: &[u8]) -> CredxResult<G2Projective> {
+    let bytes = <[u8; 96]>::try_from(bytes).map_err(|_| Error::InvalidPublicKey)?;
+    let w = Option::<G2Affine>::from(G2Affine::from_compressed(&bytes))
+        .map(G2Projective::from)
+        .ok_or(Error::InvalidPublicKey)?;
+    if w.is_identity().into() {
+        return Err(Error::InvalidPublicKey);
+    }
+    Ok(w)
+}
+
+fn signature_from_bytes(bytes: &[u8]) -> CredxResult<(G1Projective, Scalar)> {
+    if bytes.len() != BbsCiphersuite::SIGNATURE_BYTES {
+        return Err(Error::General("invalid signature"));
+    }
+    let a = point_from_bytes(&bytes[..BbsCiphersuite::POINT_BYTES])
+        .map_err(|_| Error::General("invalid signature"))?;
+    let e = scalar_from_bytes(&bytes[BbsCiphersuite::POINT_BYTES..])?;
+    if a.is_identity().into() || e.is_zero().into() {
+        return Err(Error::General("invalid signature"));
+    }
+    Ok((a, e))
+}
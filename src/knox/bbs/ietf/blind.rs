@@ -0,0 +1,322 @@
+use super::{
+    core_verify, finalize_signature, point_from_bytes, public_key_from_bytes, scalar_from_bytes,
+    signature_from_bytes, BbsCiphersuite,
+};
+use crate::error::Error;
+use crate::CredxResult;
+use blsful::inner_types::{Curve, Field, G1Projective, Group, Scalar};
+use rand_core::{CryptoRng, RngCore};
+use subtle::ConstantTimeEq;
+
+/// Blind BBS signatures from the CFRG Blind BBS Signatures draft
+/// <https://datatracker.ietf.org/doc/draft-kalos-bbs-blind-signatures/>
+///
+/// The prover commits to messages the signer never sees. The signer signs
+/// the commitment along with its own messages and the prover checks the
+/// result with all of the messages and the secret prover blind.
+///
+/// Commit and BlindSign are tested with the draft's mocked random scalars
+/// against the draft's steps. The draft's own fixtures aren't included yet,
+/// so interoperability with other implementations is still unverified.
+impl BbsCiphersuite {
+    /// The API id used for domain separation of blind signatures
+    pub fn blind_api_id(&self) -> Vec<u8> {
+        [self.id(), b"BLIND_H2G_HM2S_"].concat()
+    }
+
+    /// Create `count` blind generators `(Q_2, J_1, ..., J_{count-1})`
+    pub fn create_blind_generators(&self, count: usize) -> CredxResult<Vec<G1Projective>> {
+        self.generators(count, &[b"BLIND_", self.blind_api_id().as_slice()].concat())
+    }
+
+    /// Commit to the messages to be blindly signed.
+    ///
+    /// Returns the commitment with its proof of knowledge to send to the signer
+    /// and the secret prover blind needed to verify the blind signature.
+    pub fn commit<B: AsRef<[u8]>>(
+        &self,
+        committed_messages: &[B],
+        mut rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<(Vec<u8>, [u8; Self::SCALAR_BYTES])> {
+        let random_scalars = (0..committed_messages.len() + 2)
+            .map(|_| Scalar::random(&mut rng))
+            .collect::<Vec<_>>();
+        self.commit_with_scalars(committed_messages, &random_scalars)
+    }
+
+    /// Commit like [`Self::commit`] with the draft's mocked random scalars
+    /// expanded from `seed`.
+    ///
+    /// This is only for comparing commitments with the draft's fixtures.
+    /// Anyone with the seed can recover the committed messages.
+    pub fn commit_with_seed<B: AsRef<[u8]>>(
+        &self,
+        committed_messages: &[B],
+        seed: &[u8],
+    ) -> CredxResult<(Vec<u8>, [u8; Self::SCALAR_BYTES])> {
+        let random_scalars =
+            self.seeded_random_scalars(seed, committed_messages.len() + 2, &self.blind_api_id())?;
+        self.commit_with_scalars(committed_messages, &random_scalars)
+    }
+
+    /// Commit with the random scalars `(secret_prover_blind, s~, m~_1, ..., m~_M)`
+    fn commit_with_scalars<B: AsRef<[u8]>>(
+        &self,
+        committed_messages: &[B],
+        random_scalars: &[Scalar],
+    ) -> CredxResult<(Vec<u8>, [u8; Self::SCALAR_BYTES])> {
+        let api_id = self.blind_api_id();
+        let blind_generators = self.create_blind_generators(committed_messages.len() + 1)?;
+        let secret_prover_blind = random_scalars[0];
+        let mut messages = Vec::with_capacity(committed_messages.len() + 1);
+        messages.push(secret_prover_blind);
+        messages.extend(self.map_messages(committed_messages, &api_id));
+        let (commitment, proofs, challenge) =
+            self.core_commit_with_randomness(&messages, &blind_generators, &random_scalars[1..]);
+        Ok((
+            self.commitment_with_proof_octets(commitment, &proofs, challenge),
+            secret_prover_blind.to_be_bytes(),
+        ))
+    }
+
+    /// Check the proof of knowledge in a commitment created by [`Self::commit`]
+    pub fn verify_commitment(&self, commitment_with_proof: &[u8]) -> CredxResult<()> {
+        self.commitment_from_octets(commitment_with_proof)
+            .map(|_| ())
+    }
+
+    /// Sign the messages and the prover's commitment
+    pub fn blind_sign<B: AsRef<[u8]>>(
+        &self,
+        sk: &[u8],
+        pk: &[u8],
+        commitment_with_proof: &[u8],
+        header: &[u8],
+        messages: &[B],
+    ) -> CredxResult<[u8; Self::SIGNATURE_BYTES]> {
+        self.core_blind_sign(sk, pk, commitment_with_proof, header, messages, None)
+    }
+
+    /// Sign the messages and the prover's commitment adding
+    /// `signer_nym_entropy` to the last committed message
+    pub(super) fn core_blind_sign<B: AsRef<[u8]>>(
+        &self,
+        sk: &[u8],
+        pk: &[u8],
+        commitment_with_proof: &[u8],
+        header: &[u8],
+        messages: &[B],
+        signer_nym_entropy: Option<Scalar>,
+    ) -> CredxResult<[u8; Self::SIGNATURE_BYTES]> {
+        let sk = scalar_from_bytes(sk)?;
+        if sk.is_zero().into() {
+            return Err(Error::General("invalid secret key"));
+        }
+        let api_id = self.blind_api_id();
+        let (commitment, blind_generators) = self.commitment_from_octets(commitment_with_proof)?;
+        let message_scalars = self.map_messages(messages, &api_id);
+        let mut generators = self.generators(message_scalars.len() + 1, &api_id)?;
+        let signer_generators = generators.len();
+        generators.extend_from_slice(&blind_generators);
+        let domain = self.calculate_domain(pk, &generators, header, &api_id)?;
+
+        // Include the commitment so different commitments to the same
+        // signer messages never share `e`
+        let mut e_input = Vec::with_capacity(
+            Self::SCALAR_BYTES * (message_scalars.len() + 3) + Self::POINT_BYTES,
+        );
+        e_input.extend_from_slice(&sk.to_be_bytes());
+        for m in &message_scalars {
+            e_input.extend_from_slice(&m.to_be_bytes());
+        }
+        e_input.extend_from_slice(&commitment.to_affine().to_compressed());
+        if let Some(entropy) = signer_nym_entropy {
+            e_input.extend_from_slice(&entropy.to_be_bytes());
+        }
+        e_input.extend_from_slice(&domain.to_be_bytes());
+        let e = self.hash_to_scalar(&e_input, &[api_id.as_slice(), b"H2S_"].concat());
+
+        let mut b = self.calculate_b(&generators[..signer_generators], domain, &message_scalars)
+            + commitment;
+        if let Some(entropy) = signer_nym_entropy {
+            if blind_generators.len() < 2 {
+                return Err(Error::General("commitment has no nym"));
+            }
+            b += blind_generators[blind_generators.len() - 1] * entropy;
+        }
+        finalize_signature(sk, e, b)
+    }
+
+    /// Verify a blind signature with the signer's messages, the committed
+    /// messages and the secret prover blind returned by [`Self::commit`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn blind_verify<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        &self,
+        pk: &[u8],
+        signature: &[u8],
+        header: &[u8],
+        messages: &[B],
+        committed_messages: &[C],
+        secret_prover_blind: &[u8],
+    ) -> CredxResult<()> {
+        let api_id = self.blind_api_id();
+        let mut message_scalars = self.map_messages(messages, &api_id);
+        message_scalars.push(scalar_from_bytes(secret_prover_blind)?);
+        message_scalars.extend(self.map_messages(committed_messages, &api_id));
+        self.core_blind_verify(
+            pk,
+            signature,
+            header,
+            messages.len(),
+            committed_messages.len(),
+            &message_scalars,
+        )
+    }
+
+    /// Verify a blind signature over the signer's message scalars followed
+    /// by the secret prover blind and the committed message scalars
+    pub(super) fn core_blind_verify(
+        &self,
+        pk: &[u8],
+        signature: &[u8],
+        header: &[u8],
+        message_count: usize,
+        committed_count: usize,
+        message_scalars: &[Scalar],
+    ) -> CredxResult<()> {
+        let w = public_key_from_bytes(pk)?;
+        let (a, e) = signature_from_bytes(signature)?;
+        let (generators, domain) =
+            self.blind_generators_and_domain(pk, header, message_count, committed_count)?;
+        let b = self.calculate_b(&generators, domain, message_scalars);
+        core_verify(w, a, e, b)
+    }
+
+    /// The generators `(Q_1, H_1, ..., H_L, Q_2, J_1, ..., J_M)` and the domain
+    /// of a blind signature over `message_count` signer messages and
+    /// `committed_count` committed messages
+    pub(super) fn blind_generators_and_domain(
+        &self,
+        pk: &[u8],
+        header: &[u8],
+        message_count: usize,
+        committed_count: usize,
+    ) -> CredxResult<(Vec<G1Projective>, Scalar)> {
+        let api_id = self.blind_api_id();
+        let mut generators = self.generators(message_count + 1, &api_id)?;
+        generators.extend(self.create_blind_generators(committed_count + 1)?);
+        let domain = self.calculate_domain(pk, &generators, header, &api_id)?;
+        Ok((generators, domain))
+    }
+
+    /// Commit to `messages` with `generators` and prove knowledge of them.
+    ///
+    /// Returns the commitment, a response for each message and the challenge.
+    pub(crate) fn core_commit(
+        &self,
+        messages: &[Scalar],
+        generators: &[G1Projective],
+        mut rng: impl RngCore + CryptoRng,
+    ) -> (G1Projective, Vec<Scalar>, Scalar) {
+        let randomness = (0..messages.len())
+            .map(|_| Scalar::random(&mut rng))
+            .collect::<Vec<_>>();
+        self.core_commit_with_randomness(messages, generators, &randomness)
+    }
+
+    fn core_commit_with_randomness(
+        &self,
+        messages: &[Scalar],
+        generators: &[G1Projective],
+        randomness: &[Scalar],
+    ) -> (G1Projective, Vec<Scalar>, Scalar) {
+        let commitment = G1Projective::sum_of_products(generators, messages);
+        let random_commitment = G1Projective::sum_of_products(generators, randomness);
+        let challenge = self.blind_challenge(commitment, random_commitment, generators);
+        let proofs = randomness
+            .iter()
+            .zip(messages)
+            .map(|(r, m)| r + m * challenge)
+            .collect();
+        (commitment, proofs, challenge)
+    }
+
+    /// Check a proof created by [`Self::core_commit`]
+    pub(crate) fn core_verify_commitment(
+        &self,
+        commitment: G1Projective,
+        proofs: &[Scalar],
+        challenge: Scalar,
+        generators: &[G1Projective],
+    ) -> bool {
+        if proofs.len() != generators.len() {
+            return false;
+        }
+        let random_commitment =
+            G1Projective::sum_of_products(generators, proofs) - commitment * challenge;
+        self.blind_challenge(commitment, random_commitment, generators)
+            .ct_eq(&challenge)
+            .into()
+    }
+
+    /// Encode a commitment and its proof as `commitment_with_proof` octets
+    pub(crate) fn commitment_with_proof_octets(
+        &self,
+        commitment: G1Projective,
+        proofs: &[Scalar],
+        challenge: Scalar,
+    ) -> Vec<u8> {
+        let mut octets =
+            Vec::with_capacity(Self::POINT_BYTES + Self::SCALAR_BYTES * (proofs.len() + 1));
+        octets.extend_from_slice(&commitment.to_affine().to_compressed());
+        for p in proofs {
+            octets.extend_from_slice(&p.to_be_bytes());
+        }
+        octets.extend_from_slice(&challenge.to_be_bytes());
+        octets
+    }
+
+    fn commitment_from_octets(
+        &self,
+        commitment_with_proof: &[u8],
+    ) -> CredxResult<(G1Projective, Vec<G1Projective>)> {
+        let len = commitment_with_proof.len();
+        if len < Self::POINT_BYTES + 2 * Self::SCALAR_BYTES
+            || !(len - Self::POINT_BYTES).is_multiple_of(Self::SCALAR_BYTES)
+        {
+            return Err(Error::General("invalid commitment"));
+        }
+        let commitment = point_from_bytes(&commitment_with_proof[..Self::POINT_BYTES])
+            .map_err(|_| Error::General("invalid commitment"))?;
+        if commitment.is_identity().into() {
+            return Err(Error::General("invalid commitment"));
+        }
+        let mut scalars = commitment_with_proof[Self::POINT_BYTES..]
+            .chunks(Self::SCALAR_BYTES)
+            .map(scalar_from_bytes)
+            .collect::<CredxResult<Vec<_>>>()?;
+        let challenge = scalars.pop().expect("at least two scalars");
+        let blind_generators = self.create_blind_generators(scalars.len())?;
+        if self.core_verify_commitment(commitment, &scalars, challenge, &blind_generators) {
+            Ok((commitment, blind_generators))
+        } else {
+            Err(Error::General("invalid commitment proof"))
+        }
+    }
+
+    fn blind_challenge(
+        &self,
+        commitment: G1Projective,
+        random_commitment: G1Projective,
+        generators: &[G1Projective],
+    ) -> Scalar {
+        let mut input = Vec::with_capacity(8 + Self::POINT_BYTES * (generators.len() + 2));
+        input.extend_from_slice(&(generators.len().saturating_sub(1) as u64).to_be_bytes());
+        for g in generators {
+            input.extend_from_slice(&g.to_affine().to_compressed());
+        }
+        input.extend_from_slice(&commitment.to_affine().to_compressed());
+        input.extend_from_slice(&random_commitment.to_affine().to_compressed());
+        self.hash_to_scalar(&input, &[self.blind_api_id().as_slice(), b"H2S_"].concat())
+    }
+}
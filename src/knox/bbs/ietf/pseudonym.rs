@@ -0,0 +1,243 @@
+use super::{scalar_from_bytes, sorted_indexes, BbsCiphersuite};
+use crate::error::Error;
+use crate::CredxResult;
+use blsful::inner_types::{Curve, Field, G1Affine, G1Projective, Scalar};
+use rand_core::{CryptoRng, RngCore};
+
+/// Per verifier pseudonyms from the CFRG BBS per Verifier Linkability draft
+/// <https://datatracker.ietf.org/doc/draft-irtf-cfrg-bbs-per-verifier-linkability/>
+///
+/// The holder commits to a `prover_nym` with the blind signature commitment
+/// and the signer adds its own entropy so the resulting `nym_secret` is
+/// known only to the holder. Proofs show the pseudonym `OP * nym_secret`
+/// for a verifier's context id where `OP` is hashed from the context id.
+/// The same holder always has the same pseudonym with a verifier but
+/// pseudonyms for different verifiers can't be linked.
+///
+/// [`BbsPseudonymPresentation`](crate::presentation::BbsPseudonymPresentation)
+/// carries the proof and pseudonym octets for verifiers that implement the
+/// draft. The pseudonym is tested against the draft's hash to curve but the
+/// draft's pseudonym fixtures aren't included yet.
+impl BbsCiphersuite {
+    /// The length of a pseudonym in bytes
+    pub const PSEUDONYM_BYTES: usize = 48;
+
+    /// The pseudonym base point `OP` for a verifier's context id
+    pub fn pseudonym_base(&self, context_id: &[u8]) -> G1Projective {
+        self.hash_to_curve(
+            context_id,
+            &[self.blind_api_id().as_slice(), b"OP_"].concat(),
+        )
+    }
+
+    /// Compute the pseudonym for `nym_secret` with a verifier's context id
+    pub fn pseudonym(
+        &self,
+        context_id: &[u8],
+        nym_secret: &[u8],
+    ) -> CredxResult<[u8; Self::PSEUDONYM_BYTES]> {
+        let nym_secret = scalar_from_bytes(nym_secret)?;
+        Ok((self.pseudonym_base(context_id) * nym_secret)
+            .to_affine()
+            .to_compressed())
+    }
+
+    /// Commit to the messages to be blindly signed and the `prover_nym`.
+    ///
+    /// Returns the commitment with its proof of knowledge to send to the signer
+    /// and the secret prover blind.
+    pub fn commit_with_nym<B: AsRef<[u8]>>(
+        &self,
+        committed_messages: &[B],
+        prover_nym: &[u8],
+        mut rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<(Vec<u8>, [u8; Self::SCALAR_BYTES])> {
+        let prover_nym = scalar_from_bytes(prover_nym)?;
+        let blind_generators = self.create_blind_generators(committed_messages.len() + 2)?;
+        let secret_prover_blind = Scalar::random(&mut rng);
+        let mut messages = Vec::with_capacity(committed_messages.len() + 2);
+        messages.push(secret_prover_blind);
+        messages.extend(self.map_messages(committed_messages, &self.blind_api_id()));
+        messages.push(prover_nym);
+        let (commitment, proofs, challenge) = self.core_commit(&messages, &blind_generators, rng);
+        Ok((
+            self.commitment_with_proof_octets(commitment, &proofs, challenge),
+            secret_prover_blind.to_be_bytes(),
+        ))
+    }
+
+    /// Sign the messages and a commitment created by [`Self::commit_with_nym`].
+    ///
+    /// Returns the signature and the signer's nym entropy which must be
+    /// sent to the holder with the signature.
+    pub fn blind_sign_with_nym<B: AsRef<[u8]>>(
+        &self,
+        sk: &[u8],
+        pk: &[u8],
+        commitment_with_proof: &[u8],
+        header: &[u8],
+        messages: &[B],
+        rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<([u8; Self::SIGNATURE_BYTES], [u8; Self::SCALAR_BYTES])> {
+        let signer_nym_entropy = Scalar::random(rng);
+        let signature = self.core_blind_sign(
+            sk,
+            pk,
+            commitment_with_proof,
+            header,
+            messages,
+            Some(signer_nym_entropy),
+        )?;
+        Ok((signature, signer_nym_entropy.to_be_bytes()))
+    }
+
+    /// Verify a signature created by [`Self::blind_sign_with_nym`] and
+    /// return the holder's `nym_secret`
+    #[allow(clippy::too_many_arguments)]
+    pub fn finalize_with_nym<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        &self,
+        pk: &[u8],
+        signature: &[u8],
+        header: &[u8],
+        messages: &[B],
+        committed_messages: &[C],
+        prover_nym: &[u8],
+        signer_nym_entropy: &[u8],
+        secret_prover_blind: &[u8],
+    ) -> CredxResult<[u8; Self::SCALAR_BYTES]> {
+        let nym_secret = scalar_from_bytes(prover_nym)? + scalar_from_bytes(signer_nym_entropy)?;
+        let message_scalars = self.nym_message_scalars(
+            messages,
+            committed_messages,
+            secret_prover_blind,
+            nym_secret,
+        )?;
+        self.core_blind_verify(
+            pk,
+            signature,
+            header,
+            messages.len(),
+            committed_messages.len() + 1,
+            &message_scalars,
+        )?;
+        Ok(nym_secret.to_be_bytes())
+    }
+
+    /// Create a proof of knowledge of a signature created by
+    /// [`Self::blind_sign_with_nym`] that reveals the signer's messages at
+    /// `disclosed_indexes` and shows the pseudonym for `context_id`.
+    ///
+    /// The committed messages, the secret prover blind and the nym secret
+    /// are never disclosed. Returns the proof and the pseudonym.
+    #[allow(clippy::too_many_arguments)]
+    pub fn pseudonym_proof_gen<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        &self,
+        pk: &[u8],
+        signature: &[u8],
+        header: &[u8],
+        ph: &[u8],
+        context_id: &[u8],
+        messages: &[B],
+        committed_messages: &[C],
+        nym_secret: &[u8],
+        secret_prover_blind: &[u8],
+        disclosed_indexes: &[usize],
+        mut rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<(Vec<u8>, [u8; Self::PSEUDONYM_BYTES])> {
+        let nym_secret = scalar_from_bytes(nym_secret)?;
+        let message_scalars = self.nym_message_scalars(
+            messages,
+            committed_messages,
+            secret_prover_blind,
+            nym_secret,
+        )?;
+        let disclosed = sorted_indexes(disclosed_indexes, messages.len())?;
+        let (generators, domain) = self.blind_generators_and_domain(
+            pk,
+            header,
+            messages.len(),
+            committed_messages.len() + 1,
+        )?;
+        let undisclosed = message_scalars.len() - disclosed.len();
+        let random_scalars = (0..5 + undisclosed)
+            .map(|_| Scalar::random(&mut rng))
+            .collect::<Vec<_>>();
+        let op = self.pseudonym_base(context_id);
+        let proof = self.core_proof_gen(
+            signature,
+            &generators,
+            domain,
+            &message_scalars,
+            &disclosed,
+            ph,
+            &self.blind_api_id(),
+            &random_scalars,
+            Some((message_scalars.len() - 1, op)),
+        )?;
+        Ok((proof, (op * nym_secret).to_affine().to_compressed()))
+    }
+
+    /// Verify a proof created by [`Self::pseudonym_proof_gen`] for a signature
+    /// over `message_count` signer messages and `committed_count` committed
+    /// messages that reveals `disclosed_messages` by their index
+    #[allow(clippy::too_many_arguments)]
+    pub fn pseudonym_proof_verify<B: AsRef<[u8]>>(
+        &self,
+        pk: &[u8],
+        proof: &[u8],
+        pseudonym: &[u8],
+        context_id: &[u8],
+        header: &[u8],
+        ph: &[u8],
+        message_count: usize,
+        committed_count: usize,
+        disclosed_messages: &[(usize, B)],
+    ) -> CredxResult<()> {
+        let pseudonym = <[u8; Self::PSEUDONYM_BYTES]>::try_from(pseudonym)
+            .ok()
+            .and_then(|p| Option::<G1Affine>::from(G1Affine::from_compressed(&p)))
+            .map(G1Projective::from)
+            .ok_or(Error::InvalidSignatureProofData)?;
+        if disclosed_messages.iter().any(|(i, _)| *i >= message_count) {
+            return Err(Error::InvalidSignatureProofData);
+        }
+        let api_id = self.blind_api_id();
+        let disclosed_scalars = disclosed_messages
+            .iter()
+            .map(|(i, m)| (*i, self.map_messages(&[m], &api_id)[0]))
+            .collect::<Vec<_>>();
+        let (generators, domain) =
+            self.blind_generators_and_domain(pk, header, message_count, committed_count + 1)?;
+        // The nym secret is the last message after the signer's messages,
+        // the secret prover blind and the committed messages
+        let nym_index = message_count + committed_count + 1;
+        self.core_proof_verify(
+            pk,
+            proof,
+            &generators,
+            domain,
+            &disclosed_scalars,
+            ph,
+            &api_id,
+            Some((nym_index, self.pseudonym_base(context_id), pseudonym)),
+        )
+    }
+
+    fn nym_message_scalars<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        &self,
+        messages: &[B],
+        committed_messages: &[C],
+        secret_prover_blind: &[u8],
+        nym_secret: Scalar,
+    ) -> CredxResult<Vec<Scalar>> {
+        if nym_secret.is_zero().into() {
+            return Err(Error::General("invalid nym secret"));
+        }
+        let api_id = self.blind_api_id();
+        let mut message_scalars = self.map_messages(messages, &api_id);
+        message_scalars.push(scalar_from_bytes(secret_prover_blind)?);
+        message_scalars.extend(self.map_messages(committed_messages, &api_id));
+        message_scalars.push(nym_secret);
+        Ok(message_scalars)
+    }
+}
@@ -14,6 +14,8 @@ impl MessageGenerators {
     }
 
     /// Create a new set of message generators using a specific API ID
+    ///
+    /// These are not the IRTF draft's generators, see [`super::BbsCiphersuite::create_generators`]
     pub fn with_api_id(count: NonZeroUsize, api_id: Option<&[u8]>) -> Self {
         const SEED_DST: &[u8] = b"SIG_GENERATOR_SEED_";
         const GENERATOR_DST: &[u8] = b"SIG_GENERATOR_DST_";
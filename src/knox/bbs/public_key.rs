@@ -1,6 +1,8 @@
 use super::{MessageGenerators, SecretKey};
 use crate::{
-    error::Error, knox::short_group_sig_core::short_group_traits::PublicKey as PublicKeyTrait,
+    error::Error,
+    knox::short_group_sig_core::{short_group_traits::PublicKey as PublicKeyTrait, KeyRelation},
+    CredxResult,
 };
 use blsful::inner_types::*;
 use serde::{Deserialize, Serialize};
@@ -28,6 +30,17 @@ impl From<&SecretKey> for CompressedPublicKey {
 impl PublicKeyTrait for CompressedPublicKey {
     type MessageGenerator = G1Projective;
     type BlindMessageGenerator = G1Projective;
+
+    fn key_relations(&self) -> CredxResult<Vec<KeyRelation>> {
+        if self.max_messages == 0 {
+            return Err(Error::InvalidPublicKey);
+        }
+        Ok(vec![KeyRelation::G2 {
+            secret: 0,
+            base: G2Projective::GENERATOR,
+            public: self.x,
+        }])
+    }
 }
 
 impl From<CompressedPublicKey> for Vec<u8> {
@@ -101,6 +114,16 @@ pub struct PublicKey {
 impl PublicKeyTrait for PublicKey {
     type MessageGenerator = G1Projective;
     type BlindMessageGenerator = G1Projective;
+
+    /// The generators must be the ones derived from `w`
+    fn key_relations(&self) -> CredxResult<Vec<KeyRelation>> {
+        let compressed = self.compress();
+        let relations = compressed.key_relations()?;
+        if compressed.decompress() != *self {
+            return Err(Error::InvalidPublicKey);
+        }
+        Ok(relations)
+    }
 }
 
 impl From<&PublicKey> for CompressedPublicKey {
@@ -1,13 +1,15 @@
 use super::{
-    BlindSignature, BlindSignatureContext, PokSignature, PokSignatureProof, PublicKey, SecretKey,
-    Signature,
+    BbsCiphersuite, BlindSignature, BlindSignatureContext, PokSignature, PokSignatureProof,
+    PublicKey, SecretKey, Signature,
 };
 use crate::error::Error;
 use crate::knox::short_group_sig_core::short_group_traits::{
     BlindSignature as _, BlindSignatureContext as _, ProofOfSignatureKnowledge,
     ProofOfSignatureKnowledgeContribution, ShortGroupSignatureScheme,
 };
-use crate::knox::short_group_sig_core::{ProofCommittedBuilder, ProofMessage};
+use crate::knox::short_group_sig_core::{
+    BlindSignatureFormat, ProofCommittedBuilder, ProofMessage,
+};
 use crate::CredxResult;
 use blsful::inner_types::{Curve, G1Affine, G1Projective, Scalar};
 use merlin::Transcript;
@@ -18,6 +20,24 @@ use std::num::NonZeroUsize;
 #[derive(Debug, Copy, Clone, Deserialize, Serialize)]
 pub struct BbsScheme;
 
+impl BbsScheme {
+    /// The ciphersuite used to compute pseudonyms
+    pub const PSEUDONYM_CIPHERSUITE: BbsCiphersuite = BbsCiphersuite::Bls12381Sha256;
+
+    /// The pseudonym base point for a verifier's context id
+    pub fn pseudonym_base(context_id: &[u8]) -> G1Projective {
+        Self::PSEUDONYM_CIPHERSUITE.pseudonym_base(context_id)
+    }
+
+    /// The pseudonym for `nym_secret` with a verifier's context id.
+    ///
+    /// This is the same pseudonym [`BbsCiphersuite::pseudonym_proof_gen`]
+    /// shows for the nym secret.
+    pub fn pseudonym(context_id: &[u8], nym_secret: Scalar) -> G1Projective {
+        Self::pseudonym_base(context_id) * nym_secret
+    }
+}
+
 impl ShortGroupSignatureScheme for BbsScheme {
     type PublicKey = PublicKey;
     type SecretKey = SecretKey;
@@ -98,11 +118,60 @@ impl ShortGroupSignatureScheme for BbsScheme {
                 commitment,
                 challenge,
                 proofs,
+                format: BlindSignatureFormat::Credx,
             },
             Scalar::ZERO,
         ))
     }
 
+    fn new_blind_signature_context_with_format(
+        messages: &[(usize, Scalar)],
+        public_key: &Self::PublicKey,
+        nonce: Scalar,
+        format: BlindSignatureFormat,
+        rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<(Self::BlindSignatureContext, Scalar)> {
+        match format {
+            BlindSignatureFormat::Credx => {
+                Self::new_blind_signature_context(messages, public_key, nonce, rng)
+            }
+            BlindSignatureFormat::Cfrg => {
+                let mut generators = Vec::with_capacity(messages.len());
+                let mut secrets = Vec::with_capacity(messages.len());
+                for (i, m) in messages {
+                    if *i >= public_key.y.len() {
+                        return Err(Error::General("invalid blind signing"));
+                    }
+                    generators.push(public_key.y[*i]);
+                    secrets.push(*m);
+                }
+                let (commitment, proofs, challenge) =
+                    BlindSignatureContext::CFRG_CIPHERSUITE.core_commit(&secrets, &generators, rng);
+                Ok((
+                    BlindSignatureContext {
+                        commitment,
+                        challenge,
+                        proofs,
+                        format,
+                    },
+                    Scalar::ZERO,
+                ))
+            }
+        }
+    }
+
+    fn blind_message_generators(
+        public_key: &Self::PublicKey,
+        indices: &[usize],
+    ) -> CredxResult<(Vec<G1Projective>, Option<G1Projective>)> {
+        let generators = indices
+            .iter()
+            .map(|i| public_key.y.get(*i).copied())
+            .collect::<Option<Vec<_>>>()
+            .ok_or(Error::General("invalid blind signing"))?;
+        Ok((generators, None))
+    }
+
     fn commit_signature_pok(
         signature: Self::Signature,
         public_key: &Self::PublicKey,
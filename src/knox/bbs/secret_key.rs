@@ -16,6 +16,7 @@ use zeroize::Zeroize;
 ///
 /// See <https://eprint.iacr.org/2023/275.pdf>
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Zeroize)]
+#[zeroize(drop)]
 pub struct SecretKey {
     pub(crate) x: Scalar,
     pub(crate) max_messages: usize,
@@ -27,6 +28,10 @@ impl SecretKeyTrait for SecretKey {
     fn public_key(&self) -> PublicKey {
         PublicKey::from(self)
     }
+
+    fn key_secrets(&self) -> Vec<Scalar> {
+        vec![self.x]
+    }
 }
 
 impl SecretKey {
@@ -0,0 +1,112 @@
+use super::{domain_calculation, BbsScheme, PublicKey, SecretKey, Signature};
+use crate::error::Error;
+use crate::knox::threshold::{
+    combine_inversion, inversion_participants, partial_inversion, split_scalar, InversionNonce,
+    PartialInversion, ThresholdSignatureScheme,
+};
+use crate::CredxResult;
+use blsful::inner_types::{G1Projective, Scalar};
+use elliptic_curve::hash2curve::ExpandMsgXmd;
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use zeroize::Zeroize;
+
+const DST: &[u8] = b"BBS_THRESHOLD_E_";
+
+/// A participant's Shamir share of a BBS secret key
+#[derive(Clone, Debug, Deserialize, Serialize, Zeroize)]
+#[zeroize(drop)]
+pub struct SecretKeyShare {
+    /// The participant index
+    pub index: u32,
+    pub(crate) x: Scalar,
+    #[zeroize(skip)]
+    pub(crate) public_key: PublicKey,
+}
+
+/// A participant's part of a BBS signature
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PartialSignature {
+    /// The participant's part of `A`
+    pub a: PartialInversion,
+}
+
+impl ThresholdSignatureScheme for BbsScheme {
+    type SecretKeyShare = SecretKeyShare;
+    type PartialSignature = PartialSignature;
+
+    const SIGNING_NONCES: usize = 1;
+
+    fn signing_participants(threshold: usize) -> usize {
+        inversion_participants(threshold)
+    }
+
+    fn split_secret_key(
+        sk: &SecretKey,
+        threshold: usize,
+        limit: usize,
+        rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<Vec<SecretKeyShare>> {
+        let public_key = PublicKey::from(sk);
+        Ok(split_scalar(sk.x, threshold, limit, rng)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, x)| SecretKeyShare {
+                index: (i + 1) as u32,
+                x,
+                public_key: public_key.clone(),
+            })
+            .collect())
+    }
+
+    fn partial_sign(
+        share: &SecretKeyShare,
+        msgs: &[Scalar],
+        request_id: &[u8],
+        mut nonces: Vec<InversionNonce>,
+    ) -> CredxResult<PartialSignature> {
+        if msgs.len() > share.public_key.y.len() {
+            return Err(Error::General("Too many messages"));
+        }
+        if nonces.len() != Self::SIGNING_NONCES || nonces[0].index != share.index {
+            return Err(Error::General("invalid signing nonces"));
+        }
+        let e = compute_e(&share.public_key, msgs, request_id);
+        let b = G1Projective::GENERATOR + G1Projective::sum_of_products(&share.public_key.y, msgs);
+        Ok(PartialSignature {
+            a: partial_inversion(share.x, e, b, nonces.remove(0)),
+        })
+    }
+
+    fn combine_signatures(
+        pk: &PublicKey,
+        msgs: &[Scalar],
+        request_id: &[u8],
+        partials: &[PartialSignature],
+    ) -> CredxResult<Signature> {
+        let partials = partials.iter().map(|p| p.a.clone()).collect::<Vec<_>>();
+        let signature = Signature {
+            a: combine_inversion(&partials)?,
+            e: compute_e(pk, msgs, request_id),
+        };
+        if signature.verify(pk, msgs).into() {
+            Ok(signature)
+        } else {
+            Err(Error::InvalidSigningOperation)
+        }
+    }
+}
+
+/// No participant knows the secret key so `e` is derived from the
+/// signing request instead, which must be unique for each signature
+fn compute_e(pk: &PublicKey, msgs: &[Scalar], request_id: &[u8]) -> Scalar {
+    let mut bytes = Vec::with_capacity(8 + request_id.len() + 32 * msgs.len() + 32);
+    bytes.extend_from_slice(&(request_id.len() as u64).to_be_bytes());
+    bytes.extend_from_slice(request_id);
+    for msg in msgs {
+        bytes.extend_from_slice(&msg.to_be_bytes());
+    }
+    bytes.extend_from_slice(&domain_calculation(pk).to_be_bytes());
+    Scalar::hash::<ExpandMsgXmd<Sha256>>(&bytes, DST)
+}
@@ -6,6 +6,7 @@ mod public_key;
 mod scheme;
 mod secret_key;
 mod signature;
+mod threshold;
 
 pub use blind_signature::*;
 pub use blind_signature_context::*;
@@ -15,3 +16,4 @@ pub use public_key::*;
 pub use scheme::*;
 pub use secret_key::*;
 pub use signature::*;
+pub use threshold::*;
@@ -88,6 +88,10 @@ impl BlindSignatureContext {
 impl BlindSignatureContextTrait for BlindSignatureContext {
     type SecretKey = SecretKey;
 
+    fn commitment(&self) -> G1Projective {
+        self.commitment
+    }
+
     /// Assumes the proof of hidden messages
     /// If other proofs were included, those will need to be verified another way
     fn verify(
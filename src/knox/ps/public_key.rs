@@ -1,6 +1,8 @@
 use super::SecretKey;
 use crate::{
-    error::Error, knox::short_group_sig_core::short_group_traits::PublicKey as PublicKeyTrait,
+    error::Error,
+    knox::short_group_sig_core::{short_group_traits::PublicKey as PublicKeyTrait, KeyRelation},
+    CredxResult,
 };
 use blsful::inner_types::*;
 use serde::{Deserialize, Serialize};
@@ -14,6 +16,17 @@ use subtle::Choice;
 ///
 /// `w` corresponds to m' in the paper to achieve
 /// EUF-CMA security level.
+///
+/// There is no compressed or seed-derived form of this key. Each `y` and
+/// `y_blinds` element is the generator raised to an independent secret,
+/// and the signer needs those secrets to sign. Points derived from a
+/// public seed, or `y` elements that are public multiples of one
+/// another, would give anyone a linear relation between the secrets and
+/// let them forge signatures. So unlike
+/// [`crate::knox::bbs::CompressedPublicKey`] the key grows with the
+/// number of messages. [`crate::issuer::IssuerPublic::key_id`] only lets
+/// bundles and presentation schemas refer to a key the receiver already
+/// has; it doesn't make the key smaller.
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct PublicKey {
     /// The secret for m'
@@ -54,6 +67,35 @@ impl From<&SecretKey> for PublicKey {
 impl PublicKeyTrait for PublicKey {
     type MessageGenerator = G2Projective;
     type BlindMessageGenerator = G1Projective;
+
+    /// `w`, `x` and each `y` in G2 and each `y_blinds` in G1
+    /// with the same secret as its `y`
+    fn key_relations(&self) -> CredxResult<Vec<KeyRelation>> {
+        if self.y.is_empty() || self.y.len() != self.y_blinds.len() {
+            return Err(Error::InvalidPublicKey);
+        }
+        let mut relations = Vec::with_capacity(2 + self.y.len() * 2);
+        for (secret, public) in [self.w, self.x].into_iter().enumerate() {
+            relations.push(KeyRelation::G2 {
+                secret,
+                base: G2Projective::GENERATOR,
+                public,
+            });
+        }
+        for (i, (y, y_blind)) in self.y.iter().zip(&self.y_blinds).enumerate() {
+            relations.push(KeyRelation::G2 {
+                secret: i + 2,
+                base: G2Projective::GENERATOR,
+                public: *y,
+            });
+            relations.push(KeyRelation::G1 {
+                secret: i + 2,
+                base: G1Projective::GENERATOR,
+                public: *y_blind,
+            });
+        }
+        Ok(relations)
+    }
 }
 
 impl From<PublicKey> for Vec<u8> {
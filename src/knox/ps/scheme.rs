@@ -112,6 +112,18 @@ impl ShortGroupSignatureScheme for PsScheme {
         ))
     }
 
+    fn blind_message_generators(
+        public_key: &Self::PublicKey,
+        indices: &[usize],
+    ) -> CredxResult<(Vec<G1Projective>, Option<G1Projective>)> {
+        let generators = indices
+            .iter()
+            .map(|i| public_key.y_blinds.get(*i).copied())
+            .collect::<Option<Vec<_>>>()
+            .ok_or(Error::General("invalid blind signing"))?;
+        Ok((generators, Some(G1Projective::GENERATOR)))
+    }
+
     fn commit_signature_pok(
         signature: Self::Signature,
         public_key: &Self::PublicKey,
@@ -50,6 +50,14 @@ impl SecretKeyTrait for SecretKey {
         }
         PublicKey { w, x, y, y_blinds }
     }
+
+    fn key_secrets(&self) -> Vec<Scalar> {
+        let mut secrets = Vec::with_capacity(2 + self.y.len());
+        secrets.push(self.w);
+        secrets.push(self.x);
+        secrets.extend_from_slice(&self.y);
+        secrets
+    }
 }
 
 impl SecretKey {
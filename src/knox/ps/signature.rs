@@ -53,7 +53,7 @@ impl Signature {
     /// The size in bytes of the signature
     pub const BYTES: usize = 128;
 
-    const DST: &'static [u8] = b"PS_SIG_BLS12381G1_XMD:BLAKE2B_SSWU_RO_";
+    pub(crate) const DST: &'static [u8] = b"PS_SIG_BLS12381G1_XMD:BLAKE2B_SSWU_RO_";
 
     /// Generate a new signature where all messages are known to the signer
     pub fn new<M>(sk: &SecretKey, msgs: M) -> CredxResult<Self>
@@ -0,0 +1,116 @@
+use super::{PsScheme, PublicKey, SecretKey, Signature};
+use crate::error::Error;
+use crate::knox::threshold::{
+    lagrange_coefficients, split_scalar, InversionNonce, ThresholdSignatureScheme,
+};
+use crate::CredxResult;
+use blsful::inner_types::{G1Projective, Scalar};
+use elliptic_curve::hash2curve::ExpandMsgXmd;
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+/// A participant's Shamir share of a PS secret key
+#[derive(Clone, Debug, Deserialize, Serialize, Zeroize)]
+#[zeroize(drop)]
+pub struct SecretKeyShare {
+    /// The participant index
+    pub index: u32,
+    pub(crate) w: Scalar,
+    pub(crate) x: Scalar,
+    pub(crate) y: Vec<Scalar>,
+}
+
+/// A participant's part of a PS signature
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PartialSignature {
+    /// The participant index
+    pub index: u32,
+    /// The participant's `sigma_2`
+    pub sigma_2: G1Projective,
+}
+
+impl ThresholdSignatureScheme for PsScheme {
+    type SecretKeyShare = SecretKeyShare;
+    type PartialSignature = PartialSignature;
+
+    const SIGNING_NONCES: usize = 0;
+
+    fn signing_participants(threshold: usize) -> usize {
+        threshold
+    }
+
+    fn split_secret_key(
+        sk: &SecretKey,
+        threshold: usize,
+        limit: usize,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<Vec<SecretKeyShare>> {
+        let w = split_scalar(sk.w, threshold, limit, &mut rng)?;
+        let x = split_scalar(sk.x, threshold, limit, &mut rng)?;
+        let y =
+            sk.y.iter()
+                .map(|y| split_scalar(*y, threshold, limit, &mut rng))
+                .collect::<CredxResult<Vec<_>>>()?;
+        Ok((0..limit)
+            .map(|i| SecretKeyShare {
+                index: (i + 1) as u32,
+                w: w[i],
+                x: x[i],
+                y: y.iter().map(|y| y[i]).collect(),
+            })
+            .collect())
+    }
+
+    fn partial_sign(
+        share: &SecretKeyShare,
+        msgs: &[Scalar],
+        _request_id: &[u8],
+        nonces: Vec<InversionNonce>,
+    ) -> CredxResult<PartialSignature> {
+        if !nonces.is_empty() {
+            return Err(Error::General("PS signatures don't use nonces"));
+        }
+        if share.y.len() < msgs.len() {
+            return Err(Error::General("Too many messages"));
+        }
+        let m_tick = Signature::compute_m_tick(msgs);
+        let mut exp = share.x + share.w * m_tick;
+        for (y, m) in share.y.iter().zip(msgs) {
+            exp += *y * *m;
+        }
+        Ok(PartialSignature {
+            index: share.index,
+            sigma_2: sigma_1(m_tick) * exp,
+        })
+    }
+
+    fn combine_signatures(
+        pk: &PublicKey,
+        msgs: &[Scalar],
+        _request_id: &[u8],
+        partials: &[PartialSignature],
+    ) -> CredxResult<Signature> {
+        let indices = partials.iter().map(|p| p.index).collect::<Vec<_>>();
+        let coefficients = lagrange_coefficients(&indices)?;
+        let m_tick = Signature::compute_m_tick(msgs);
+        let sigma_2 = partials
+            .iter()
+            .zip(&coefficients)
+            .fold(G1Projective::IDENTITY, |acc, (p, l)| acc + p.sigma_2 * l);
+        let signature = Signature {
+            sigma_1: sigma_1(m_tick),
+            sigma_2,
+            m_tick,
+        };
+        if signature.verify(pk, msgs).into() {
+            Ok(signature)
+        } else {
+            Err(Error::InvalidSigningOperation)
+        }
+    }
+}
+
+fn sigma_1(m_tick: Scalar) -> G1Projective {
+    G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(&m_tick.to_be_bytes()[..], Signature::DST)
+}
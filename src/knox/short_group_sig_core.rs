@@ -1,11 +1,17 @@
+/// Blind signature request formats
+pub mod blind_signature_format;
 /// Hidden message types
 pub mod hidden_message;
+/// Proofs of key correctness
+pub mod key_correctness_proof;
 /// Commitment builder
 pub mod proof_committed_builder;
 /// Proof message types
 pub mod proof_message;
 pub mod short_group_traits;
 
+pub use blind_signature_format::*;
 pub use hidden_message::*;
+pub use key_correctness_proof::*;
 pub use proof_committed_builder::*;
 pub use proof_message::*;
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// The format of the commitment and proof of committed messages a holder
+/// sends to an issuer when requesting a blind signature
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum BlindSignatureFormat {
+    /// The proof of committed messages bound to the issuer's nonce
+    #[default]
+    Credx,
+    /// The commitment proof from the CFRG Blind BBS Signatures draft computed
+    /// over the issuer's message generators. Only BBS supports this format.
+    /// It follows the draft's algorithms but isn't tested against the
+    /// draft's fixtures, so other implementations may not accept it.
+    ///
+    /// The draft's proof isn't bound to the issuer's nonce so issuers
+    /// should only sign each nonce once.
+    Cfrg,
+}
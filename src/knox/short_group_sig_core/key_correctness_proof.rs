@@ -0,0 +1,191 @@
+use crate::error::Error;
+use crate::CredxResult;
+use blsful::inner_types::{Curve, Field, G1Projective, G2Projective, Group, Scalar};
+use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use subtle::ConstantTimeEq;
+
+/// A relation `public = base * secret` between a public key point
+/// and one of the secret key scalars
+#[allow(clippy::large_enum_variant)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum KeyRelation {
+    /// A relation in G1
+    G1 {
+        /// The index of the secret
+        secret: usize,
+        /// The base point
+        base: G1Projective,
+        /// The public key point
+        public: G1Projective,
+    },
+    /// A relation in G2
+    G2 {
+        /// The index of the secret
+        secret: usize,
+        /// The base point
+        base: G2Projective,
+        /// The public key point
+        public: G2Projective,
+    },
+}
+
+impl KeyRelation {
+    /// The index of the secret
+    pub fn secret(&self) -> usize {
+        match self {
+            Self::G1 { secret, .. } | Self::G2 { secret, .. } => *secret,
+        }
+    }
+
+    fn holds(&self, secret: Scalar) -> bool {
+        match self {
+            Self::G1 { base, public, .. } => *base * secret == *public,
+            Self::G2 { base, public, .. } => *base * secret == *public,
+        }
+    }
+
+    fn add_commitment(&self, random: Scalar, transcript: &mut Transcript) {
+        match self {
+            Self::G1 { base, .. } => transcript.append_message(
+                b"key relation commitment",
+                &(*base * random).to_affine().to_compressed(),
+            ),
+            Self::G2 { base, .. } => transcript.append_message(
+                b"key relation commitment",
+                &(*base * random).to_affine().to_compressed(),
+            ),
+        }
+    }
+
+    fn add_verifier_commitment(&self, response: Scalar, challenge: Scalar, t: &mut Transcript) {
+        match self {
+            Self::G1 { base, public, .. } => t.append_message(
+                b"key relation commitment",
+                &(*base * response - *public * challenge)
+                    .to_affine()
+                    .to_compressed(),
+            ),
+            Self::G2 { base, public, .. } => t.append_message(
+                b"key relation commitment",
+                &(*base * response - *public * challenge)
+                    .to_affine()
+                    .to_compressed(),
+            ),
+        }
+    }
+
+    fn add_challenge_contribution(&self, transcript: &mut Transcript) {
+        transcript.append_u64(b"key relation secret", self.secret() as u64);
+        match self {
+            Self::G1 { base, public, .. } => {
+                transcript.append_message(b"key relation base", &base.to_affine().to_compressed());
+                transcript
+                    .append_message(b"key relation public", &public.to_affine().to_compressed());
+            }
+            Self::G2 { base, public, .. } => {
+                transcript.append_message(b"key relation base", &base.to_affine().to_compressed());
+                transcript
+                    .append_message(b"key relation public", &public.to_affine().to_compressed());
+            }
+        }
+    }
+
+    fn is_identity(&self) -> bool {
+        match self {
+            Self::G1 { base, public, .. } => (base.is_identity() | public.is_identity()).into(),
+            Self::G2 { base, public, .. } => (base.is_identity() | public.is_identity()).into(),
+        }
+    }
+}
+
+/// A Schnorr proof of knowledge of the secret key for a set of
+/// [`KeyRelation`]s showing the public key was computed correctly
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct KeyCorrectnessProof {
+    /// The Fiat-Shamir challenge
+    pub challenge: Scalar,
+    /// The responses for each secret
+    pub responses: Vec<Scalar>,
+}
+
+impl KeyCorrectnessProof {
+    /// Prove `relations` hold for `secrets`
+    pub fn new(
+        relations: &[KeyRelation],
+        secrets: &[Scalar],
+        transcript: &mut Transcript,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<Self> {
+        Self::check_relations(relations, secrets.len())?;
+        if relations.iter().any(|r| !r.holds(secrets[r.secret()])) {
+            return Err(Error::InvalidPublicKey);
+        }
+        let randoms = (0..secrets.len())
+            .map(|_| Scalar::random(&mut rng))
+            .collect::<Vec<_>>();
+        Self::add_relations(relations, transcript);
+        for r in relations {
+            r.add_commitment(randoms[r.secret()], transcript);
+        }
+        let challenge = Self::challenge(transcript);
+        let responses = randoms
+            .iter()
+            .zip(secrets)
+            .map(|(r, s)| r + challenge * s)
+            .collect();
+        Ok(Self {
+            challenge,
+            responses,
+        })
+    }
+
+    /// Verify the proof for `relations`
+    pub fn verify(
+        &self,
+        relations: &[KeyRelation],
+        transcript: &mut Transcript,
+    ) -> CredxResult<()> {
+        Self::check_relations(relations, self.responses.len())?;
+        Self::add_relations(relations, transcript);
+        for r in relations {
+            r.add_verifier_commitment(self.responses[r.secret()], self.challenge, transcript);
+        }
+        let challenge = Self::challenge(transcript);
+        if challenge.ct_eq(&self.challenge).unwrap_u8() == 1 {
+            Ok(())
+        } else {
+            Err(Error::InvalidPublicKey)
+        }
+    }
+
+    /// Every secret must be used by a relation and no point can be the identity
+    fn check_relations(relations: &[KeyRelation], secret_count: usize) -> CredxResult<()> {
+        let mut used = vec![false; secret_count];
+        for r in relations {
+            if r.is_identity() {
+                return Err(Error::InvalidPublicKey);
+            }
+            *used.get_mut(r.secret()).ok_or(Error::InvalidPublicKey)? = true;
+        }
+        if used.iter().all(|u| *u) {
+            Ok(())
+        } else {
+            Err(Error::InvalidPublicKey)
+        }
+    }
+
+    fn add_relations(relations: &[KeyRelation], transcript: &mut Transcript) {
+        transcript.append_u64(b"key relations length", relations.len() as u64);
+        for r in relations {
+            r.add_challenge_contribution(transcript);
+        }
+    }
+
+    fn challenge(transcript: &mut Transcript) -> Scalar {
+        let mut res = [0u8; 64];
+        transcript.challenge_bytes(b"key correctness challenge", &mut res);
+        Scalar::from_bytes_wide(&res)
+    }
+}
@@ -1,8 +1,9 @@
 //! Traits for abstracting public keys, secret keys, signatures, blind signatures,
 //! and zero-knowledge proofs of message and signature knowledge
-use crate::knox::short_group_sig_core::ProofMessage;
+use crate::error::Error;
+use crate::knox::short_group_sig_core::{BlindSignatureFormat, KeyRelation, ProofMessage};
 use crate::CredxResult;
-use blsful::inner_types::{Group, GroupEncoding, Scalar};
+use blsful::inner_types::{G1Projective, Group, GroupEncoding, Scalar};
 use elliptic_curve::Field;
 use merlin::Transcript;
 use rand_core::{CryptoRng, RngCore};
@@ -27,6 +28,10 @@ pub trait PublicKey: Sized + Clone + Debug + Serialize + for<'de> Deserialize<'d
     fn to_bytes(&self) -> Vec<u8> {
         serde_bare::to_vec(&self).expect("to serialize public key")
     }
+
+    /// The relations to the secret key proven by a key correctness proof.
+    /// Errors if the key is malformed.
+    fn key_relations(&self) -> CredxResult<Vec<KeyRelation>>;
 }
 
 /// Trait for abstracting secret keys
@@ -36,6 +41,9 @@ pub trait SecretKey: Sized + Clone + Debug + Serialize + for<'de> Deserialize<'d
 
     /// Return a public key from the secret key
     fn public_key(&self) -> Self::PublicKey;
+
+    /// The secrets referred to by [`PublicKey::key_relations`]
+    fn key_secrets(&self) -> Vec<Scalar>;
 }
 
 /// Trait for abstracting signatures
@@ -77,6 +85,12 @@ pub trait BlindSignatureContext:
 {
     /// The secret key type
     type SecretKey: SecretKey;
+    /// The commitment to the hidden messages
+    fn commitment(&self) -> G1Projective;
+    /// The format of the commitment proof
+    fn format(&self) -> BlindSignatureFormat {
+        BlindSignatureFormat::Credx
+    }
     /// Assumes the proof of hidden messages
     /// If other proofs were included, those will need to be verified another way
     fn verify(
@@ -204,6 +218,30 @@ pub trait ShortGroupSignatureScheme:
         rng: impl RngCore + CryptoRng,
     ) -> CredxResult<(Self::BlindSignatureContext, Scalar)>;
 
+    /// Create the structures needed to send to an issuer to complete a blinded
+    /// signature with the commitment proof in `format`
+    fn new_blind_signature_context_with_format(
+        messages: &[(usize, Scalar)],
+        public_key: &Self::PublicKey,
+        nonce: Scalar,
+        format: BlindSignatureFormat,
+        rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<(Self::BlindSignatureContext, Scalar)> {
+        match format {
+            BlindSignatureFormat::Credx => {
+                Self::new_blind_signature_context(messages, public_key, nonce, rng)
+            }
+            _ => Err(Error::General("unsupported blind signature format")),
+        }
+    }
+
+    /// The generators the blind signature context commitment uses for the
+    /// messages at `indices` and for the blinder if the scheme has one
+    fn blind_message_generators(
+        public_key: &Self::PublicKey,
+        indices: &[usize],
+    ) -> CredxResult<(Vec<G1Projective>, Option<G1Projective>)>;
+
     /// Create a new signature proof of knowledge and selective disclosure proof
     /// from a verifier's request
     fn commit_signature_pok(
@@ -0,0 +1,316 @@
+use crate::error::Error;
+use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use crate::CredxResult;
+use blsful::inner_types::{Field, G1Projective, Scalar};
+use rand_core::{CryptoRng, RngCore};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt::Debug;
+use zeroize::Zeroize;
+
+/// Shamir split `secret` so any `threshold` of the `limit` shares recover it.
+///
+/// The share at position `i` belongs to participant index `i + 1`.
+pub fn split_scalar(
+    secret: Scalar,
+    threshold: usize,
+    limit: usize,
+    mut rng: impl RngCore + CryptoRng,
+) -> CredxResult<Vec<Scalar>> {
+    check_threshold(threshold, limit)?;
+    let mut coefficients = Vec::with_capacity(threshold);
+    coefficients.push(secret);
+    for _ in 1..threshold {
+        coefficients.push(Scalar::random(&mut rng));
+    }
+    let shares = (1..=limit)
+        .map(|index| {
+            let x = Scalar::from(index as u64);
+            coefficients
+                .iter()
+                .rev()
+                .fold(Scalar::ZERO, |acc, c| acc * x + c)
+        })
+        .collect();
+    coefficients.zeroize();
+    Ok(shares)
+}
+
+/// The Lagrange coefficients to interpolate shares at `indices` to zero
+pub fn lagrange_coefficients(indices: &[u32]) -> CredxResult<Vec<Scalar>> {
+    let mut coefficients = Vec::with_capacity(indices.len());
+    for (i, index) in indices.iter().enumerate() {
+        if *index == 0 || indices[..i].contains(index) {
+            return Err(Error::General("invalid threshold share index"));
+        }
+        let x_i = Scalar::from(*index as u64);
+        let mut numerator = Scalar::ONE;
+        let mut denominator = Scalar::ONE;
+        for other in indices.iter().filter(|j| *j != index) {
+            let x_j = Scalar::from(*other as u64);
+            numerator *= x_j;
+            denominator *= x_j - x_i;
+        }
+        coefficients.push(numerator * denominator.invert().unwrap());
+    }
+    Ok(coefficients)
+}
+
+/// Check `threshold` of `limit` participants is a usable threshold
+pub fn check_threshold(threshold: usize, limit: usize) -> CredxResult<()> {
+    if threshold < 2 || threshold > limit || limit > u32::MAX as usize {
+        return Err(Error::General("invalid threshold"));
+    }
+    Ok(())
+}
+
+/// The number of participants needed for a multiparty inversion when
+/// `threshold` shares recover the secret key
+pub fn inversion_participants(threshold: usize) -> usize {
+    2 * threshold - 1
+}
+
+/// One dealer's shares of the random values for the inversion nonces of
+/// a participant. Send them to the recipient privately.
+#[derive(Clone, Debug, Deserialize, Serialize, Zeroize)]
+#[zeroize(drop)]
+pub struct NonceShares {
+    /// The participant index of the dealer
+    pub dealer: u32,
+    /// The participant index of the recipient
+    pub recipient: u32,
+    pub(crate) r: Vec<Scalar>,
+    pub(crate) z: Vec<Scalar>,
+}
+
+/// A participant's single use shares of a random value `r` and of zero
+/// for one multiparty inversion.
+///
+/// Using a nonce twice reveals the secret key share so it is consumed by
+/// [`partial_inversion`].
+#[derive(Clone, Debug, Deserialize, Serialize, Zeroize)]
+#[zeroize(drop)]
+pub struct InversionNonce {
+    /// The participant index
+    pub index: u32,
+    pub(crate) r: Scalar,
+    pub(crate) z: Scalar,
+}
+
+/// A participant's part of `base / (k + e)` for a shared secret `k`
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PartialInversion {
+    /// The participant index
+    pub index: u32,
+    /// The share of `r * (k + e)`
+    pub u: Scalar,
+    /// `base * r` with the share of `r`
+    pub r_base: G1Projective,
+}
+
+/// Deal `count` inversion nonces to each of the `limit` participants.
+///
+/// Each participant's nonces are the sums of the shares from the same
+/// dealers. With at least `threshold` dealers no `threshold - 1`
+/// participants know the random values.
+pub fn deal_inversion_nonces(
+    dealer: u32,
+    count: usize,
+    threshold: usize,
+    limit: usize,
+    mut rng: impl RngCore + CryptoRng,
+) -> CredxResult<Vec<NonceShares>> {
+    check_threshold(threshold, limit)?;
+    let participants = inversion_participants(threshold);
+    if participants > limit {
+        return Err(Error::General("not enough participants for an inversion"));
+    }
+    let mut shares = (1..=limit)
+        .map(|recipient| NonceShares {
+            dealer,
+            recipient: recipient as u32,
+            r: Vec::with_capacity(count),
+            z: Vec::with_capacity(count),
+        })
+        .collect::<Vec<_>>();
+    for _ in 0..count {
+        let r = split_scalar(Scalar::random(&mut rng), threshold, limit, &mut rng)?;
+        let z = split_scalar(Scalar::ZERO, participants, limit, &mut rng)?;
+        for (s, (r, z)) in shares.iter_mut().zip(r.into_iter().zip(z)) {
+            s.r.push(r);
+            s.z.push(z);
+        }
+    }
+    Ok(shares)
+}
+
+/// Sum the shares for `recipient` from at least `threshold` dealers
+/// into its inversion nonces
+pub fn receive_inversion_nonces(
+    recipient: u32,
+    threshold: usize,
+    shares: &[NonceShares],
+) -> CredxResult<Vec<InversionNonce>> {
+    let dealers = shares.iter().map(|s| s.dealer).collect::<Vec<_>>();
+    if shares.len() < threshold
+        || dealers
+            .iter()
+            .enumerate()
+            .any(|(i, d)| dealers[..i].contains(d))
+    {
+        return Err(Error::General("nonces need a threshold of dealers"));
+    }
+    let count = shares[0].r.len();
+    if shares
+        .iter()
+        .any(|s| s.recipient != recipient || s.r.len() != count || s.z.len() != count)
+    {
+        return Err(Error::General("invalid nonce shares"));
+    }
+    Ok((0..count)
+        .map(|i| InversionNonce {
+            index: recipient,
+            r: shares.iter().map(|s| s.r[i]).sum(),
+            z: shares.iter().map(|s| s.z[i]).sum(),
+        })
+        .collect())
+}
+
+/// Create a participant's part of `base / (k + e)` with its share of `k`
+pub fn partial_inversion(
+    key_share: Scalar,
+    e: Scalar,
+    base: G1Projective,
+    nonce: InversionNonce,
+) -> PartialInversion {
+    PartialInversion {
+        index: nonce.index,
+        u: nonce.r * (key_share + e) + nonce.z,
+        r_base: base * nonce.r,
+    }
+}
+
+/// Combine the parts of at least `2 * threshold - 1` participants into
+/// `base / (k + e)`.
+///
+/// The result is only correct with enough honest parts so callers check
+/// it against a public key.
+pub fn combine_inversion(partials: &[PartialInversion]) -> CredxResult<G1Projective> {
+    let indices = partials.iter().map(|p| p.index).collect::<Vec<_>>();
+    let coefficients = lagrange_coefficients(&indices)?;
+    let (u, r_base) = partials.iter().zip(&coefficients).fold(
+        (Scalar::ZERO, G1Projective::IDENTITY),
+        |(u, r_base), (p, l)| (u + p.u * l, r_base + p.r_base * l),
+    );
+    let u_inv = Option::<Scalar>::from(u.invert()).ok_or(Error::InvalidSigningOperation)?;
+    Ok(r_base * u_inv)
+}
+
+/// Signature schemes that can sign with shares of the secret key.
+///
+/// A PS signature is linear in the secret key so each participant signs
+/// with its share and a threshold of parts are interpolated. A BBS
+/// signature computes `B / (x + e)` so the participants compute it with
+/// a multiparty inversion that needs [`inversion_participants`] parts and
+/// uses an [`InversionNonce`] from each participant.
+pub trait ThresholdSignatureScheme: ShortGroupSignatureScheme {
+    /// A participant's share of the secret key
+    type SecretKeyShare: Clone + Debug + Serialize + DeserializeOwned;
+    /// A participant's part of a signature
+    type PartialSignature: Clone + Debug + Serialize + DeserializeOwned;
+
+    /// The number of inversion nonces each participant uses for a signature
+    const SIGNING_NONCES: usize;
+
+    /// The number of participants needed to sign when `threshold` shares
+    /// recover the secret key
+    fn signing_participants(threshold: usize) -> usize;
+
+    /// Split the secret key into `limit` shares where `threshold` can sign
+    fn split_secret_key(
+        sk: &Self::SecretKey,
+        threshold: usize,
+        limit: usize,
+        rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<Vec<Self::SecretKeyShare>>;
+
+    /// Create a participant's part of the signature over `msgs` for the
+    /// signing request `request_id` with [`Self::SIGNING_NONCES`] nonces
+    fn partial_sign(
+        share: &Self::SecretKeyShare,
+        msgs: &[Scalar],
+        request_id: &[u8],
+        nonces: Vec<InversionNonce>,
+    ) -> CredxResult<Self::PartialSignature>;
+
+    /// Combine the parts into a signature over `msgs`
+    fn combine_signatures(
+        pk: &Self::PublicKey,
+        msgs: &[Scalar],
+        request_id: &[u8],
+        partials: &[Self::PartialSignature],
+    ) -> CredxResult<Self::Signature>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blsful::inner_types::Group;
+    use rand_core::OsRng;
+
+    #[test]
+    fn shares_interpolate_to_secret() {
+        let secret = Scalar::random(OsRng);
+        let shares = split_scalar(secret, 3, 5, OsRng).unwrap();
+        for indices in [[1u32, 2, 3], [2, 4, 5], [5, 1, 3]] {
+            let coefficients = lagrange_coefficients(&indices).unwrap();
+            let value = indices
+                .iter()
+                .zip(&coefficients)
+                .fold(Scalar::ZERO, |acc, (i, l)| {
+                    acc + shares[*i as usize - 1] * l
+                });
+            assert_eq!(value, secret);
+        }
+        assert!(lagrange_coefficients(&[1, 1, 2]).is_err());
+        assert!(lagrange_coefficients(&[0, 1, 2]).is_err());
+        assert!(split_scalar(secret, 1, 5, OsRng).is_err());
+        assert!(split_scalar(secret, 6, 5, OsRng).is_err());
+    }
+
+    #[test]
+    fn inversion_with_shares() {
+        let (threshold, limit) = (2, 4);
+        let key = Scalar::random(OsRng);
+        let key_shares = split_scalar(key, threshold, limit, OsRng).unwrap();
+        let dealt = [1u32, 3]
+            .iter()
+            .map(|d| deal_inversion_nonces(*d, 2, threshold, limit, OsRng).unwrap())
+            .collect::<Vec<_>>();
+        let nonces = (1..=limit as u32)
+            .map(|i| {
+                let shares = dealt
+                    .iter()
+                    .map(|d| d[i as usize - 1].clone())
+                    .collect::<Vec<_>>();
+                receive_inversion_nonces(i, threshold, &shares).unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert!(receive_inversion_nonces(1, threshold, &dealt[0][..1]).is_err());
+
+        let base = G1Projective::random(OsRng);
+        let e = Scalar::random(OsRng);
+        let expected = base * (key + e).invert().unwrap();
+        for (n, participants) in [[1usize, 2, 4], [4, 3, 2]].iter().enumerate() {
+            let partials = participants
+                .iter()
+                .map(|i| {
+                    let nonce = nonces[*i - 1][n].clone();
+                    partial_inversion(key_shares[*i - 1], e, base, nonce)
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(combine_inversion(&partials).unwrap(), expected);
+            // A threshold of parts isn't enough
+            assert_ne!(combine_inversion(&partials[..2]).unwrap(), expected);
+        }
+    }
+}
@@ -44,6 +44,8 @@ pub mod credential;
 pub mod error;
 /// Issuer related methods
 pub mod issuer;
+/// JSON Web Proof serializations
+pub mod jwp;
 /// Internal crypto primitives
 pub mod knox;
 /// Presentation related methods
@@ -55,6 +57,8 @@ pub mod statement;
 mod utils;
 /// Presentation verifiers
 mod verifier;
+/// W3C Verifiable Credentials mapping
+pub mod w3c;
 
 /// One import to rule them all
 pub mod prelude {
@@ -73,10 +77,3 @@ pub mod prelude {
 
     pub use blsful;
 }
-
-mod mapping {
-    #![allow(dead_code)]
-    #![allow(unused_assignments)]
-    pub mod map_credential;
-    pub mod map_presentation;
-}
@@ -1,9 +1,13 @@
+mod bbs_pseudonym;
+mod blind_claims;
 mod commitment;
 mod create;
 mod credential;
 mod equality;
 mod membership;
+mod presence;
 mod proof;
+mod pseudonym;
 mod range;
 mod revocation;
 mod schema;
@@ -12,11 +16,15 @@ mod verifiable_encryption;
 mod verifiable_encryption_decryption;
 mod verify;
 
+pub use bbs_pseudonym::*;
+pub use blind_claims::*;
 pub use commitment::*;
 pub use credential::*;
 pub use equality::*;
 pub use membership::*;
+pub use presence::*;
 pub use proof::*;
+pub use pseudonym::*;
 pub use range::*;
 pub use revocation::*;
 pub use schema::*;
@@ -27,7 +35,13 @@ pub use verifiable_encryption_decryption::*;
 use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
 use crate::knox::short_group_sig_core::{HiddenMessage, ProofMessage};
 use crate::verifier::*;
-use crate::{claim::ClaimData, error::Error, statement::Statements, utils::*, CredxResult};
+use crate::{
+    claim::{AbsentClaim, ClaimData, NestedClaimData},
+    error::Error,
+    statement::Statements,
+    utils::*,
+    CredxResult,
+};
 use blsful::inner_types::{G1Affine, G2Affine, Scalar};
 use elliptic_curve::{ff::Field, group::prime::PrimeCurveAffine};
 use indexmap::{IndexMap, IndexSet};
@@ -52,6 +66,9 @@ pub(crate) enum PresentationBuilders<'a, S: ShortGroupSignatureScheme> {
     Range(Box<RangeBuilder<'a>>),
     Membership(Box<MembershipProofBuilder<'a>>),
     VerifiableEncryptionDecryption(Box<VerifiableEncryptionDecryptionBuilder<'a>>),
+    Presence(Box<PresenceBuilder<'a>>),
+    BlindClaims(Box<BlindClaimsBuilder<'a, S>>),
+    Pseudonym(Box<PseudonymBuilder<'a>>),
 }
 
 impl<S: ShortGroupSignatureScheme> PresentationBuilders<'_, S> {
@@ -66,6 +83,9 @@ impl<S: ShortGroupSignatureScheme> PresentationBuilders<'_, S> {
             Self::Range(r) => r.gen_proof(challenge),
             Self::Membership(m) => m.gen_proof(challenge),
             Self::VerifiableEncryptionDecryption(v) => v.gen_proof(challenge),
+            Self::Presence(p) => p.gen_proof(challenge),
+            Self::BlindClaims(b) => b.gen_proof(challenge),
+            Self::Pseudonym(p) => p.gen_proof(challenge),
         }
     }
 }
@@ -128,6 +148,26 @@ impl<'a, S: ShortGroupSignatureScheme> From<VerifiableEncryptionDecryptionBuilde
     }
 }
 
+impl<'a, S: ShortGroupSignatureScheme> From<PresenceBuilder<'a>> for PresentationBuilders<'a, S> {
+    fn from(value: PresenceBuilder<'a>) -> Self {
+        Self::Presence(Box::new(value))
+    }
+}
+
+impl<'a, S: ShortGroupSignatureScheme> From<PseudonymBuilder<'a>> for PresentationBuilders<'a, S> {
+    fn from(value: PseudonymBuilder<'a>) -> Self {
+        Self::Pseudonym(Box::new(value))
+    }
+}
+
+impl<'a, S: ShortGroupSignatureScheme> From<BlindClaimsBuilder<'a, S>>
+    for PresentationBuilders<'a, S>
+{
+    fn from(value: BlindClaimsBuilder<'a, S>) -> Self {
+        Self::BlindClaims(Box::new(value))
+    }
+}
+
 /// Defines the proofs for a verifier
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Presentation<S: ShortGroupSignatureScheme> {
@@ -150,6 +190,30 @@ pub struct Presentation<S: ShortGroupSignatureScheme> {
 }
 
 impl<S: ShortGroupSignatureScheme> Presentation<S> {
+    /// Reassemble the disclosed messages from the signature statement `id`
+    /// into their nested shape using the claim paths
+    pub fn nested_disclosed_messages(&self, id: &str) -> CredxResult<NestedClaimData> {
+        let dm = self
+            .disclosed_messages
+            .get(id)
+            .ok_or(Error::InvalidPresentationData(format!(
+                "no disclosed messages found for statement '{}'",
+                id
+            )))?;
+        NestedClaimData::from_paths(dm)
+    }
+
+    /// The pseudonym shown for the pseudonym statement `id`
+    pub fn pseudonym(&self, id: &str) -> CredxResult<[u8; 48]> {
+        match self.proofs.get(id) {
+            Some(PresentationProofs::Pseudonym(p)) => Ok(p.to_bytes()),
+            _ => Err(Error::InvalidPresentationData(format!(
+                "no pseudonym proof found for statement '{}'",
+                id
+            ))),
+        }
+    }
+
     #[allow(clippy::type_complexity)]
     fn split_statements(
         schema: &PresentationSchema<S>,
@@ -161,7 +225,7 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
         let mut predicate_statements: IndexMap<&String, &Statements<S>> = IndexMap::new();
 
         for (id, statement) in &schema.statements {
-            if let Statements::Signature(_) = statement {
+            if let Statements::Signature(_) | Statements::BlindClaims(_) = statement {
                 signature_statements.insert(id, statement);
             } else {
                 predicate_statements.insert(id, statement);
@@ -232,6 +296,11 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
                 shared_proof_msg_indices.insert(id, vec![false; c.claims.len()]);
             }
         }
+        for (id, statement) in signature_statements {
+            if let Statements::BlindClaims(bs) = statement {
+                shared_proof_msg_indices.insert(id, vec![false; bs.issuer.schema.claims.len()]);
+            }
+        }
 
         let mut same_proof_messages = Vec::new();
 
@@ -273,6 +342,39 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
         }
 
         for (id, sig) in signature_statements {
+            if let Statements::BlindClaims(bs) = sig {
+                let blinded = if let PresentationCredential::BlindClaims(b) = &credentials[*id] {
+                    b
+                } else {
+                    continue;
+                };
+                // Claims the issuer fills in aren't known to the holder so only
+                // the blinded claims can be used by other statements
+                let mut proof_claims = vec![
+                    (
+                        ClaimData::from(AbsentClaim),
+                        ProofMessage::Revealed(Scalar::ZERO)
+                    );
+                    bs.issuer.schema.claims.len()
+                ];
+                for index in bs.claim_indices()? {
+                    let claim = blinded.claims.get(&index).ok_or_else(|| {
+                        Error::InvalidPresentationData(format!(
+                            "blind claims credential '{}' is missing claim '{}'",
+                            id, index
+                        ))
+                    })?;
+                    let claim_value = claim.to_scalar();
+                    let message = if shared_proof_msg_indices[id][index] {
+                        HiddenMessage::ExternalBlinding(claim_value, Scalar::random(&mut rng))
+                    } else {
+                        HiddenMessage::ProofSpecificBlinding(claim_value)
+                    };
+                    proof_claims[index] = (claim.clone(), ProofMessage::Hidden(message));
+                }
+                proof_messages.insert(*id, proof_claims);
+                continue;
+            }
             let signature = if let PresentationCredential::Signature(signature) = &credentials[*id]
             {
                 signature
@@ -288,7 +390,7 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
                 // it must use a shared blinder, otherwise its proof specific
                 if let Statements::Signature(ss) = sig {
                     let claim_label = ss.issuer.schema.claim_indices.get_index(index).unwrap();
-                    if ss.disclosed.contains(claim_label) {
+                    if ss.is_disclosed(claim_label) {
                         proof_claims.push((claim.clone(), ProofMessage::Revealed(claim_value)));
                     } else if shared_proof_msg_indices[id][index] {
                         let blinder = Scalar::random(&mut rng);
@@ -0,0 +1,175 @@
+use crate::error::Error;
+use crate::knox::bbs::BbsCiphersuite;
+use crate::CredxResult;
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A signature from the CFRG BBS per Verifier Linkability draft held by
+/// the holder with everything needed to show pseudonyms.
+///
+/// The signer creates it with [`BbsCiphersuite::blind_sign_with_nym`]
+/// over its messages and the holder's commitment from
+/// [`BbsCiphersuite::commit_with_nym`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BbsNymCredential {
+    /// The signature's ciphersuite
+    pub ciphersuite: BbsCiphersuite,
+    /// The signer's BBS header
+    pub header: Vec<u8>,
+    /// The signer's messages
+    pub messages: Vec<Vec<u8>>,
+    /// The holder's committed messages
+    pub committed_messages: Vec<Vec<u8>>,
+    /// The BBS signature
+    pub signature: Vec<u8>,
+    nym_secret: Vec<u8>,
+    secret_prover_blind: Vec<u8>,
+}
+
+/// A presentation with the output of the draft's ProofGen with pseudonym,
+/// the proof and pseudonym octets, so verifiers that implement the draft
+/// can check it.
+///
+/// Unlike a [`PseudonymStatement`](crate::statement::PseudonymStatement)
+/// in a [`Presentation`](super::Presentation) it proves a
+/// [`BbsNymCredential`] rather than a credx credential.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BbsPseudonymPresentation {
+    /// The signature's ciphersuite
+    pub ciphersuite: BbsCiphersuite,
+    /// The signer's BBS header
+    pub header: Vec<u8>,
+    /// The verifier's presentation header
+    pub presentation_header: Vec<u8>,
+    /// The verifier's context id
+    pub context_id: Vec<u8>,
+    /// The number of signer messages
+    pub message_count: usize,
+    /// The number of committed messages
+    pub committed_count: usize,
+    /// The disclosed signer messages by index
+    pub disclosed_messages: BTreeMap<usize, Vec<u8>>,
+    /// The draft's proof octets
+    pub proof: Vec<u8>,
+    /// The draft's pseudonym octets
+    pub pseudonym: Vec<u8>,
+}
+
+impl BbsNymCredential {
+    /// Verify the signature with the signer's public key and compute the
+    /// holder's nym secret from the holder's `prover_nym` and
+    /// `secret_prover_blind` and the signer's `signer_nym_entropy`
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        ciphersuite: BbsCiphersuite,
+        pk: &[u8],
+        signature: &[u8],
+        header: &[u8],
+        messages: &[Vec<u8>],
+        committed_messages: &[Vec<u8>],
+        prover_nym: &[u8],
+        signer_nym_entropy: &[u8],
+        secret_prover_blind: &[u8],
+    ) -> CredxResult<Self> {
+        let nym_secret = ciphersuite.finalize_with_nym(
+            pk,
+            signature,
+            header,
+            messages,
+            committed_messages,
+            prover_nym,
+            signer_nym_entropy,
+            secret_prover_blind,
+        )?;
+        Ok(Self {
+            ciphersuite,
+            header: header.to_vec(),
+            messages: messages.to_vec(),
+            committed_messages: committed_messages.to_vec(),
+            signature: signature.to_vec(),
+            nym_secret: nym_secret.to_vec(),
+            secret_prover_blind: secret_prover_blind.to_vec(),
+        })
+    }
+
+    /// The holder's pseudonym for a verifier's context id
+    pub fn pseudonym(&self, context_id: &[u8]) -> CredxResult<Vec<u8>> {
+        Ok(self
+            .ciphersuite
+            .pseudonym(context_id, &self.nym_secret)?
+            .to_vec())
+    }
+
+    /// Disclose the signer messages at `disclosed_indexes` and show the
+    /// pseudonym for the verifier's context id
+    pub fn present(
+        &self,
+        pk: &[u8],
+        presentation_header: &[u8],
+        context_id: &[u8],
+        disclosed_indexes: &[usize],
+        rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<BbsPseudonymPresentation> {
+        let (proof, pseudonym) = self.ciphersuite.pseudonym_proof_gen(
+            pk,
+            &self.signature,
+            &self.header,
+            presentation_header,
+            context_id,
+            &self.messages,
+            &self.committed_messages,
+            &self.nym_secret,
+            &self.secret_prover_blind,
+            disclosed_indexes,
+            rng,
+        )?;
+        Ok(BbsPseudonymPresentation {
+            ciphersuite: self.ciphersuite,
+            header: self.header.clone(),
+            presentation_header: presentation_header.to_vec(),
+            context_id: context_id.to_vec(),
+            message_count: self.messages.len(),
+            committed_count: self.committed_messages.len(),
+            disclosed_messages: disclosed_indexes
+                .iter()
+                .map(|i| (*i, self.messages[*i].clone()))
+                .collect(),
+            proof,
+            pseudonym: pseudonym.to_vec(),
+        })
+    }
+}
+
+impl BbsPseudonymPresentation {
+    /// Verify the proof and pseudonym with the signer's public key for the
+    /// verifier's presentation header and context id
+    pub fn verify(
+        &self,
+        pk: &[u8],
+        presentation_header: &[u8],
+        context_id: &[u8],
+    ) -> CredxResult<()> {
+        if self.presentation_header != presentation_header || self.context_id != context_id {
+            return Err(Error::InvalidPresentationData(
+                "the presentation is for another presentation header or context id".to_string(),
+            ));
+        }
+        let disclosed = self
+            .disclosed_messages
+            .iter()
+            .map(|(i, m)| (*i, m.as_slice()))
+            .collect::<Vec<_>>();
+        self.ciphersuite.pseudonym_proof_verify(
+            pk,
+            &self.proof,
+            &self.pseudonym,
+            &self.context_id,
+            &self.header,
+            &self.presentation_header,
+            self.message_count,
+            self.committed_count,
+            &disclosed,
+        )
+    }
+}
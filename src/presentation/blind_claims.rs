@@ -0,0 +1,113 @@
+use crate::claim::ClaimData;
+use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use crate::knox::short_group_sig_core::ProofMessage;
+use crate::presentation::{PresentationBuilder, PresentationProofs};
+use crate::statement::BlindClaimsStatement;
+use crate::CredxResult;
+use blsful::inner_types::{G1Projective, Scalar};
+use elliptic_curve::{group::Curve, Field};
+use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The claims a holder blinds in a blind signing request
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BlindClaimsCredential {
+    /// The normalized blinded claims by their index in the schema
+    pub claims: BTreeMap<usize, ClaimData>,
+    /// The blinder from the blind signature context
+    pub blinder: Scalar,
+}
+
+/// A blind claims builder
+///
+/// Proves knowledge of the opening of the blind signature context commitment
+/// `C = Σ g_i * m_i + h * β` where `h * β` is only used by schemes with a blinder.
+pub(crate) struct BlindClaimsBuilder<'a, S: ShortGroupSignatureScheme> {
+    statement: &'a BlindClaimsStatement<S>,
+    commitment: G1Projective,
+    messages: Vec<(usize, Scalar, Scalar)>,
+    blinder: Option<(Scalar, Scalar)>,
+}
+
+impl<S: ShortGroupSignatureScheme> PresentationBuilder<S> for BlindClaimsBuilder<'_, S> {
+    fn gen_proof(self, challenge: Scalar) -> PresentationProofs<S> {
+        let message_proofs = self
+            .messages
+            .iter()
+            .map(|(index, m, b)| (*index, b + challenge * m))
+            .collect();
+        let blinder_proof = self.blinder.map(|(beta, r)| r + challenge * beta);
+        BlindClaimsProof {
+            id: self.statement.id.clone(),
+            commitment: self.commitment,
+            message_proofs,
+            blinder_proof,
+        }
+        .into()
+    }
+}
+
+impl<'a, S: ShortGroupSignatureScheme> BlindClaimsBuilder<'a, S> {
+    /// Creates a blind claims builder
+    pub fn commit(
+        statement: &'a BlindClaimsStatement<S>,
+        messages: &[(ClaimData, ProofMessage<Scalar>)],
+        blinder: Scalar,
+        mut rng: impl RngCore + CryptoRng,
+        transcript: &mut Transcript,
+    ) -> CredxResult<Self> {
+        let indices = statement.claim_indices()?;
+        let (generators, blinder_generator) =
+            S::blind_message_generators(&statement.issuer.verifying_key, &indices)?;
+        let mut commitment = G1Projective::IDENTITY;
+        let mut blind_commitment = G1Projective::IDENTITY;
+        let mut hidden = Vec::with_capacity(indices.len());
+        for (index, g) in indices.iter().zip(&generators) {
+            let (_, proof_message) = messages[*index];
+            let m = proof_message.get_message();
+            let b = proof_message
+                .get_blinder(&mut rng)
+                .expect("blind claims are hidden");
+            commitment += g * m;
+            blind_commitment += g * b;
+            hidden.push((*index, m, b));
+        }
+        let blinder = blinder_generator.map(|h| {
+            let r = Scalar::random(&mut rng);
+            commitment += h * blinder;
+            blind_commitment += h * r;
+            (blinder, r)
+        });
+
+        transcript.append_message(b"", statement.id.as_bytes());
+        transcript.append_message(
+            b"commitment",
+            commitment.to_affine().to_compressed().as_slice(),
+        );
+        transcript.append_message(
+            b"blind commitment",
+            blind_commitment.to_affine().to_compressed().as_slice(),
+        );
+        Ok(Self {
+            statement,
+            commitment,
+            messages: hidden,
+            blinder,
+        })
+    }
+}
+
+/// A proof of knowledge of the blinded claims
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BlindClaimsProof {
+    /// The statement identifier
+    pub id: String,
+    /// The blind signature context commitment
+    pub commitment: G1Projective,
+    /// The schnorr proofs for the blinded claims by their index in the schema
+    pub message_proofs: BTreeMap<usize, Scalar>,
+    /// The schnorr proof for the blinder if the scheme uses one
+    pub blinder_proof: Option<Scalar>,
+}
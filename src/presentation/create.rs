@@ -63,6 +63,23 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
                 )?;
                 builders.push(builder.into());
                 disclosed_messages.insert((*id).clone(), dm);
+            } else if let Statements::BlindClaims(bs) = sig_statement {
+                let blinded = if let PresentationCredential::BlindClaims(b) = &credentials[*id] {
+                    b
+                } else {
+                    return Err(Error::InvalidPresentationData(format!(
+                        "blind claims statement '{}' requires a blind claims credential",
+                        id
+                    )));
+                };
+                let builder = BlindClaimsBuilder::commit(
+                    bs,
+                    &messages[*id],
+                    blinded.blinder,
+                    rng,
+                    &mut transcript,
+                )?;
+                builders.push(builder.into());
             }
         }
 
@@ -176,11 +193,38 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
                     id_to_builder.insert(*id, builders.len());
                     builders.push(builder.into());
                 }
+                Statements::Presence(p) => {
+                    let (_, proof_message) = messages[&p.reference_id][p.claim];
+                    if matches!(proof_message, ProofMessage::Revealed(_)) {
+                        return Err(Error::InvalidClaimData(
+                            "revealed claim cannot be used for presence proofs",
+                        ));
+                    }
+                    let message = proof_message.get_message();
+                    let blinder = proof_message.get_blinder(rng).unwrap();
+                    let builder =
+                        PresenceBuilder::commit(p, message, blinder, rng, &mut transcript)?;
+                    id_to_builder.insert(*id, builders.len());
+                    builders.push(builder.into());
+                }
+                Statements::Pseudonym(p) => {
+                    let (_, proof_message) = messages[&p.reference_id][p.claim];
+                    if matches!(proof_message, ProofMessage::Revealed(_)) {
+                        return Err(Error::InvalidClaimData(
+                            "revealed claim cannot be used for pseudonyms",
+                        ));
+                    }
+                    let message = proof_message.get_message();
+                    let blinder = proof_message.get_blinder(rng).unwrap();
+                    let builder = PseudonymBuilder::commit(p, message, blinder, &mut transcript)?;
+                    id_to_builder.insert(*id, builders.len());
+                    builders.push(builder.into());
+                }
                 Statements::Range(_) => {
                     // handle after these since they depend on commitment builders
                     range_id.insert(*id);
                 }
-                Statements::Signature(_) => {}
+                Statements::Signature(_) | Statements::BlindClaims(_) => {}
             }
         }
         let mut range_builders = Vec::<PresentationBuilders<S>>::with_capacity(range_id.len());
@@ -193,19 +237,17 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
                         id
                     )))?
             {
-                let sig = if let PresentationCredential::Signature(sig) = credentials
+                let claim = match credentials
                     .get(&r.signature_id)
                     .ok_or(Error::InvalidPresentationData(format!("range proof statement with id '{}' references a signature statement with id '{}' but no signature statement has that id.", id, r.signature_id)))?
                 {
-                    sig
-                } else {
-                    continue;
+                    PresentationCredential::Signature(sig) => sig.claims.get(r.claim),
+                    PresentationCredential::BlindClaims(b) => b.claims.get(&r.claim),
+                    PresentationCredential::Membership(_) => continue,
                 };
                 let builder_index = id_to_builder[&r.reference_id];
                 if let PresentationBuilders::Commitment(commitment) = &builders[builder_index] {
-                    if let ClaimData::Number(n) = sig
-                        .claims
-                        .get(r.claim)
+                    if let ClaimData::Number(n) = claim
                         .ok_or(Error::InvalidPresentationData(format!("range proof statement with id '{}' references claim '{}' which doesn't exist", id, r.claim)))?
                     {
                         let builder =
@@ -13,6 +13,8 @@ pub enum PresentationCredential<S: ShortGroupSignatureScheme> {
     Signature(Box<Credential<S>>),
     /// A membership check credential
     Membership(Box<MembershipCredential>),
+    /// The claims blinded in a blind signing request
+    BlindClaims(Box<BlindClaimsCredential>),
 }
 
 impl<S: ShortGroupSignatureScheme> From<Credential<S>> for PresentationCredential<S> {
@@ -26,3 +28,9 @@ impl<S: ShortGroupSignatureScheme> From<MembershipCredential> for PresentationCr
         Self::Membership(Box::new(value))
     }
 }
+
+impl<S: ShortGroupSignatureScheme> From<BlindClaimsCredential> for PresentationCredential<S> {
+    fn from(value: BlindClaimsCredential) -> Self {
+        Self::BlindClaims(Box::new(value))
+    }
+}
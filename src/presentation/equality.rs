@@ -32,12 +32,19 @@ impl<'a> EqualityBuilder<'a> {
                         reference_statement.id, id
                     )))
                 }
-                Some(cred) => {
-                    if let PresentationCredential::Signature(c) = cred {
-                        let sc = c.claims[*claim_index].to_scalar();
-                        scalars.push(sc);
-                    }
+                Some(PresentationCredential::Signature(c)) => {
+                    scalars.push(c.claims[*claim_index].to_scalar());
                 }
+                Some(PresentationCredential::BlindClaims(b)) => {
+                    let claim = b.claims.get(claim_index).ok_or_else(|| {
+                        Error::InvalidPresentationData(format!(
+                            "equality statement with id '{}' references claim '{}' which isn't blinded in '{}'",
+                            reference_statement.id, claim_index, id
+                        ))
+                    })?;
+                    scalars.push(claim.to_scalar());
+                }
+                Some(PresentationCredential::Membership(_)) => {}
             }
         }
         let mut res = true;
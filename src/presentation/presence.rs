@@ -0,0 +1,134 @@
+use crate::claim::AbsentClaim;
+use crate::error::Error;
+use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use crate::presentation::{PresentationBuilder, PresentationProofs};
+use crate::statement::PresenceStatement;
+use crate::CredxResult;
+use blsful::inner_types::{G1Projective, Scalar};
+use elliptic_curve::{group::Curve, Field};
+use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// A presence builder
+///
+/// Commits to the claim as `C = g * m + h * ρ`. An absent claim is proven by
+/// showing `C - g * A` is a multiple of `h` where `A` is the absent value.
+/// A present claim is proven by showing knowledge of the inverse `w` of
+/// `m - A` such that `g = (C - g * A) * w + h * t`.
+#[derive(Debug)]
+pub(crate) struct PresenceBuilder<'a> {
+    pub(crate) commitment: G1Projective,
+    pub(crate) statement: &'a PresenceStatement<G1Projective>,
+    pub(crate) rho: Scalar,
+    pub(crate) r_rho: Scalar,
+    pub(crate) inverse: Option<PresenceInverseBuilder>,
+}
+
+/// The secrets for proving a claim is not the absent value
+#[derive(Debug)]
+pub(crate) struct PresenceInverseBuilder {
+    pub(crate) w: Scalar,
+    pub(crate) t: Scalar,
+    pub(crate) r_w: Scalar,
+    pub(crate) r_t: Scalar,
+}
+
+impl<S: ShortGroupSignatureScheme> PresentationBuilder<S> for PresenceBuilder<'_> {
+    fn gen_proof(self, challenge: Scalar) -> PresentationProofs<S> {
+        let blinder_proof = self.r_rho + challenge * self.rho;
+        let inverse_proof = self
+            .inverse
+            .map(|i| (i.r_w + challenge * i.w, i.r_t + challenge * i.t));
+        PresenceProof {
+            id: self.statement.id.clone(),
+            commitment: self.commitment,
+            blinder_proof,
+            inverse_proof,
+        }
+        .into()
+    }
+}
+
+impl<'a> PresenceBuilder<'a> {
+    /// Creates a presence builder
+    pub fn commit(
+        statement: &'a PresenceStatement<G1Projective>,
+        message: Scalar,
+        b: Scalar,
+        mut rng: impl RngCore + CryptoRng,
+        transcript: &mut Transcript,
+    ) -> CredxResult<Self> {
+        let offset = message - AbsentClaim::scalar();
+        let is_present = offset.is_zero().unwrap_u8() == 0;
+        match (statement.present, is_present) {
+            (true, false) => {
+                return Err(Error::InvalidClaimData(
+                    "cannot prove presence of an absent claim",
+                ))
+            }
+            (false, true) => {
+                return Err(Error::InvalidClaimData(
+                    "cannot prove absence of a present claim",
+                ))
+            }
+            (_, _) => {}
+        }
+
+        let rho = Scalar::random(&mut rng);
+        let r_rho = Scalar::random(&mut rng);
+        let commitment = statement.message_generator * message + statement.blinder_generator * rho;
+        let blind_commitment =
+            statement.message_generator * b + statement.blinder_generator * r_rho;
+        let offset_commitment = commitment - statement.message_generator * AbsentClaim::scalar();
+
+        let (inverse, blind_relation) = if is_present {
+            let w = offset.invert().unwrap();
+            let t = -(rho * w);
+            let r_w = Scalar::random(&mut rng);
+            let r_t = Scalar::random(&mut rng);
+            let blind_inverse = offset_commitment * r_w + statement.blinder_generator * r_t;
+            (
+                Some(PresenceInverseBuilder { w, t, r_w, r_t }),
+                blind_inverse,
+            )
+        } else {
+            (None, statement.blinder_generator * r_rho)
+        };
+
+        transcript.append_message(b"", statement.id.as_bytes());
+        transcript.append_message(
+            b"commitment",
+            commitment.to_affine().to_compressed().as_slice(),
+        );
+        transcript.append_message(
+            b"blind commitment",
+            blind_commitment.to_affine().to_compressed().as_slice(),
+        );
+        transcript.append_message(
+            b"blind presence relation",
+            blind_relation.to_affine().to_compressed().as_slice(),
+        );
+        Ok(Self {
+            commitment,
+            statement,
+            rho,
+            r_rho,
+            inverse,
+        })
+    }
+}
+
+/// A presence proof
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PresenceProof {
+    /// The statement identifier
+    pub id: String,
+    /// The commitment
+    pub commitment: G1Projective,
+    /// The schnorr blinder proof
+    pub blinder_proof: Scalar,
+    /// The schnorr proofs for the inverse of the claim's offset from the
+    /// absent value, only included when proving presence
+    pub inverse_proof: Option<(Scalar, Scalar)>,
+}
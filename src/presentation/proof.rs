@@ -2,8 +2,8 @@ use super::SignatureProof;
 use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
 use crate::presentation::verifiable_encryption_decryption::VerifiableEncryptionDecryptionProof;
 use crate::presentation::{
-    CommitmentProof, EqualityProof, MembershipProof, RangeProof, RevocationProof,
-    VerifiableEncryptionProof,
+    BlindClaimsProof, CommitmentProof, EqualityProof, MembershipProof, PresenceProof,
+    PseudonymProof, RangeProof, RevocationProof, VerifiableEncryptionProof,
 };
 use serde::{Deserialize, Serialize};
 
@@ -30,6 +30,12 @@ pub enum PresentationProofs<S: ShortGroupSignatureScheme> {
     Membership(Box<MembershipProof>),
     /// Verifiable Encryption Decryption Proofs
     VerifiableEncryptionDecryption(Box<VerifiableEncryptionDecryptionProof>),
+    /// Presence Proofs
+    Presence(Box<PresenceProof>),
+    /// Blind claims proofs
+    BlindClaims(Box<BlindClaimsProof>),
+    /// Pseudonym proofs
+    Pseudonym(Box<PseudonymProof>),
 }
 
 impl<S: ShortGroupSignatureScheme> From<SignatureProof<S>> for PresentationProofs<S> {
@@ -38,6 +44,12 @@ impl<S: ShortGroupSignatureScheme> From<SignatureProof<S>> for PresentationProof
     }
 }
 
+impl<S: ShortGroupSignatureScheme> From<BlindClaimsProof> for PresentationProofs<S> {
+    fn from(p: BlindClaimsProof) -> Self {
+        Self::BlindClaims(Box::new(p))
+    }
+}
+
 impl<S: ShortGroupSignatureScheme> From<RevocationProof> for PresentationProofs<S> {
     fn from(p: RevocationProof) -> Self {
         Self::Revocation(Box::new(p))
@@ -82,6 +94,18 @@ impl<S: ShortGroupSignatureScheme> From<VerifiableEncryptionDecryptionProof>
     }
 }
 
+impl<S: ShortGroupSignatureScheme> From<PseudonymProof> for PresentationProofs<S> {
+    fn from(value: PseudonymProof) -> Self {
+        Self::Pseudonym(Box::new(value))
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> From<PresenceProof> for PresentationProofs<S> {
+    fn from(value: PresenceProof) -> Self {
+        Self::Presence(Box::new(value))
+    }
+}
+
 impl<S: ShortGroupSignatureScheme> PresentationProofs<S> {
     /// Get the underlying statement identifier
     pub fn id(&self) -> &String {
@@ -94,6 +118,9 @@ impl<S: ShortGroupSignatureScheme> PresentationProofs<S> {
             Self::Range(r) => &r.id,
             Self::Membership(m) => &m.id,
             Self::VerifiableEncryptionDecryption(v) => &v.id,
+            Self::Presence(p) => &p.id,
+            Self::BlindClaims(b) => &b.id,
+            Self::Pseudonym(p) => &p.id,
         }
     }
 }
@@ -0,0 +1,74 @@
+use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use crate::presentation::{PresentationBuilder, PresentationProofs};
+use crate::statement::PseudonymStatement;
+use crate::CredxResult;
+use blsful::inner_types::{G1Projective, Scalar};
+use elliptic_curve::group::Curve;
+use merlin::Transcript;
+use serde::{Deserialize, Serialize};
+
+/// A pseudonym builder
+///
+/// Shows `P = OP * m` and proves it uses the same claim as the signature
+/// proof by committing to `OP * b` with the claim's shared blinder `b`.
+/// This is a credx proof, not the draft's proof with nym, which
+/// [`BbsPseudonymPresentation`](super::BbsPseudonymPresentation) carries.
+#[derive(Debug)]
+pub(crate) struct PseudonymBuilder<'a> {
+    pub(crate) pseudonym: G1Projective,
+    pub(crate) statement: &'a PseudonymStatement,
+}
+
+impl<S: ShortGroupSignatureScheme> PresentationBuilder<S> for PseudonymBuilder<'_> {
+    fn gen_proof(self, _challenge: Scalar) -> PresentationProofs<S> {
+        PseudonymProof {
+            id: self.statement.id.clone(),
+            pseudonym: self.pseudonym,
+        }
+        .into()
+    }
+}
+
+impl<'a> PseudonymBuilder<'a> {
+    /// Creates a pseudonym builder
+    pub fn commit(
+        statement: &'a PseudonymStatement,
+        message: Scalar,
+        b: Scalar,
+        transcript: &mut Transcript,
+    ) -> CredxResult<Self> {
+        let base = statement.pseudonym_base();
+        let pseudonym = base * message;
+        let blind_pseudonym = base * b;
+
+        transcript.append_message(b"", statement.id.as_bytes());
+        transcript.append_message(
+            b"pseudonym",
+            pseudonym.to_affine().to_compressed().as_slice(),
+        );
+        transcript.append_message(
+            b"blind pseudonym",
+            blind_pseudonym.to_affine().to_compressed().as_slice(),
+        );
+        Ok(Self {
+            pseudonym,
+            statement,
+        })
+    }
+}
+
+/// A pseudonym proof
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PseudonymProof {
+    /// The statement identifier
+    pub id: String,
+    /// The pseudonym
+    pub pseudonym: G1Projective,
+}
+
+impl PseudonymProof {
+    /// The pseudonym in compressed form
+    pub fn to_bytes(&self) -> [u8; 48] {
+        self.pseudonym.to_affine().to_compressed()
+    }
+}
@@ -8,7 +8,7 @@ use blsful::inner_types::Scalar;
 use bulletproofs::RangeProof as RangeProofBulletproof;
 use elliptic_curve::group::Curve;
 use merlin::Transcript;
-use serde::{Deserialize, Serialize};
+use serde::{de::Error as DError, Deserialize, Deserializer, Serialize, Serializer};
 
 #[derive(Debug)]
 pub(crate) struct RangeBuilder<'a> {
@@ -222,5 +222,44 @@ pub struct RangeProof {
     /// The statement identifier
     pub id: String,
     /// The range proof
+    #[serde(
+        serialize_with = "serialize_range_proof",
+        deserialize_with = "deserialize_range_proof"
+    )]
     pub proof: RangeProofBulletproof,
 }
+
+/// Bulletproofs serializes range proofs as bytes which human readable
+/// formats like JSON write as a sequence it can't read back, so use hex
+fn serialize_range_proof<S: Serializer>(
+    proof: &RangeProofBulletproof,
+    s: S,
+) -> Result<S::Ok, S::Error> {
+    if s.is_human_readable() {
+        hex::encode(proof.to_bytes()).serialize(s)
+    } else {
+        proof.serialize(s)
+    }
+}
+
+/// Earlier releases wrote the byte sequence so read that as well
+fn deserialize_range_proof<'de, D: Deserializer<'de>>(
+    d: D,
+) -> Result<RangeProofBulletproof, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RangeProofText {
+        Hex(String),
+        Bytes(Vec<u8>),
+    }
+
+    if d.is_human_readable() {
+        let bytes = match RangeProofText::deserialize(d)? {
+            RangeProofText::Hex(h) => hex::decode(h).map_err(DError::custom)?,
+            RangeProofText::Bytes(b) => b,
+        };
+        RangeProofBulletproof::from_bytes(&bytes).map_err(|_| DError::custom("invalid range proof"))
+    } else {
+        RangeProofBulletproof::deserialize(d)
+    }
+}
@@ -1,8 +1,12 @@
+use crate::issuer::IssuerKeyRegistry;
 use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
 use crate::random_string;
-use crate::{statement::Statements, utils::*};
+use crate::statement::{CompactSignatureStatement, Statements};
+use crate::utils::*;
+use crate::CredxResult;
 use indexmap::IndexMap;
 use log::debug;
+use rand_core::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
 use uint_zigzag::Uint;
 
@@ -24,14 +28,61 @@ pub struct PresentationSchema<S: ShortGroupSignatureScheme + Serialize> {
 impl<S: ShortGroupSignatureScheme> PresentationSchema<S> {
     /// Create a new presentation schema with random id
     pub fn new(statements: &[Statements<S>]) -> Self {
-        let id = random_string(16, rand::thread_rng());
+        Self::new_with_rng(statements, rand::thread_rng())
+    }
+
+    /// Create a new presentation schema with a random id generated from `rng`
+    pub fn new_with_rng(statements: &[Statements<S>], rng: impl RngCore + CryptoRng) -> Self {
+        let id = random_string(16, rng);
         Self::new_with_id(statements, &id)
     }
 
     /// Create a new presentation schema with given id
+    ///
+    /// Credentials with a validity period must be valid now,
+    /// see [`Self::new_with_id_valid_at`].
     pub fn new_with_id(statements: &[Statements<S>], pres_schema_id: &str) -> Self {
+        Self::new_with_id_valid_at(
+            statements,
+            pres_schema_id,
+            chrono::Utc::now().timestamp() as isize,
+        )
+    }
+
+    /// Create a new presentation schema with random id where credentials
+    /// with a validity period must be valid at `time`
+    pub fn new_valid_at(statements: &[Statements<S>], time: isize) -> Self {
+        let id = random_string(16, rand::thread_rng());
+        Self::new_with_id_valid_at(statements, &id, time)
+    }
+
+    /// Create a new presentation schema with given id where credentials
+    /// with a validity period must be valid at `time`.
+    ///
+    /// Signature statements for issuers whose schema declares a validity
+    /// period get the statements from
+    /// [`SignatureStatement::validity_statements`](crate::statement::SignatureStatement::validity_statements)
+    /// unless statements
+    /// with the same ids are already given.
+    pub fn new_with_id_valid_at(
+        statements: &[Statements<S>],
+        pres_schema_id: &str,
+        time: isize,
+    ) -> Self {
         let id = pres_schema_id.into();
-        let statements = statements.iter().map(|s| (s.id(), (*s).clone())).collect();
+        let mut statements: IndexMap<String, Statements<S>> =
+            statements.iter().map(|s| (s.id(), (*s).clone())).collect();
+        let validity = statements
+            .values()
+            .filter_map(|s| match s {
+                Statements::Signature(s) => s.validity_statements(time).ok(),
+                _ => None,
+            })
+            .flatten()
+            .collect::<Vec<_>>();
+        for statement in validity {
+            statements.entry(statement.id()).or_insert(statement);
+        }
         let presentation_schema = Self { id, statements };
         debug!(
             "Presentation Schema: {}",
@@ -53,3 +104,74 @@ impl<S: ShortGroupSignatureScheme> PresentationSchema<S> {
         }
     }
 }
+
+/// A presentation schema whose signature statements refer to
+/// their issuers by key id.
+///
+/// Resolve it with an [`IssuerKeyRegistry`] to get the [`PresentationSchema`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CompactPresentationSchema<S: ShortGroupSignatureScheme + Serialize> {
+    /// The unique presentation context id
+    pub id: String,
+    /// The statements associated with this presentation
+    #[serde(
+        serialize_with = "serialize_indexmap",
+        deserialize_with = "deserialize_indexmap",
+        bound(serialize = "CompactStatements<S>: Serialize"),
+        bound(deserialize = "CompactStatements<S>: Deserialize<'de>")
+    )]
+    pub statements: IndexMap<String, CompactStatements<S>>,
+}
+
+/// The statements in a [`CompactPresentationSchema`]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum CompactStatements<S: ShortGroupSignatureScheme> {
+    /// Signature statements with the issuer key id
+    Signature(Box<CompactSignatureStatement>),
+    /// Any other statement
+    #[serde(bound(
+        serialize = "Statements<S>: Serialize",
+        deserialize = "Statements<S>: Deserialize<'de>"
+    ))]
+    Statement(Box<Statements<S>>),
+}
+
+impl<S: ShortGroupSignatureScheme> From<&PresentationSchema<S>> for CompactPresentationSchema<S> {
+    fn from(schema: &PresentationSchema<S>) -> Self {
+        let statements = schema
+            .statements
+            .iter()
+            .map(|(id, statement)| {
+                let compact = match statement {
+                    Statements::Signature(s) => {
+                        CompactStatements::Signature(Box::new(s.as_ref().into()))
+                    }
+                    s => CompactStatements::Statement(Box::new(s.clone())),
+                };
+                (id.clone(), compact)
+            })
+            .collect();
+        Self {
+            id: schema.id.clone(),
+            statements,
+        }
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> CompactPresentationSchema<S> {
+    /// Look up the issuers and return the full presentation schema
+    pub fn resolve(&self, registry: &IssuerKeyRegistry<S>) -> CredxResult<PresentationSchema<S>> {
+        let mut statements = IndexMap::with_capacity(self.statements.len());
+        for (id, statement) in &self.statements {
+            let statement = match statement {
+                CompactStatements::Signature(s) => Statements::from(s.resolve(registry)?),
+                CompactStatements::Statement(s) => s.as_ref().clone(),
+            };
+            statements.insert(id.clone(), statement);
+        }
+        Ok(PresentationSchema {
+            id: self.id.clone(),
+            statements,
+        })
+    }
+}
@@ -16,6 +16,8 @@ pub(crate) struct SignatureBuilder<'a, S: ShortGroupSignatureScheme> {
     id: &'a String,
     /// The messages that belong to this signature
     disclosed_messages: IndexMap<usize, Scalar>,
+    /// The version of the issuer key that signed the credential
+    key_version: u32,
     /// The signature proof of knowledge builder
     pok_sig: S::ProofOfSignatureKnowledgeContribution,
 }
@@ -27,6 +29,7 @@ impl<S: ShortGroupSignatureScheme> PresentationBuilder<S> for SignatureBuilder<'
         SignatureProof {
             id: self.id.clone(),
             disclosed_messages: self.disclosed_messages,
+            key_version: self.key_version,
             pok: self.pok_sig.generate_proof(challenge).unwrap(),
         }
         .into()
@@ -42,9 +45,18 @@ impl<'a, S: ShortGroupSignatureScheme> SignatureBuilder<'a, S> {
         rng: impl RngCore + CryptoRng,
         transcript: &mut Transcript,
     ) -> CredxResult<Self> {
+        // Only search for the signing key when the issuer has rotated keys
+        let key_version = if statement.issuer.previous_keys.is_empty() {
+            statement.issuer.key_version
+        } else {
+            let scalars = messages.iter().map(|m| m.get_message()).collect::<Vec<_>>();
+            statement
+                .issuer
+                .key_version_for_signature(signature, &scalars)?
+        };
         match S::ProofOfSignatureKnowledgeContribution::commit(
             signature,
-            &statement.issuer.verifying_key,
+            statement.issuer.verifying_key_for(key_version)?,
             messages,
             rng,
         ) {
@@ -62,6 +74,7 @@ impl<'a, S: ShortGroupSignatureScheme> SignatureBuilder<'a, S> {
                 Ok(Self {
                     id: &statement.id,
                     disclosed_messages,
+                    key_version,
                     pok_sig: poksig,
                 })
             }
@@ -80,6 +93,9 @@ pub struct SignatureProof<S: ShortGroupSignatureScheme> {
         deserialize_with = "deserialize_indexmap"
     )]
     pub disclosed_messages: IndexMap<usize, Scalar>,
+    /// The version of the issuer key used to verify the proof
+    #[serde(default)]
+    pub key_version: u32,
     /// The proof
     pub pok: S::ProofOfSignatureKnowledge,
 }
@@ -25,6 +25,20 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
                     verifier.add_challenge_contribution(self.challenge, &mut transcript)?;
                     verifiers.push(verifier.into());
                 }
+                (Statements::BlindClaims(bs), Some(PresentationProofs::BlindClaims(proof))) => {
+                    let verifier = BlindClaimsVerifier {
+                        statement: bs,
+                        proof,
+                    };
+                    verifier.add_challenge_contribution(self.challenge, &mut transcript)?;
+                    verifiers.push(verifier.into());
+                }
+                (Statements::BlindClaims(_), _) => {
+                    return Err(Error::InvalidPresentationData(format!(
+                        "expected a blind claims proof for statement '{}', but no proof was found",
+                        id
+                    )))
+                }
                 (Statements::Signature(_), None) => {
                     return Err(Error::InvalidPresentationData(format!(
                         "expected a signature proof for statement '{}', but not proof was found",
@@ -143,6 +157,34 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
                     verifier.add_challenge_contribution(self.challenge, &mut transcript)?;
                     verifiers.push(verifier.into());
                 }
+                (Statements::Presence(statement), Some(PresentationProofs::Presence(proof))) => {
+                    let hidden_messages =
+                        self.get_sig_hidden_message_proofs(schema, &statement.reference_id)?;
+                    let message_proof = hidden_messages
+                        .get(&statement.claim)
+                        .ok_or(Error::InvalidPresentationData(format!("presence statement with id '{}' references a claim proof '{}' that doesn't exist or was not included", statement.id, statement.claim)))?;
+                    let verifier = PresenceVerifier {
+                        statement,
+                        proof,
+                        message_proof: *message_proof,
+                    };
+                    verifier.add_challenge_contribution(self.challenge, &mut transcript)?;
+                    verifiers.push(verifier.into());
+                }
+                (Statements::Pseudonym(statement), Some(PresentationProofs::Pseudonym(proof))) => {
+                    let hidden_messages =
+                        self.get_sig_hidden_message_proofs(schema, &statement.reference_id)?;
+                    let message_proof = hidden_messages
+                        .get(&statement.claim)
+                        .ok_or(Error::InvalidPresentationData(format!("pseudonym statement with id '{}' references a claim proof '{}' that doesn't exist or was not included", statement.id, statement.claim)))?;
+                    let verifier = PseudonymVerifier {
+                        statement,
+                        proof,
+                        message_proof: *message_proof,
+                    };
+                    verifier.add_challenge_contribution(self.challenge, &mut transcript)?;
+                    verifiers.push(verifier.into());
+                }
                 (_, _) => {
                     return Err(Error::InvalidPresentationData(format!(
                         "an unknown predicate statement was found in the presentation: {:?}",
@@ -198,7 +240,7 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
                         let hidden_messages = s
                             .pok
                             .get_hidden_message_proofs(
-                                &sig_st.issuer.verifying_key,
+                                sig_st.issuer.verifying_key_for(s.key_version)?,
                                 disclosed_messages.as_slice(),
                             )?;
                         Ok(hidden_messages)
@@ -206,6 +248,7 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
                     st => Err(Error::InvalidPresentationData(format!("signature proof of id '{}' associated statement is not a signature statement: associated statement: {:?}", s.id, st))),
                 }
             }
+            PresentationProofs::BlindClaims(b) => Ok(b.message_proofs.clone()),
             p => Err(Error::InvalidPresentationData(format!(
                 "proof with id '{}' is not a signature proof: proof {:?}",
                 reference_id, p
@@ -38,15 +38,30 @@ impl RevocationRegistry {
 
     /// Remove the specified elements from the registry
     pub fn revoke(&mut self, sk: &SecretKey, elements: &[String]) -> CredxResult<()> {
-        let mut removals = Vec::new();
+        self.revoke_with(elements, |mut value, removals| {
+            value.remove_elements_assign(sk, removals);
+            Ok(value)
+        })
+    }
+
+    /// Remove the specified elements from the registry where `remove`
+    /// computes the new registry value
+    pub(crate) fn revoke_with<F>(&mut self, elements: &[String], remove: F) -> CredxResult<()>
+    where
+        F: FnOnce(Accumulator, &[Element]) -> CredxResult<Accumulator>,
+    {
+        let mut removals = Vec::with_capacity(elements.len());
         for e in elements {
-            if !self.active.shift_remove(e) {
+            if !self.active.contains(e) {
                 return Err(Error::InvalidRevocationRegistryRevokeOperation);
             }
             removals.push(Element::hash(e.as_bytes()));
         }
 
-        self.value.remove_elements_assign(sk, removals.as_slice());
+        self.value = remove(self.value, removals.as_slice())?;
+        for e in elements {
+            self.active.shift_remove(e);
+        }
 
         Ok(())
     }
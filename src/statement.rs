@@ -1,15 +1,23 @@
+mod blind_claims;
+mod bounding_box;
 mod commitment;
 mod equality;
 mod membership;
+mod presence;
+mod pseudonym;
 mod range;
 mod revocation;
 mod signature;
 mod verifiable_encryption;
 mod verifiable_encryption_decryption;
 
+pub use blind_claims::*;
+pub use bounding_box::*;
 pub use commitment::*;
 pub use equality::*;
 pub use membership::*;
+pub use presence::*;
+pub use pseudonym::*;
 pub use range::*;
 pub use revocation::*;
 pub use signature::*;
@@ -56,6 +64,16 @@ pub enum Statements<S: ShortGroupSignatureScheme> {
     /// Membership statements
     Membership(Box<MembershipStatement>),
     VerifiableEncryptionDecryption(Box<VerifiableEncryptionDecryptionStatement<G1Projective>>),
+    /// Presence statements
+    Presence(Box<PresenceStatement<G1Projective>>),
+    /// Blind claims statements
+    #[serde(bound(
+        serialize = "BlindClaimsStatement<S>: Serialize",
+        deserialize = "BlindClaimsStatement<S>: Deserialize<'de>"
+    ))]
+    BlindClaims(Box<BlindClaimsStatement<S>>),
+    /// Pseudonym statements
+    Pseudonym(Box<PseudonymStatement>),
 }
 
 impl<S: ShortGroupSignatureScheme> From<SignatureStatement<S>> for Statements<S> {
@@ -110,6 +128,24 @@ impl<S: ShortGroupSignatureScheme> From<VerifiableEncryptionDecryptionStatement<
     }
 }
 
+impl<S: ShortGroupSignatureScheme> From<PresenceStatement<G1Projective>> for Statements<S> {
+    fn from(p: PresenceStatement<G1Projective>) -> Self {
+        Self::Presence(Box::new(p))
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> From<BlindClaimsStatement<S>> for Statements<S> {
+    fn from(b: BlindClaimsStatement<S>) -> Self {
+        Self::BlindClaims(Box::new(b))
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> From<PseudonymStatement> for Statements<S> {
+    fn from(p: PseudonymStatement) -> Self {
+        Self::Pseudonym(Box::new(p))
+    }
+}
+
 impl<S: ShortGroupSignatureScheme> Statements<S> {
     /// Return the statement id
     pub fn id(&self) -> String {
@@ -122,6 +158,9 @@ impl<S: ShortGroupSignatureScheme> Statements<S> {
             Self::Range(r) => r.id(),
             Self::Membership(m) => m.id(),
             Self::VerifiableEncryptionDecryption(v) => v.id(),
+            Self::Presence(p) => p.id(),
+            Self::BlindClaims(b) => b.id(),
+            Self::Pseudonym(p) => p.id(),
         }
     }
 
@@ -136,6 +175,9 @@ impl<S: ShortGroupSignatureScheme> Statements<S> {
             Self::Range(r) => r.reference_ids(),
             Self::Membership(m) => m.reference_ids(),
             Self::VerifiableEncryptionDecryption(v) => v.reference_ids(),
+            Self::Presence(p) => p.reference_ids(),
+            Self::BlindClaims(b) => b.reference_ids(),
+            Self::Pseudonym(p) => p.reference_ids(),
         }
     }
 
@@ -150,6 +192,9 @@ impl<S: ShortGroupSignatureScheme> Statements<S> {
             Self::Range(r) => r.add_challenge_contribution(transcript),
             Self::Membership(m) => m.add_challenge_contribution(transcript),
             Self::VerifiableEncryptionDecryption(v) => v.add_challenge_contribution(transcript),
+            Self::Presence(p) => p.add_challenge_contribution(transcript),
+            Self::BlindClaims(b) => b.add_challenge_contribution(transcript),
+            Self::Pseudonym(p) => p.add_challenge_contribution(transcript),
         }
     }
 
@@ -164,6 +209,9 @@ impl<S: ShortGroupSignatureScheme> Statements<S> {
             Self::Range(r) => r.get_claim_index(reference_id),
             Self::Membership(m) => m.get_claim_index(reference_id),
             Self::VerifiableEncryptionDecryption(v) => v.get_claim_index(reference_id),
+            Self::Presence(p) => p.get_claim_index(reference_id),
+            Self::BlindClaims(b) => b.get_claim_index(reference_id),
+            Self::Pseudonym(p) => p.get_claim_index(reference_id),
         }
     }
 }
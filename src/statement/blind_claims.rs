@@ -0,0 +1,78 @@
+use super::Statement;
+use crate::error::Error;
+use crate::issuer::IssuerPublic;
+use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use crate::CredxResult;
+use merlin::Transcript;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use uint_zigzag::Uint;
+
+/// A statement about the claims a holder blinds when requesting a blind signature.
+///
+/// The proof shows knowledge of the claims in the blind signature context
+/// commitment so other statements can reference them by their index in the
+/// issuer's schema like claims in a signature statement.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BlindClaimsStatement<S: ShortGroupSignatureScheme> {
+    /// The statement id
+    pub id: String,
+    /// The labels of the blinded claims
+    pub claims: BTreeSet<String>,
+    /// The issuer that will sign the claims
+    #[serde(bound(
+        serialize = "IssuerPublic<S>: Serialize",
+        deserialize = "IssuerPublic<S>: Deserialize<'de>"
+    ))]
+    pub issuer: IssuerPublic<S>,
+}
+
+impl<S: ShortGroupSignatureScheme> BlindClaimsStatement<S> {
+    /// The schema indices of the blinded claims in ascending order
+    pub fn claim_indices(&self) -> CredxResult<Vec<usize>> {
+        let mut indices = self
+            .claims
+            .iter()
+            .map(|label| {
+                self.issuer.schema.get_claim_index(label).ok_or_else(|| {
+                    Error::InvalidPresentationData(format!(
+                        "blind claims statement '{}' references claim '{}' which isn't in the schema",
+                        self.id, label
+                    ))
+                })
+            })
+            .collect::<CredxResult<Vec<_>>>()?;
+        indices.sort_unstable();
+        Ok(indices)
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> Statement for BlindClaimsStatement<S> {
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn reference_ids(&self) -> Vec<String> {
+        Vec::with_capacity(0)
+    }
+
+    fn add_challenge_contribution(&self, transcript: &mut Transcript) {
+        transcript.append_message(b"statement type", b"blind claims");
+        transcript.append_message(b"statement id", self.id.as_bytes());
+        transcript.append_message(
+            b"blind claims length",
+            &Uint::from(self.claims.len()).to_vec(),
+        );
+        for label in &self.claims {
+            transcript.append_message(b"blind claim label", label.as_bytes());
+        }
+        self.issuer.add_challenge_contribution(transcript);
+    }
+
+    fn get_claim_index(&self, reference_id: &str) -> usize {
+        self.issuer
+            .schema
+            .get_claim_index(reference_id)
+            .expect("claim label is in the issuer's schema")
+    }
+}
@@ -0,0 +1,150 @@
+use crate::claim::{GeoBoundingBox, GeolocationClaim, LATITUDE_LABEL, LONGITUDE_LABEL};
+use crate::error::Error;
+use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use crate::presentation::PresentationSchema;
+use crate::statement::{CommitmentStatement, RangeStatement, Statements};
+use crate::CredxResult;
+use blsful::inner_types::G1Projective;
+use elliptic_curve_tools::group;
+use serde::{Deserialize, Serialize};
+
+/// A compound statement that a hidden geolocation lies within one of a set of
+/// bounding boxes.
+///
+/// The statement expands into a commitment and range statement for the
+/// latitude and the longitude claims. Range proofs cannot express a disjunction
+/// so the prover selects the box that contains the point and the verifier
+/// learns which of the boxes was used but not the point itself.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BoundingBoxStatement {
+    /// The generator for the message element
+    #[serde(with = "group")]
+    pub message_generator: G1Projective,
+    /// The generator for the random element
+    #[serde(with = "group")]
+    pub blinder_generator: G1Projective,
+    /// The statement id
+    pub id: String,
+    /// The signature statement id
+    pub reference_id: String,
+    /// The latitude claim index in the signature statement
+    pub latitude_claim: usize,
+    /// The longitude claim index in the signature statement
+    pub longitude_claim: usize,
+    /// The allowed bounding boxes
+    pub boxes: Vec<GeoBoundingBox>,
+}
+
+impl BoundingBoxStatement {
+    /// The index of the first box that contains the point
+    pub fn select_box(&self, point: &GeolocationClaim) -> Option<usize> {
+        self.boxes.iter().position(|b| b.contains(point))
+    }
+
+    /// The commitment and range statements proving the geolocation lies in the
+    /// box at `box_index`
+    pub fn statements<S: ShortGroupSignatureScheme>(
+        &self,
+        box_index: usize,
+    ) -> CredxResult<Vec<Statements<S>>> {
+        let bounding_box = self.boxes.get(box_index).ok_or_else(|| {
+            Error::InvalidPresentationData(format!(
+                "bounding box statement '{}' has no box at index '{}'",
+                self.id, box_index
+            ))
+        })?;
+        let mut statements = Vec::with_capacity(4);
+        for (label, claim, lower, upper) in [
+            (
+                LATITUDE_LABEL,
+                self.latitude_claim,
+                bounding_box.south,
+                bounding_box.north,
+            ),
+            (
+                LONGITUDE_LABEL,
+                self.longitude_claim,
+                bounding_box.west,
+                bounding_box.east,
+            ),
+        ] {
+            let commitment = CommitmentStatement {
+                message_generator: self.message_generator,
+                blinder_generator: self.blinder_generator,
+                id: format!("{}.{}.commitment", self.id, label),
+                reference_id: self.reference_id.clone(),
+                claim,
+            };
+            let range = RangeStatement {
+                id: format!("{}.{}.range", self.id, label),
+                reference_id: commitment.id.clone(),
+                signature_id: self.reference_id.clone(),
+                claim,
+                lower: Some(lower),
+                upper: Some(upper),
+            };
+            statements.push(commitment.into());
+            statements.push(range.into());
+        }
+        Ok(statements)
+    }
+
+    /// The statements for the first box that contains the point
+    pub fn statements_for_point<S: ShortGroupSignatureScheme>(
+        &self,
+        point: &GeolocationClaim,
+    ) -> CredxResult<Vec<Statements<S>>> {
+        let box_index = self.select_box(point).ok_or_else(|| {
+            Error::InvalidPresentationData(format!(
+                "the geolocation is not within any of the bounding box statement '{}' boxes",
+                self.id
+            ))
+        })?;
+        self.statements(box_index)
+    }
+
+    /// Check the presentation schema contains the expanded statements for one
+    /// of the boxes and return the index of that box
+    pub fn find_box<S: ShortGroupSignatureScheme>(
+        &self,
+        schema: &PresentationSchema<S>,
+    ) -> CredxResult<usize> {
+        for box_index in 0..self.boxes.len() {
+            let expected = self.statements::<S>(box_index)?;
+            if expected.iter().all(|e| {
+                schema
+                    .statements
+                    .get(&e.id())
+                    .is_some_and(|s| Self::is_same(e, s))
+            }) {
+                return Ok(box_index);
+            }
+        }
+        Err(Error::InvalidPresentationData(format!(
+            "the presentation schema does not prove any of the bounding box statement '{}' boxes",
+            self.id
+        )))
+    }
+
+    fn is_same<S: ShortGroupSignatureScheme>(
+        expected: &Statements<S>,
+        actual: &Statements<S>,
+    ) -> bool {
+        match (expected, actual) {
+            (Statements::Commitment(e), Statements::Commitment(a)) => {
+                e.message_generator == a.message_generator
+                    && e.blinder_generator == a.blinder_generator
+                    && e.reference_id == a.reference_id
+                    && e.claim == a.claim
+            }
+            (Statements::Range(e), Statements::Range(a)) => {
+                e.reference_id == a.reference_id
+                    && e.signature_id == a.signature_id
+                    && e.claim == a.claim
+                    && e.lower == a.lower
+                    && e.upper == a.upper
+            }
+            (_, _) => false,
+        }
+    }
+}
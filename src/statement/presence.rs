@@ -0,0 +1,56 @@
+use crate::statement::Statement;
+use elliptic_curve::group::{Group, GroupEncoding};
+use elliptic_curve_tools::group;
+use merlin::Transcript;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use uint_zigzag::Uint;
+
+/// A statement that a hidden optional claim is present or absent
+/// without revealing its value
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PresenceStatement<P: Group + GroupEncoding + DeserializeOwned + Serialize> {
+    /// The generator for the message element
+    #[serde(with = "group")]
+    pub message_generator: P,
+    /// The generator for the random element
+    #[serde(with = "group")]
+    pub blinder_generator: P,
+    /// The statement id
+    pub id: String,
+    /// The other statement id
+    pub reference_id: String,
+    /// The claim index in the other statement
+    pub claim: usize,
+    /// [`true`] to prove the claim is present, [`false`] to prove it is absent
+    pub present: bool,
+}
+
+impl<P: Group + GroupEncoding + DeserializeOwned + Serialize> Statement for PresenceStatement<P> {
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn reference_ids(&self) -> Vec<String> {
+        vec![self.reference_id.clone()]
+    }
+
+    fn add_challenge_contribution(&self, transcript: &mut Transcript) {
+        transcript.append_message(b"statement type", b"presence");
+        transcript.append_message(b"statement id", self.id.as_bytes());
+        transcript.append_message(b"reference statement id", self.reference_id.as_bytes());
+        transcript.append_message(b"claim index", &Uint::from(self.claim).to_vec());
+        transcript.append_message(b"claim present", &[u8::from(self.present)]);
+        transcript.append_message(
+            b"message generator",
+            self.message_generator.to_bytes().as_ref(),
+        );
+        transcript.append_message(
+            b"blinder generator",
+            self.blinder_generator.to_bytes().as_ref(),
+        );
+    }
+
+    fn get_claim_index(&self, _reference_id: &str) -> usize {
+        self.claim
+    }
+}
@@ -0,0 +1,58 @@
+use crate::knox::bbs::BbsScheme;
+use crate::statement::Statement;
+use blsful::inner_types::G1Projective;
+use merlin::Transcript;
+use serde::{Deserialize, Serialize};
+use uint_zigzag::Uint;
+
+/// A statement that shows a pseudonym for a verifier computed from a hidden claim.
+///
+/// The pseudonym is `OP * m` where `m` is the claim and `OP` is hashed from
+/// the verifier's context id as in the CFRG BBS per Verifier Linkability draft.
+/// The same claim always gives the same pseudonym for a context id so
+/// verifiers can recognize returning holders without learning the claim.
+///
+/// The pseudonym is proven as part of the presentation proof, not with the
+/// draft's proof with nym, so only credx can verify it. Use
+/// [`crate::knox::bbs::BbsCiphersuite::pseudonym_proof_gen`] when other
+/// implementations of the draft must verify the pseudonym.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PseudonymStatement {
+    /// The statement id
+    pub id: String,
+    /// The other statement id
+    pub reference_id: String,
+    /// The claim index in the other statement
+    pub claim: usize,
+    /// The verifier's context id
+    pub context_id: String,
+}
+
+impl PseudonymStatement {
+    /// The pseudonym base point `OP`
+    pub fn pseudonym_base(&self) -> G1Projective {
+        BbsScheme::pseudonym_base(self.context_id.as_bytes())
+    }
+}
+
+impl Statement for PseudonymStatement {
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn reference_ids(&self) -> Vec<String> {
+        vec![self.reference_id.clone()]
+    }
+
+    fn add_challenge_contribution(&self, transcript: &mut Transcript) {
+        transcript.append_message(b"statement type", b"pseudonym");
+        transcript.append_message(b"statement id", self.id.as_bytes());
+        transcript.append_message(b"reference statement id", self.reference_id.as_bytes());
+        transcript.append_message(b"claim index", &Uint::from(self.claim).to_vec());
+        transcript.append_message(b"context id", self.context_id.as_bytes());
+    }
+
+    fn get_claim_index(&self, _reference_id: &str) -> usize {
+        self.claim
+    }
+}
@@ -1,6 +1,12 @@
-use super::Statement;
-use crate::issuer::IssuerPublic;
+use super::{CommitmentStatement, PseudonymStatement, RangeStatement, Statement, Statements};
+use crate::claim::claim_path_starts_with;
+use crate::error::Error;
+use crate::issuer::{IssuerKeyId, IssuerKeyRegistry, IssuerPublic};
+use crate::knox::accumulator::vb20::Accumulator;
 use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use crate::CredxResult;
+use blsful::inner_types::G1Projective;
+use elliptic_curve::hash2curve::ExpandMsgXmd;
 use merlin::Transcript;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeSet;
@@ -9,7 +15,7 @@ use uint_zigzag::Uint;
 /// A PS signature statement
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SignatureStatement<S: ShortGroupSignatureScheme> {
-    /// The labels for the disclosed claims
+    /// The labels or paths for the disclosed claims
     pub disclosed: BTreeSet<String>,
     /// The statement id
     pub id: String,
@@ -21,6 +27,104 @@ pub struct SignatureStatement<S: ShortGroupSignatureScheme> {
     pub issuer: IssuerPublic<S>,
 }
 
+impl<S: ShortGroupSignatureScheme> SignatureStatement<S> {
+    /// [`true`] if the claim label is disclosed by this statement.
+    /// A disclosed path also discloses every claim nested below it.
+    pub fn is_disclosed(&self, label: &str) -> bool {
+        self.disclosed
+            .iter()
+            .any(|d| claim_path_starts_with(label, d))
+    }
+
+    /// The commitment and range statements proving the credential is valid at
+    /// `time`, i.e. `valid_from <= time <= valid_until` for the validity claims
+    /// declared in the issuer's schema.
+    /// Disclosed validity claims are shown to the verifier instead.
+    ///
+    /// Presentation schemas add these for every signature statement whose
+    /// issuer schema has a validity period, see
+    /// [`PresentationSchema::new_valid_at`](crate::presentation::PresentationSchema::new_valid_at).
+    /// The range proofs are created and checked with the presentation.
+    pub fn validity_statements(&self, time: isize) -> CredxResult<Vec<Statements<S>>> {
+        let schema = &self.issuer.schema;
+        if schema.valid_from.is_none() && schema.valid_until.is_none() {
+            return Err(Error::InvalidPresentationData(format!(
+                "the schema for signature statement '{}' has no validity period",
+                self.id
+            )));
+        }
+        let message_generator = G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(
+            b"validity message generator",
+            b"BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        );
+        let blinder_generator = G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(
+            b"validity blinder generator",
+            b"BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        );
+        let mut statements = Vec::with_capacity(4);
+        for (name, label, lower, upper) in [
+            ("valid_from", &schema.valid_from, None, Some(time)),
+            ("valid_until", &schema.valid_until, Some(time), None),
+        ] {
+            let label = match label {
+                Some(label) if !self.is_disclosed(label) => label,
+                _ => continue,
+            };
+            let claim = schema.get_claim_index(label).ok_or_else(|| {
+                Error::InvalidPresentationData(format!(
+                    "validity claim '{}' not found in the schema",
+                    label
+                ))
+            })?;
+            let commitment = CommitmentStatement {
+                message_generator,
+                blinder_generator,
+                id: format!("{}.{}.commitment", self.id, name),
+                reference_id: self.id.clone(),
+                claim,
+            };
+            let range = RangeStatement {
+                id: format!("{}.{}.range", self.id, name),
+                reference_id: commitment.id.clone(),
+                signature_id: self.id.clone(),
+                claim,
+                lower,
+                upper,
+            };
+            statements.push(commitment.into());
+            statements.push(range.into());
+        }
+        Ok(statements)
+    }
+
+    /// The pseudonym statement showing the holder's pseudonym for a verifier's
+    /// `context_id` computed from the hidden claim `label` like a link secret
+    pub fn pseudonym_statement(
+        &self,
+        label: &str,
+        context_id: &str,
+    ) -> CredxResult<PseudonymStatement> {
+        if self.is_disclosed(label) {
+            return Err(Error::InvalidPresentationData(format!(
+                "claim '{}' is disclosed by signature statement '{}' and can't be used for a pseudonym",
+                label, self.id
+            )));
+        }
+        let claim = self.issuer.schema.get_claim_index(label).ok_or_else(|| {
+            Error::InvalidPresentationData(format!(
+                "pseudonym claim '{}' not found in the schema",
+                label
+            ))
+        })?;
+        Ok(PseudonymStatement {
+            id: format!("{}.{}.pseudonym", self.id, label),
+            reference_id: self.id.clone(),
+            claim,
+            context_id: context_id.to_string(),
+        })
+    }
+}
+
 impl<S: ShortGroupSignatureScheme> Statement for SignatureStatement<S> {
     /// Return this statement unique identifier
     fn id(&self) -> String {
@@ -49,7 +153,50 @@ impl<S: ShortGroupSignatureScheme> Statement for SignatureStatement<S> {
         self.issuer.add_challenge_contribution(transcript);
     }
 
-    fn get_claim_index(&self, _reference_id: &str) -> usize {
-        unimplemented!()
+    fn get_claim_index(&self, reference_id: &str) -> usize {
+        self.issuer
+            .schema
+            .get_claim_index(reference_id)
+            .expect("claim label is in the issuer's schema")
+    }
+}
+
+/// A signature statement that refers to its issuer by key id
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CompactSignatureStatement {
+    /// The labels or paths for the disclosed claims
+    pub disclosed: BTreeSet<String>,
+    /// The statement id
+    pub id: String,
+    /// The issuer's key id
+    pub issuer_key_id: IssuerKeyId,
+    /// The issuer's revocation registry
+    pub revocation_registry: Accumulator,
+}
+
+impl<S: ShortGroupSignatureScheme> From<&SignatureStatement<S>> for CompactSignatureStatement {
+    fn from(s: &SignatureStatement<S>) -> Self {
+        Self {
+            disclosed: s.disclosed.clone(),
+            id: s.id.clone(),
+            issuer_key_id: s.issuer.key_id(),
+            revocation_registry: s.issuer.revocation_registry,
+        }
+    }
+}
+
+impl CompactSignatureStatement {
+    /// Look up the issuer and return the full statement
+    pub fn resolve<S: ShortGroupSignatureScheme>(
+        &self,
+        registry: &IssuerKeyRegistry<S>,
+    ) -> CredxResult<SignatureStatement<S>> {
+        let mut issuer = registry.get(&self.issuer_key_id)?.clone();
+        issuer.revocation_registry = self.revocation_registry;
+        Ok(SignatureStatement {
+            disclosed: self.disclosed.clone(),
+            id: self.id.clone(),
+            issuer,
+        })
     }
 }
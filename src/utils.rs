@@ -6,6 +6,7 @@ use serde::{
     Deserialize, Deserializer, Serialize, Serializer,
 };
 use std::{
+    collections::BTreeMap,
     fmt::{self, Formatter},
     hash::Hash,
     marker::PhantomData,
@@ -144,3 +145,23 @@ pub fn deserialize_indexmap_nested<
     }
     Ok(result)
 }
+
+/// Serialize a map as a sequence of entries for keys that formats like
+/// JSON can't use as map keys
+pub fn serialize_map_entries<K: Serialize, V: Serialize, S: Serializer>(
+    map: &BTreeMap<K, V>,
+    s: S,
+) -> Result<S::Ok, S::Error> {
+    s.collect_seq(map.iter())
+}
+
+pub fn deserialize_map_entries<
+    'de,
+    K: Ord + DeserializeOwned,
+    V: DeserializeOwned,
+    D: Deserializer<'de>,
+>(
+    d: D,
+) -> Result<BTreeMap<K, V>, D::Error> {
+    Ok(Vec::<(K, V)>::deserialize(d)?.into_iter().collect())
+}
@@ -1,15 +1,21 @@
+mod blind_claims;
 mod commitment;
 mod equality;
 mod membership;
+mod presence;
+mod pseudonym;
 mod range;
 mod revocation;
 mod signature;
 mod verifiable_encryption;
 mod verifiable_encryption_decryption;
 
+pub use blind_claims::*;
 pub use commitment::*;
 pub use equality::*;
 pub use membership::*;
+pub use presence::*;
+pub use pseudonym::*;
 pub use range::*;
 pub use revocation::*;
 pub use signature::*;
@@ -42,6 +48,9 @@ pub(crate) enum ProofVerifiers<'a, 'b, 'c, S: ShortGroupSignatureScheme> {
     Range(Box<RangeProofVerifier<'a, 'b, 'c>>),
     Membership(Box<MembershipVerifier<'a, 'b>>),
     VerifiableEncryptionDecryption(Box<VerifiableEncryptionDecryptionVerifier<'a, 'b>>),
+    Presence(Box<PresenceVerifier<'a, 'b>>),
+    BlindClaims(Box<BlindClaimsVerifier<'a, 'b, S>>),
+    Pseudonym(Box<PseudonymVerifier<'a, 'b>>),
 }
 
 impl<'a, 'b, S: ShortGroupSignatureScheme> From<SignatureVerifier<'a, 'b, S>>
@@ -108,6 +117,30 @@ impl<'a, 'b, S: ShortGroupSignatureScheme> From<VerifiableEncryptionDecryptionVe
     }
 }
 
+impl<'a, 'b, S: ShortGroupSignatureScheme> From<PresenceVerifier<'a, 'b>>
+    for ProofVerifiers<'a, 'b, '_, S>
+{
+    fn from(a: PresenceVerifier<'a, 'b>) -> Self {
+        Self::Presence(Box::new(a))
+    }
+}
+
+impl<'a, 'b, S: ShortGroupSignatureScheme> From<PseudonymVerifier<'a, 'b>>
+    for ProofVerifiers<'a, 'b, '_, S>
+{
+    fn from(a: PseudonymVerifier<'a, 'b>) -> Self {
+        Self::Pseudonym(Box::new(a))
+    }
+}
+
+impl<'a, 'b, S: ShortGroupSignatureScheme> From<BlindClaimsVerifier<'a, 'b, S>>
+    for ProofVerifiers<'a, 'b, '_, S>
+{
+    fn from(a: BlindClaimsVerifier<'a, 'b, S>) -> Self {
+        Self::BlindClaims(Box::new(a))
+    }
+}
+
 impl<S: ShortGroupSignatureScheme> ProofVerifiers<'_, '_, '_, S> {
     /// Verify any additional proof material
     pub fn verify(&self, challenge: Scalar) -> CredxResult<()> {
@@ -120,6 +153,9 @@ impl<S: ShortGroupSignatureScheme> ProofVerifiers<'_, '_, '_, S> {
             Self::Range(r) => r.verify(challenge),
             Self::Membership(m) => m.verify(challenge),
             Self::VerifiableEncryptionDecryption(v) => v.verify(challenge),
+            Self::Presence(p) => p.verify(challenge),
+            Self::BlindClaims(b) => b.verify(challenge),
+            Self::Pseudonym(p) => p.verify(challenge),
         }
     }
 }
@@ -0,0 +1,71 @@
+use crate::error::Error;
+use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use crate::presentation::BlindClaimsProof;
+use crate::statement::BlindClaimsStatement;
+use crate::verifier::ProofVerifier;
+use crate::CredxResult;
+use blsful::inner_types::{G1Projective, Scalar};
+use elliptic_curve::group::Curve;
+use merlin::Transcript;
+
+pub struct BlindClaimsVerifier<'a, 'b, S: ShortGroupSignatureScheme> {
+    pub statement: &'a BlindClaimsStatement<S>,
+    pub proof: &'b BlindClaimsProof,
+}
+
+impl<S: ShortGroupSignatureScheme> ProofVerifier for BlindClaimsVerifier<'_, '_, S> {
+    fn add_challenge_contribution(
+        &self,
+        challenge: Scalar,
+        transcript: &mut Transcript,
+    ) -> CredxResult<()> {
+        let indices = self.statement.claim_indices()?;
+        if indices.len() != self.proof.message_proofs.len()
+            || indices
+                .iter()
+                .any(|i| !self.proof.message_proofs.contains_key(i))
+        {
+            return Err(Error::InvalidPresentationData(format!(
+                "blind claims proof '{}' doesn't match the blinded claims in the statement",
+                self.statement.id
+            )));
+        }
+        let (generators, blinder_generator) =
+            S::blind_message_generators(&self.statement.issuer.verifying_key, &indices)?;
+        let mut blind_commitment = self.proof.commitment * -challenge;
+        for (index, g) in indices.iter().zip(&generators) {
+            blind_commitment += g * self.proof.message_proofs[index];
+        }
+        match (blinder_generator, self.proof.blinder_proof) {
+            (Some(h), Some(blinder_proof)) => blind_commitment += h * blinder_proof,
+            (None, None) => {}
+            (_, _) => {
+                return Err(Error::InvalidPresentationData(format!(
+                    "blind claims proof '{}' has an unexpected blinder proof",
+                    self.statement.id
+                )))
+            }
+        }
+
+        transcript.append_message(b"", self.statement.id.as_bytes());
+        transcript.append_message(
+            b"commitment",
+            self.proof.commitment.to_affine().to_compressed().as_slice(),
+        );
+        transcript.append_message(
+            b"blind commitment",
+            blind_commitment.to_affine().to_compressed().as_slice(),
+        );
+        Ok(())
+    }
+
+    fn verify(&self, _challenge: Scalar) -> CredxResult<()> {
+        if self.proof.commitment == G1Projective::IDENTITY {
+            return Err(Error::InvalidPresentationData(format!(
+                "blind claims proof '{}' has an invalid commitment",
+                self.statement.id
+            )));
+        }
+        Ok(())
+    }
+}
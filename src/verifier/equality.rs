@@ -53,7 +53,7 @@ impl<S: ShortGroupSignatureScheme> ProofVerifier for EqualityVerifier<'_, '_, '_
                             let hidden_messages = s
                                 .pok
                                 .get_hidden_message_proofs(
-                                    &sig_st.issuer.verifying_key,
+                                    sig_st.issuer.verifying_key_for(s.key_version)?,
                                     disclosed_messages.as_slice(),
                                 )?;
                             let hidden_message = hidden_messages
@@ -64,6 +64,13 @@ impl<S: ShortGroupSignatureScheme> ProofVerifier for EqualityVerifier<'_, '_, '_
                         _ => return Err(Error::InvalidPresentationData(format!("tried to use a non-signature statement reference in an equality proof: equality_verifier: {:?}", self))),
                     };
                 }
+                PresentationProofs::BlindClaims(b) => {
+                    let message_proof = b
+                        .message_proofs
+                        .get(claim_idx)
+                        .ok_or(Error::InvalidPresentationData(format!("the referenced claim_idx '{}' from in the equality proof statement '{}' is not blinded: equality_verifier: {:?}", claim_idx, id, self)))?;
+                    messages.push(*message_proof);
+                }
                 _ => return Err(Error::InvalidPresentationData(format!("tried to use a non-signature proof in an equality proof using reference statement id '{}': equality_verifier: {:?}", id, self))),
             }
         }
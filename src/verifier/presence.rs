@@ -0,0 +1,66 @@
+use crate::claim::AbsentClaim;
+use crate::error::Error;
+use crate::presentation::PresenceProof;
+use crate::statement::PresenceStatement;
+use crate::verifier::ProofVerifier;
+use crate::CredxResult;
+use blsful::inner_types::{G1Projective, Scalar};
+use elliptic_curve::group::Curve;
+use merlin::Transcript;
+
+pub struct PresenceVerifier<'a, 'b> {
+    pub statement: &'a PresenceStatement<G1Projective>,
+    pub proof: &'b PresenceProof,
+    pub message_proof: Scalar,
+}
+
+impl ProofVerifier for PresenceVerifier<'_, '_> {
+    fn add_challenge_contribution(
+        &self,
+        challenge: Scalar,
+        transcript: &mut Transcript,
+    ) -> CredxResult<()> {
+        let blind_commitment = self.proof.commitment * -challenge
+            + self.statement.message_generator * self.message_proof
+            + self.statement.blinder_generator * self.proof.blinder_proof;
+        let offset_commitment =
+            self.proof.commitment - self.statement.message_generator * AbsentClaim::scalar();
+
+        let blind_relation = match (self.statement.present, self.proof.inverse_proof) {
+            (true, Some((inverse_proof, inverse_blinder_proof))) => {
+                self.statement.message_generator * -challenge
+                    + offset_commitment * inverse_proof
+                    + self.statement.blinder_generator * inverse_blinder_proof
+            }
+            (false, None) => {
+                offset_commitment * -challenge
+                    + self.statement.blinder_generator * self.proof.blinder_proof
+            }
+            (_, _) => {
+                return Err(Error::InvalidPresentationData(format!(
+                    "presence proof '{}' does not match the statement",
+                    self.statement.id
+                )))
+            }
+        };
+
+        transcript.append_message(b"", self.statement.id.as_bytes());
+        transcript.append_message(
+            b"commitment",
+            self.proof.commitment.to_affine().to_compressed().as_slice(),
+        );
+        transcript.append_message(
+            b"blind commitment",
+            blind_commitment.to_affine().to_compressed().as_slice(),
+        );
+        transcript.append_message(
+            b"blind presence relation",
+            blind_relation.to_affine().to_compressed().as_slice(),
+        );
+        Ok(())
+    }
+
+    fn verify(&self, _challenge: Scalar) -> CredxResult<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use crate::error::Error;
+use crate::presentation::PseudonymProof;
+use crate::statement::PseudonymStatement;
+use crate::verifier::ProofVerifier;
+use crate::CredxResult;
+use blsful::inner_types::{G1Projective, Scalar};
+use elliptic_curve::group::{Curve, Group};
+use merlin::Transcript;
+
+/// Verifies a pseudonym proven by a credx presentation. Pseudonym proofs
+/// from other implementations of the draft aren't accepted.
+pub struct PseudonymVerifier<'a, 'b> {
+    pub statement: &'a PseudonymStatement,
+    pub proof: &'b PseudonymProof,
+    pub message_proof: Scalar,
+}
+
+impl ProofVerifier for PseudonymVerifier<'_, '_> {
+    fn add_challenge_contribution(
+        &self,
+        challenge: Scalar,
+        transcript: &mut Transcript,
+    ) -> CredxResult<()> {
+        if self.proof.pseudonym.is_identity().into() {
+            return Err(Error::InvalidPresentationData(format!(
+                "pseudonym proof '{}' has an invalid pseudonym",
+                self.statement.id
+            )));
+        }
+        let blind_pseudonym: G1Projective = self.proof.pseudonym * -challenge
+            + self.statement.pseudonym_base() * self.message_proof;
+
+        transcript.append_message(b"", self.statement.id.as_bytes());
+        transcript.append_message(
+            b"pseudonym",
+            self.proof.pseudonym.to_affine().to_compressed().as_slice(),
+        );
+        transcript.append_message(
+            b"blind pseudonym",
+            blind_pseudonym.to_affine().to_compressed().as_slice(),
+        );
+        Ok(())
+    }
+
+    fn verify(&self, _challenge: Scalar) -> CredxResult<()> {
+        Ok(())
+    }
+}
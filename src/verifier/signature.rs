@@ -39,7 +39,9 @@ impl<S: ShortGroupSignatureScheme> ProofVerifier for SignatureVerifier<'_, '_, S
         transcript: &mut Transcript,
     ) -> CredxResult<()> {
         self.signature_proof.pok.add_proof_contribution(
-            &self.statement.issuer.verifying_key,
+            self.statement
+                .issuer
+                .verifying_key_for(self.signature_proof.key_version)?,
             &self.disclosed_messages,
             challenge,
             transcript,
@@ -49,7 +51,9 @@ impl<S: ShortGroupSignatureScheme> ProofVerifier for SignatureVerifier<'_, '_, S
 
     fn verify(&self, challenge: Scalar) -> CredxResult<()> {
         self.signature_proof.pok.verify(
-            &self.statement.issuer.verifying_key,
+            self.statement
+                .issuer
+                .verifying_key_for(self.signature_proof.key_version)?,
             &self.disclosed_messages,
             challenge,
         )
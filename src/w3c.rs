@@ -0,0 +1,228 @@
+mod credential;
+mod data_integrity;
+mod error;
+mod presentation;
+
+pub use credential::*;
+pub use data_integrity::*;
+pub use error::*;
+pub use presentation::*;
+
+use crate::claim::{AbsentClaim, ClaimData, ClaimType, HashedClaim, NumberClaim, RevocationClaim};
+use crate::credential::{ClaimSchema, CredentialSchema};
+use crate::error::Error;
+use crate::issuer::{IssuerKeyId, IssuerPublic};
+use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use crate::CredxResult;
+use base64::Engine;
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use serde_json::{json, Map, Value};
+
+/// The VC Data Model 2.0 base context
+pub const VC_CONTEXT_V2: &str = "https://www.w3.org/ns/credentials/v2";
+/// The `type` of every verifiable credential
+pub const VERIFIABLE_CREDENTIAL_TYPE: &str = "VerifiableCredential";
+/// The `type` of every verifiable presentation
+pub const VERIFIABLE_PRESENTATION_TYPE: &str = "VerifiablePresentation";
+
+/// The multibase prefix for base64url without padding
+const MULTIBASE_BASE64URL: &str = "u";
+
+/// The URN namespace for credx ids that aren't URIs
+const URN_PREFIX: &str = "urn:credx:";
+
+/// Whether `id` already has a URI scheme and only URI characters
+fn is_uri(id: &str) -> bool {
+    let Some((scheme, rest)) = id.split_once(':') else {
+        return false;
+    };
+    let mut scheme_chars = scheme.chars();
+    scheme_chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && scheme_chars.all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        && !rest.is_empty()
+        && rest
+            .chars()
+            .all(|c| c.is_ascii_graphic() && !"\"<>\\^`{|}".contains(c))
+}
+
+/// Issuer ids that are URIs are used as they are, other issuers are
+/// identified by their key id
+fn encode_issuer_identifier<S: ShortGroupSignatureScheme>(issuer: &IssuerPublic<S>) -> String {
+    if is_uri(&issuer.id) {
+        issuer.id.clone()
+    } else {
+        format!("{}issuer:{}", URN_PREFIX, hex::encode(issuer.key_id()))
+    }
+}
+
+/// Presentation schema ids that are URIs are used as they are, other ids
+/// are percent encoded in a URN
+fn encode_schema_identifier(id: &str) -> String {
+    if is_uri(id) {
+        return id.to_string();
+    }
+    let mut encoded = format!("{}presentation-schema:", URN_PREFIX);
+    for b in id.bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~".contains(&b) {
+            encoded.push(b as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", b));
+        }
+    }
+    encoded
+}
+
+/// VC Data Model 1.1 documents may give the issuer as an object with an `id`
+fn deserialize_issuer<'de, D: Deserializer<'de>>(d: D) -> Result<String, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Issuer {
+        Id(String),
+        Object { id: String },
+    }
+    match Issuer::deserialize(d)? {
+        Issuer::Id(id) | Issuer::Object { id } => Ok(id),
+    }
+}
+
+/// The verification method of an issuer is its identifier with the key id as the fragment
+fn encode_verification_method<S: ShortGroupSignatureScheme>(issuer: &IssuerPublic<S>) -> String {
+    format!(
+        "{}#{}",
+        encode_issuer_identifier(issuer),
+        hex::encode(issuer.key_id())
+    )
+}
+
+fn decode_verification_method(verification_method: &str) -> CredxResult<(&str, IssuerKeyId)> {
+    let invalid = || W3cError::InvalidIdentifier(verification_method.to_string());
+    let (issuer, key_id) = verification_method.rsplit_once('#').ok_or_else(invalid)?;
+    let key_id = hex::decode(key_id)
+        .ok()
+        .and_then(|k| IssuerKeyId::try_from(k).ok())
+        .ok_or_else(invalid)?;
+    Ok((issuer, key_id))
+}
+
+fn encode_proof_value<T: Serialize>(value: &T) -> CredxResult<String> {
+    let bytes = serde_bare::to_vec(value)
+        .map_err(|_| Error::General("unable to serialize the proof value"))?;
+    Ok(format!(
+        "{}{}",
+        MULTIBASE_BASE64URL,
+        base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(bytes)
+    ))
+}
+
+fn decode_proof_value<T: DeserializeOwned>(proof_value: &str) -> CredxResult<T> {
+    let encoded = proof_value
+        .strip_prefix(MULTIBASE_BASE64URL)
+        .ok_or(W3cError::InvalidProofValue)?;
+    let bytes = base64::prelude::BASE64_URL_SAFE_NO_PAD
+        .decode(encoded)
+        .map_err(|_| W3cError::InvalidProofValue)?;
+    Ok(serde_bare::from_slice(&bytes).map_err(|_| W3cError::InvalidProofValue)?)
+}
+
+fn check_types(types: &[String], expected: &'static str) -> CredxResult<()> {
+    if types.iter().any(|t| t == expected) {
+        Ok(())
+    } else {
+        Err(W3cError::MissingType(expected).into())
+    }
+}
+
+/// Claims that JSON can represent are written as JSON values, absent
+/// claims as `null` and the rest as a JSON-LD value object holding
+/// the claim's text form.
+fn claim_to_json(claim: &ClaimData) -> Value {
+    match claim {
+        ClaimData::Hashed(HashedClaim {
+            value,
+            print_friendly: true,
+        }) if std::str::from_utf8(value).is_ok() => {
+            Value::String(String::from_utf8(value.clone()).unwrap())
+        }
+        ClaimData::Number(NumberClaim { value }) => Value::from(*value as i64),
+        ClaimData::Revocation(RevocationClaim { value }) => Value::String(value.clone()),
+        ClaimData::Absent(_) => Value::Null,
+        _ => json!({ "@value": claim.to_text() }),
+    }
+}
+
+fn claim_from_json(claim_schema: &ClaimSchema, value: &Value) -> CredxResult<ClaimData> {
+    let invalid = || W3cError::InvalidClaim(claim_schema.label.clone());
+    let claim = match (claim_schema.claim_type, value) {
+        (_, Value::Null) => AbsentClaim.into(),
+        (ClaimType::Hashed, Value::String(s)) => HashedClaim::from(s.as_str()).into(),
+        (ClaimType::Revocation, Value::String(s)) => RevocationClaim::from(s.as_str()).into(),
+        (ClaimType::Number, Value::Number(n)) => {
+            NumberClaim::from(n.as_i64().ok_or_else(invalid)? as isize).into()
+        }
+        (_, Value::Object(o)) if o.len() == 1 => {
+            let text = o
+                .get("@value")
+                .and_then(Value::as_str)
+                .filter(|t| t.get(0..4).is_some())
+                .ok_or_else(invalid)?;
+            let claim = ClaimData::from_text(text).map_err(|_| invalid())?;
+            if !claim.is_type(claim_schema.claim_type) {
+                return Err(invalid().into());
+            }
+            claim
+        }
+        (_, _) => return Err(invalid().into()),
+    };
+    Ok(claim)
+}
+
+/// The `credentialSubject` for claims labeled by the schema
+fn claims_to_subject<'a>(
+    claims: impl Iterator<Item = (&'a String, &'a ClaimData)>,
+) -> Map<String, Value> {
+    claims
+        .map(|(label, claim)| (label.clone(), claim_to_json(claim)))
+        .collect()
+}
+
+fn context() -> Vec<Value> {
+    vec![Value::String(VC_CONTEXT_V2.to_string())]
+}
+
+fn check_context(context: &[Value]) -> CredxResult<()> {
+    if context.first().and_then(Value::as_str) == Some(VC_CONTEXT_V2) {
+        Ok(())
+    } else {
+        Err(W3cError::InvalidContext.into())
+    }
+}
+
+/// The `validFrom` and `validUntil` datetimes of the schema's validity
+/// claims that are known
+fn validity_period<'a>(
+    schema: &CredentialSchema,
+    claim: impl Fn(&str) -> Option<&'a ClaimData>,
+) -> (Option<String>, Option<String>) {
+    let datetime = |label: &Option<String>| match label.as_deref().and_then(&claim) {
+        Some(ClaimData::Number(n)) => chrono::DateTime::from_timestamp(n.value as i64, 0)
+            .map(|dt| dt.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)),
+        _ => None,
+    };
+    (datetime(&schema.valid_from), datetime(&schema.valid_until))
+}
+
+/// The validity period isn't signed directly but must match the signed
+/// validity claims
+fn check_validity_period<'a>(
+    valid_from: &Option<String>,
+    valid_until: &Option<String>,
+    schema: &CredentialSchema,
+    claim: impl Fn(&str) -> Option<&'a ClaimData>,
+) -> CredxResult<()> {
+    let (from, until) = validity_period(schema, claim);
+    if *valid_from == from && *valid_until == until {
+        Ok(())
+    } else {
+        Err(W3cError::ValidityPeriodMismatch.into())
+    }
+}
@@ -0,0 +1,164 @@
+use super::*;
+use crate::credential::{Credential, CredentialBundle, MembershipCredential};
+use crate::issuer::IssuerKeyRegistry;
+use crate::knox::accumulator::vb20::Accumulator;
+
+/// A credx credential in the W3C Verifiable Credentials Data Model 2.0.
+///
+/// The `credentialSubject` holds the claims by their schema labels and
+/// the proof holds the signature and revocation handle. The proof's
+/// verification method is the issuer's key id so the issuer can be
+/// resolved with an [`IssuerKeyRegistry`].
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct W3cCredential {
+    /// The JSON-LD contexts
+    #[serde(rename = "@context")]
+    pub context: Vec<Value>,
+    /// The credential types
+    #[serde(rename = "type")]
+    pub types: Vec<String>,
+    /// The issuer id
+    #[serde(deserialize_with = "deserialize_issuer")]
+    pub issuer: String,
+    /// When the credential becomes valid, `issuanceDate` in
+    /// VC Data Model 1.1 documents
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        alias = "issuanceDate"
+    )]
+    pub valid_from: Option<String>,
+    /// When the credential expires
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub valid_until: Option<String>,
+    /// The claims by label
+    pub credential_subject: Map<String, Value>,
+    /// The issuer's signature
+    pub proof: W3cProof,
+}
+
+/// The credx data in a [`W3cCredential`] proof value
+#[derive(Deserialize, Serialize)]
+struct CredentialProofValue<S: ShortGroupSignatureScheme> {
+    signature: S::Signature,
+    revocation_handle: MembershipCredential,
+    revocation_index: usize,
+    revocation_registry: Accumulator,
+}
+
+impl<S: ShortGroupSignatureScheme> TryFrom<&CredentialBundle<S>> for W3cCredential {
+    type Error = Error;
+
+    fn try_from(bundle: &CredentialBundle<S>) -> CredxResult<Self> {
+        let schema = &bundle.issuer.schema;
+        if schema.claims.len() != bundle.credential.claims.len() {
+            return Err(Error::InvalidClaimData(
+                "claims don't match the credential schema",
+            ));
+        }
+        let (valid_from, valid_until) = validity_period(schema, |label| {
+            schema
+                .get_claim_index(label)
+                .map(|i| &bundle.credential.claims[i])
+        });
+        let proof_value = encode_proof_value(&CredentialProofValue::<S> {
+            signature: bundle.credential.signature.clone(),
+            revocation_handle: bundle.credential.revocation_handle,
+            revocation_index: bundle.credential.revocation_index,
+            revocation_registry: bundle.issuer.revocation_registry,
+        })?;
+        Ok(Self {
+            context: context(),
+            types: vec![VERIFIABLE_CREDENTIAL_TYPE.to_string()],
+            issuer: encode_issuer_identifier(&bundle.issuer),
+            valid_from,
+            valid_until,
+            credential_subject: claims_to_subject(
+                schema
+                    .claims
+                    .iter()
+                    .map(|c| &c.label)
+                    .zip(&bundle.credential.claims),
+            ),
+            proof: W3cProof::new(
+                ASSERTION_METHOD,
+                Some(encode_verification_method(&bundle.issuer)),
+                None,
+                proof_value,
+            ),
+        })
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> TryFrom<CredentialBundle<S>> for W3cCredential {
+    type Error = Error;
+
+    fn try_from(bundle: CredentialBundle<S>) -> CredxResult<Self> {
+        Self::try_from(&bundle)
+    }
+}
+
+impl W3cCredential {
+    /// Get the credential bundle with the issuer from `registry`.
+    ///
+    /// The claims are read using the issuer's schema. Use
+    /// [`W3cCredential::verify`] to check the proof as well.
+    pub fn to_bundle<S: ShortGroupSignatureScheme>(
+        &self,
+        registry: &IssuerKeyRegistry<S>,
+    ) -> CredxResult<CredentialBundle<S>> {
+        check_types(&self.types, VERIFIABLE_CREDENTIAL_TYPE)?;
+        let verification_method = self
+            .proof
+            .verification_method
+            .as_deref()
+            .ok_or_else(|| W3cError::InvalidIdentifier(String::new()))?;
+        let (issuer_id, key_id) = decode_verification_method(verification_method)?;
+        if issuer_id != self.issuer {
+            return Err(W3cError::IssuerMismatch(self.issuer.clone()).into());
+        }
+        let mut issuer = registry.get(&key_id)?.clone();
+        if encode_issuer_identifier(&issuer) != self.issuer {
+            return Err(W3cError::IssuerMismatch(self.issuer.clone()).into());
+        }
+
+        if let Some(label) = self
+            .credential_subject
+            .keys()
+            .find(|label| !issuer.schema.claim_indices.contains(*label))
+        {
+            return Err(W3cError::UnknownClaim(label.clone()).into());
+        }
+        let claims = issuer
+            .schema
+            .claims
+            .iter()
+            .map(|claim_schema| {
+                let value = self
+                    .credential_subject
+                    .get(&claim_schema.label)
+                    .ok_or_else(|| W3cError::MissingClaim(claim_schema.label.clone()))?;
+                claim_from_json(claim_schema, value)
+            })
+            .collect::<CredxResult<Vec<_>>>()?;
+        check_validity_period(
+            &self.valid_from,
+            &self.valid_until,
+            &issuer.schema,
+            |label| issuer.schema.get_claim_index(label).map(|i| &claims[i]),
+        )?;
+
+        let proof_value: CredentialProofValue<S> = decode_proof_value(&self.proof.proof_value)?;
+        issuer.revocation_registry = proof_value.revocation_registry;
+        Ok(CredentialBundle {
+            issuer,
+            credential: Credential {
+                claims,
+                signature: proof_value.signature,
+                revocation_handle: proof_value.revocation_handle,
+                revocation_index: proof_value.revocation_index,
+            },
+        })
+    }
+}
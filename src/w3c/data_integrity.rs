@@ -0,0 +1,119 @@
+use super::*;
+use crate::credential::CredentialBundle;
+use crate::issuer::IssuerKeyRegistry;
+use crate::presentation::{Presentation, PresentationSchema};
+
+/// The `type` of the proofs created by credx
+pub const DATA_INTEGRITY_PROOF_TYPE: &str = "DataIntegrityProof";
+/// The Data Integrity cryptosuite for credx signatures and presentations
+pub const CREDX_CRYPTOSUITE: &str = "credx-2026";
+/// The proof purpose of an issuer's signature
+pub const ASSERTION_METHOD: &str = "assertionMethod";
+/// The proof purpose of a holder's presentation
+pub const AUTHENTICATION: &str = "authentication";
+
+/// A W3C Data Integrity proof using the [`CREDX_CRYPTOSUITE`].
+///
+/// The cryptosuite reads the `credentialSubject` into [`ClaimData`]
+/// using the issuer's schema. The proof value is the credx signature or
+/// presentation proofs for those claims.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct W3cProof {
+    /// The proof type
+    #[serde(rename = "type")]
+    pub proof_type: String,
+    /// The cryptosuite that created the proof, empty if the proof type
+    /// doesn't have one
+    #[serde(default)]
+    pub cryptosuite: String,
+    /// Why the proof was created
+    #[serde(default)]
+    pub proof_purpose: String,
+    /// The issuer key that verifies the proof
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub verification_method: Option<String>,
+    /// The hex encoded presentation nonce
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub challenge: Option<String>,
+    /// The multibase encoded credx proof
+    pub proof_value: String,
+}
+
+impl W3cProof {
+    pub(crate) fn new(
+        proof_purpose: &str,
+        verification_method: Option<String>,
+        challenge: Option<String>,
+        proof_value: String,
+    ) -> Self {
+        Self {
+            proof_type: DATA_INTEGRITY_PROOF_TYPE.to_string(),
+            cryptosuite: CREDX_CRYPTOSUITE.to_string(),
+            proof_purpose: proof_purpose.to_string(),
+            verification_method,
+            challenge,
+            proof_value,
+        }
+    }
+
+    /// Check this is a credx proof created for `proof_purpose`
+    fn check(&self, proof_purpose: &str) -> CredxResult<()> {
+        if self.proof_type != DATA_INTEGRITY_PROOF_TYPE || self.cryptosuite != CREDX_CRYPTOSUITE {
+            return Err(W3cError::UnsupportedProof(format!(
+                "{} {}",
+                self.proof_type, self.cryptosuite
+            ))
+            .into());
+        }
+        if self.proof_purpose != proof_purpose {
+            return Err(W3cError::InvalidProofPurpose(self.proof_purpose.clone()).into());
+        }
+        Ok(())
+    }
+}
+
+impl W3cCredential {
+    /// Verify the credential's Data Integrity proof and return the
+    /// credential bundle.
+    ///
+    /// The verification method must name an issuer key in `registry` and
+    /// the claims must verify against the issuer's signature and
+    /// revocation registry.
+    pub fn verify<S: ShortGroupSignatureScheme>(
+        &self,
+        registry: &IssuerKeyRegistry<S>,
+    ) -> CredxResult<CredentialBundle<S>> {
+        check_context(&self.context)?;
+        self.proof.check(ASSERTION_METHOD)?;
+        let bundle = self.to_bundle(registry)?;
+        bundle.verify()?;
+        Ok(bundle)
+    }
+}
+
+impl W3cPresentation {
+    /// Verify the presentation's Data Integrity proofs for `schema` and
+    /// `nonce` and return the presentation.
+    ///
+    /// The challenge must be the nonce and each credential's verification
+    /// method must be the key of the issuer in its signature statement.
+    pub fn verify<S: ShortGroupSignatureScheme>(
+        &self,
+        schema: &PresentationSchema<S>,
+        nonce: &[u8],
+    ) -> CredxResult<Presentation<S>> {
+        check_context(&self.context)?;
+        self.proof.check(AUTHENTICATION)?;
+        if self.proof.challenge.as_deref() != Some(hex::encode(nonce).as_str()) {
+            return Err(W3cError::ChallengeMismatch.into());
+        }
+        for credential in &self.verifiable_credential {
+            check_context(&credential.context)?;
+            credential.proof.check(ASSERTION_METHOD)?;
+        }
+        let presentation = self.to_presentation(schema)?;
+        presentation.verify(schema, nonce)?;
+        Ok(presentation)
+    }
+}
@@ -0,0 +1,40 @@
+use crate::error::Error;
+
+/// Reasons W3C credentials and presentations can't be mapped to credx
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum W3cError {
+    /// The first `@context` isn't the VC Data Model 2.0 context
+    InvalidContext,
+    /// The `type` doesn't include the expected type
+    MissingType(&'static str),
+    /// An issuer, schema or verification method id isn't a credx id
+    InvalidIdentifier(String),
+    /// The proof type and cryptosuite aren't the credx Data Integrity cryptosuite
+    UnsupportedProof(String),
+    /// The proof was created for a different purpose
+    InvalidProofPurpose(String),
+    /// The presentation proof challenge isn't the nonce
+    ChallengeMismatch,
+    /// The proof value isn't a multibase encoded credx proof
+    InvalidProofValue,
+    /// The verification method names a different issuer than the credential
+    IssuerMismatch(String),
+    /// The presentation was created for a different presentation schema
+    SchemaMismatch(String),
+    /// The validity period doesn't match the signed validity claims
+    ValidityPeriodMismatch,
+    /// The `credentialSubject` is missing the claim with this label
+    MissingClaim(String),
+    /// The `credentialSubject` has a claim with this label that isn't in the schema
+    UnknownClaim(String),
+    /// The claim with this label doesn't match the type in the schema
+    InvalidClaim(String),
+    /// No credential in the presentation has the signature proof for this statement
+    MissingCredential(String),
+}
+
+impl From<W3cError> for Error {
+    fn from(e: W3cError) -> Self {
+        Self::InvalidW3cData(e)
+    }
+}
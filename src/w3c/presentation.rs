@@ -0,0 +1,245 @@
+use super::*;
+use crate::presentation::{Presentation, PresentationProofs, PresentationSchema};
+use crate::statement::Statements;
+use crate::utils::*;
+use blsful::inner_types::Scalar;
+use indexmap::IndexMap;
+use std::collections::BTreeSet;
+
+/// A credx presentation in the W3C Verifiable Credentials Data Model 2.0.
+///
+/// Each signature statement becomes a [`W3cCredential`] with the disclosed
+/// claims and the proofs of the statements that only refer to that
+/// signature. The presentation's proof holds the challenge and the proofs
+/// of statements that refer to several signatures or none.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct W3cPresentation {
+    /// The JSON-LD contexts
+    #[serde(rename = "@context")]
+    pub context: Vec<Value>,
+    /// The presentation types
+    #[serde(rename = "type")]
+    pub types: Vec<String>,
+    /// The presentation schema id, `schema` in earlier credx documents
+    #[serde(alias = "schema")]
+    pub presentation_schema: String,
+    /// The credentials for each signature statement
+    pub verifiable_credential: Vec<W3cCredential>,
+    /// The holder's proof
+    pub proof: W3cProof,
+}
+
+/// The credx data in a [`W3cPresentation`] proof value
+#[derive(Deserialize, Serialize)]
+struct PresentationProofValue<S: ShortGroupSignatureScheme> {
+    challenge: Scalar,
+    #[serde(
+        serialize_with = "serialize_indexmap",
+        deserialize_with = "deserialize_indexmap",
+        bound(serialize = "PresentationProofs<S>: Serialize"),
+        bound(deserialize = "PresentationProofs<S>: Deserialize<'de>")
+    )]
+    proofs: IndexMap<String, PresentationProofs<S>>,
+}
+
+/// The credx data in the proof value of a credential in a [`W3cPresentation`]
+#[derive(Deserialize, Serialize)]
+struct PresentedCredentialProofValue<S: ShortGroupSignatureScheme> {
+    #[serde(
+        serialize_with = "serialize_indexmap",
+        deserialize_with = "deserialize_indexmap",
+        bound(serialize = "PresentationProofs<S>: Serialize"),
+        bound(deserialize = "PresentationProofs<S>: Deserialize<'de>")
+    )]
+    proofs: IndexMap<String, PresentationProofs<S>>,
+}
+
+impl W3cPresentation {
+    /// Map `presentation` created for `schema` and `nonce`
+    pub fn new<S: ShortGroupSignatureScheme>(
+        presentation: &Presentation<S>,
+        schema: &PresentationSchema<S>,
+        nonce: &[u8],
+    ) -> CredxResult<Self> {
+        let mut credential_proofs = IndexMap::new();
+        let mut proofs = IndexMap::new();
+        for (id, statement) in &schema.statements {
+            if let Statements::Signature(_) = statement {
+                credential_proofs.insert(id.clone(), IndexMap::new());
+            }
+        }
+        for id in schema.statements.keys() {
+            let proof = match presentation.proofs.get(id) {
+                Some(proof) => proof.clone(),
+                None => continue,
+            };
+            match referenced_signature(schema, id) {
+                Some(sig_id) => credential_proofs[&sig_id].insert(id.clone(), proof),
+                None => proofs.insert(id.clone(), proof),
+            };
+        }
+        for (id, proof) in &presentation.proofs {
+            if !schema.statements.contains_key(id) {
+                proofs.insert(id.clone(), proof.clone());
+            }
+        }
+
+        let mut verifiable_credential = Vec::with_capacity(credential_proofs.len());
+        for (id, proofs) in credential_proofs {
+            let issuer = match &schema.statements[&id] {
+                Statements::Signature(ss) => &ss.issuer,
+                _ => unreachable!(),
+            };
+            let disclosed = presentation
+                .disclosed_messages
+                .get(&id)
+                .ok_or_else(|| W3cError::MissingCredential(id.clone()))?;
+            let (valid_from, valid_until) =
+                validity_period(&issuer.schema, |label| disclosed.get(label));
+            verifiable_credential.push(W3cCredential {
+                context: context(),
+                types: vec![VERIFIABLE_CREDENTIAL_TYPE.to_string()],
+                issuer: encode_issuer_identifier(issuer),
+                valid_from,
+                valid_until,
+                credential_subject: claims_to_subject(disclosed.iter()),
+                proof: W3cProof::new(
+                    ASSERTION_METHOD,
+                    Some(encode_verification_method(issuer)),
+                    None,
+                    encode_proof_value(&PresentedCredentialProofValue { proofs })?,
+                ),
+            });
+        }
+
+        Ok(Self {
+            context: context(),
+            types: vec![VERIFIABLE_PRESENTATION_TYPE.to_string()],
+            presentation_schema: encode_schema_identifier(&schema.id),
+            verifiable_credential,
+            proof: W3cProof::new(
+                AUTHENTICATION,
+                None,
+                Some(hex::encode(nonce)),
+                encode_proof_value(&PresentationProofValue {
+                    challenge: presentation.challenge,
+                    proofs,
+                })?,
+            ),
+        })
+    }
+
+    /// Get the presentation created for `schema`.
+    ///
+    /// The disclosed claims are read using the issuer schemas in the
+    /// signature statements and the proofs are in statement order.
+    /// Use [`W3cPresentation::verify`] to check the proofs as well.
+    pub fn to_presentation<S: ShortGroupSignatureScheme>(
+        &self,
+        schema: &PresentationSchema<S>,
+    ) -> CredxResult<Presentation<S>> {
+        check_types(&self.types, VERIFIABLE_PRESENTATION_TYPE)?;
+        if self.presentation_schema != encode_schema_identifier(&schema.id) {
+            return Err(W3cError::SchemaMismatch(self.presentation_schema.clone()).into());
+        }
+        let PresentationProofValue {
+            challenge,
+            mut proofs,
+        } = decode_proof_value(&self.proof.proof_value)?;
+
+        let mut disclosed_messages = IndexMap::new();
+        for credential in &self.verifiable_credential {
+            check_types(&credential.types, VERIFIABLE_CREDENTIAL_TYPE)?;
+            let credential_proofs: PresentedCredentialProofValue<S> =
+                decode_proof_value(&credential.proof.proof_value)?;
+            let id = credential_proofs
+                .proofs
+                .iter()
+                .find(|(_, p)| matches!(p, PresentationProofs::Signature(_)))
+                .map(|(id, _)| id.clone())
+                .ok_or(W3cError::InvalidProofValue)?;
+            let issuer = match schema.statements.get(&id) {
+                Some(Statements::Signature(ss)) => &ss.issuer,
+                _ => return Err(W3cError::MissingCredential(id).into()),
+            };
+            if credential.issuer != encode_issuer_identifier(issuer)
+                || credential.proof.verification_method.as_deref()
+                    != Some(encode_verification_method(issuer).as_str())
+            {
+                return Err(W3cError::IssuerMismatch(credential.issuer.clone()).into());
+            }
+            if let Some(label) = credential
+                .credential_subject
+                .keys()
+                .find(|label| !issuer.schema.claim_indices.contains(*label))
+            {
+                return Err(W3cError::UnknownClaim(label.clone()).into());
+            }
+            let mut disclosed = IndexMap::new();
+            for claim_schema in &issuer.schema.claims {
+                if let Some(value) = credential.credential_subject.get(&claim_schema.label) {
+                    disclosed.insert(
+                        claim_schema.label.clone(),
+                        claim_from_json(claim_schema, value)?,
+                    );
+                }
+            }
+            check_validity_period(
+                &credential.valid_from,
+                &credential.valid_until,
+                &issuer.schema,
+                |label| disclosed.get(label),
+            )?;
+            disclosed_messages.insert(id, disclosed);
+            proofs.extend(credential_proofs.proofs);
+        }
+
+        for (id, statement) in &schema.statements {
+            if matches!(statement, Statements::Signature(_)) && !disclosed_messages.contains_key(id)
+            {
+                return Err(W3cError::MissingCredential(id.clone()).into());
+            }
+        }
+        let statement_order = |id: &String| {
+            schema
+                .statements
+                .get_index_of(id)
+                .unwrap_or(schema.statements.len())
+        };
+        proofs.sort_by_cached_key(|id, _| statement_order(id));
+        disclosed_messages.sort_by_cached_key(|id, _| statement_order(id));
+        Ok(Presentation {
+            proofs,
+            challenge,
+            disclosed_messages,
+        })
+    }
+}
+
+/// The signature statement that statement `id` refers to, directly or
+/// through other statements, if it refers to exactly one
+fn referenced_signature<S: ShortGroupSignatureScheme>(
+    schema: &PresentationSchema<S>,
+    id: &str,
+) -> Option<String> {
+    let mut pending = vec![id.to_string()];
+    let mut seen = BTreeSet::new();
+    let mut signatures = BTreeSet::new();
+    while let Some(id) = pending.pop() {
+        if !seen.insert(id.clone()) {
+            continue;
+        }
+        match schema.statements.get(&id)? {
+            Statements::Signature(_) => {
+                signatures.insert(id);
+            }
+            statement => pending.extend(statement.reference_ids()),
+        }
+    }
+    if signatures.len() == 1 {
+        signatures.pop_first()
+    } else {
+        None
+    }
+}
@@ -0,0 +1,73 @@
+use credx::claim::{HashedClaim, NumberClaim, RevocationClaim};
+use credx::credential::CredentialBundle;
+use credx::issuer::{Issuer, IssuerPublic};
+use credx::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use credx::knox::{bbs::BbsScheme, ps::PsScheme};
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::SignatureStatement;
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::btreeset;
+use serde::de::DeserializeOwned;
+
+const NONCE: [u8; 16] = [7u8; 16];
+
+fn read_fixture<T: DeserializeOwned>(scheme: &str, name: &str) -> T {
+    let path = format!(
+        "{}/tests/fixtures/baseline/{}_{}.json",
+        env!("CARGO_MANIFEST_DIR"),
+        scheme,
+        name
+    );
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+/// Issuers, credentials and presentations created by credx 0.2.1, before
+/// key rotation, optional claims and validity periods, still load and verify
+#[test]
+fn baseline_data_verifies() {
+    let res = test_baseline_data_verifies::<BbsScheme>("bbs");
+    assert!(res.is_ok(), "{:?}", res);
+    let res = test_baseline_data_verifies::<PsScheme>("ps");
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_baseline_data_verifies<S: ShortGroupSignatureScheme>(scheme: &str) -> CredxResult<()> {
+    let bundle: CredentialBundle<S> = read_fixture(scheme, "credential_bundle");
+    bundle.verify()?;
+    let presentation_schema: PresentationSchema<S> = read_fixture(scheme, "presentation_schema");
+    let presentation: Presentation<S> = read_fixture(scheme, "presentation");
+    presentation.verify(&presentation_schema, &NONCE)?;
+
+    // Presentations created now verify with the same schema
+    let credentials = indexmap! {
+        "signature".to_string() => bundle.credential.clone().into(),
+        "revocation".to_string() => bundle.credential.clone().into(),
+    };
+    Presentation::create(&credentials, &presentation_schema, &NONCE)?
+        .verify(&presentation_schema, &NONCE)?;
+
+    // Stored issuers keep issuing credentials for the same public data
+    let mut issuer: Issuer<S> = read_fixture(scheme, "issuer");
+    let issuer_public = IssuerPublic::from(&issuer);
+    assert_eq!(
+        serde_json::to_value(&issuer_public).unwrap(),
+        serde_json::to_value(&bundle.issuer).unwrap()
+    );
+    let credential = issuer.sign_credential(&[
+        RevocationClaim::from("0b1c2d3e-4f5a-4b6c-8d7e-9f0a1b2c3d4e").into(),
+        HashedClaim::from("Jane Doe").into(),
+        HashedClaim::from("P Sherman 42 Wallaby Way Sydney").into(),
+        NumberClaim::from(30303).into(),
+    ])?;
+    credential.verify()?;
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "signature".to_string(),
+        issuer: issuer_public,
+    };
+    let presentation_schema = PresentationSchema::new(&[sig_st.into()]);
+    let credentials = indexmap! { "signature".to_string() => credential.credential.into() };
+    Presentation::create(&credentials, &presentation_schema, &NONCE)?
+        .verify(&presentation_schema, &NONCE)
+}
@@ -0,0 +1,109 @@
+use credx::claim::{ClaimData, ClaimType, HashedClaim, NumberClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CredentialSchema};
+use credx::issuer::Issuer;
+use credx::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use credx::knox::{bbs::BbsScheme, ps::PsScheme};
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::{RevocationStatement, SignatureStatement};
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::btreeset;
+
+fn schema() -> CredentialSchema {
+    CredentialSchema::new(
+        Some("Batch"),
+        None,
+        &[],
+        &[
+            ClaimSchema {
+                claim_type: ClaimType::Revocation,
+                label: "identifier".to_string(),
+                print_friendly: false,
+                optional: false,
+                normalization: None,
+                validators: vec![],
+            },
+            ClaimSchema {
+                claim_type: ClaimType::Hashed,
+                label: "name".to_string(),
+                print_friendly: true,
+                optional: false,
+                normalization: None,
+                validators: vec![],
+            },
+        ],
+    )
+    .unwrap()
+}
+
+fn claims(id: &str, name: &str) -> Vec<ClaimData> {
+    vec![
+        RevocationClaim::from(id).into(),
+        HashedClaim::from(name).into(),
+    ]
+}
+
+#[test]
+fn batch_issuance() {
+    let res = test_batch_issuance::<BbsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+    let res = test_batch_issuance::<PsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_batch_issuance<S: ShortGroupSignatureScheme>() -> CredxResult<()>
+where
+    S::SecretKey: Sync,
+    S::Signature: Send,
+{
+    let (_, mut issuer) = Issuer::<S>::new(&schema());
+    issuer.sign_credential(&claims("revoked", "Revoked"))?;
+    issuer.revoke_credentials(&[RevocationClaim::from("revoked")])?;
+
+    let mut batch = (0..20)
+        .map(|i| claims(&format!("employee-{}", i), &format!("Employee {}", i)))
+        .collect::<Vec<_>>();
+    // Wrong claim type
+    batch.push(vec![
+        RevocationClaim::from("bad").into(),
+        NumberClaim::from(5).into(),
+    ]);
+    // Duplicate revocation id
+    batch.push(claims("employee-3", "Someone Else"));
+    // Already revoked
+    batch.push(claims("revoked", "Revoked"));
+
+    let results = issuer.sign_credentials_batch(&batch);
+    assert_eq!(results.len(), batch.len());
+    assert!(results[..20].iter().all(|r| r.is_ok()));
+    assert!(results[20..].iter().all(|r| r.is_err()));
+    assert!(issuer.revocation_registry.active.contains("employee-19"));
+    assert!(!issuer.revocation_registry.active.contains("bad"));
+
+    // Every credential is issued against the final accumulator
+    let issuer_public = results[0].as_ref().unwrap().issuer.clone();
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "signature".to_string(),
+        issuer: issuer_public.clone(),
+    };
+    let rev_st = RevocationStatement {
+        id: "revocation".to_string(),
+        reference_id: sig_st.id.clone(),
+        accumulator: issuer_public.revocation_registry,
+        verification_key: issuer_public.revocation_verifying_key,
+        claim: 0,
+    };
+    let presentation_schema = PresentationSchema::new(&[sig_st.into(), rev_st.into()]);
+    let nonce = [6u8; 16];
+    for result in [&results[0], &results[19]] {
+        let credential = &result.as_ref().unwrap().credential;
+        let credentials = indexmap! {
+            "signature".to_string() => credential.clone().into(),
+            "revocation".to_string() => credential.clone().into(),
+        };
+        let presentation = Presentation::create(&credentials, &presentation_schema, &nonce)?;
+        presentation.verify(&presentation_schema, &nonce)?;
+    }
+    Ok(())
+}
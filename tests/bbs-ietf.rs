@@ -0,0 +1,383 @@
+use blsful::inner_types::*;
+use credx::knox::bbs::BbsCiphersuite;
+use serde_json::Value;
+
+const MESSAGES: [&str; 10] = [
+    "9872ad089e452c7b6e283dfac2a80d58e8d0ff71cc4d5e310a1debdda4a45f02",
+    "c344136d9ab02da4dd5908bbba913ae6f58c2cc844b802a6f811f5fb075f9b80",
+    "7372e9daa5ed31e6cd5c825eac1b855e84476a1d94932aa348e07b73",
+    "77fe97eb97a1ebe2e81e4e3597a3ee740a66e9ef2412472c",
+    "496694774c5604ab1b2544eababcf0f53278ff50",
+    "515ae153e22aae04ad16f759e07237b4",
+    "d183ddc6e2665aa4e2f088af",
+    "ac55fb33a75909ed",
+    "96012096",
+    "",
+];
+const HEADER: &str = "11223344556677889900aabbccddeeff";
+const PRESENTATION_HEADER: &str =
+    "bed231d880675ed101ead304512e043ade9958dd0241ea70b4b3957fba941501";
+const KEY_MATERIAL: &str = "746869732d49532d6a7573742d616e2d546573742d494b4d2d746f2d67656e65726174652d246528724074232d6b6579";
+
+fn fixture(name: &str) -> Value {
+    let path = format!("{}/tests/fixtures/bbs/{}", env!("CARGO_MANIFEST_DIR"), name);
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn bytes(value: &Value) -> Vec<u8> {
+    hex::decode(value.as_str().unwrap()).unwrap()
+}
+
+fn check_fixture(cs: BbsCiphersuite, name: &str) {
+    let fixture = fixture(name);
+    assert_eq!(fixture["ciphersuite"].as_str().unwrap().as_bytes(), cs.id());
+    assert_eq!(
+        cs.p1().to_affine().to_compressed().to_vec(),
+        bytes(&fixture["P1"])
+    );
+
+    let expected = fixture["generators"].as_array().unwrap();
+    assert_eq!(expected.len(), 11);
+    let generators = cs.create_generators(expected.len()).unwrap();
+    for (g, e) in generators.iter().zip(expected) {
+        assert_eq!(g.to_affine().to_compressed().to_vec(), bytes(e));
+    }
+
+    let key_pair = &fixture["keyPair"];
+    let sk = cs
+        .key_gen(
+            &bytes(&key_pair["keyMaterial"]),
+            &bytes(&key_pair["keyInfo"]),
+            Some(&bytes(&key_pair["keyDst"])),
+        )
+        .unwrap();
+    assert_eq!(sk.to_vec(), bytes(&key_pair["secretKey"]));
+    // The default key dst is the same as the fixture's
+    let default = cs
+        .key_gen(
+            &bytes(&key_pair["keyMaterial"]),
+            &bytes(&key_pair["keyInfo"]),
+            None,
+        )
+        .unwrap();
+    assert_eq!(sk, default);
+    let pk = cs.sk_to_pk(&sk).unwrap();
+    assert_eq!(pk.to_vec(), bytes(&key_pair["publicKey"]));
+
+    let signatures = fixture["signatures"].as_array().unwrap();
+    assert_eq!(signatures.len(), 2);
+    for case in signatures {
+        let header = bytes(&case["header"]);
+        let messages = case["messages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(bytes)
+            .collect::<Vec<_>>();
+        let signature = cs.sign(&sk, &pk, &header, &messages).unwrap();
+        assert_eq!(
+            signature.to_vec(),
+            bytes(&case["signature"]),
+            "{}",
+            case["caseName"]
+        );
+        cs.verify(&pk, &signature, &header, &messages).unwrap();
+    }
+}
+
+/// The draft's seed for the mocked random scalars
+const MOCKED_SEED: &[u8] = b"3.141592653589793238462643383279";
+
+/// OS2IP of 48 bytes reduced mod r
+fn os2ip_mod_r(bytes: &[u8]) -> Scalar {
+    let mut wide = [0u8; 64];
+    for (w, b) in wide.iter_mut().zip(bytes.iter().rev()) {
+        *w = *b;
+    }
+    Scalar::from_bytes_wide(&wide)
+}
+
+fn compressed(p: G1Projective) -> Vec<u8> {
+    p.to_affine().to_compressed().to_vec()
+}
+
+/// ProofGen from the draft's steps with the mocked random scalars
+fn reference_proof(
+    cs: BbsCiphersuite,
+    pk: &[u8],
+    signature: &[u8],
+    header: &[u8],
+    ph: &[u8],
+    messages: &[Vec<u8>],
+    disclosed: &[usize],
+) -> Vec<u8> {
+    let api_id = cs.api_id();
+    let h2s_dst = [api_id.as_slice(), b"H2S_"].concat();
+    let l = messages.len();
+    let undisclosed = (0..l)
+        .filter(|i| !disclosed.contains(i))
+        .collect::<Vec<_>>();
+
+    // calculate_random_scalars with mocked_calculate_random_scalars
+    let count = 5 + undisclosed.len();
+    let mut v = vec![0u8; 48 * count];
+    cs.expand_message(
+        MOCKED_SEED,
+        &[api_id.as_slice(), b"MOCK_RANDOM_SCALARS_DST_"].concat(),
+        &mut v,
+    )
+    .unwrap();
+    let random = v.chunks(48).map(os2ip_mod_r).collect::<Vec<_>>();
+    let (r1, r2, e_t, r1_t, r3_t) = (random[0], random[1], random[2], random[3], random[4]);
+
+    let a = G1Projective::from(
+        G1Affine::from_compressed(&signature[..48].try_into().unwrap()).unwrap(),
+    );
+    let e = Scalar::from_be_bytes(&signature[48..].try_into().unwrap()).unwrap();
+    let msg = cs.messages_to_scalars(messages);
+    let generators = cs.create_generators(l + 1).unwrap();
+
+    // calculate_domain
+    let mut dom_input = pk.to_vec();
+    dom_input.extend_from_slice(&(l as u64).to_be_bytes());
+    for g in &generators {
+        dom_input.extend(compressed(*g));
+    }
+    dom_input.extend_from_slice(&api_id);
+    dom_input.extend_from_slice(&(header.len() as u64).to_be_bytes());
+    dom_input.extend_from_slice(header);
+    let domain = cs.hash_to_scalar(&dom_input, &h2s_dst);
+
+    // ProofInit
+    let mut b = cs.p1() + generators[0] * domain;
+    for (h, m) in generators[1..].iter().zip(&msg) {
+        b += h * m;
+    }
+    let d = b * r2;
+    let a_bar = a * (r1 * r2);
+    let b_bar = d * r1 - a_bar * e;
+    let t1 = a_bar * e_t + d * r1_t;
+    let mut t2 = d * r3_t;
+    for (j, m_t) in undisclosed.iter().zip(&random[5..]) {
+        t2 += generators[j + 1] * m_t;
+    }
+
+    // ProofChallengeCalculate
+    let mut c_octs = (disclosed.len() as u64).to_be_bytes().to_vec();
+    for i in disclosed {
+        c_octs.extend_from_slice(&(*i as u64).to_be_bytes());
+        c_octs.extend_from_slice(&msg[*i].to_be_bytes());
+    }
+    for p in [a_bar, b_bar, d, t1, t2] {
+        c_octs.extend(compressed(p));
+    }
+    c_octs.extend_from_slice(&domain.to_be_bytes());
+    c_octs.extend_from_slice(&(ph.len() as u64).to_be_bytes());
+    c_octs.extend_from_slice(ph);
+    let c = cs.hash_to_scalar(&c_octs, &h2s_dst);
+
+    // ProofFinalize
+    let r3 = r2.invert().unwrap();
+    let mut proof = [a_bar, b_bar, d]
+        .into_iter()
+        .flat_map(compressed)
+        .collect::<Vec<_>>();
+    for s in [e_t + e * c, r1_t - r1 * c, r3_t - r3 * c] {
+        proof.extend_from_slice(&s.to_be_bytes());
+    }
+    for (j, m_t) in undisclosed.iter().zip(&random[5..]) {
+        proof.extend_from_slice(&(m_t + msg[*j] * c).to_be_bytes());
+    }
+    proof.extend_from_slice(&c.to_be_bytes());
+    proof
+}
+
+fn check_mocked_proofs(cs: BbsCiphersuite, name: &str) {
+    let fixture = fixture(name);
+    let pk = bytes(&fixture["keyPair"]["publicKey"]);
+    let ph = hex::decode(PRESENTATION_HEADER).unwrap();
+    for case in fixture["signatures"].as_array().unwrap() {
+        let header = bytes(&case["header"]);
+        let signature = bytes(&case["signature"]);
+        let messages = case["messages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(bytes)
+            .collect::<Vec<_>>();
+        let l = messages.len();
+        for disclosed in [vec![], vec![0], vec![0, 2, 4, 6], (0..l).collect()] {
+            if disclosed.iter().any(|i| *i >= l) {
+                continue;
+            }
+            let proof = cs
+                .proof_gen_with_seed(
+                    &pk,
+                    &signature,
+                    &header,
+                    &ph,
+                    &messages,
+                    &disclosed,
+                    MOCKED_SEED,
+                )
+                .unwrap();
+            assert_eq!(
+                proof,
+                reference_proof(cs, &pk, &signature, &header, &ph, &messages, &disclosed),
+                "{} disclosing {:?}",
+                case["caseName"],
+                disclosed
+            );
+            let revealed = disclosed
+                .iter()
+                .map(|i| (*i, messages[*i].as_slice()))
+                .collect::<Vec<_>>();
+            cs.proof_verify(&pk, &proof, &header, &ph, l, &revealed)
+                .unwrap();
+        }
+    }
+}
+
+#[test]
+fn sha256_mocked_proofs() {
+    check_mocked_proofs(BbsCiphersuite::Bls12381Sha256, "bls12-381-sha-256.json");
+}
+
+#[test]
+fn shake256_mocked_proofs() {
+    check_mocked_proofs(BbsCiphersuite::Bls12381Shake256, "bls12-381-shake-256.json");
+}
+
+#[test]
+fn sha256_fixtures() {
+    check_fixture(BbsCiphersuite::Bls12381Sha256, "bls12-381-sha-256.json");
+}
+
+#[test]
+fn shake256_fixtures() {
+    check_fixture(BbsCiphersuite::Bls12381Shake256, "bls12-381-shake-256.json");
+}
+
+#[test]
+fn sign_and_prove() {
+    for cs in [
+        BbsCiphersuite::Bls12381Sha256,
+        BbsCiphersuite::Bls12381Shake256,
+    ] {
+        test_sign_and_prove(cs);
+    }
+}
+
+fn test_sign_and_prove(cs: BbsCiphersuite) {
+    let messages = MESSAGES
+        .iter()
+        .map(|m| hex::decode(m).unwrap())
+        .collect::<Vec<_>>();
+    let header = hex::decode(HEADER).unwrap();
+    let ph = hex::decode(PRESENTATION_HEADER).unwrap();
+    let sk = cs
+        .key_gen(&hex::decode(KEY_MATERIAL).unwrap(), b"", None)
+        .unwrap();
+    let pk = cs.sk_to_pk(&sk).unwrap();
+
+    let signature = cs.sign(&sk, &pk, &header, &messages).unwrap();
+    cs.verify(&pk, &signature, &header, &messages).unwrap();
+    // Signing is deterministic
+    assert_eq!(signature, cs.sign(&sk, &pk, &header, &messages).unwrap());
+    assert!(cs.verify(&pk, &signature, b"", &messages).is_err());
+    let mut modified = messages.clone();
+    modified[9] = b"modified".to_vec();
+    assert!(cs.verify(&pk, &signature, &header, &modified).is_err());
+    assert!(cs.verify(&pk, &signature, &header, &messages[..9]).is_err());
+
+    for disclosed_indexes in [vec![], vec![0, 2, 4, 6], (0..10).collect::<Vec<_>>()] {
+        let proof = cs
+            .proof_gen(
+                &pk,
+                &signature,
+                &header,
+                &ph,
+                &messages,
+                &disclosed_indexes,
+                rand::thread_rng(),
+            )
+            .unwrap();
+        assert_eq!(
+            proof.len(),
+            3 * 48 + (4 + messages.len() - disclosed_indexes.len()) * 32
+        );
+        let disclosed = disclosed_indexes
+            .iter()
+            .map(|i| (*i, messages[*i].as_slice()))
+            .collect::<Vec<_>>();
+        cs.proof_verify(&pk, &proof, &header, &ph, messages.len(), &disclosed)
+            .unwrap();
+        assert!(cs
+            .proof_verify(&pk, &proof, &header, b"", messages.len(), &disclosed)
+            .is_err());
+        assert!(cs
+            .proof_verify(&pk, &proof, b"", &ph, messages.len(), &disclosed)
+            .is_err());
+        if let Some((i, _)) = disclosed.first() {
+            let mut wrong = disclosed.clone();
+            wrong[0] = (*i, b"wrong".as_slice());
+            assert!(cs
+                .proof_verify(&pk, &proof, &header, &ph, messages.len(), &wrong)
+                .is_err());
+        }
+        let mut tampered = proof.clone();
+        tampered[proof.len() - 1] ^= 1;
+        assert!(cs
+            .proof_verify(&pk, &tampered, &header, &ph, messages.len(), &disclosed)
+            .is_err());
+    }
+
+    // Proofs are unlinkable
+    let first = cs
+        .proof_gen(
+            &pk,
+            &signature,
+            &header,
+            &ph,
+            &messages,
+            &[0],
+            rand::thread_rng(),
+        )
+        .unwrap();
+    let second = cs
+        .proof_gen(
+            &pk,
+            &signature,
+            &header,
+            &ph,
+            &messages,
+            &[0],
+            rand::thread_rng(),
+        )
+        .unwrap();
+    assert_ne!(first, second);
+
+    assert!(cs
+        .proof_gen(
+            &pk,
+            &signature,
+            &header,
+            &ph,
+            &messages,
+            &[10],
+            rand::thread_rng()
+        )
+        .is_err());
+    assert!(cs
+        .proof_gen(
+            &pk,
+            &signature,
+            &header,
+            &ph,
+            &messages,
+            &[1, 1],
+            rand::thread_rng()
+        )
+        .is_err());
+    assert!(cs.key_gen(&[0u8; 31], b"", None).is_err());
+}
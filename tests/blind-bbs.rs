@@ -0,0 +1,428 @@
+use blsful::inner_types::*;
+use credx::blind::BlindCredentialRequest;
+use credx::claim::{ClaimData, ClaimType, HashedClaim, NumberClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CredentialOffer, CredentialRequest, CredentialSchema};
+use credx::issuer::{Issuer, IssuerPublic, OfferTracker};
+use credx::knox::bbs::{BbsCiphersuite, BbsScheme};
+use credx::knox::ps::PsScheme;
+use credx::knox::short_group_sig_core::BlindSignatureFormat;
+use elliptic_curve::hash2curve::{ExpandMsgXmd, ExpandMsgXof};
+use maplit::btreemap;
+use rand::thread_rng;
+use std::collections::BTreeMap;
+
+const MESSAGES: [&str; 3] = [
+    "9872ad089e452c7b6e283dfac2a80d58e8d0ff71cc4d5e310a1debdda4a45f02",
+    "c344136d9ab02da4dd5908bbba913ae6f58c2cc844b802a6f811f5fb075f9b80",
+    "7372e9daa5ed31e6cd5c825eac1b855e84476a1d94932aa348e07b73",
+];
+const COMMITTED_MESSAGES: [&str; 2] = [
+    "77fe97eb97a1ebe2e81e4e3597a3ee740a66e9ef2412472c",
+    "496694774c5604ab1b2544eababcf0f53278ff50",
+];
+const HEADER: &str = "11223344556677889900aabbccddeeff";
+const KEY_MATERIAL: &str = "746869732d49532d6a7573742d616e2d546573742d494b4d2d746f2d67656e65726174652d246528724074232d6b6579";
+
+fn hex_list(values: &[&str]) -> Vec<Vec<u8>> {
+    values.iter().map(|v| hex::decode(v).unwrap()).collect()
+}
+
+#[test]
+fn blind_sign_and_verify() {
+    for cs in [
+        BbsCiphersuite::Bls12381Sha256,
+        BbsCiphersuite::Bls12381Shake256,
+    ] {
+        check_blind_sign(cs);
+    }
+}
+
+fn check_blind_sign(cs: BbsCiphersuite) {
+    let sk = cs
+        .key_gen(&hex::decode(KEY_MATERIAL).unwrap(), b"", None)
+        .unwrap();
+    let pk = cs.sk_to_pk(&sk).unwrap();
+    let header = hex::decode(HEADER).unwrap();
+    let messages = hex_list(&MESSAGES);
+    let committed = hex_list(&COMMITTED_MESSAGES);
+
+    let (commitment, prover_blind) = cs.commit(&committed, thread_rng()).unwrap();
+    assert_eq!(commitment.len(), 48 + 32 * (committed.len() + 2));
+    assert!(cs.verify_commitment(&commitment).is_ok());
+
+    let signature = cs
+        .blind_sign(&sk, &pk, &commitment, &header, &messages)
+        .unwrap();
+    assert_eq!(signature.len(), BbsCiphersuite::SIGNATURE_BYTES);
+    assert!(cs
+        .blind_verify(
+            &pk,
+            &signature,
+            &header,
+            &messages,
+            &committed,
+            &prover_blind
+        )
+        .is_ok());
+
+    // All messages and the prover blind are needed
+    let other_blind = Scalar::random(thread_rng()).to_be_bytes();
+    assert!(cs
+        .blind_verify(
+            &pk,
+            &signature,
+            &header,
+            &messages,
+            &committed,
+            &other_blind
+        )
+        .is_err());
+    assert!(cs
+        .blind_verify(
+            &pk,
+            &signature,
+            &header,
+            &messages,
+            &committed[..1],
+            &prover_blind
+        )
+        .is_err());
+    assert!(cs
+        .blind_verify(
+            &pk,
+            &signature,
+            &header,
+            &messages[1..],
+            &committed,
+            &prover_blind
+        )
+        .is_err());
+    assert!(cs
+        .blind_verify(&pk, &signature, b"", &messages, &committed, &prover_blind)
+        .is_err());
+    // A blind signature isn't a signature over the signer's messages alone
+    assert!(cs.verify(&pk, &signature, &header, &messages).is_err());
+
+    // Tampered commitments are rejected
+    let mut tampered = commitment.clone();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+    assert!(cs.verify_commitment(&tampered).is_err());
+    assert!(cs
+        .blind_sign(&sk, &pk, &tampered, &header, &messages)
+        .is_err());
+    assert!(cs
+        .blind_sign(
+            &sk,
+            &pk,
+            &commitment[..commitment.len() - 1],
+            &header,
+            &messages
+        )
+        .is_err());
+    // Commitments are bound to the ciphersuite
+    let other = match cs {
+        BbsCiphersuite::Bls12381Sha256 => BbsCiphersuite::Bls12381Shake256,
+        BbsCiphersuite::Bls12381Shake256 => BbsCiphersuite::Bls12381Sha256,
+    };
+    assert!(other.verify_commitment(&commitment).is_err());
+
+    // Committing to nothing still hides the prover blind
+    let (commitment, prover_blind) = cs.commit::<&[u8]>(&[], thread_rng()).unwrap();
+    assert_eq!(commitment.len(), 48 + 64);
+    let signature = cs
+        .blind_sign(&sk, &pk, &commitment, &header, &messages)
+        .unwrap();
+    assert!(cs
+        .blind_verify::<_, &[u8]>(&pk, &signature, &header, &messages, &[], &prover_blind)
+        .is_ok());
+}
+
+/// The draft's seed for the mocked random scalars
+const MOCKED_SEED: &[u8] = b"3.141592653589793238462643383279";
+
+/// OS2IP of 48 bytes reduced mod r
+fn os2ip_mod_r(bytes: &[u8]) -> Scalar {
+    let mut wide = [0u8; 64];
+    for (w, b) in wide.iter_mut().zip(bytes.iter().rev()) {
+        *w = *b;
+    }
+    Scalar::from_bytes_wide(&wide)
+}
+
+fn compressed(p: G1Projective) -> Vec<u8> {
+    p.to_affine().to_compressed().to_vec()
+}
+
+/// Commit from the draft's steps with the mocked random scalars
+fn reference_commit(cs: BbsCiphersuite, committed: &[Vec<u8>]) -> (Vec<u8>, Scalar) {
+    let api_id = cs.blind_api_id();
+    let m = committed.len();
+    let mut v = vec![0u8; 48 * (m + 2)];
+    cs.expand_message(
+        MOCKED_SEED,
+        &[api_id.as_slice(), b"MOCK_RANDOM_SCALARS_DST_"].concat(),
+        &mut v,
+    )
+    .unwrap();
+    let random = v.chunks(48).map(os2ip_mod_r).collect::<Vec<_>>();
+    let (prover_blind, s_t, m_t) = (random[0], random[1], &random[2..]);
+    let msg = committed
+        .iter()
+        .map(|c| {
+            cs.hash_to_scalar(
+                c,
+                &[api_id.as_slice(), b"MAP_MSG_TO_SCALAR_AS_HASH_"].concat(),
+            )
+        })
+        .collect::<Vec<_>>();
+    let generators = cs.create_blind_generators(m + 1).unwrap();
+    let (q_2, j) = (generators[0], &generators[1..]);
+
+    let mut c = q_2 * prover_blind;
+    let mut c_bar = q_2 * s_t;
+    for ((j, m), m_t) in j.iter().zip(&msg).zip(m_t) {
+        c += j * m;
+        c_bar += j * m_t;
+    }
+    let mut c_octs = (m as u64).to_be_bytes().to_vec();
+    for g in &generators {
+        c_octs.extend(compressed(*g));
+    }
+    c_octs.extend(compressed(c));
+    c_octs.extend(compressed(c_bar));
+    let challenge = cs.hash_to_scalar(&c_octs, &[api_id.as_slice(), b"H2S_"].concat());
+
+    let mut commitment = compressed(c);
+    commitment.extend_from_slice(&(s_t + prover_blind * challenge).to_be_bytes());
+    for (m_t, m) in m_t.iter().zip(&msg) {
+        commitment.extend_from_slice(&(m_t + m * challenge).to_be_bytes());
+    }
+    commitment.extend_from_slice(&challenge.to_be_bytes());
+    (commitment, prover_blind)
+}
+
+/// BlindSign from the draft's steps
+fn reference_blind_sign(
+    cs: BbsCiphersuite,
+    sk: &[u8],
+    pk: &[u8],
+    commitment: &[u8],
+    header: &[u8],
+    messages: &[Vec<u8>],
+    committed_count: usize,
+) -> Vec<u8> {
+    let api_id = cs.blind_api_id();
+    let h2s_dst = [api_id.as_slice(), b"H2S_"].concat();
+    let sk = Scalar::from_be_bytes(&sk.try_into().unwrap()).unwrap();
+    let c = G1Projective::from(
+        G1Affine::from_compressed(&commitment[..48].try_into().unwrap()).unwrap(),
+    );
+    let msg = messages
+        .iter()
+        .map(|m| {
+            cs.hash_to_scalar(
+                m,
+                &[api_id.as_slice(), b"MAP_MSG_TO_SCALAR_AS_HASH_"].concat(),
+            )
+        })
+        .collect::<Vec<_>>();
+    // The signer's generators use the blind API id
+    let signer_generators = blind_api_generators(cs, messages.len() + 1);
+    let mut generators = signer_generators.clone();
+    generators.extend(cs.create_blind_generators(committed_count + 1).unwrap());
+
+    let mut dom_input = pk.to_vec();
+    dom_input.extend_from_slice(&((generators.len() - 1) as u64).to_be_bytes());
+    for g in &generators {
+        dom_input.extend(compressed(*g));
+    }
+    dom_input.extend_from_slice(&api_id);
+    dom_input.extend_from_slice(&(header.len() as u64).to_be_bytes());
+    dom_input.extend_from_slice(header);
+    let domain = cs.hash_to_scalar(&dom_input, &h2s_dst);
+
+    let mut e_input = sk.to_be_bytes().to_vec();
+    for m in &msg {
+        e_input.extend_from_slice(&m.to_be_bytes());
+    }
+    e_input.extend(compressed(c));
+    e_input.extend_from_slice(&domain.to_be_bytes());
+    let e = cs.hash_to_scalar(&e_input, &h2s_dst);
+
+    let mut b = cs.p1() + signer_generators[0] * domain + c;
+    for (h, m) in signer_generators[1..].iter().zip(&msg) {
+        b += h * m;
+    }
+    let mut signature = compressed(b * (sk + e).invert().unwrap());
+    signature.extend_from_slice(&e.to_be_bytes());
+    signature
+}
+
+/// `create_generators` with the blind API id from the draft's generator steps
+fn blind_api_generators(cs: BbsCiphersuite, count: usize) -> Vec<G1Projective> {
+    let api_id = cs.blind_api_id();
+    let seed_dst = [api_id.as_slice(), b"SIG_GENERATOR_SEED_"].concat();
+    let generator_dst = [api_id.as_slice(), b"SIG_GENERATOR_DST_"].concat();
+    let mut v = [0u8; 48];
+    cs.expand_message(
+        &[api_id.as_slice(), b"MESSAGE_GENERATOR_SEED"].concat(),
+        &seed_dst,
+        &mut v,
+    )
+    .unwrap();
+    (1..=count as u64)
+        .map(|i| {
+            let input = [v.as_slice(), &i.to_be_bytes()].concat();
+            cs.expand_message(&input, &seed_dst, &mut v).unwrap();
+            match cs {
+                BbsCiphersuite::Bls12381Sha256 => {
+                    G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(&v, &generator_dst)
+                }
+                BbsCiphersuite::Bls12381Shake256 => {
+                    G1Projective::hash::<ExpandMsgXof<sha3::Shake256>>(&v, &generator_dst)
+                }
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn mocked_blind_signatures() {
+    for cs in [
+        BbsCiphersuite::Bls12381Sha256,
+        BbsCiphersuite::Bls12381Shake256,
+    ] {
+        let sk = cs
+            .key_gen(&hex::decode(KEY_MATERIAL).unwrap(), b"", None)
+            .unwrap();
+        let pk = cs.sk_to_pk(&sk).unwrap();
+        let header = hex::decode(HEADER).unwrap();
+        let messages = hex_list(&MESSAGES);
+        let committed = hex_list(&COMMITTED_MESSAGES);
+        for m in 0..=committed.len() {
+            let (commitment, prover_blind) =
+                cs.commit_with_seed(&committed[..m], MOCKED_SEED).unwrap();
+            let (expected, expected_blind) = reference_commit(cs, &committed[..m]);
+            assert_eq!(commitment, expected);
+            assert_eq!(prover_blind, expected_blind.to_be_bytes());
+
+            let signature = cs
+                .blind_sign(&sk, &pk, &commitment, &header, &messages)
+                .unwrap();
+            assert_eq!(
+                signature.to_vec(),
+                reference_blind_sign(cs, &sk, &pk, &commitment, &header, &messages, m)
+            );
+            cs.blind_verify(
+                &pk,
+                &signature,
+                &header,
+                &messages,
+                &committed[..m],
+                &prover_blind,
+            )
+            .unwrap();
+        }
+    }
+}
+
+fn schema() -> CredentialSchema {
+    let claim = |claim_type, label: &str| ClaimSchema {
+        claim_type,
+        label: label.to_string(),
+        print_friendly: false,
+        optional: false,
+        normalization: None,
+        validators: vec![],
+    };
+    CredentialSchema::new(
+        Some("Blind BBS"),
+        None,
+        &["link_secret", "birth_year"],
+        &[
+            claim(ClaimType::Revocation, "identifier"),
+            claim(ClaimType::Hashed, "link_secret"),
+            claim(ClaimType::Number, "birth_year"),
+        ],
+    )
+    .unwrap()
+}
+
+fn blind_claims() -> BTreeMap<String, ClaimData> {
+    btreemap! {
+        "link_secret".to_string() => HashedClaim::from("my link secret").into(),
+        "birth_year".to_string() => NumberClaim::from(1990).into(),
+    }
+}
+
+fn known_claims() -> BTreeMap<String, ClaimData> {
+    btreemap! {
+        "identifier".to_string() => RevocationClaim::from("e4f5a6b7-c8d9-4e0f-9a1b-2c3d4e5f6a7b").into(),
+    }
+}
+
+#[test]
+fn cfrg_blind_credential_request() {
+    let (issuer_public, mut issuer) = Issuer::<BbsScheme>::new(&schema());
+    let nonce = Scalar::random(thread_rng());
+    let (request, blinder) = BlindCredentialRequest::new_with_format(
+        &issuer_public,
+        &blind_claims(),
+        nonce,
+        BlindSignatureFormat::Cfrg,
+    )
+    .unwrap();
+    let ctx = &request.blind_signature_context;
+    assert_eq!(ctx.format, BlindSignatureFormat::Cfrg);
+    let octets = ctx.commitment_with_proof();
+    assert_eq!(octets.len(), 48 + 32 * 3);
+
+    let bundle = issuer
+        .blind_sign_credential(&request, &known_claims())
+        .unwrap();
+    assert!(bundle.to_unblinded(&blind_claims(), blinder).is_ok());
+
+    // A proof can't be checked as the other format
+    let mut other = request.clone();
+    other.blind_signature_context.format = BlindSignatureFormat::Credx;
+    assert!(issuer
+        .blind_sign_credential(&other, &known_claims())
+        .is_err());
+
+    // PS doesn't support the CFRG format
+    let (ps_public, _) = Issuer::<PsScheme>::new(&schema());
+    assert!(BlindCredentialRequest::new_with_format(
+        &ps_public,
+        &blind_claims(),
+        nonce,
+        BlindSignatureFormat::Cfrg,
+    )
+    .is_err());
+}
+
+#[test]
+fn cfrg_offer() {
+    let (_, mut issuer) = Issuer::<BbsScheme>::new(&schema());
+    let offer = CredentialOffer::new_blind(
+        &known_claims(),
+        &["link_secret", "birth_year"],
+        IssuerPublic::from(&issuer),
+    )
+    .unwrap()
+    .with_blind_signature_format(BlindSignatureFormat::Cfrg);
+    let mut offers = OfferTracker::new();
+    offers.add_offer(&offer).unwrap();
+
+    // Requests in another format are rejected
+    let (mut request, state) = CredentialRequest::new(&offer, &blind_claims()).unwrap();
+    let (credx, _) =
+        BlindCredentialRequest::new_with_nonce(&offer.issuer, &blind_claims(), offer.nonce)
+            .unwrap();
+    let cfrg = request.blind_request.replace(credx);
+    assert!(offers.issue(&mut issuer, &request).is_err());
+    request.blind_request = cfrg;
+
+    let response = offers.issue(&mut issuer, &request).unwrap();
+    assert!(response.to_credential(&state).is_ok());
+}
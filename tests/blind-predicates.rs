@@ -0,0 +1,248 @@
+use blsful::inner_types::*;
+use credx::blind::BlindCredentialRequest;
+use credx::claim::{ClaimData, ClaimType, HashedClaim, NumberClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CredentialOffer, CredentialRequest, CredentialSchema};
+use credx::issuer::{Issuer, IssuerPublic, OfferTracker};
+use credx::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use credx::knox::{bbs::BbsScheme, ps::PsScheme};
+use credx::presentation::PresentationSchema;
+use credx::statement::{
+    BlindClaimsStatement, CommitmentStatement, EqualityStatement, RangeStatement,
+    SignatureStatement, Statement,
+};
+use credx::{random_string, CredxResult};
+use indexmap::indexmap;
+use maplit::{btreemap, btreeset};
+use rand::thread_rng;
+use std::collections::BTreeMap;
+
+fn claim(claim_type: ClaimType, label: &str) -> ClaimSchema {
+    ClaimSchema {
+        claim_type,
+        label: label.to_string(),
+        print_friendly: false,
+        optional: false,
+        normalization: None,
+        validators: vec![],
+    }
+}
+
+#[test]
+fn blind_predicates() {
+    let res = test_blind_predicates::<BbsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+    let res = test_blind_predicates::<PsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn blind_claims(link_secret: &str, birth_year: isize) -> BTreeMap<String, ClaimData> {
+    btreemap! {
+        "link_secret".to_string() => HashedClaim::from(link_secret).into(),
+        "birth_year".to_string() => NumberClaim::from(birth_year).into(),
+    }
+}
+
+fn test_blind_predicates<S: ShortGroupSignatureScheme>() -> CredxResult<()> {
+    // The holder already has a credential with their link secret
+    let existing_schema = CredentialSchema::new(
+        Some("Existing"),
+        None,
+        &[],
+        &[
+            claim(ClaimType::Revocation, "identifier"),
+            claim(ClaimType::Hashed, "link_secret"),
+        ],
+    )?;
+    let (existing_public, mut existing_issuer) = Issuer::<S>::new(&existing_schema);
+    let existing = existing_issuer.sign_credential(&[
+        RevocationClaim::from("b1a2c3d4-e5f6-4a7b-8c9d-0e1f2a3b4c5d").into(),
+        HashedClaim::from("my link secret").into(),
+    ])?;
+
+    // The new credential blinds the link secret and birth year
+    let schema = CredentialSchema::new(
+        Some("Blind"),
+        None,
+        &["link_secret", "birth_year"],
+        &[
+            claim(ClaimType::Revocation, "identifier"),
+            claim(ClaimType::Hashed, "link_secret"),
+            claim(ClaimType::Number, "birth_year"),
+        ],
+    )?;
+    let (issuer_public, mut issuer) = Issuer::<S>::new(&schema);
+
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {},
+        id: random_string(16, thread_rng()),
+        issuer: existing_public,
+    };
+    let blind_st = BlindClaimsStatement {
+        id: random_string(16, thread_rng()),
+        claims: btreeset! {"link_secret".to_string(), "birth_year".to_string()},
+        issuer: issuer_public.clone(),
+    };
+    assert_eq!(sig_st.get_claim_index("link_secret"), 1);
+    assert_eq!(blind_st.get_claim_index("birth_year"), 2);
+    let eq_st = EqualityStatement {
+        id: random_string(16, thread_rng()),
+        ref_id_claim_index: indexmap! {
+            sig_st.id.clone() => 1,
+            blind_st.id.clone() => 1,
+        },
+    };
+    let comm_st = CommitmentStatement {
+        id: random_string(16, thread_rng()),
+        reference_id: blind_st.id.clone(),
+        message_generator: G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(
+            b"message generator",
+            b"BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        ),
+        blinder_generator: G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(
+            b"blinder generator",
+            b"BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        ),
+        claim: 2,
+    };
+    let range_st = RangeStatement {
+        id: random_string(16, thread_rng()),
+        reference_id: comm_st.id.clone(),
+        signature_id: blind_st.id.clone(),
+        claim: 2,
+        lower: Some(1900),
+        upper: Some(2008),
+    };
+    let presentation_schema = PresentationSchema::new(&[
+        sig_st.clone().into(),
+        blind_st.into(),
+        eq_st.into(),
+        comm_st.into(),
+        range_st.into(),
+    ]);
+    let credentials = indexmap! { sig_st.id.clone() => existing.credential.into() };
+    let known = btreemap! {
+        "identifier".to_string() => RevocationClaim::from("c2d3e4f5-a6b7-4c8d-9e0f-1a2b3c4d5e6f").into(),
+    };
+
+    let nonce = Scalar::random(thread_rng());
+    let (request, blinder) = BlindCredentialRequest::new_with_presentation(
+        &issuer_public,
+        &blind_claims("my link secret", 1990),
+        nonce,
+        &credentials,
+        &presentation_schema,
+    )?;
+    let bundle =
+        issuer.blind_sign_credential_with_schema(&request, &known, &presentation_schema)?;
+    bundle.to_unblinded(&blind_claims("my link secret", 1990), blinder)?;
+
+    // The issuer requires its own schema, not one the holder chose. A
+    // holder proving equality with a credential from an issuer they made up
+    // is rejected even with the same schema id
+    let (fake_public, mut fake_issuer) = Issuer::<S>::new(&existing_schema);
+    let fake = fake_issuer.sign_credential(&[
+        RevocationClaim::from("e4f5a6b7-c8d9-4e0f-9a1b-2c3d4e5f6a7b").into(),
+        HashedClaim::from("another link secret").into(),
+    ])?;
+    let fake_sig_st = SignatureStatement {
+        issuer: fake_public,
+        ..sig_st.clone()
+    };
+    let mut statements = presentation_schema
+        .statements
+        .values()
+        .cloned()
+        .collect::<Vec<_>>();
+    statements[0] = fake_sig_st.into();
+    for holder_schema in [
+        PresentationSchema::new(&statements),
+        PresentationSchema::new_with_id(&statements, &presentation_schema.id),
+    ] {
+        let (substituted, _) = BlindCredentialRequest::new_with_presentation(
+            &issuer_public,
+            &blind_claims("another link secret", 1990),
+            nonce,
+            &indexmap! { sig_st.id.clone() => fake.credential.clone().into() },
+            &holder_schema,
+        )?;
+        assert!(issuer
+            .blind_sign_credential_with_schema(&substituted, &known, &presentation_schema)
+            .is_err());
+    }
+    // A request without a presentation is rejected
+    let (unproven, _) = BlindCredentialRequest::new_with_nonce(
+        &issuer_public,
+        &blind_claims("my link secret", 1990),
+        nonce,
+    )?;
+    assert!(issuer
+        .blind_sign_credential_with_schema(&unproven, &known, &presentation_schema)
+        .is_err());
+
+    // The blinded link secret must match the existing credential
+    assert!(BlindCredentialRequest::new_with_presentation(
+        &issuer_public,
+        &blind_claims("another link secret", 1990),
+        nonce,
+        &credentials,
+        &presentation_schema,
+    )
+    .is_err());
+    // The blinded birth year must be in range
+    assert!(BlindCredentialRequest::new_with_presentation(
+        &issuer_public,
+        &blind_claims("my link secret", 2010),
+        nonce,
+        &credentials,
+        &presentation_schema,
+    )
+    .is_err());
+
+    // A presentation is only valid for the blind signature context it opens
+    let (mut other, _) = BlindCredentialRequest::new_with_nonce(
+        &issuer_public,
+        &blind_claims("my link secret", 2010),
+        nonce,
+    )?;
+    other.presentation = request.presentation.clone();
+    assert!(issuer
+        .blind_sign_credential_with_schema(&other, &known, &presentation_schema)
+        .is_err());
+    // and the nonce it was created with
+    let mut other = request.clone();
+    other.nonce = Scalar::random(thread_rng());
+    assert!(other
+        .verify_presentation(&issuer_public, &presentation_schema)
+        .is_err());
+
+    // Offers can require the presentation
+    let offer = CredentialOffer::new_blind(
+        &btreemap! {
+            "identifier".to_string() => RevocationClaim::from("d3e4f5a6-b7c8-4d9e-8f0a-1b2c3d4e5f6a").into(),
+        },
+        &["link_secret", "birth_year"],
+        IssuerPublic::from(&issuer),
+    )?
+    .with_presentation_schema(presentation_schema)?;
+    let mut offers = OfferTracker::new();
+    offers.add_offer(&offer)?;
+
+    // Requests without the presentation are rejected
+    let (unproven, _) = BlindCredentialRequest::new_with_nonce(
+        &issuer_public,
+        &blind_claims("my link secret", 1990),
+        offer.nonce,
+    )?;
+    let (mut request, state) = CredentialRequest::new_with_credentials(
+        &offer,
+        &blind_claims("my link secret", 1990),
+        &credentials,
+    )?;
+    let proven = request.blind_request.replace(unproven);
+    assert!(offers.issue(&mut issuer, &request).is_err());
+    request.blind_request = proven;
+
+    let response = offers.issue(&mut issuer, &request)?;
+    response.to_credential(&state)?;
+    Ok(())
+}
@@ -0,0 +1,183 @@
+use credx::claim::{ClaimData, ClaimType, HashedClaim, NumberClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, Credential, CredentialSchema};
+use credx::error::Error;
+use credx::issuer::Issuer;
+use credx::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use credx::knox::{bbs::BbsScheme, ps::PsScheme};
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::SignatureStatement;
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::btreeset;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn claim_schema(claim_type: ClaimType, label: &str, optional: bool) -> ClaimSchema {
+    ClaimSchema {
+        claim_type,
+        label: label.to_string(),
+        print_friendly: true,
+        optional,
+        normalization: None,
+        validators: vec![],
+    }
+}
+
+fn schema() -> CredentialSchema {
+    CredentialSchema::new(
+        Some("Validity"),
+        None,
+        &[],
+        &[
+            claim_schema(ClaimType::Revocation, "identifier", false),
+            claim_schema(ClaimType::Hashed, "name", false),
+            claim_schema(ClaimType::Number, "issued", false),
+            claim_schema(ClaimType::Number, "expires", false),
+            claim_schema(ClaimType::Number, "level", true),
+        ],
+    )
+    .unwrap()
+}
+
+fn claims(id: &str, valid_from: isize, valid_until: isize) -> Vec<ClaimData> {
+    vec![
+        RevocationClaim::from(id).into(),
+        HashedClaim::from("Jane Doe").into(),
+        NumberClaim::from(valid_from).into(),
+        NumberClaim::from(valid_until).into(),
+        NumberClaim::from(1).into(),
+    ]
+}
+
+fn present<S: ShortGroupSignatureScheme>(
+    signature_statement: &SignatureStatement<S>,
+    credential: &Credential<S>,
+    time: isize,
+) -> CredxResult<()> {
+    let mut statements = vec![signature_statement.clone().into()];
+    statements.extend(signature_statement.validity_statements(time)?);
+    verify(
+        PresentationSchema::new(&statements),
+        signature_statement,
+        credential,
+    )
+}
+
+fn verify<S: ShortGroupSignatureScheme>(
+    presentation_schema: PresentationSchema<S>,
+    signature_statement: &SignatureStatement<S>,
+    credential: &Credential<S>,
+) -> CredxResult<()> {
+    let nonce = [3u8; 16];
+    let credentials = indexmap! { signature_statement.id.clone() => credential.clone().into() };
+    let presentation = Presentation::create(&credentials, &presentation_schema, &nonce)?;
+    presentation.verify(&presentation_schema, &nonce)
+}
+
+fn now() -> isize {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as isize
+}
+
+#[test]
+fn schema_validity_claims() {
+    let schema = schema();
+    assert!(schema
+        .clone()
+        .with_validity_period(Some("name"), None)
+        .is_err());
+    assert!(schema
+        .clone()
+        .with_validity_period(Some("level"), None)
+        .is_err());
+    assert!(schema
+        .clone()
+        .with_validity_period(None, Some("missing"))
+        .is_err());
+    assert!(schema
+        .clone()
+        .with_validity_period(Some("issued"), Some("issued"))
+        .is_err());
+    let schema = schema
+        .with_validity_period(Some("issued"), Some("expires"))
+        .unwrap();
+    assert_eq!(schema.valid_from.as_deref(), Some("issued"));
+    assert_eq!(schema.valid_until.as_deref(), Some("expires"));
+    let json = serde_json::to_string(&schema).unwrap();
+    let schema2: CredentialSchema = serde_json::from_str(&json).unwrap();
+    assert_eq!(schema2.valid_until, schema.valid_until);
+}
+
+#[test]
+fn credential_validity_period() {
+    let res = test_credential_validity_period::<BbsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+    let res = test_credential_validity_period::<PsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_credential_validity_period<S: ShortGroupSignatureScheme>() -> CredxResult<()> {
+    let schema = schema().with_validity_period(Some("issued"), Some("expires"))?;
+    let (issuer_public, mut issuer) = Issuer::<S>::new(&schema);
+    let now = now();
+    // Credentials can't expire before they become valid
+    assert!(issuer
+        .sign_credential(&claims(
+            "f1e2d3c4-b5a6-4978-8a9b-0c1d2e3f4a5b",
+            now + 1000,
+            now - 1000
+        ))
+        .is_err());
+    // Or be issued after they expired
+    assert_eq!(
+        issuer
+            .sign_credential(&claims(
+                "f1e2d3c4-b5a6-4978-8a9b-0c1d2e3f4a5b",
+                now - 2000,
+                now - 1000
+            ))
+            .unwrap_err(),
+        Error::InvalidClaimData("credential has already expired")
+    );
+    let bundle = issuer.sign_credential(&claims(
+        "f1e2d3c4-b5a6-4978-8a9b-0c1d2e3f4a5b",
+        now - 1000,
+        now + 1000,
+    ))?;
+
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "signature".to_string(),
+        issuer: issuer_public,
+    };
+    present(&sig_st, &bundle.credential, now - 1000)?;
+    present(&sig_st, &bundle.credential, now)?;
+    present(&sig_st, &bundle.credential, now + 1000)?;
+    assert!(present(&sig_st, &bundle.credential, now - 1001).is_err());
+    assert!(present(&sig_st, &bundle.credential, now + 1001).is_err());
+
+    // Presentation schemas add the validity statements themselves
+    let presentation_schema = PresentationSchema::new(&[sig_st.clone().into()]);
+    assert_eq!(presentation_schema.statements.len(), 5);
+    verify(presentation_schema, &sig_st, &bundle.credential)?;
+    let presentation_schema = PresentationSchema::new_valid_at(&[sig_st.clone().into()], now + 500);
+    verify(presentation_schema, &sig_st, &bundle.credential)?;
+    let presentation_schema =
+        PresentationSchema::new_valid_at(&[sig_st.clone().into()], now + 1001);
+    assert!(verify(presentation_schema, &sig_st, &bundle.credential).is_err());
+
+    // Schemas without a validity period can't require one
+    let (issuer_public, _) = Issuer::<S>::new(&self::schema());
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {},
+        id: "signature".to_string(),
+        issuer: issuer_public,
+    };
+    assert!(sig_st.validity_statements(now).is_err());
+    assert_eq!(
+        PresentationSchema::new(&[sig_st.into()]).statements.len(),
+        1
+    );
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use credx::blind::BlindCredentialRequest;
+use credx::claim::{ClaimType, ClaimValidator, HashedClaim, NumberClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CredentialSchema};
+use credx::error::Error;
+use credx::issuer::Issuer;
+use credx::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use credx::knox::{bbs::BbsScheme, ps::PsScheme};
+use credx::CredxResult;
+use maplit::btreemap;
+
+fn schema() -> CredentialSchema {
+    CredentialSchema::new(
+        Some("Received"),
+        None,
+        &["secret"],
+        &[
+            ClaimSchema {
+                claim_type: ClaimType::Revocation,
+                label: "identifier".to_string(),
+                print_friendly: false,
+                optional: false,
+                normalization: None,
+                validators: vec![],
+            },
+            ClaimSchema {
+                claim_type: ClaimType::Number,
+                label: "age".to_string(),
+                print_friendly: true,
+                optional: false,
+                normalization: None,
+                validators: vec![ClaimValidator::Range {
+                    min: Some(0),
+                    max: Some(150),
+                }],
+            },
+            ClaimSchema {
+                claim_type: ClaimType::Hashed,
+                label: "secret".to_string(),
+                print_friendly: false,
+                optional: false,
+                normalization: None,
+                validators: vec![],
+            },
+        ],
+    )
+    .unwrap()
+}
+
+#[test]
+fn received_credentials_are_verified() {
+    let res = test_received_credentials_are_verified::<BbsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+    let res = test_received_credentials_are_verified::<PsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_received_credentials_are_verified<S: ShortGroupSignatureScheme>() -> CredxResult<()> {
+    let (issuer_public, mut issuer) = Issuer::<S>::new(&schema());
+    let bundle = issuer.sign_credential(&[
+        RevocationClaim::from("0d1c2b3a-4f5e-4d6c-8b7a-9f8e7d6c5b4a").into(),
+        NumberClaim::from(30).into(),
+        HashedClaim::from("secret").into(),
+    ])?;
+    bundle.verify()?;
+
+    // Claims that weren't signed
+    let mut tampered = bundle.clone();
+    tampered.credential.claims[1] = NumberClaim::from(31).into();
+    assert_eq!(
+        tampered.verify(),
+        Err(Error::InvalidCredential(
+            "signature is not valid for the issuer"
+        ))
+    );
+    // Claims that don't satisfy the schema validators
+    tampered.credential.claims[1] = NumberClaim::from(200).into();
+    assert_eq!(
+        tampered.verify(),
+        Err(Error::InvalidClaimData("claim is not valid"))
+    );
+    // A different issuer
+    let mut tampered = bundle.clone();
+    let (other_public, _) = Issuer::<S>::new(&schema());
+    tampered.issuer = other_public.clone();
+    assert_eq!(
+        tampered.verify(),
+        Err(Error::InvalidCredential(
+            "signature is not valid for the issuer"
+        ))
+    );
+    // The key proof is a separate step
+    let mut tampered = bundle.clone();
+    tampered.issuer.verifiable_encryption_key = other_public.verifiable_encryption_key;
+    tampered.verify()?;
+    assert_eq!(
+        tampered.issuer.verify_key_proof(),
+        Err(Error::InvalidPublicKey)
+    );
+    // An issuer without a key proof, like one from before proofs were published
+    let mut tampered = bundle.clone();
+    tampered.issuer.key_proof = None;
+    tampered.verify()?;
+    assert_eq!(
+        tampered.issuer.verify_key_proof(),
+        Err(Error::MissingKeyProof)
+    );
+    // A revocation handle for another registry
+    let mut tampered = bundle.clone();
+    tampered.issuer.revocation_registry = other_public.revocation_registry;
+    assert_eq!(
+        tampered.verify(),
+        Err(Error::InvalidCredential(
+            "revocation handle is not valid for the revocation registry"
+        ))
+    );
+    let mut tampered = bundle.clone();
+    tampered.credential.revocation_index = 2;
+    assert!(tampered.verify().is_err());
+
+    // Blind credentials are checked when unblinded
+    let blind_claims = btreemap! { "secret".to_string() => HashedClaim::from("link").into() };
+    let known_claims = btreemap! {
+        "identifier".to_string() => RevocationClaim::from("5e4d3c2b-1a0f-4e9d-8c7b-6a5f4e3d2c1b").into(),
+        "age".to_string() => NumberClaim::from(40).into(),
+    };
+    let (request, blinder) = BlindCredentialRequest::new(&issuer_public, &blind_claims)?;
+    let blind_bundle = issuer.blind_sign_credential(&request, &known_claims)?;
+    let wrong_claims = btreemap! { "secret".to_string() => HashedClaim::from("other").into() };
+    assert_eq!(
+        blind_bundle
+            .clone()
+            .to_unblinded(&wrong_claims, blinder)
+            .unwrap_err(),
+        Error::InvalidCredential("signature is not valid for the issuer")
+    );
+    let mut malicious = blind_bundle.clone();
+    malicious.issuer.revocation_registry = other_public.revocation_registry;
+    assert!(malicious.to_unblinded(&blind_claims, blinder).is_err());
+    blind_bundle
+        .to_unblinded(&blind_claims, blinder)?
+        .verify()?;
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use credx::claim::{
+    register_claim_validator, ClaimData, ClaimType, ClaimValidator, HashedClaim, NumberClaim,
+    RevocationClaim, ISO3166_ALPHA2_VALIDATOR, LUHN_VALIDATOR,
+};
+use credx::credential::{ClaimSchema, CredentialSchema};
+use credx::issuer::Issuer;
+use credx::knox::bbs::BbsScheme;
+
+fn claim(claim_type: ClaimType, label: &str, validators: Vec<ClaimValidator>) -> ClaimSchema {
+    ClaimSchema {
+        claim_type,
+        label: label.to_string(),
+        print_friendly: true,
+        optional: false,
+        normalization: None,
+        validators,
+    }
+}
+
+fn schema() -> CredentialSchema {
+    CredentialSchema::new(
+        Some("Membership"),
+        None,
+        &[],
+        &[
+            claim(ClaimType::Revocation, "identifier", vec![]),
+            claim(
+                ClaimType::Hashed,
+                "card_number",
+                vec![ClaimValidator::custom(LUHN_VALIDATOR)],
+            ),
+            claim(
+                ClaimType::Hashed,
+                "country",
+                vec![ClaimValidator::custom(ISO3166_ALPHA2_VALIDATOR)],
+            ),
+            claim(
+                ClaimType::Number,
+                "member_number",
+                vec![ClaimValidator::Custom {
+                    name: "divisible_by".to_string(),
+                    parameters: vec!["7".to_string()],
+                }],
+            ),
+        ],
+    )
+    .unwrap()
+}
+
+fn claims(card_number: &str, country: &str, member_number: isize) -> Vec<ClaimData> {
+    vec![
+        RevocationClaim::from("8c7d2b61-5a4e-4f0c-9d3e-2b1a0f9e8d7c").into(),
+        HashedClaim::from(card_number).into(),
+        HashedClaim::from(country).into(),
+        NumberClaim::from(member_number).into(),
+    ]
+}
+
+#[test]
+fn custom_validators_are_enforced_when_signing() {
+    let schema = schema();
+    let (_, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+    // Not registered yet so it can't be satisfied
+    assert!(issuer
+        .sign_credential(&claims("4111111111111111", "US", 49))
+        .is_err());
+
+    register_claim_validator(
+        "divisible_by",
+        |parameters: &[String], claim: &ClaimData| {
+            let divisor = parameters.first()?.parse::<isize>().ok()?;
+            match claim {
+                ClaimData::Number(n) => Some(n.value % divisor == 0),
+                _ => None,
+            }
+        },
+    )
+    .unwrap();
+    assert!(register_claim_validator(LUHN_VALIDATOR, |_: &[String], _: &ClaimData| None).is_err());
+
+    assert!(issuer
+        .sign_credential(&claims("4111111111111111", "US", 49))
+        .is_ok());
+    assert!(issuer
+        .sign_credential(&claims("4111111111111112", "US", 49))
+        .is_err());
+    assert!(issuer
+        .sign_credential(&claims("4111111111111111", "ZZ", 49))
+        .is_err());
+    assert!(issuer
+        .sign_credential(&claims("4111111111111111", "US", 50))
+        .is_err());
+}
+
+#[test]
+fn custom_validators_serialize_by_name() {
+    let schema = schema();
+    let json = serde_json::to_string(&schema).unwrap();
+    assert!(json.contains(r#"{"Custom":{"name":"luhn"}}"#));
+    assert!(json.contains(r#"{"Custom":{"name":"divisible_by","parameters":["7"]}}"#));
+    let decoded = serde_json::from_str::<CredentialSchema>(&json).unwrap();
+    assert_eq!(decoded.claims[3].validators.len(), 1);
+
+    // The validator parameters are part of the claim schema transcript
+    let mut altered = schema.clone();
+    altered.claims[3].validators = vec![ClaimValidator::Custom {
+        name: "divisible_by".to_string(),
+        parameters: vec!["11".to_string()],
+    }];
+    let challenge = |s: &CredentialSchema| {
+        let mut transcript = merlin::Transcript::new(b"custom validators");
+        s.claims[3].add_challenge_contribution(&mut transcript);
+        let mut out = [0u8; 32];
+        transcript.challenge_bytes(b"test", &mut out);
+        out
+    };
+    assert_eq!(challenge(&schema), challenge(&decoded));
+    assert_ne!(challenge(&schema), challenge(&altered));
+}
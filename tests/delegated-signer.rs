@@ -0,0 +1,143 @@
+#![cfg(unix)]
+
+use credx::claim::{ClaimData, ClaimType, HashedClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, Credential, CredentialSchema};
+use credx::issuer::{
+    Issuer, IssuerPublic, UnixSocketSigner, UnixSocketSignerServer, MAX_REQUEST_BYTES,
+};
+use credx::knox::bbs::BbsScheme;
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::{RevocationStatement, SignatureStatement};
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::btreeset;
+use rand::thread_rng;
+use std::io::{Read, Write};
+use std::os::unix::net::UnixStream;
+use std::time::Duration;
+
+fn schema() -> CredentialSchema {
+    CredentialSchema::new(
+        Some("Delegated"),
+        None,
+        &[],
+        &[
+            ClaimSchema {
+                claim_type: ClaimType::Revocation,
+                label: "identifier".to_string(),
+                print_friendly: false,
+                optional: false,
+                normalization: None,
+                validators: vec![],
+            },
+            ClaimSchema {
+                claim_type: ClaimType::Hashed,
+                label: "name".to_string(),
+                print_friendly: true,
+                optional: false,
+                normalization: None,
+                validators: vec![],
+            },
+        ],
+    )
+    .unwrap()
+}
+
+fn present(
+    issuer_public: IssuerPublic<BbsScheme>,
+    credential: Credential<BbsScheme>,
+) -> CredxResult<()> {
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "signature".to_string(),
+        issuer: issuer_public.clone(),
+    };
+    let rev_st = RevocationStatement {
+        id: "revocation".to_string(),
+        reference_id: sig_st.id.clone(),
+        accumulator: issuer_public.revocation_registry,
+        verification_key: issuer_public.revocation_verifying_key,
+        claim: 0,
+    };
+    let presentation_schema = PresentationSchema::new(&[sig_st.into(), rev_st.into()]);
+    let nonce = [5u8; 16];
+    let credentials = indexmap! {
+        "signature".to_string() => credential.clone().into(),
+        "revocation".to_string() => credential.into(),
+    };
+    let presentation = Presentation::create(&credentials, &presentation_schema, &nonce)?;
+    presentation.verify(&presentation_schema, &nonce)
+}
+
+#[test]
+fn unix_socket_signer_issues_credentials() {
+    let res = test_unix_socket_signer_issues_credentials();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_unix_socket_signer_issues_credentials() -> CredxResult<()> {
+    let schema = schema();
+    let (_, issuer) = Issuer::<BbsScheme>::new(&schema);
+
+    let path = std::env::temp_dir().join(format!("credx-signer-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut server = UnixSocketSignerServer::<BbsScheme, _>::bind(&path, issuer.signer.clone())?;
+    server.set_timeout(Duration::from_millis(200));
+    std::thread::spawn(move || server.serve());
+
+    // Requests longer than the limit are dropped without an answer
+    let mut stream = UnixStream::connect(&path).unwrap();
+    stream
+        .write_all(&vec![b' '; MAX_REQUEST_BYTES + 1])
+        .unwrap();
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).unwrap();
+    assert!(response.is_empty());
+
+    // Connections are answered on their own threads so an idle one doesn't
+    // block the others and is dropped after the timeout
+    let mut idle = UnixStream::connect(&path).unwrap();
+
+    let (issuer_public, mut delegated) = Issuer::new_with_signer(
+        "did:example:delegated",
+        &schema,
+        UnixSocketSigner::<BbsScheme>::connect(&path)?,
+        thread_rng(),
+    )?;
+    issuer_public.verify_key_proof()?;
+    assert_eq!(
+        serde_json::to_string(&issuer_public.verifying_key).unwrap(),
+        serde_json::to_string(&IssuerPublic::from(&issuer).verifying_key).unwrap()
+    );
+
+    let credential = delegated.sign_credential(&[
+        RevocationClaim::from("0c1d2e3f-4a5b-4c6d-8e7f-9a0b1c2d3e4f").into(),
+        HashedClaim::from("Jane Doe").into(),
+    ])?;
+    present(issuer_public, credential.credential.clone())?;
+    let mut response = Vec::new();
+    idle.read_to_end(&mut response).unwrap();
+    assert!(response.is_empty());
+
+    let mut other = delegated.sign_credential(&[
+        RevocationClaim::from("5f4e3d2c-1b0a-4f9e-8d7c-6b5a4f3e2d1c").into(),
+        HashedClaim::from("John Doe").into(),
+    ])?;
+    let revoked = match &credential.credential.claims[0] {
+        ClaimData::Revocation(rc) => rc.clone(),
+        _ => unreachable!(),
+    };
+    delegated.revoke_credentials(std::slice::from_ref(&revoked))?;
+    assert!(delegated.update_revocation_handle(revoked).is_err());
+    let issuer_public = IssuerPublic::from(&delegated);
+    assert!(present(issuer_public.clone(), credential.credential).is_err());
+
+    let active = match &other.credential.claims[0] {
+        ClaimData::Revocation(rc) => rc.clone(),
+        _ => unreachable!(),
+    };
+    other.credential.revocation_handle = delegated.update_revocation_handle(active)?;
+    present(issuer_public, other.credential)?;
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
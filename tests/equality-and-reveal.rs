@@ -265,6 +265,8 @@ mod reveal_and_equality_tests {
                 claim_type: ClaimType::Hashed,
                 label: SSN_LBL.to_string(),
                 print_friendly: true,
+                optional: false,
+                normalization: None,
                 validators: vec![ClaimValidator::Length {
                     min: Some(3),
                     max: Some(u8::MAX as usize),
@@ -274,6 +276,8 @@ mod reveal_and_equality_tests {
                 claim_type: ClaimType::Hashed,
                 label: NAME_LBL.to_string(),
                 print_friendly: true,
+                optional: false,
+                normalization: None,
                 validators: vec![ClaimValidator::Length {
                     min: None,
                     max: Some(u8::MAX as usize),
@@ -283,6 +287,8 @@ mod reveal_and_equality_tests {
                 claim_type: ClaimType::Revocation,
                 label: ID_LBL.to_string(),
                 print_friendly: false,
+                optional: false,
+                normalization: None,
                 validators: vec![],
             },
         ];
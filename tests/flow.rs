@@ -49,12 +49,16 @@ fn test_presentation_1_credential_works() -> CredxResult<()> {
             claim_type: ClaimType::Revocation,
             label: "identifier".to_string(),
             print_friendly: false,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "name".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![ClaimValidator::Length {
                 min: Some(3),
                 max: Some(u8::MAX as usize),
@@ -64,6 +68,8 @@ fn test_presentation_1_credential_works() -> CredxResult<()> {
             claim_type: ClaimType::Hashed,
             label: "address".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![ClaimValidator::Length {
                 min: None,
                 max: Some(u8::MAX as usize),
@@ -73,6 +79,8 @@ fn test_presentation_1_credential_works() -> CredxResult<()> {
             claim_type: ClaimType::Number,
             label: "age".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![ClaimValidator::Range {
                 min: Some(0),
                 max: Some(u16::MAX as isize),
@@ -179,12 +187,16 @@ fn presentation_decrypt_claim_works() {
             claim_type: ClaimType::Revocation,
             label: "identifier".to_string(),
             print_friendly: false,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "name".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![ClaimValidator::Length {
                 min: Some(3),
                 max: Some(u8::MAX as usize),
@@ -194,6 +206,8 @@ fn presentation_decrypt_claim_works() {
             claim_type: ClaimType::Hashed,
             label: "address".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![ClaimValidator::Length {
                 min: None,
                 max: Some(u8::MAX as usize),
@@ -203,6 +217,8 @@ fn presentation_decrypt_claim_works() {
             claim_type: ClaimType::Number,
             label: "age".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![ClaimValidator::Range {
                 min: Some(0),
                 max: None,
@@ -259,7 +275,7 @@ fn presentation_decrypt_claim_works() {
         &presentation.proofs[&verenc_id]
     {
         let decrypted_name = verenc
-            .decrypt_and_verify(&issuer.verifiable_decryption_key)
+            .decrypt_and_verify(&issuer.signer.verifiable_decryption_key)
             .unwrap();
         assert_eq!(decrypted_name.to_bytes(), b"John Doe");
     } else {
@@ -275,18 +291,24 @@ fn presentation_decrypt_scalar_claim() {
             claim_type: ClaimType::Revocation,
             label: "identifier".to_string(),
             print_friendly: false,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Scalar,
             label: "name".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "address".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![ClaimValidator::Length {
                 min: None,
                 max: Some(u8::MAX as usize),
@@ -296,6 +318,8 @@ fn presentation_decrypt_scalar_claim() {
             claim_type: ClaimType::Number,
             label: "phone-number".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![ClaimValidator::Range {
                 min: Some(0),
                 max: None,
@@ -365,7 +389,7 @@ fn presentation_decrypt_scalar_claim() {
     if let PresentationProofs::VerifiableEncryption(verenc) = &presentation.proofs[&verenc1_id] {
         // This works because the name is less than 32 bytes
         let decrypted_name_scalar = verenc
-            .decrypt_scalar(&issuer.verifiable_decryption_key)
+            .decrypt_scalar(&issuer.signer.verifiable_decryption_key)
             .unwrap();
         let decrypted_name = ScalarClaim::from(decrypted_name_scalar)
             .decode_to_str()
@@ -378,7 +402,7 @@ fn presentation_decrypt_scalar_claim() {
     if let PresentationProofs::VerifiableEncryption(verenc) = &presentation.proofs[&verenc2_id] {
         // This works because the phone number is a less than 32 bytes
         let decrypted_phone_scalar = verenc
-            .decrypt_scalar(&issuer.verifiable_decryption_key)
+            .decrypt_scalar(&issuer.signer.verifiable_decryption_key)
             .unwrap();
         let decrypted_phone = NumberClaim::from(decrypted_phone_scalar);
         assert_eq!(decrypted_phone.value, 8018881111);
@@ -397,12 +421,16 @@ fn presentation_with_domain_proof() {
             claim_type: ClaimType::Revocation,
             label: "identifier".to_string(),
             print_friendly: false,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "name".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![ClaimValidator::Length {
                 min: Some(3),
                 max: Some(u8::MAX as usize),
@@ -412,6 +440,8 @@ fn presentation_with_domain_proof() {
             claim_type: ClaimType::Hashed,
             label: "address".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![ClaimValidator::Length {
                 min: None,
                 max: Some(u8::MAX as usize),
@@ -421,6 +451,8 @@ fn presentation_with_domain_proof() {
             claim_type: ClaimType::Number,
             label: "age".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![ClaimValidator::Range {
                 min: Some(0),
                 max: Some(u16::MAX as isize),
@@ -557,12 +589,16 @@ fn test_presentation_1_credential_alter_revealed_message_fails() -> CredxResult<
             claim_type: ClaimType::Revocation,
             label: "identifier".to_string(),
             print_friendly: false,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "name".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![ClaimValidator::Length {
                 min: Some(3),
                 max: Some(u8::MAX as usize),
@@ -572,6 +608,8 @@ fn test_presentation_1_credential_alter_revealed_message_fails() -> CredxResult<
             claim_type: ClaimType::Hashed,
             label: "address".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![
                 ClaimValidator::Length {
                     min: None,
@@ -592,6 +630,8 @@ fn test_presentation_1_credential_alter_revealed_message_fails() -> CredxResult<
             claim_type: ClaimType::Number,
             label: "age".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![ClaimValidator::Range {
                 min: Some(0),
                 max: Some(u32::MAX as isize),
@@ -693,18 +733,24 @@ fn test_blind_sign_request() -> CredxResult<()> {
             claim_type: ClaimType::Revocation,
             label: "identifier".to_string(),
             print_friendly: false,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Scalar,
             label: "link_secret".to_string(),
             print_friendly: false,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "name".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![ClaimValidator::Length {
                 min: Some(3),
                 max: Some(u8::MAX as usize),
@@ -714,6 +760,8 @@ fn test_blind_sign_request() -> CredxResult<()> {
             claim_type: ClaimType::Hashed,
             label: "address".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![ClaimValidator::Length {
                 min: None,
                 max: Some(u8::MAX as usize),
@@ -723,6 +771,8 @@ fn test_blind_sign_request() -> CredxResult<()> {
             claim_type: ClaimType::Number,
             label: "age".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![ClaimValidator::Range {
                 min: Some(0),
                 max: Some(u16::MAX as isize),
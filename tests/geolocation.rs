@@ -0,0 +1,167 @@
+use blsful::inner_types::*;
+use credx::claim::{
+    ClaimData, ClaimType, GeoBoundingBox, GeolocationClaim, NestedClaimData, RevocationClaim,
+};
+use credx::credential::{ClaimSchema, CredentialSchema, NestedClaimSchema};
+use credx::issuer::Issuer;
+use credx::knox::bbs::BbsScheme;
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::{BoundingBoxStatement, SignatureStatement, Statements};
+use credx::{random_string, CredxResult};
+use indexmap::indexmap;
+use maplit::{btreemap, btreeset};
+use rand::{thread_rng, RngCore};
+
+fn residence_schema() -> CredxResult<CredentialSchema> {
+    CredentialSchema::new_nested(
+        Some("Residence"),
+        None,
+        &[],
+        &[
+            ClaimSchema {
+                claim_type: ClaimType::Revocation,
+                label: "identifier".to_string(),
+                print_friendly: false,
+                optional: false,
+                normalization: None,
+                validators: vec![],
+            }
+            .into(),
+            NestedClaimSchema::geolocation("residence", true, false),
+        ],
+    )
+}
+
+fn residence_claims(point: GeolocationClaim) -> NestedClaimData {
+    NestedClaimData::Object(btreemap! {
+        "identifier".to_string() => ClaimData::from(RevocationClaim::from("1b0d7a3e-9f7c-4c55-8a0e-6c2f1d3e5b4a")).into(),
+        "residence".to_string() => point.into(),
+    })
+}
+
+fn bounding_box_statement(
+    schema: &CredentialSchema,
+    reference_id: &str,
+    boxes: Vec<GeoBoundingBox>,
+) -> BoundingBoxStatement {
+    BoundingBoxStatement {
+        message_generator: G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(
+            b"message generator",
+            b"BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        ),
+        blinder_generator: G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(
+            b"blinder generator",
+            b"BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        ),
+        id: random_string(16, thread_rng()),
+        reference_id: reference_id.to_string(),
+        latitude_claim: schema.get_claim_index("residence.latitude").unwrap(),
+        longitude_claim: schema.get_claim_index("residence.longitude").unwrap(),
+        boxes,
+    }
+}
+
+fn county_boxes() -> CredxResult<Vec<GeoBoundingBox>> {
+    // Two boxes roughly covering an L-shaped county
+    Ok(vec![
+        GeoBoundingBox::from_degrees(40.0, -112.2, 40.8, -111.5)?,
+        GeoBoundingBox::from_degrees(40.8, -112.2, 41.2, -111.9)?,
+    ])
+}
+
+#[test]
+fn geolocation_claims_are_fixed_point() {
+    let point = GeolocationClaim::from_degrees(40.7608, -111.891).unwrap();
+    assert_eq!(point.latitude, 407_608_000);
+    assert_eq!(point.longitude, -1_118_910_000);
+    assert_eq!(point.latitude_degrees(), 40.7608);
+    let [latitude, longitude] = point.to_claims();
+    assert_eq!(
+        GeolocationClaim::from_claims(&latitude, &longitude).unwrap(),
+        point
+    );
+    assert!(GeolocationClaim::from_degrees(90.1, 0.0).is_err());
+    assert!(GeolocationClaim::from_degrees(0.0, f64::NAN).is_err());
+    assert!(GeoBoundingBox::from_degrees(41.0, -111.0, 40.0, -112.0).is_err());
+
+    let schema = residence_schema().unwrap();
+    assert_eq!(
+        schema.claim_indices.iter().collect::<Vec<_>>(),
+        vec!["identifier", "residence.latitude", "residence.longitude"]
+    );
+}
+
+#[test]
+fn geolocation_within_bounding_boxes() {
+    let res = test_geolocation_within_bounding_boxes();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_geolocation_within_bounding_boxes() -> CredxResult<()> {
+    let schema = residence_schema()?;
+    let (issuer_public, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+    // Inside the second box only
+    let point = GeolocationClaim::from_degrees(41.05, -112.0)?;
+    let credential = issuer.sign_nested_credential(&residence_claims(point))?;
+
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {},
+        id: random_string(16, thread_rng()),
+        issuer: issuer_public,
+    };
+    let bbox_st = bounding_box_statement(&schema, &sig_st.id, county_boxes()?);
+    assert_eq!(bbox_st.select_box(&point), Some(1));
+
+    let mut statements = vec![sig_st.clone().into()];
+    statements.extend(bbox_st.statements_for_point::<BbsScheme>(&point)?);
+    let presentation_schema = PresentationSchema::new(&statements);
+
+    let mut nonce = [0u8; 16];
+    thread_rng().fill_bytes(&mut nonce);
+    let credentials = indexmap! { sig_st.id.clone() => credential.credential.into() };
+    let presentation = Presentation::create(&credentials, &presentation_schema, &nonce)?;
+
+    assert_eq!(bbox_st.find_box(&presentation_schema)?, 1);
+    presentation.verify(&presentation_schema, &nonce)?;
+    assert!(presentation
+        .disclosed_messages
+        .values()
+        .all(|dm| dm.is_empty()));
+    Ok(())
+}
+
+#[test]
+fn geolocation_outside_bounding_boxes() {
+    let schema = residence_schema().unwrap();
+    let (issuer_public, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+    // Inside the union of the boxes' extent but not inside either box
+    let point = GeolocationClaim::from_degrees(41.05, -111.6).unwrap();
+    let credential = issuer
+        .sign_nested_credential(&residence_claims(point))
+        .unwrap();
+
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {},
+        id: random_string(16, thread_rng()),
+        issuer: issuer_public,
+    };
+    let bbox_st = bounding_box_statement(&schema, &sig_st.id, county_boxes().unwrap());
+    assert!(bbox_st.statements_for_point::<BbsScheme>(&point).is_err());
+
+    // Claiming the point is in a box it isn't fails to prove
+    let mut statements = vec![sig_st.clone().into()];
+    statements.extend(bbox_st.statements::<BbsScheme>(0).unwrap());
+    let presentation_schema = PresentationSchema::new(&statements);
+    let nonce = [1u8; 16];
+    let credentials = indexmap! { sig_st.id.clone() => credential.credential.into() };
+    assert!(Presentation::create(&credentials, &presentation_schema, &nonce).is_err());
+
+    // A schema with altered bounds isn't accepted for the statement
+    let mut presentation_schema = presentation_schema;
+    for statement in presentation_schema.statements.values_mut() {
+        if let Statements::Range(r) = statement {
+            r.upper = Some(r.upper.unwrap() + 1);
+        }
+    }
+    assert!(bbox_st.find_box(&presentation_schema).is_err());
+}
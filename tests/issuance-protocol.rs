@@ -0,0 +1,154 @@
+use credx::blind::BlindCredentialRequest;
+use credx::claim::{ClaimData, ClaimType, HashedClaim, RevocationClaim};
+use credx::credential::{
+    ClaimSchema, CredentialOffer, CredentialRequest, CredentialResponse, CredentialSchema,
+};
+use credx::issuer::{Issuer, IssuerPublic, OfferIssuer, OfferTracker};
+use credx::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use credx::knox::{bbs::BbsScheme, ps::PsScheme};
+use credx::CredxResult;
+use maplit::btreemap;
+use serde::{de::DeserializeOwned, Serialize};
+
+fn schema() -> CredentialSchema {
+    let claim = |claim_type, label: &str| ClaimSchema {
+        claim_type,
+        label: label.to_string(),
+        print_friendly: false,
+        optional: false,
+        normalization: None,
+        validators: vec![],
+    };
+    CredentialSchema::new(
+        Some("Protocol"),
+        None,
+        &["link_secret"],
+        &[
+            claim(ClaimType::Revocation, "identifier"),
+            claim(ClaimType::Hashed, "name"),
+            claim(ClaimType::Hashed, "link_secret"),
+        ],
+    )
+    .unwrap()
+}
+
+/// Send a message over the wire
+fn send<T: Serialize + DeserializeOwned>(message: &T) -> T {
+    serde_json::from_str(&serde_json::to_string(message).unwrap()).unwrap()
+}
+
+#[test]
+fn issuance_protocol() {
+    let res = test_issuance_protocol::<BbsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+    let res = test_issuance_protocol::<PsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_issuance_protocol<S: ShortGroupSignatureScheme>() -> CredxResult<()> {
+    let (issuer_public, mut issuer) = Issuer::<S>::new(&schema());
+    let mut offers = OfferTracker::new();
+
+    // Blind issuance with a link secret
+    let offer = CredentialOffer::new_blind(
+        &btreemap! {
+            "identifier".to_string() => RevocationClaim::from("2c3d4e5f-6a7b-4c8d-9e0f-1a2b3c4d5e6f").into(),
+            "name".to_string() => HashedClaim::from("Jane Doe").into(),
+        },
+        &["link_secret"],
+        issuer_public.clone(),
+    )?;
+    offers.add_offer(&offer)?;
+    assert!(offers.add_offer(&offer).is_err());
+
+    let link_secret = btreemap! {
+        "link_secret".to_string() => ClaimData::from(HashedClaim::from("my link secret")),
+    };
+    let offer = send(&offer);
+    assert!(CredentialRequest::new(&offer, &btreemap! {}).is_err());
+    let (request, state) = CredentialRequest::new(&offer, &link_secret)?;
+
+    // A blind request that isn't bound to the offer nonce
+    let (unbound, _) = BlindCredentialRequest::new(&issuer_public, &link_secret)?;
+    let mut replayed = request.clone();
+    replayed.blind_request = Some(unbound);
+    assert!(offers.issue(&mut issuer, &replayed).is_err());
+    // A request without the blind claims
+    replayed.blind_request = None;
+    assert!(offers.issue(&mut issuer, &replayed).is_err());
+
+    let response = offers.issue(&mut issuer, &send(&request))?;
+    // Each offer is answered once
+    assert!(offers.issue(&mut issuer, &request).is_err());
+    assert!(offers.get_offer(&offer.offer_id).is_none());
+
+    let credential = send(&response).to_credential(&state)?;
+    credential.verify()?;
+    assert_eq!(
+        credential.credential.claims[2],
+        HashedClaim::from("my link secret").into()
+    );
+
+    // Issuance with only known claims
+    let offer = CredentialOffer::new(
+        &[
+            RevocationClaim::from("7a8b9c0d-1e2f-4a3b-8c4d-5e6f7a8b9c0d").into(),
+            HashedClaim::from("John Doe").into(),
+            HashedClaim::from("issuer chosen").into(),
+        ],
+        IssuerPublic::from(&issuer),
+    );
+    offers.add_offer(&offer)?;
+    let (request, state) = CredentialRequest::new(&send(&offer), &btreemap! {})?;
+    let response: CredentialResponse<S> = offers.issue(&mut issuer, &request)?;
+    // Responses must be for the requested offer
+    let mut wrong = response.clone();
+    wrong.offer_id = [0u8; 16];
+    assert!(wrong.to_credential(&state).is_err());
+    response.to_credential(&state)?;
+
+    // Cancelled offers can't be answered
+    let offer = CredentialOffer::new(
+        &[
+            RevocationClaim::from("0f1e2d3c-4b5a-4698-a7b6-c5d4e3f2a1b0").into(),
+            HashedClaim::from("Jim Doe").into(),
+            HashedClaim::from("issuer chosen").into(),
+        ],
+        IssuerPublic::from(&issuer),
+    );
+    offers.add_offer(&offer)?;
+    let (request, _) = CredentialRequest::new(&offer, &btreemap! {})?;
+    offers.cancel_offer(&offer.offer_id)?;
+    assert!(offers.issue(&mut issuer, &request).is_err());
+
+    // Expired offers can't be answered and are forgotten
+    let offer = CredentialOffer::new(
+        &[
+            RevocationClaim::from("5d4c3b2a-1f0e-4d9c-8b7a-6f5e4d3c2b1a").into(),
+            HashedClaim::from("Joan Doe").into(),
+            HashedClaim::from("issuer chosen").into(),
+        ],
+        IssuerPublic::from(&issuer),
+    );
+    let now = chrono::Utc::now().timestamp();
+    offers.add_offer_until(&offer, now - 1)?;
+    assert!(offers.get_offer(&offer.offer_id).is_none());
+    let (request, state) = CredentialRequest::new(&offer, &btreemap! {})?;
+    assert!(offers.issue(&mut issuer, &request).is_err());
+
+    // Answered offer ids are kept until their offer expires
+    assert_eq!(offers.answered.len(), 3);
+    let mut offers = send(&offers);
+    assert_eq!(offers.answered.len(), 3);
+    for expires in offers.answered.values_mut() {
+        *expires = now - 1;
+    }
+    offers.prune();
+    assert!(offers.answered.is_empty());
+
+    // Any issuer that can sign answers offers
+    offers.add_offer(&offer)?;
+    let signer: &mut dyn OfferIssuer<S> = &mut issuer;
+    offers.issue(signer, &request)?.to_credential(&state)?;
+    Ok(())
+}
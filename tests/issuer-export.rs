@@ -0,0 +1,121 @@
+use credx::claim::{ClaimType, HashedClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CredentialSchema};
+use credx::issuer::{Argon2Params, EncryptedIssuer, Issuer, IssuerPublic};
+use credx::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use credx::knox::{bbs::BbsScheme, ps::PsScheme};
+use credx::CredxResult;
+use rand::thread_rng;
+
+// Keep the tests fast, real exports should use the defaults
+const TEST_PARAMS: Argon2Params = Argon2Params {
+    memory_kib: 64,
+    iterations: 1,
+    parallelism: 1,
+};
+
+fn schema() -> CredentialSchema {
+    CredentialSchema::new(
+        Some("Export"),
+        None,
+        &[],
+        &[
+            ClaimSchema {
+                claim_type: ClaimType::Revocation,
+                label: "identifier".to_string(),
+                print_friendly: false,
+                optional: false,
+                normalization: None,
+                validators: vec![],
+            },
+            ClaimSchema {
+                claim_type: ClaimType::Hashed,
+                label: "name".to_string(),
+                print_friendly: true,
+                optional: false,
+                normalization: None,
+                validators: vec![],
+            },
+        ],
+    )
+    .unwrap()
+}
+
+fn same_public<S: ShortGroupSignatureScheme>(a: &IssuerPublic<S>, b: &IssuerPublic<S>) -> bool {
+    serde_json::to_vec(a).unwrap() == serde_json::to_vec(b).unwrap()
+}
+
+#[test]
+fn password_export_round_trip() {
+    let res = test_password_export_round_trip();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_password_export_round_trip() -> CredxResult<()> {
+    let (issuer_public, issuer) = Issuer::<BbsScheme>::new(&schema());
+    let exported = issuer.export_with_password_and_params(
+        b"correct horse battery staple",
+        TEST_PARAMS,
+        thread_rng(),
+    )?;
+    let json = serde_json::to_string(&exported).unwrap();
+    assert!(!json.contains("signing_key"));
+    let exported: EncryptedIssuer<BbsScheme> = serde_json::from_str(&json).unwrap();
+    assert!(same_public(&exported.public, &issuer_public));
+
+    let mut imported = exported.decrypt_with_password(b"correct horse battery staple")?;
+    assert!(same_public(&IssuerPublic::from(&imported), &issuer_public));
+    let credential = imported.sign_credential(&[
+        RevocationClaim::from("3f2a1b0c-9d8e-4f7a-6b5c-4d3e2f1a0b9c").into(),
+        HashedClaim::from("Jane Doe").into(),
+    ])?;
+    let messages = credential
+        .credential
+        .claims
+        .iter()
+        .map(|c| c.to_scalar())
+        .collect::<Vec<_>>();
+    assert!(bool::from(
+        credential
+            .credential
+            .signature
+            .verify(&issuer_public.verifying_key, &messages)
+    ));
+
+    assert!(exported.decrypt_with_password(b"Tr0ub4dor&3").is_err());
+    assert!(exported.decrypt_with_key(&[0u8; 32]).is_err());
+    Ok(())
+}
+
+#[test]
+fn key_export_round_trip() {
+    let (issuer_public, issuer) = Issuer::<PsScheme>::new(&schema());
+    let key = [42u8; 32];
+    let exported = issuer.export_with_key(&key, thread_rng()).unwrap();
+    let imported = exported.decrypt_with_key(&key).unwrap();
+    assert!(same_public(&IssuerPublic::from(&imported), &issuer_public));
+    assert!(exported.decrypt_with_key(&[43u8; 32]).is_err());
+    assert!(exported.decrypt_with_password(b"password").is_err());
+}
+
+#[test]
+fn tampered_public_parts_are_rejected() {
+    let (_, issuer) = Issuer::<BbsScheme>::new(&schema());
+    let (other_public, _) = Issuer::<BbsScheme>::new(&schema());
+    let key = [7u8; 32];
+
+    let mut exported = issuer.export_with_key(&key, thread_rng()).unwrap();
+    exported.public = other_public;
+    assert!(exported.decrypt_with_key(&key).is_err());
+
+    let mut exported = issuer.export_with_key(&key, thread_rng()).unwrap();
+    exported.version += 1;
+    assert!(exported.decrypt_with_key(&key).is_err());
+
+    let mut exported = issuer
+        .export_with_password_and_params(b"password", TEST_PARAMS, thread_rng())
+        .unwrap();
+    if let credx::issuer::IssuerWrappingKdf::Argon2id { iterations, .. } = &mut exported.kdf {
+        *iterations += 1;
+    }
+    assert!(exported.decrypt_with_password(b"password").is_err());
+}
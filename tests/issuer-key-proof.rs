@@ -0,0 +1,131 @@
+use credx::claim::{ClaimType, HashedClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CredentialSchema};
+use credx::issuer::{Issuer, IssuerPublic};
+use credx::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use credx::knox::{bbs::BbsScheme, ps::PsScheme};
+use credx::presentation::PresentationSchema;
+use credx::statement::SignatureStatement;
+use credx::CredxResult;
+use maplit::btreeset;
+use rand::thread_rng;
+
+fn schema() -> CredentialSchema {
+    let claim = |claim_type, label: &str| ClaimSchema {
+        claim_type,
+        label: label.to_string(),
+        print_friendly: false,
+        optional: false,
+        normalization: None,
+        validators: vec![],
+    };
+    CredentialSchema::new(
+        Some("Key proof"),
+        None,
+        &[],
+        &[
+            claim(ClaimType::Revocation, "identifier"),
+            claim(ClaimType::Hashed, "name"),
+            claim(ClaimType::Hashed, "address"),
+        ],
+    )
+    .unwrap()
+}
+
+#[test]
+fn issuer_key_proofs() {
+    let res = test_issuer_key_proofs::<BbsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+    let res = test_issuer_key_proofs::<PsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_issuer_key_proofs<S: ShortGroupSignatureScheme>() -> CredxResult<()> {
+    let (issuer_public, mut issuer) = Issuer::<S>::new(&schema());
+    let (other_public, _) = Issuer::<S>::new(&schema());
+    issuer_public.verify_key_proof()?;
+    let bundle = issuer.sign_credential(&[
+        RevocationClaim::from("5e6f7a8b-9c0d-4e1f-8a2b-3c4d5e6f7a8b").into(),
+        HashedClaim::from("Jane Doe").into(),
+        HashedClaim::from("1 Main St").into(),
+    ])?;
+    bundle.issuer.verify_key_proof()?;
+
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "signature".to_string(),
+        issuer: issuer_public.clone(),
+    };
+    PresentationSchema::new(&[sig_st.clone().into()]).verify_issuer_key_proofs()?;
+
+    // The proof is bound to the issuer's keys and id
+    let mut forged = issuer_public.clone();
+    forged.verifying_key = other_public.verifying_key.clone();
+    assert!(forged.verify_key_proof().is_err());
+    let mut forged = issuer_public.clone();
+    forged.revocation_verifying_key = other_public.revocation_verifying_key;
+    assert!(forged.verify_key_proof().is_err());
+    let mut forged = issuer_public.clone();
+    forged.verifiable_encryption_key = other_public.verifiable_encryption_key;
+    assert!(forged.verify_key_proof().is_err());
+    let mut forged = issuer_public.clone();
+    forged.id = other_public.id.clone();
+    assert!(forged.verify_key_proof().is_err());
+    let mut forged = issuer_public.clone();
+    forged.key_proof = other_public.key_proof.clone();
+    assert!(forged.verify_key_proof().is_err());
+    let mut sig_st = sig_st;
+    sig_st.issuer = forged;
+    assert!(PresentationSchema::new(&[sig_st.into()])
+        .verify_issuer_key_proofs()
+        .is_err());
+
+    // Issuers without a proof can create one
+    issuer.key_proof = None;
+    let issuer_public = IssuerPublic::from(&issuer);
+    assert!(issuer_public.verify_key_proof().is_err());
+    issuer.prove_keys(thread_rng())?.verify_key_proof()?;
+
+    // Rotated keys get a new proof and the previous key keeps its proof
+    let rotated = issuer.rotate_keys(thread_rng())?;
+    rotated.verify_key_proof()?;
+    let mut stale = rotated.clone();
+    stale.key_proof = bundle.issuer.key_proof.clone();
+    assert!(stale.verify_key_proof().is_err());
+    let mut uncovered = rotated.clone();
+    uncovered.previous_keys[0].key_proof = None;
+    assert!(uncovered.verify_key_proof().is_err());
+    let mut uncovered = rotated.clone();
+    uncovered.previous_keys[0].verifying_key = other_public.verifying_key.clone();
+    assert!(uncovered.verify_key_proof().is_err());
+
+    // Issuers with another signer get their proof from the signer
+    let (signer_public, mut signer_issuer) =
+        Issuer::new_with_signer(&issuer.id, &schema(), issuer.signer.clone(), thread_rng())?;
+    signer_public.verify_key_proof()?;
+    signer_issuer
+        .sign_credential(&[
+            RevocationClaim::from("delegated").into(),
+            HashedClaim::from("John Doe").into(),
+            HashedClaim::from("P Sherman 42 Wallaby Way Sydney").into(),
+        ])?
+        .verify()?;
+
+    // A new signer proves its own keys, the old key keeps its proof
+    issuer.rotate_keys(thread_rng())?;
+    let next_public = signer_issuer.rotate_signer(issuer.signer.clone())?;
+    next_public.verify_key_proof()?;
+    assert!(next_public.previous_keys[0].key_proof.is_some());
+    Ok(())
+}
+
+#[test]
+fn ps_blind_generators_must_match() {
+    let (issuer_public, _) = Issuer::<PsScheme>::new(&schema());
+    // The blinding generators must use the same secrets as the verifying generators
+    let mut forged = issuer_public.clone();
+    forged.verifying_key.y_blinds.swap(0, 1);
+    assert!(forged.verify_key_proof().is_err());
+    let mut forged = issuer_public;
+    forged.verifying_key.y_blinds.pop();
+    assert!(forged.verify_key_proof().is_err());
+}
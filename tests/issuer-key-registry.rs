@@ -0,0 +1,112 @@
+use credx::claim::{ClaimType, HashedClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CompactCredentialBundle, CredentialSchema};
+use credx::issuer::{Issuer, IssuerKeyRegistry, IssuerPublic};
+use credx::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use credx::knox::{bbs::BbsScheme, ps::PsScheme};
+use credx::presentation::{CompactPresentationSchema, Presentation, PresentationSchema};
+use credx::statement::{RevocationStatement, SignatureStatement};
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::btreeset;
+
+fn schema() -> CredentialSchema {
+    let claim = |claim_type, label: &str| ClaimSchema {
+        claim_type,
+        label: label.to_string(),
+        print_friendly: false,
+        optional: false,
+        normalization: None,
+        validators: vec![],
+    };
+    CredentialSchema::new(
+        Some("Registry"),
+        None,
+        &[],
+        &[
+            claim(ClaimType::Revocation, "identifier"),
+            claim(ClaimType::Hashed, "name"),
+        ],
+    )
+    .unwrap()
+}
+
+#[test]
+fn compact_bundles_and_schemas() {
+    let res = test_compact_bundles_and_schemas::<BbsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+    let res = test_compact_bundles_and_schemas::<PsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_compact_bundles_and_schemas<S: ShortGroupSignatureScheme>() -> CredxResult<()> {
+    let (issuer_public, mut issuer) = Issuer::<S>::new(&schema());
+    let (other_public, _) = Issuer::<S>::new(&schema());
+    assert_ne!(issuer_public.key_id(), other_public.key_id());
+
+    let mut registry = IssuerKeyRegistry::new();
+    let key_id = registry.add(&issuer_public);
+    assert_eq!(key_id, issuer_public.key_id());
+
+    let bundle = issuer.sign_credential(&[
+        RevocationClaim::from("0b1c2d3e-4f5a-4b6c-8d7e-9f0a1b2c3d4e").into(),
+        HashedClaim::from("Jane Doe").into(),
+    ])?;
+    let revoked = RevocationClaim::from("9a8b7c6d-5e4f-4a3b-8c2d-1e0f9a8b7c6d");
+    issuer.sign_credential(&[revoked.clone().into(), HashedClaim::from("John Doe").into()])?;
+    // Revoking another credential changes the registry but not the key id
+    issuer.revoke_credentials(&[revoked])?;
+    let issuer_public = IssuerPublic::from(&issuer);
+    assert_eq!(issuer_public.key_id(), key_id);
+    assert_ne!(
+        issuer_public.revocation_registry,
+        bundle.issuer.revocation_registry
+    );
+
+    let compact = CompactCredentialBundle::from(&bundle);
+    assert_eq!(compact.issuer_key_id, key_id);
+    assert!(
+        serde_json::to_string(&compact).unwrap().len()
+            < serde_json::to_string(&bundle).unwrap().len()
+    );
+    let resolved = compact.resolve(&registry)?;
+    resolved.verify()?;
+    assert_eq!(
+        resolved.issuer.revocation_registry,
+        bundle.issuer.revocation_registry
+    );
+    assert!(compact.resolve(&IssuerKeyRegistry::new()).is_err());
+
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "signature".to_string(),
+        issuer: bundle.issuer.clone(),
+    };
+    let rev_st = RevocationStatement {
+        id: "revocation".to_string(),
+        reference_id: sig_st.id.clone(),
+        accumulator: bundle.issuer.revocation_registry,
+        verification_key: bundle.issuer.revocation_verifying_key,
+        claim: 0,
+    };
+    let presentation_schema = PresentationSchema::new(&[sig_st.into(), rev_st.into()]);
+    let compact = CompactPresentationSchema::from(&presentation_schema);
+    let json = serde_json::to_string(&compact).unwrap();
+    assert!(json.len() < serde_json::to_string(&presentation_schema).unwrap().len());
+
+    // The verifier resolves the schema it received from its own registry
+    let compact: CompactPresentationSchema<S> = serde_json::from_str(&json).unwrap();
+    let resolved = compact.resolve(&registry)?;
+    let nonce = [3u8; 16];
+    let credentials = indexmap! {
+        "signature".to_string() => bundle.credential.clone().into(),
+        "revocation".to_string() => bundle.credential.clone().into(),
+    };
+    let presentation = Presentation::create(&credentials, &resolved, &nonce)?;
+    presentation.verify(&presentation_schema, &nonce)?;
+    presentation.verify(&resolved, &nonce)?;
+
+    // Removed issuers can't be resolved
+    assert!(registry.remove(&key_id).is_some());
+    assert!(compact.resolve(&registry).is_err());
+    Ok(())
+}
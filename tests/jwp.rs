@@ -0,0 +1,343 @@
+use base64::Engine;
+use blsful::inner_types::*;
+use credx::claim::{ClaimType, HashedClaim, NumberClaim, RevocationClaim, ScalarClaim};
+use credx::credential::{ClaimSchema, CredentialBundle, CredentialSchema};
+use credx::error::Error;
+use credx::issuer::{Issuer, IssuerKeyRegistry};
+use credx::jwp::{BbsIssuedJwp, BbsPresentedJwp, IssuedJwp, JwpError, PresentedJwp};
+use credx::knox::bbs::{BbsCiphersuite, BbsScheme};
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::{
+    CommitmentStatement, RangeStatement, RevocationStatement, SignatureStatement,
+};
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::btreeset;
+use rand::thread_rng;
+
+fn schema() -> CredentialSchema {
+    let claim = |claim_type, label: &str| ClaimSchema {
+        claim_type,
+        label: label.to_string(),
+        print_friendly: true,
+        optional: false,
+        normalization: None,
+        validators: vec![],
+    };
+    CredentialSchema::new(
+        Some("JWP"),
+        None,
+        &[],
+        &[
+            claim(ClaimType::Revocation, "identifier"),
+            claim(ClaimType::Hashed, "name"),
+            claim(ClaimType::Number, "age"),
+            claim(ClaimType::Scalar, "link_secret"),
+        ],
+    )
+    .unwrap()
+}
+
+fn issue() -> CredxResult<CredentialBundle<BbsScheme>> {
+    let (_, mut issuer) = Issuer::<BbsScheme>::new(&schema());
+    issuer.sign_credential(&[
+        RevocationClaim::from("0d2f4e8a-52c1-4d1a-9b8e-5f1c0a7e3b21").into(),
+        HashedClaim::from("Jane Doe").into(),
+        NumberClaim::from(42).into(),
+        ScalarClaim::from(Scalar::from(7u64)).into(),
+    ])
+}
+
+fn jwp_error<T: std::fmt::Debug>(res: CredxResult<T>) -> JwpError {
+    match res {
+        Err(Error::InvalidJwp(e)) => e,
+        res => panic!("expected a JWP error, found {:?}", res),
+    }
+}
+
+#[test]
+fn jwp_issued_form() -> CredxResult<()> {
+    let bundle = issue()?;
+    let mut registry = IssuerKeyRegistry::new();
+    registry.add(&bundle.issuer);
+
+    let jwp = IssuedJwp::try_from(&bundle)?;
+    assert_eq!(jwp.header.alg, "credx-BBS");
+    assert_eq!(
+        jwp.header.claims,
+        ["identifier", "name", "age", "link_secret"]
+    );
+    let compact = jwp.to_compact()?;
+    assert_eq!(compact.split('.').count(), 3);
+    assert_eq!(IssuedJwp::from_compact(&compact)?, jwp);
+
+    let read = IssuedJwp::from_compact(&compact)?.to_bundle(&registry)?;
+    assert!(read.verify().is_ok());
+    assert_eq!(read.credential.claims, bundle.credential.claims);
+    assert_eq!(read.issuer.key_id(), bundle.issuer.key_id());
+
+    // The payloads are covered by the signature
+    let mut tampered = jwp.clone();
+    tampered.payloads[2] = b"num:43".to_vec();
+    assert!(tampered.to_bundle(&registry)?.verify().is_err());
+    // The header is checked against the issuer
+    let mut tampered = jwp.clone();
+    tampered.header.claims.swap(1, 2);
+    assert_eq!(
+        jwp_error(tampered.to_bundle(&registry)),
+        JwpError::ClaimsMismatch
+    );
+    let mut tampered = jwp.clone();
+    // The proof isn't the registered BBS algorithm's proof
+    tampered.header.alg = "BBS".to_string();
+    assert_eq!(
+        jwp_error(tampered.to_bundle(&registry)),
+        JwpError::UnsupportedAlgorithm("BBS".to_string())
+    );
+    let mut tampered = jwp.clone();
+    tampered.payloads[2] = b"ut8:Jane Doe".to_vec();
+    assert_eq!(
+        jwp_error(tampered.to_bundle(&registry)),
+        JwpError::InvalidPayload("age".to_string())
+    );
+    assert!(jwp.to_bundle(&IssuerKeyRegistry::new()).is_err());
+
+    let parts = compact.split('.').collect::<Vec<_>>();
+    let missing = format!("{}.~{}.{}", parts[0], parts[1], parts[2]);
+    assert_eq!(
+        jwp_error(IssuedJwp::from_compact(&missing)),
+        JwpError::MissingPayload("identifier".to_string())
+    );
+    assert_eq!(
+        jwp_error(IssuedJwp::from_compact(&parts[..2].join("."))),
+        JwpError::InvalidSerialization
+    );
+    assert_eq!(
+        jwp_error(IssuedJwp::from_compact(&format!(
+            "e30.{}.{}",
+            parts[1], parts[2]
+        ))),
+        JwpError::InvalidHeader
+    );
+    Ok(())
+}
+
+#[test]
+fn jwp_presented_form() -> CredxResult<()> {
+    let bundle = issue()?;
+    let issuer = &bundle.issuer;
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "signature".to_string(),
+        issuer: issuer.clone(),
+    };
+    let rev_st = RevocationStatement {
+        id: "revocation".to_string(),
+        reference_id: sig_st.id.clone(),
+        accumulator: issuer.revocation_registry,
+        verification_key: issuer.revocation_verifying_key,
+        claim: 0,
+    };
+    let comm_st = CommitmentStatement {
+        id: "commitment".to_string(),
+        reference_id: sig_st.id.clone(),
+        message_generator: G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(
+            b"message generator",
+            b"BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        ),
+        blinder_generator: G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(
+            b"blinder generator",
+            b"BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        ),
+        claim: 2,
+    };
+    let range_st = RangeStatement {
+        id: "range".to_string(),
+        reference_id: comm_st.id.clone(),
+        signature_id: sig_st.id.clone(),
+        claim: 2,
+        lower: Some(18),
+        upper: None,
+    };
+    let presentation_schema = PresentationSchema::new(&[
+        sig_st.clone().into(),
+        rev_st.into(),
+        comm_st.into(),
+        range_st.into(),
+    ]);
+    let nonce = b"jwp presentation nonce";
+    let credentials = indexmap! { "signature".to_string() => bundle.credential.clone().into() };
+    let presentation = Presentation::create(&credentials, &presentation_schema, nonce)?;
+
+    let jwp = PresentedJwp::new(&presentation, &presentation_schema, nonce)?;
+    assert_eq!(jwp.payloads.iter().filter(|p| p.is_some()).count(), 1);
+    assert_eq!(jwp.payloads[1].as_deref(), Some(b"ut8:Jane Doe".as_slice()));
+    let compact = jwp.to_compact()?;
+    assert_eq!(compact.split('.').count(), 4);
+    assert_eq!(PresentedJwp::from_compact(&compact)?, jwp);
+
+    let read = PresentedJwp::from_compact(&compact)?.to_presentation(&presentation_schema)?;
+    assert!(read.verify(&presentation_schema, nonce).is_ok());
+    assert!(read.verify(&presentation_schema, b"another nonce").is_err());
+    assert_eq!(
+        serde_json::to_string(&read).unwrap(),
+        serde_json::to_string(&presentation).unwrap()
+    );
+
+    // Disclosing a different value or claim doesn't verify
+    let mut tampered = jwp.clone();
+    tampered.payloads[1] = Some(b"ut8:John Doe".to_vec());
+    let read = tampered.to_presentation(&presentation_schema)?;
+    assert!(read.verify(&presentation_schema, nonce).is_err());
+    let mut tampered = jwp.clone();
+    tampered.payloads[2] = Some(b"num:42".to_vec());
+    let read = tampered.to_presentation(&presentation_schema)?;
+    assert!(read.verify(&presentation_schema, nonce).is_err());
+
+    let (other, _) = Issuer::<BbsScheme>::new(&schema());
+    let mut tampered = jwp.clone();
+    tampered.issuer_header.kid = hex::encode(other.key_id());
+    assert!(matches!(
+        jwp_error(tampered.to_presentation(&presentation_schema)),
+        JwpError::IssuerMismatch(_)
+    ));
+    let mut tampered = jwp.clone();
+    tampered.payloads.pop();
+    assert_eq!(
+        jwp_error(tampered.to_presentation(&presentation_schema)),
+        JwpError::ClaimsMismatch
+    );
+
+    // A JWP only holds one issuer's payloads
+    let other_st = SignatureStatement {
+        disclosed: btreeset! {},
+        id: "other".to_string(),
+        issuer: other,
+    };
+    let two_signatures = PresentationSchema::new(&[sig_st.into(), other_st.into()]);
+    assert_eq!(
+        jwp_error(PresentedJwp::new(&presentation, &two_signatures, nonce)),
+        JwpError::UnsupportedPresentation
+    );
+    assert_eq!(
+        jwp_error(jwp.to_presentation(&two_signatures)),
+        JwpError::UnsupportedPresentation
+    );
+    Ok(())
+}
+
+fn bbs_keys() -> ([u8; 32], [u8; 96]) {
+    let cs = BbsCiphersuite::Bls12381Sha256;
+    let sk = cs.key_gen(&[9u8; 32], b"jwp", None).unwrap();
+    let pk = cs.sk_to_pk(&sk).unwrap();
+    (sk, pk)
+}
+
+fn bbs_claims() -> Vec<credx::claim::ClaimData> {
+    vec![
+        RevocationClaim::from("0d2f4e8a-52c1-4d1a-9b8e-5f1c0a7e3b21").into(),
+        HashedClaim::from("Jane Doe").into(),
+        NumberClaim::from(42).into(),
+        ScalarClaim::from(Scalar::from(7u64)).into(),
+    ]
+}
+
+fn decode(part: &str) -> Vec<u8> {
+    base64::prelude::BASE64_URL_SAFE_NO_PAD
+        .decode(part)
+        .unwrap()
+}
+
+#[test]
+fn jwp_bbs_issued_form() -> CredxResult<()> {
+    let (sk, pk) = bbs_keys();
+    let jwp = BbsIssuedJwp::sign(&schema(), &bbs_claims(), "issuer-key-1", &sk, &pk)?;
+    let header = jwp.header()?;
+    assert_eq!(header.alg, "BBS");
+    assert_eq!(header.kid, "issuer-key-1");
+    assert_eq!(jwp.proof.len(), BbsCiphersuite::SIGNATURE_BYTES);
+    jwp.verify(&pk)?;
+    assert_eq!(jwp.claims(&schema())?, bbs_claims());
+
+    // The compact form is a BBS signature over the payloads with the
+    // header octets as the BBS header
+    let compact = jwp.to_compact();
+    let parts = compact.split('.').collect::<Vec<_>>();
+    let payloads = parts[1].split('~').map(decode).collect::<Vec<_>>();
+    BbsCiphersuite::Bls12381Sha256.verify(&pk, &decode(parts[2]), &decode(parts[0]), &payloads)?;
+    assert_eq!(BbsIssuedJwp::from_compact(&compact)?, jwp);
+
+    let mut tampered = jwp.clone();
+    tampered.payloads[2] = b"num:43".to_vec();
+    assert!(tampered.verify(&pk).is_err());
+    let mut tampered = jwp.clone();
+    tampered.header = serde_json::to_vec(&serde_json::json!({
+        "alg": "BBS",
+        "kid": "issuer-key-2",
+        "claims": header.claims,
+    }))
+    .unwrap();
+    assert!(tampered.verify(&pk).is_err());
+    let (_, other_pk) = {
+        let cs = BbsCiphersuite::Bls12381Sha256;
+        let sk = cs.key_gen(&[8u8; 32], b"jwp", None)?;
+        let pk = cs.sk_to_pk(&sk)?;
+        (sk, pk)
+    };
+    assert!(jwp.verify(&other_pk).is_err());
+
+    // The credx profile and the registered algorithm aren't interchangeable
+    let credx_jwp = IssuedJwp::try_from(&issue()?)?.to_compact()?;
+    assert_eq!(
+        jwp_error(BbsIssuedJwp::from_compact(&credx_jwp).and_then(|j| j.header())),
+        JwpError::UnsupportedAlgorithm("credx-BBS".to_string())
+    );
+    assert_eq!(
+        jwp_error(IssuedJwp::from_compact(&compact)?.to_bundle(&IssuerKeyRegistry::new())),
+        JwpError::UnsupportedAlgorithm("BBS".to_string())
+    );
+    Ok(())
+}
+
+#[test]
+fn jwp_bbs_presented_form() -> CredxResult<()> {
+    let (sk, pk) = bbs_keys();
+    let jwp = BbsIssuedJwp::sign(&schema(), &bbs_claims(), "issuer-key-1", &sk, &pk)?;
+    let nonce = b"jwp presentation nonce";
+    let presented = jwp.present(
+        &pk,
+        &btreeset! {"name".to_string(), "age".to_string()},
+        nonce,
+        thread_rng(),
+    )?;
+    assert_eq!(presented.issuer_header, jwp.header);
+    assert_eq!(presented.presentation_header()?.alg, "BBS");
+    assert_eq!(presented.payloads.iter().filter(|p| p.is_some()).count(), 2);
+    presented.verify(&pk, nonce)?;
+    assert_eq!(
+        presented.disclosed_claims(&schema())?,
+        maplit::btreemap! {
+            "name".to_string() => HashedClaim::from("Jane Doe").into(),
+            "age".to_string() => NumberClaim::from(42).into(),
+        }
+    );
+
+    let compact = presented.to_compact();
+    assert_eq!(compact.split('.').count(), 4);
+    assert_eq!(BbsPresentedJwp::from_compact(&compact)?, presented);
+    assert_eq!(
+        jwp_error(presented.verify(&pk, b"another nonce")),
+        JwpError::NonceMismatch
+    );
+    let mut tampered = presented.clone();
+    tampered.payloads[1] = Some(b"ut8:John Doe".to_vec());
+    assert!(tampered.verify(&pk, nonce).is_err());
+    let mut tampered = presented.clone();
+    tampered.payloads[0] = Some(jwp.payloads[0].clone());
+    assert!(tampered.verify(&pk, nonce).is_err());
+    assert_eq!(
+        jwp_error(jwp.present(&pk, &btreeset! {"email".to_string()}, nonce, thread_rng())),
+        JwpError::MissingPayload("email".to_string())
+    );
+    Ok(())
+}
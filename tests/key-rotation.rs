@@ -0,0 +1,145 @@
+use credx::claim::{ClaimType, HashedClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, Credential, CredentialSchema};
+use credx::issuer::{Issuer, IssuerPublic};
+use credx::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use credx::knox::{bbs::BbsScheme, ps::PsScheme};
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::{RevocationStatement, SignatureStatement};
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::btreeset;
+use rand::thread_rng;
+
+fn schema() -> CredentialSchema {
+    CredentialSchema::new(
+        Some("Rotation"),
+        None,
+        &[],
+        &[
+            ClaimSchema {
+                claim_type: ClaimType::Revocation,
+                label: "identifier".to_string(),
+                print_friendly: false,
+                optional: false,
+                normalization: None,
+                validators: vec![],
+            },
+            ClaimSchema {
+                claim_type: ClaimType::Hashed,
+                label: "name".to_string(),
+                print_friendly: true,
+                optional: false,
+                normalization: None,
+                validators: vec![],
+            },
+        ],
+    )
+    .unwrap()
+}
+
+fn present<S: ShortGroupSignatureScheme>(
+    issuer_public: &IssuerPublic<S>,
+    credential: &Credential<S>,
+) -> CredxResult<()> {
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "signature".to_string(),
+        issuer: issuer_public.clone(),
+    };
+    let rev_st = RevocationStatement {
+        id: "revocation".to_string(),
+        reference_id: sig_st.id.clone(),
+        accumulator: issuer_public.revocation_registry,
+        verification_key: issuer_public.revocation_verifying_key,
+        claim: 0,
+    };
+    let presentation_schema = PresentationSchema::new(&[sig_st.into(), rev_st.into()]);
+    let nonce = [9u8; 16];
+    let credentials = indexmap! {
+        "signature".to_string() => credential.clone().into(),
+        "revocation".to_string() => credential.clone().into(),
+    };
+    let presentation = Presentation::create(&credentials, &presentation_schema, &nonce)?;
+    presentation.verify(&presentation_schema, &nonce)
+}
+
+#[test]
+fn rotated_keys_are_accepted_during_grace_period() {
+    let res = test_rotated_keys_are_accepted_during_grace_period::<BbsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+    let res = test_rotated_keys_are_accepted_during_grace_period::<PsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_rotated_keys_are_accepted_during_grace_period<S: ShortGroupSignatureScheme>(
+) -> CredxResult<()> {
+    let (issuer_public, mut issuer) = Issuer::<S>::new(&schema());
+    assert_eq!(issuer_public.key_version, 0);
+    let old = issuer.sign_credential(&[
+        RevocationClaim::from("1a2b3c4d-5e6f-4a7b-8c9d-0e1f2a3b4c5d").into(),
+        HashedClaim::from("Jane Doe").into(),
+    ])?;
+    present(&issuer_public, &old.credential)?;
+
+    let rotated_public = issuer.rotate_keys(thread_rng())?;
+    assert_eq!(rotated_public.key_version, 1);
+    assert_eq!(rotated_public.id, issuer_public.id);
+    assert_eq!(rotated_public.previous_keys.len(), 1);
+    assert_eq!(rotated_public.key_version_of(&old.credential)?, 0);
+
+    // Old credentials verify against the new issuer public data while both keys are accepted
+    present(&rotated_public, &old.credential)?;
+
+    let reissued = issuer.reissue_credential(&old.credential)?;
+    assert_eq!(reissued.credential.claims, old.credential.claims);
+    assert_eq!(rotated_public.key_version_of(&reissued.credential)?, 1);
+    present(&rotated_public, &reissued.credential)?;
+    // The new key isn't accepted by verifiers that only know the old one
+    assert!(present(&issuer_public, &reissued.credential).is_err());
+
+    let final_public = issuer.end_grace_period(0)?;
+    assert!(final_public.previous_keys.is_empty());
+    assert!(present(&final_public, &old.credential).is_err());
+    assert!(issuer.reissue_credential(&old.credential).is_err());
+    assert!(issuer.end_grace_period(0).is_err());
+    present(&final_public, &reissued.credential)?;
+
+    // Revoked credentials can't be moved to the new key
+    issuer.revoke_credentials(&[RevocationClaim::from(
+        "1a2b3c4d-5e6f-4a7b-8c9d-0e1f2a3b4c5d",
+    )])?;
+    assert!(issuer.reissue_credential(&reissued.credential).is_err());
+    Ok(())
+}
+
+#[test]
+fn issuers_rotate_signers() {
+    let schema = schema();
+    let (_, issuer) = Issuer::<BbsScheme>::new(&schema);
+    let (issuer_public, mut delegated) = Issuer::new_with_signer(
+        "did:example:rotation",
+        &schema,
+        issuer.signer.clone(),
+        thread_rng(),
+    )
+    .unwrap();
+    let old = delegated
+        .sign_credential(&[
+            RevocationClaim::from("6b5a4f3e-2d1c-4b0a-9f8e-7d6c5b4a3f2e").into(),
+            HashedClaim::from("John Doe").into(),
+        ])
+        .unwrap();
+    present(&issuer_public, &old.credential).unwrap();
+
+    let mut rotated = issuer.clone();
+    rotated.rotate_keys(thread_rng()).unwrap();
+    let rotated_public = delegated.rotate_signer(rotated.signer.clone()).unwrap();
+    assert_eq!(rotated_public.key_version, 1);
+    present(&rotated_public, &old.credential).unwrap();
+    let reissued = delegated.reissue_credential(&old.credential).unwrap();
+    present(&rotated_public, &reissued.credential).unwrap();
+
+    // A signer with a different revocation key would invalidate existing handles
+    let (_, other) = Issuer::<BbsScheme>::new(&schema);
+    assert!(delegated.rotate_signer(other.signer).is_err());
+}
@@ -0,0 +1,135 @@
+use credx::claim::{
+    ClaimData, ClaimType, ClaimValidator, HashedClaim, NestedClaimData, NumberClaim,
+    RevocationClaim,
+};
+use credx::credential::{ClaimSchema, CredentialSchema, NestedClaimSchema};
+use credx::issuer::Issuer;
+use credx::knox::bbs::BbsScheme;
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::SignatureStatement;
+use credx::{random_string, CredxResult};
+use indexmap::indexmap;
+use maplit::{btreemap, btreeset};
+use rand::thread_rng;
+use rand_core::RngCore;
+
+fn claim(claim_type: ClaimType, label: &str) -> NestedClaimSchema {
+    ClaimSchema {
+        claim_type,
+        label: label.to_string(),
+        print_friendly: true,
+        optional: false,
+        normalization: None,
+        validators: vec![],
+    }
+    .into()
+}
+
+fn nested_schema() -> CredxResult<CredentialSchema> {
+    CredentialSchema::new_nested(
+        Some("Nested"),
+        None,
+        &[],
+        &[
+            claim(ClaimType::Revocation, "identifier"),
+            claim(ClaimType::Hashed, "name"),
+            NestedClaimSchema::Object {
+                label: "address".to_string(),
+                claims: vec![
+                    claim(ClaimType::Hashed, "street"),
+                    claim(ClaimType::Hashed, "city"),
+                    NestedClaimSchema::Object {
+                        label: "geo".to_string(),
+                        claims: vec![ClaimSchema {
+                            claim_type: ClaimType::Number,
+                            label: "postal_code".to_string(),
+                            print_friendly: true,
+                            optional: false,
+                            normalization: None,
+                            validators: vec![ClaimValidator::Range {
+                                min: Some(0),
+                                max: Some(99999),
+                            }],
+                        }
+                        .into()],
+                    },
+                ],
+            },
+        ],
+    )
+}
+
+fn nested_claims() -> NestedClaimData {
+    let address = btreemap! {
+        "street".to_string() => ClaimData::from(HashedClaim::from("42 Wallaby Way")).into(),
+        "city".to_string() => ClaimData::from(HashedClaim::from("Sydney")).into(),
+        "geo".to_string() => NestedClaimData::Object(btreemap! {
+            "postal_code".to_string() => ClaimData::from(NumberClaim::from(2000)).into(),
+        }),
+    };
+    NestedClaimData::Object(btreemap! {
+        "identifier".to_string() => ClaimData::from(RevocationClaim::from("4e3d5e2a-4c16-4b2b-91d5-5a5c8d4b0c07")).into(),
+        "name".to_string() => ClaimData::from(HashedClaim::from("P Sherman")).into(),
+        "address".to_string() => NestedClaimData::Object(address),
+    })
+}
+
+#[test]
+fn nested_schema_is_flattened_in_declaration_order() {
+    let schema = nested_schema().unwrap();
+    assert_eq!(
+        schema.claim_indices.iter().collect::<Vec<_>>(),
+        vec![
+            "identifier",
+            "name",
+            "address.street",
+            "address.city",
+            "address.geo.postal_code"
+        ]
+    );
+    assert_eq!(schema.get_claim_index("$.address.city"), Some(3));
+    assert!(CredentialSchema::new_nested(
+        None,
+        None,
+        &[],
+        &[claim(ClaimType::Hashed, "address.street")]
+    )
+    .is_err());
+}
+
+#[test]
+fn nested_claims_disclose_by_path() {
+    let res = test_nested_claims_disclose_by_path();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_nested_claims_disclose_by_path() -> CredxResult<()> {
+    let schema = nested_schema()?;
+    let (issuer_public, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+    let credential = issuer.sign_nested_credential(&nested_claims())?;
+    assert_eq!(
+        credential.credential.claims[3],
+        HashedClaim::from("Sydney").into()
+    );
+
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"$.address".to_string(), "name".to_string()},
+        id: random_string(16, thread_rng()),
+        issuer: issuer_public,
+    };
+    let sig_st_id = sig_st.id.clone();
+    let mut nonce = [0u8; 16];
+    thread_rng().fill_bytes(&mut nonce);
+
+    let credentials = indexmap! { sig_st.id.clone() => credential.credential.into() };
+    let presentation_schema = PresentationSchema::new(&[sig_st.into()]);
+    let presentation = Presentation::create(&credentials, &presentation_schema, &nonce)?;
+    presentation.verify(&presentation_schema, &nonce)?;
+
+    let disclosed = presentation.nested_disclosed_messages(&sig_st_id)?;
+    let expected = nested_claims();
+    assert_eq!(disclosed.get("address"), expected.get("address"));
+    assert_eq!(disclosed.get("name"), expected.get("name"));
+    assert!(disclosed.get("identifier").is_none());
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use credx::claim::{
+    ClaimData, ClaimNormalization, ClaimType, ClaimValidator, HashedClaim, RevocationClaim,
+    UnicodeNormalization,
+};
+use credx::credential::{ClaimSchema, CredentialSchema};
+use credx::issuer::Issuer;
+use credx::knox::bbs::BbsScheme;
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::{EqualityStatement, SignatureStatement};
+use credx::{random_string, CredxResult};
+use indexmap::indexmap;
+use maplit::btreeset;
+use rand::{thread_rng, RngCore};
+
+const NORMALIZATION: ClaimNormalization = ClaimNormalization {
+    unicode: Some(UnicodeNormalization::Nfc),
+    case_fold: true,
+    trim_whitespace: true,
+};
+
+fn schema(normalization: Option<ClaimNormalization>) -> CredxResult<CredentialSchema> {
+    CredentialSchema::new(
+        Some("Normalized"),
+        None,
+        &[],
+        &[
+            ClaimSchema {
+                claim_type: ClaimType::Revocation,
+                label: "identifier".to_string(),
+                print_friendly: false,
+                optional: false,
+                normalization: None,
+                validators: vec![],
+            },
+            ClaimSchema {
+                claim_type: ClaimType::Hashed,
+                label: "name".to_string(),
+                print_friendly: true,
+                optional: false,
+                normalization,
+                validators: vec![],
+            },
+            ClaimSchema {
+                claim_type: ClaimType::Hashed,
+                label: "country".to_string(),
+                print_friendly: true,
+                optional: false,
+                normalization,
+                validators: vec![ClaimValidator::AnyOne(vec![
+                    HashedClaim::from("Côte d'Ivoire").into(),
+                    HashedClaim::from("Curaçao").into(),
+                ])],
+            },
+        ],
+    )
+}
+
+fn claims(id: &str, name: &str, country: &str) -> Vec<ClaimData> {
+    vec![
+        RevocationClaim::from(id).into(),
+        HashedClaim::from(name).into(),
+        HashedClaim::from(country).into(),
+    ]
+}
+
+#[test]
+fn normalized_claims_are_equal_across_issuers() {
+    let res = test_normalized_claims_are_equal_across_issuers();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_normalized_claims_are_equal_across_issuers() -> CredxResult<()> {
+    let schema = schema(Some(NORMALIZATION))?;
+    let (issuer_public_1, mut issuer_1) = Issuer::<BbsScheme>::new(&schema);
+    let (issuer_public_2, mut issuer_2) = Issuer::<BbsScheme>::new(&schema);
+
+    // NFC vs NFD, different case and padding
+    let credential_1 = issuer_1.sign_credential(&claims(
+        "c7b3b0c5-1bb4-4a4c-8f2e-7b2a4b8b3e4d",
+        "José Smith",
+        "Curaçao",
+    ))?;
+    let credential_2 = issuer_2.sign_credential(&claims(
+        "0a2b1f3c-6d9e-4c3b-9f8a-2e5d7c1b4a6f",
+        "  JOSE\u{301} SMITH\n",
+        "CURAC\u{327}AO",
+    ))?;
+    assert_eq!(
+        credential_1.credential.claims[1],
+        credential_2.credential.claims[1]
+    );
+    assert_eq!(
+        credential_2.credential.claims[1],
+        HashedClaim::from("josé smith").into()
+    );
+    assert_eq!(
+        schema.claims[1].claim_from_text("ut8:JOSE\u{301} Smith")?,
+        credential_1.credential.claims[1]
+    );
+
+    let sig_st_1 = SignatureStatement {
+        disclosed: btreeset! {},
+        id: random_string(16, thread_rng()),
+        issuer: issuer_public_1,
+    };
+    let sig_st_2 = SignatureStatement {
+        disclosed: btreeset! {},
+        id: random_string(16, thread_rng()),
+        issuer: issuer_public_2,
+    };
+    let eq_st = EqualityStatement {
+        id: random_string(16, thread_rng()),
+        ref_id_claim_index: indexmap! {
+            sig_st_1.id.clone() => 1,
+            sig_st_2.id.clone() => 1,
+        },
+    };
+    let credentials = indexmap! {
+        sig_st_1.id.clone() => credential_1.credential.into(),
+        sig_st_2.id.clone() => credential_2.credential.into(),
+    };
+    let presentation_schema =
+        PresentationSchema::new(&[sig_st_1.into(), sig_st_2.into(), eq_st.into()]);
+    let mut nonce = [0u8; 16];
+    thread_rng().fill_bytes(&mut nonce);
+    let presentation = Presentation::create(&credentials, &presentation_schema, &nonce)?;
+    presentation.verify(&presentation_schema, &nonce)?;
+    Ok(())
+}
+
+#[test]
+fn unnormalized_claims_are_signed_as_is() {
+    let schema = schema(None).unwrap();
+    let (_, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+    assert!(issuer
+        .sign_credential(&claims(
+            "c7b3b0c5-1bb4-4a4c-8f2e-7b2a4b8b3e4d",
+            "José Smith",
+            "CURAC\u{327}AO",
+        ))
+        .is_err());
+    let credential = issuer
+        .sign_credential(&claims(
+            "0a2b1f3c-6d9e-4c3b-9f8a-2e5d7c1b4a6f",
+            " JOSE\u{301} SMITH",
+            "Curaçao",
+        ))
+        .unwrap();
+    assert_eq!(
+        credential.credential.claims[1],
+        HashedClaim::from(" JOSE\u{301} SMITH").into()
+    );
+}
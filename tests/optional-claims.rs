@@ -0,0 +1,246 @@
+use blsful::inner_types::*;
+use credx::blind::BlindCredentialRequest;
+use credx::claim::{
+    AbsentClaim, ClaimData, ClaimType, HashedClaim, NumberClaim, RevocationClaim, ScalarClaim,
+};
+use credx::credential::{ClaimSchema, CredentialSchema};
+use credx::issuer::{Issuer, IssuerPublic};
+use credx::knox::bbs::BbsScheme;
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::{PresenceStatement, SignatureStatement};
+use credx::{random_string, CredxResult};
+use indexmap::indexmap;
+use maplit::{btreemap, btreeset};
+use rand::{thread_rng, RngCore};
+
+const CRED_ID: &str = "91742856-6eda-45fb-a709-d22ebb5ec8a5";
+
+fn claim(claim_type: ClaimType, label: &str, optional: bool) -> ClaimSchema {
+    ClaimSchema {
+        claim_type,
+        label: label.to_string(),
+        print_friendly: false,
+        optional,
+        normalization: None,
+        validators: vec![],
+    }
+}
+
+fn optional_schema() -> CredxResult<CredentialSchema> {
+    CredentialSchema::new(
+        Some("Optional"),
+        None,
+        &["link_secret"],
+        &[
+            claim(ClaimType::Revocation, "identifier", false),
+            claim(ClaimType::Scalar, "link_secret", true),
+            claim(ClaimType::Hashed, "name", false),
+            claim(ClaimType::Hashed, "middle_name", true),
+            claim(ClaimType::Number, "age", true),
+        ],
+    )
+}
+
+fn presence_statement(id: &str, claim: usize, present: bool) -> PresenceStatement<G1Projective> {
+    PresenceStatement {
+        message_generator: G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(
+            b"message generator",
+            b"BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        ),
+        blinder_generator: G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(
+            b"blinder generator",
+            b"BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        ),
+        id: random_string(16, thread_rng()),
+        reference_id: id.to_string(),
+        claim,
+        present,
+    }
+}
+
+fn prove_presence(
+    issuer_public: &IssuerPublic<BbsScheme>,
+    claims: &[ClaimData],
+    signature: credx::credential::Credential<BbsScheme>,
+    presence: &[(usize, bool)],
+) -> CredxResult<(
+    Presentation<BbsScheme>,
+    PresentationSchema<BbsScheme>,
+    [u8; 16],
+)> {
+    assert_eq!(claims, signature.claims.as_slice());
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {},
+        id: random_string(16, thread_rng()),
+        issuer: issuer_public.clone(),
+    };
+    let mut statements = vec![sig_st.clone().into()];
+    for (claim, present) in presence {
+        statements.push(presence_statement(&sig_st.id, *claim, *present).into());
+    }
+    let mut nonce = [0u8; 16];
+    thread_rng().fill_bytes(&mut nonce);
+    let credentials = indexmap! { sig_st.id.clone() => signature.into() };
+    let presentation_schema = PresentationSchema::new(&statements);
+    let presentation = Presentation::create(&credentials, &presentation_schema, &nonce)?;
+    Ok((presentation, presentation_schema, nonce))
+}
+
+#[test]
+fn omitted_optional_claims_are_signed_as_absent() {
+    let res = test_omitted_optional_claims_are_signed_as_absent();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_omitted_optional_claims_are_signed_as_absent() -> CredxResult<()> {
+    let schema = optional_schema()?;
+    let (issuer_public, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+    let credential = issuer.sign_credential_from_paths(&btreemap! {
+        "identifier".to_string() => RevocationClaim::from(CRED_ID).into(),
+        "name".to_string() => HashedClaim::from("John Doe").into(),
+        "age".to_string() => NumberClaim::from(30).into(),
+    })?;
+    let expected: Vec<ClaimData> = vec![
+        RevocationClaim::from(CRED_ID).into(),
+        AbsentClaim.into(),
+        HashedClaim::from("John Doe").into(),
+        AbsentClaim.into(),
+        NumberClaim::from(30).into(),
+    ];
+
+    let (presentation, presentation_schema, nonce) = prove_presence(
+        &issuer_public,
+        &expected,
+        credential.credential.clone(),
+        &[(3, false), (4, true)],
+    )?;
+    presentation.verify(&presentation_schema, &nonce)?;
+
+    // The statements can't be satisfied for the opposite presence
+    assert!(prove_presence(
+        &issuer_public,
+        &expected,
+        credential.credential.clone(),
+        &[(3, true)]
+    )
+    .is_err());
+    assert!(prove_presence(
+        &issuer_public,
+        &expected,
+        credential.credential,
+        &[(4, false)]
+    )
+    .is_err());
+    Ok(())
+}
+
+#[test]
+fn presence_proof_does_not_verify_for_opposite_statement() {
+    let res = test_presence_proof_does_not_verify_for_opposite_statement();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_presence_proof_does_not_verify_for_opposite_statement() -> CredxResult<()> {
+    let schema = optional_schema()?;
+    let (issuer_public, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+    let claims: Vec<ClaimData> = vec![
+        RevocationClaim::from(CRED_ID).into(),
+        AbsentClaim.into(),
+        HashedClaim::from("John Doe").into(),
+        HashedClaim::from("Quincy").into(),
+        AbsentClaim.into(),
+    ];
+    let credential = issuer.sign_credential(&claims)?;
+    let (presentation, mut presentation_schema, nonce) = prove_presence(
+        &issuer_public,
+        &claims,
+        credential.credential,
+        &[(1, false)],
+    )?;
+    presentation.verify(&presentation_schema, &nonce)?;
+
+    for statement in presentation_schema.statements.values_mut() {
+        if let credx::statement::Statements::Presence(p) = statement {
+            p.present = true;
+        }
+    }
+    assert!(presentation.verify(&presentation_schema, &nonce).is_err());
+    Ok(())
+}
+
+#[test]
+fn issuer_rejects_invalid_absent_claims() {
+    let schema = optional_schema().unwrap();
+    let (_, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+
+    // name is required
+    assert!(issuer
+        .sign_credential(&[
+            RevocationClaim::from(CRED_ID).into(),
+            AbsentClaim.into(),
+            AbsentClaim.into(),
+            AbsentClaim.into(),
+            AbsentClaim.into(),
+        ])
+        .is_err());
+    assert!(issuer
+        .sign_credential_from_paths(&btreemap! {
+            "identifier".to_string() => RevocationClaim::from(CRED_ID).into(),
+        })
+        .is_err());
+    // real values can't collide with the absent value
+    assert!(issuer
+        .sign_credential(&[
+            RevocationClaim::from(CRED_ID).into(),
+            ScalarClaim::from(AbsentClaim::scalar()).into(),
+            HashedClaim::from("John Doe").into(),
+            AbsentClaim.into(),
+            AbsentClaim.into(),
+        ])
+        .is_err());
+    // revocation claims are always required
+    assert!(CredentialSchema::new(
+        None,
+        None,
+        &[],
+        &[claim(ClaimType::Revocation, "identifier", true)]
+    )
+    .is_err());
+}
+
+#[test]
+fn blind_signed_optional_claims() {
+    let res = test_blind_signed_optional_claims();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_blind_signed_optional_claims() -> CredxResult<()> {
+    let schema = optional_schema()?;
+    let (issuer_public, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+
+    let blind_claims = btreemap! { "link_secret".to_string() => AbsentClaim.into() };
+    let (request, blinder) = BlindCredentialRequest::new(&issuer_public, &blind_claims)?;
+    let blind_bundle = issuer.blind_sign_credential(
+        &request,
+        &btreemap! {
+            "identifier".to_string() => RevocationClaim::from(CRED_ID).into(),
+            "name".to_string() => HashedClaim::from("John Doe").into(),
+        },
+    )?;
+    let credential = blind_bundle.to_unblinded(&blind_claims, blinder)?;
+    let expected: Vec<ClaimData> = vec![
+        RevocationClaim::from(CRED_ID).into(),
+        AbsentClaim.into(),
+        HashedClaim::from("John Doe").into(),
+        AbsentClaim.into(),
+        AbsentClaim.into(),
+    ];
+    let (presentation, presentation_schema, nonce) = prove_presence(
+        &issuer_public,
+        &expected,
+        credential.credential,
+        &[(1, false), (2, true), (4, false)],
+    )?;
+    presentation.verify(&presentation_schema, &nonce)?;
+    Ok(())
+}
@@ -34,12 +34,16 @@ fn test_out_of_range_panic() -> CredxResult<()> {
             claim_type: ClaimType::Revocation,
             label: "identifier".to_string(),
             print_friendly: false,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
             label: "age".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
     ];
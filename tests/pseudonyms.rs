@@ -0,0 +1,338 @@
+use blsful::inner_types::*;
+use credx::claim::{ClaimData, ClaimType, HashedClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CredentialSchema};
+use credx::issuer::Issuer;
+use credx::knox::bbs::{BbsCiphersuite, BbsScheme};
+use credx::knox::ps::PsScheme;
+use credx::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use credx::presentation::{
+    BbsNymCredential, BbsPseudonymPresentation, Presentation, PresentationProofs,
+    PresentationSchema,
+};
+use credx::statement::SignatureStatement;
+use credx::{random_string, CredxResult};
+use elliptic_curve::hash2curve::{ExpandMsgXmd, ExpandMsgXof};
+use indexmap::indexmap;
+use maplit::btreeset;
+use rand::thread_rng;
+
+const MESSAGES: [&str; 3] = [
+    "9872ad089e452c7b6e283dfac2a80d58e8d0ff71cc4d5e310a1debdda4a45f02",
+    "c344136d9ab02da4dd5908bbba913ae6f58c2cc844b802a6f811f5fb075f9b80",
+    "7372e9daa5ed31e6cd5c825eac1b855e84476a1d94932aa348e07b73",
+];
+const COMMITTED_MESSAGES: [&str; 1] = ["77fe97eb97a1ebe2e81e4e3597a3ee740a66e9ef2412472c"];
+const HEADER: &str = "11223344556677889900aabbccddeeff";
+const PRESENTATION_HEADER: &str =
+    "bed231d880675ed101ead304512e043ade9958dd0241ea70b4b3957fba941501";
+const KEY_MATERIAL: &str = "746869732d49532d6a7573742d616e2d546573742d494b4d2d746f2d67656e65726174652d246528724074232d6b6579";
+
+fn hex_list(values: &[&str]) -> Vec<Vec<u8>> {
+    values.iter().map(|v| hex::decode(v).unwrap()).collect()
+}
+
+#[test]
+fn ietf_pseudonyms() {
+    for cs in [
+        BbsCiphersuite::Bls12381Sha256,
+        BbsCiphersuite::Bls12381Shake256,
+    ] {
+        check_pseudonyms(cs);
+    }
+}
+
+fn check_pseudonyms(cs: BbsCiphersuite) {
+    let sk = cs
+        .key_gen(&hex::decode(KEY_MATERIAL).unwrap(), b"", None)
+        .unwrap();
+    let pk = cs.sk_to_pk(&sk).unwrap();
+    let header = hex::decode(HEADER).unwrap();
+    let ph = hex::decode(PRESENTATION_HEADER).unwrap();
+    let messages = hex_list(&MESSAGES);
+    let committed = hex_list(&COMMITTED_MESSAGES);
+    let prover_nym = Scalar::random(thread_rng()).to_be_bytes();
+
+    let (commitment, prover_blind) = cs
+        .commit_with_nym(&committed, &prover_nym, thread_rng())
+        .unwrap();
+    let (signature, entropy) = cs
+        .blind_sign_with_nym(&sk, &pk, &commitment, &header, &messages, thread_rng())
+        .unwrap();
+    let nym_secret = cs
+        .finalize_with_nym(
+            &pk,
+            &signature,
+            &header,
+            &messages,
+            &committed,
+            &prover_nym,
+            &entropy,
+            &prover_blind,
+        )
+        .unwrap();
+    // The holder needs the signer's entropy
+    assert!(cs
+        .finalize_with_nym(
+            &pk,
+            &signature,
+            &header,
+            &messages,
+            &committed,
+            &prover_nym,
+            &Scalar::random(thread_rng()).to_be_bytes(),
+            &prover_blind,
+        )
+        .is_err());
+
+    let context_id = b"verifier one";
+    let prove = |context_id: &[u8], disclosed: &[usize]| {
+        cs.pseudonym_proof_gen(
+            &pk,
+            &signature,
+            &header,
+            &ph,
+            context_id,
+            &messages,
+            &committed,
+            &nym_secret,
+            &prover_blind,
+            disclosed,
+            thread_rng(),
+        )
+        .unwrap()
+    };
+    let disclosed = [(0, messages[0].as_slice()), (2, messages[2].as_slice())];
+    let (proof, pseudonym) = prove(context_id, &[0, 2]);
+    assert_eq!(pseudonym, cs.pseudonym(context_id, &nym_secret).unwrap());
+    let verify =
+        |proof: &[u8], pseudonym: &[u8], context_id: &[u8], disclosed: &[(usize, &[u8])]| {
+            cs.pseudonym_proof_verify(
+                &pk,
+                proof,
+                pseudonym,
+                context_id,
+                &header,
+                &ph,
+                messages.len(),
+                committed.len(),
+                disclosed,
+            )
+        };
+    assert!(verify(&proof, &pseudonym, context_id, &disclosed).is_ok());
+
+    // The pseudonym is the same for every proof with the verifier
+    let (other_proof, other_pseudonym) = prove(context_id, &[]);
+    assert_eq!(pseudonym, other_pseudonym);
+    assert_ne!(proof, other_proof);
+    assert!(verify(&other_proof, &other_pseudonym, context_id, &[]).is_ok());
+
+    // and different for other verifiers
+    let (other_proof, other_pseudonym) = prove(b"verifier two", &[0, 2]);
+    assert_ne!(pseudonym, other_pseudonym);
+    assert!(verify(&other_proof, &other_pseudonym, b"verifier two", &disclosed).is_ok());
+
+    // Proofs are bound to the pseudonym, context id and disclosed messages
+    assert!(verify(&proof, &other_pseudonym, context_id, &disclosed).is_err());
+    assert!(verify(&proof, &pseudonym, b"verifier two", &disclosed).is_err());
+    assert!(verify(&proof, &pseudonym, context_id, &disclosed[..1]).is_err());
+    assert!(verify(
+        &proof,
+        &pseudonym,
+        context_id,
+        &[(0, messages[0].as_slice()), (2, messages[1].as_slice())]
+    )
+    .is_err());
+    assert!(verify(&proof, &pseudonym[1..], context_id, &disclosed).is_err());
+}
+
+#[test]
+fn bbs_pseudonym_presentation() {
+    for cs in [
+        BbsCiphersuite::Bls12381Sha256,
+        BbsCiphersuite::Bls12381Shake256,
+    ] {
+        check_pseudonym_presentation(cs);
+    }
+}
+
+fn check_pseudonym_presentation(cs: BbsCiphersuite) {
+    let sk = cs
+        .key_gen(&hex::decode(KEY_MATERIAL).unwrap(), b"", None)
+        .unwrap();
+    let pk = cs.sk_to_pk(&sk).unwrap();
+    let header = hex::decode(HEADER).unwrap();
+    let ph = hex::decode(PRESENTATION_HEADER).unwrap();
+    let messages = hex_list(&MESSAGES);
+    let committed = hex_list(&COMMITTED_MESSAGES);
+    let prover_nym = Scalar::random(thread_rng()).to_be_bytes();
+    let (commitment, prover_blind) = cs
+        .commit_with_nym(&committed, &prover_nym, thread_rng())
+        .unwrap();
+    let (signature, entropy) = cs
+        .blind_sign_with_nym(&sk, &pk, &commitment, &header, &messages, thread_rng())
+        .unwrap();
+    let credential = BbsNymCredential::new(
+        cs,
+        &pk,
+        &signature,
+        &header,
+        &messages,
+        &committed,
+        &prover_nym,
+        &entropy,
+        &prover_blind,
+    )
+    .unwrap();
+
+    let context_id = b"verifier one";
+    let presentation = credential
+        .present(&pk, &ph, context_id, &[0, 2], thread_rng())
+        .unwrap();
+    let presentation: BbsPseudonymPresentation =
+        serde_json::from_str(&serde_json::to_string(&presentation).unwrap()).unwrap();
+    presentation.verify(&pk, &ph, context_id).unwrap();
+    assert_eq!(presentation.disclosed_messages.len(), 2);
+
+    // The pseudonym octets are OP * nym_secret with OP hashed to the curve
+    // from the context id
+    let nym_secret =
+        Scalar::from_be_bytes(&prover_nym).unwrap() + Scalar::from_be_bytes(&entropy).unwrap();
+    let dst = [cs.blind_api_id().as_slice(), b"OP_"].concat();
+    let op = match cs {
+        BbsCiphersuite::Bls12381Sha256 => {
+            G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(context_id, &dst)
+        }
+        BbsCiphersuite::Bls12381Shake256 => {
+            G1Projective::hash::<ExpandMsgXof<sha3::Shake256>>(context_id, &dst)
+        }
+    };
+    assert_eq!(
+        presentation.pseudonym,
+        (op * nym_secret).to_affine().to_compressed().to_vec()
+    );
+    assert_eq!(
+        presentation.pseudonym,
+        credential.pseudonym(context_id).unwrap()
+    );
+    // A verifier implementing the draft checks the octets directly
+    let disclosed = presentation
+        .disclosed_messages
+        .iter()
+        .map(|(i, m)| (*i, m.as_slice()))
+        .collect::<Vec<_>>();
+    cs.pseudonym_proof_verify(
+        &pk,
+        &presentation.proof,
+        &presentation.pseudonym,
+        context_id,
+        &header,
+        &ph,
+        messages.len(),
+        committed.len(),
+        &disclosed,
+    )
+    .unwrap();
+
+    // The presentation is bound to the verifier
+    assert!(presentation.verify(&pk, &ph, b"verifier two").is_err());
+    assert!(presentation.verify(&pk, b"", context_id).is_err());
+    let mut tampered = presentation.clone();
+    tampered.disclosed_messages.insert(1, messages[1].clone());
+    assert!(tampered.verify(&pk, &ph, context_id).is_err());
+    assert!(credential
+        .present(&pk, &ph, context_id, &[3], thread_rng())
+        .is_err());
+}
+
+fn schema() -> CredentialSchema {
+    let claim = |claim_type, label: &str| ClaimSchema {
+        claim_type,
+        label: label.to_string(),
+        print_friendly: false,
+        optional: false,
+        normalization: None,
+        validators: vec![],
+    };
+    CredentialSchema::new(
+        Some("Pseudonyms"),
+        None,
+        &[],
+        &[
+            claim(ClaimType::Revocation, "identifier"),
+            claim(ClaimType::Hashed, "link_secret"),
+            claim(ClaimType::Hashed, "name"),
+        ],
+    )
+    .unwrap()
+}
+
+#[test]
+fn presentation_pseudonyms() {
+    let res = test_presentation_pseudonyms::<BbsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+    let res = test_presentation_pseudonyms::<PsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_presentation_pseudonyms<S: ShortGroupSignatureScheme>() -> CredxResult<()> {
+    let (issuer_public, mut issuer) = Issuer::<S>::new(&schema());
+    let link_secret = HashedClaim::from("my link secret");
+    let credential = issuer.sign_credential(&[
+        RevocationClaim::from("f5a6b7c8-d9e0-4f1a-8b2c-3d4e5f6a7b8c").into(),
+        link_secret.clone().into(),
+        HashedClaim::from("Alice").into(),
+    ])?;
+    let credentials = indexmap! { "cred".to_string() => credential.credential.into() };
+
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "cred".to_string(),
+        issuer: issuer_public,
+    };
+    assert!(sig_st.pseudonym_statement("name", "verifier one").is_err());
+    assert!(sig_st
+        .pseudonym_statement("unknown", "verifier one")
+        .is_err());
+
+    let present =
+        |context_id: &str| -> CredxResult<(Presentation<S>, PresentationSchema<S>, String)> {
+            let nym_st = sig_st.pseudonym_statement("link_secret", context_id)?;
+            let id = nym_st.id.clone();
+            let schema = PresentationSchema::new(&[sig_st.clone().into(), nym_st.into()]);
+            let nonce = random_string(16, thread_rng());
+            let presentation = Presentation::create(&credentials, &schema, nonce.as_bytes())?;
+            presentation.verify(&schema, nonce.as_bytes())?;
+            Ok((presentation, schema, id))
+        };
+
+    let (presentation, schema, id) = present("verifier one")?;
+    let pseudonym = presentation.pseudonym(&id)?;
+    let nym_secret = ClaimData::from(link_secret).to_scalar();
+    assert_eq!(
+        pseudonym,
+        BbsScheme::pseudonym(b"verifier one", nym_secret)
+            .to_affine()
+            .to_compressed()
+    );
+    assert_eq!(
+        pseudonym,
+        BbsScheme::PSEUDONYM_CIPHERSUITE
+            .pseudonym(b"verifier one", &nym_secret.to_be_bytes())
+            .unwrap()
+    );
+
+    // Returning holders show the same pseudonym
+    let (again, _, _) = present("verifier one")?;
+    assert_eq!(again.pseudonym(&id)?, pseudonym);
+    // Other verifiers see a different one
+    let (other, _, other_id) = present("verifier two")?;
+    assert_ne!(other.pseudonym(&other_id)?, pseudonym);
+
+    // A pseudonym that wasn't computed from the claim is rejected
+    let mut forged = presentation.clone();
+    if let Some(PresentationProofs::Pseudonym(p)) = forged.proofs.get_mut(&id) {
+        p.pseudonym = BbsScheme::pseudonym(b"verifier one", Scalar::random(thread_rng()));
+    }
+    assert!(forged.verify(&schema, b"any nonce").is_err());
+    Ok(())
+}
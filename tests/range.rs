@@ -2,13 +2,14 @@ use blsful::inner_types::*;
 use credx::claim::{ClaimType, HashedClaim, NumberClaim, RevocationClaim};
 use credx::credential::{ClaimSchema, CredentialSchema};
 use credx::issuer::Issuer;
-use credx::knox::bbs::BbsScheme;
-use credx::presentation::{Presentation, PresentationSchema};
+use credx::knox::{bbs::BbsScheme, ps::PsScheme};
+use credx::presentation::{Presentation, PresentationProofs, PresentationSchema, RangeProof};
 use credx::statement::{CommitmentStatement, RangeStatement, SignatureStatement};
 use credx::{random_string, CredxResult};
 use indexmap::indexmap;
 use maplit::btreeset;
 use rand::{thread_rng, RngCore};
+use serde_json::Value;
 
 macro_rules! range_test_with {
     ($name: ident, $val:expr, $lower:expr, $upper:expr, $expected_to_fail:expr) => {
@@ -38,6 +39,36 @@ range_test_with!(in_range_max_implicit, isize::MAX, Some(0), None, false);
 range_test_with!(out_of_range_below, 0, Some(1), Some(isize::MAX), true);
 range_test_with!(out_of_range_above, 1001, Some(0), Some(1000), true);
 
+#[test]
+fn range_proof_json() -> CredxResult<()> {
+    // Earlier releases wrote range proofs as a byte sequence
+    let sample: (Presentation<PsScheme>, Value, Value) = serde_json::from_str(
+        &std::fs::read_to_string("samples/presentations/anoncreds_presentation_list.json").unwrap(),
+    )
+    .unwrap();
+    let range_proof = sample
+        .0
+        .proofs
+        .values()
+        .find_map(|p| match p {
+            PresentationProofs::Range(r) => Some(r),
+            _ => None,
+        })
+        .unwrap();
+    let json = serde_json::to_value(range_proof).unwrap();
+    assert!(json["proof"].is_string());
+    let read: RangeProof = serde_json::from_value(json).unwrap();
+    assert_eq!(read.proof.to_bytes(), range_proof.proof.to_bytes());
+    Ok(())
+}
+
+fn json_round_trip(
+    presentation: &Presentation<BbsScheme>,
+) -> Result<Presentation<BbsScheme>, String> {
+    let json = serde_json::to_string(presentation).map_err(|e| e.to_string())?;
+    serde_json::from_str(&json).map_err(|e| format!("read presentation failed: {e}"))
+}
+
 #[test]
 fn test_out_of_range_above() {
     assert!(test_range_proof_works(1000, Some(0), Some(1000), false).is_ok());
@@ -57,24 +88,32 @@ fn test_range_proof_works(
             claim_type: ClaimType::Revocation,
             label: "identifier".to_string(),
             print_friendly: false,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "name".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "address".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
             label: "age".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
     ];
@@ -133,21 +172,23 @@ fn test_range_proof_works(
                 Err(format!("create presentation failed: {e:?}"))
             }
         }
-        Ok(presentation) => match presentation.verify(&presentation_schema, &nonce) {
-            Err(e) => {
-                if expected_to_fail {
-                    Ok(())
-                } else {
-                    Err(format!("verify presentation failed: {e:?}"))
+        Ok(presentation) => {
+            match json_round_trip(&presentation)?.verify(&presentation_schema, &nonce) {
+                Err(e) => {
+                    if expected_to_fail {
+                        Ok(())
+                    } else {
+                        Err(format!("verify presentation failed: {e:?}"))
+                    }
                 }
-            }
-            Ok(_) => {
-                if expected_to_fail {
-                    Err("verification passed, but was expected to fail".to_string())
-                } else {
-                    Ok(())
+                Ok(_) => {
+                    if expected_to_fail {
+                        Err("verification passed, but was expected to fail".to_string())
+                    } else {
+                        Ok(())
+                    }
                 }
             }
-        },
+        }
     }
 }
@@ -515,18 +515,24 @@ fn define_soc_sec_claims_schemas() -> Vec<ClaimSchema> {
             claim_type: ClaimType::Revocation,
             label: "identifier".to_string(),
             print_friendly: false,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "soc_sec_number".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "first_last_name".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
     ];
@@ -539,60 +545,80 @@ fn define_passport_claims_schemas() -> Vec<ClaimSchema> {
             claim_type: ClaimType::Revocation,
             label: "identifier".to_string(),
             print_friendly: false,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "passport_number".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "first_last_name".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "nationality".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
             label: "dob".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "sex".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "place_of_birth".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
             label: "date_of_issue".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
             label: "date_of_expiration".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "claim_issuing_authority".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
     ];
@@ -605,54 +631,72 @@ fn define_bank_statement_claims_schemas() -> Vec<ClaimSchema> {
             claim_type: ClaimType::Revocation,
             label: "identifier".to_string(),
             print_friendly: false,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "account_number".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "first_last_name".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
             label: "start_date".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
             label: "end_date".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "address_line1".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "address_line2".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "address_state".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "address_zip".to_string(),
             print_friendly: true,
+            optional: false,
+            normalization: None,
             validators: vec![],
         },
     ];
@@ -25,12 +25,16 @@ macro_rules! setup_issuer {
                 claim_type: ClaimType::Revocation,
                 label: "identifier".to_string(),
                 print_friendly: false,
+                optional: false,
+                normalization: None,
                 validators: vec![],
             },
             ClaimSchema {
                 claim_type: ClaimType::Hashed,
                 label: "address".to_string(),
                 print_friendly: true,
+                optional: false,
+                normalization: None,
                 validators: vec![ClaimValidator::Length {
                     min: None,
                     max: Some(u8::MAX as usize),
@@ -119,7 +123,7 @@ fn test_revocation() {
 
     // Revoke credential and test that Holder cannot convince Verifier that its credential is not revoked
     let updated_registry = issuer_public.revocation_registry.remove(
-        &issuer.revocation_key,
+        &issuer.signer.revocation_key,
         element_from_membership_claim(STR_FOR_MEMBERSHIP_1),
     );
     let acc_st_updated = RevocationStatement {
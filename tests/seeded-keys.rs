@@ -0,0 +1,122 @@
+use credx::claim::{ClaimData, ClaimType, HashedClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CredentialSchema};
+use credx::issuer::{Issuer, IssuerPublic};
+use credx::knox::bbs::BbsScheme;
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::SignatureStatement;
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::btreeset;
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+
+const ISSUER_DID: &str = "did:example:76e12ec712ebc6f1c221ebfeb1f";
+
+fn claims() -> Vec<ClaimSchema> {
+    vec![
+        ClaimSchema {
+            claim_type: ClaimType::Revocation,
+            label: "identifier".to_string(),
+            print_friendly: false,
+            optional: false,
+            normalization: None,
+            validators: vec![],
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "name".to_string(),
+            print_friendly: true,
+            optional: false,
+            normalization: None,
+            validators: vec![],
+        },
+    ]
+}
+
+fn public_bytes(issuer_public: &IssuerPublic<BbsScheme>) -> Vec<u8> {
+    serde_bare::to_vec(issuer_public).unwrap()
+}
+
+#[test]
+fn seeded_issuers_are_reproducible() {
+    let res = test_seeded_issuers_are_reproducible();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_seeded_issuers_are_reproducible() -> CredxResult<()> {
+    let schema = CredentialSchema::new_with_id(
+        "urn:example:schema:1",
+        Some("Seeded"),
+        None,
+        &[],
+        &claims(),
+    )?;
+    assert_eq!(schema.id, "urn:example:schema:1");
+
+    let seed = [7u8; 32];
+    let (issuer_public_1, mut issuer_1) =
+        Issuer::<BbsScheme>::from_seed(ISSUER_DID, &schema, &seed)?;
+    let (issuer_public_2, _) = Issuer::<BbsScheme>::from_seed(ISSUER_DID, &schema, &seed)?;
+    let (issuer_public_3, _) = Issuer::<BbsScheme>::from_seed(ISSUER_DID, &schema, &[8u8; 32])?;
+    assert_eq!(issuer_public_1.id, ISSUER_DID);
+    assert_eq!(
+        public_bytes(&issuer_public_1),
+        public_bytes(&issuer_public_2)
+    );
+    assert_ne!(
+        public_bytes(&issuer_public_1),
+        public_bytes(&issuer_public_3)
+    );
+    assert!(Issuer::<BbsScheme>::from_seed(ISSUER_DID, &schema, &[7u8; 16]).is_err());
+
+    let (rng_public_1, _) =
+        Issuer::<BbsScheme>::new_with_rng(&schema, ChaChaRng::from_seed([1u8; 32]))?;
+    let (rng_public_2, _) =
+        Issuer::<BbsScheme>::new_with_rng(&schema, ChaChaRng::from_seed([1u8; 32]))?;
+    assert_eq!(rng_public_1.id, rng_public_2.id);
+    assert_eq!(public_bytes(&rng_public_1), public_bytes(&rng_public_2));
+    let mut no_claims = schema.clone();
+    no_claims.claims.clear();
+    assert!(Issuer::<BbsScheme>::new_with_id_and_rng(
+        ISSUER_DID,
+        &no_claims,
+        ChaChaRng::from_seed([1u8; 32])
+    )
+    .is_err());
+    assert!(Issuer::<BbsScheme>::from_seed(ISSUER_DID, &no_claims, &[7u8; 32]).is_err());
+
+    // The seeded issuer creates valid credentials
+    let credential = issuer_1.sign_credential(&[
+        ClaimData::from(RevocationClaim::from(
+            "b2f4e3c0-8a1d-4f6e-9c7b-5d3a2e1f0c9b",
+        )),
+        HashedClaim::from("Jane Doe").into(),
+    ])?;
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "signature".to_string(),
+        issuer: issuer_public_1,
+    };
+    let presentation_schema =
+        PresentationSchema::new_with_rng(&[sig_st.into()], ChaChaRng::from_seed([2u8; 32]));
+    let same_schema =
+        PresentationSchema::<BbsScheme>::new_with_rng(&[], ChaChaRng::from_seed([2u8; 32]));
+    assert_eq!(presentation_schema.id, same_schema.id);
+    let nonce = [3u8; 16];
+    let credentials = indexmap! { "signature".to_string() => credential.credential.into() };
+    let presentation = Presentation::create(&credentials, &presentation_schema, &nonce)?;
+    presentation.verify(&presentation_schema, &nonce)?;
+    Ok(())
+}
+
+#[test]
+fn schema_ids_can_be_chosen() {
+    let rng_schema_1 =
+        CredentialSchema::new_with_rng(None, None, &[], &claims(), ChaChaRng::from_seed([4u8; 32]))
+            .unwrap();
+    let rng_schema_2 =
+        CredentialSchema::new_with_rng(None, None, &[], &claims(), ChaChaRng::from_seed([4u8; 32]))
+            .unwrap();
+    assert_eq!(rng_schema_1.id, rng_schema_2.id);
+    assert!(CredentialSchema::new_with_id("", None, None, &[], &claims()).is_err());
+}
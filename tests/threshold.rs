@@ -0,0 +1,222 @@
+use credx::claim::{ClaimData, ClaimType, HashedClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, Credential, CredentialSchema};
+use credx::issuer::{Issuer, IssuerPublic, ThresholdDealer, ThresholdIssuer, ThresholdSigner};
+use credx::knox::bbs::BbsScheme;
+use credx::knox::ps::PsScheme;
+use credx::knox::threshold::ThresholdSignatureScheme;
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::{RevocationStatement, SignatureStatement};
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::btreeset;
+use rand::thread_rng;
+
+fn schema() -> CredentialSchema {
+    CredentialSchema::new(
+        Some("Threshold"),
+        None,
+        &[],
+        &[
+            ClaimSchema {
+                claim_type: ClaimType::Revocation,
+                label: "identifier".to_string(),
+                print_friendly: false,
+                optional: false,
+                normalization: None,
+                validators: vec![],
+            },
+            ClaimSchema {
+                claim_type: ClaimType::Hashed,
+                label: "name".to_string(),
+                print_friendly: true,
+                optional: false,
+                normalization: None,
+                validators: vec![],
+            },
+        ],
+    )
+    .unwrap()
+}
+
+fn claims(id: &str, name: &str) -> Vec<ClaimData> {
+    vec![
+        RevocationClaim::from(id).into(),
+        HashedClaim::from(name).into(),
+    ]
+}
+
+fn present<S: ThresholdSignatureScheme>(
+    issuer_public: &IssuerPublic<S>,
+    credential: &Credential<S>,
+) -> CredxResult<()> {
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "signature".to_string(),
+        issuer: issuer_public.clone(),
+    };
+    let rev_st = RevocationStatement {
+        id: "revocation".to_string(),
+        reference_id: sig_st.id.clone(),
+        accumulator: issuer_public.revocation_registry,
+        verification_key: issuer_public.revocation_verifying_key,
+        claim: 0,
+    };
+    let presentation_schema = PresentationSchema::new(&[sig_st.into(), rev_st.into()]);
+    let nonce = [4u8; 16];
+    let credentials = indexmap! {
+        "signature".to_string() => credential.clone().into(),
+        "revocation".to_string() => credential.clone().into(),
+    };
+    let presentation = Presentation::create(&credentials, &presentation_schema, &nonce)?;
+    presentation.verify(&presentation_schema, &nonce)
+}
+
+/// Every signer deals `count` nonces and receives the shares from two dealers
+fn deal_nonces<S: ThresholdSignatureScheme>(
+    coordinator: &mut ThresholdIssuer<S>,
+    signers: &mut [ThresholdSigner<S>],
+    count: usize,
+) -> CredxResult<()> {
+    let request = coordinator.nonce_request(count);
+    let dealt = signers
+        .iter()
+        .map(|s| s.deal_nonces(&request, thread_rng()))
+        .collect::<CredxResult<Vec<_>>>()?;
+    for (i, signer) in signers.iter_mut().enumerate() {
+        let shares = vec![dealt[0][i].clone(), dealt[2][i].clone()];
+        // One dealer could know the nonces
+        assert!(signer.receive_nonces(&request, &shares[..1]).is_err());
+        signer.receive_nonces(&request, &shares)?;
+    }
+    Ok(())
+}
+
+fn threshold_issuance<S: ThresholdSignatureScheme>() -> CredxResult<()> {
+    let (issuer_public, issuer) = Issuer::<S>::new(&schema());
+    assert!(ThresholdDealer::new(issuer.clone(), 1, 3).is_err());
+    assert!(ThresholdDealer::new(issuer.clone(), 4, 3).is_err());
+    // Two of three shares need three signers for an inversion
+    assert!(ThresholdDealer::new(issuer.clone(), 2, 2).is_err());
+    let dealer = ThresholdDealer::new(issuer, 2, 3)?;
+    let (mut coordinator, mut signers) = dealer.split(thread_rng())?;
+    assert_eq!(coordinator.signing_participants(), 3);
+    let per_credential = S::SIGNING_NONCES + 1;
+    deal_nonces(&mut coordinator, &mut signers, 2 * per_credential + 2)?;
+
+    // The signers issue a credential for the original issuer keys
+    let request =
+        coordinator.signing_request(&claims("4a3b2c1d-0e9f-4a8b-7c6d-5e4f3a2b1c0d", "Jane Doe"))?;
+    let partials = signers
+        .iter_mut()
+        .map(|s| s.partial_sign_credential(&request))
+        .collect::<CredxResult<Vec<_>>>()?;
+    // Each nonce is used once
+    assert!(signers[0].partial_sign_credential(&request).is_err());
+    // Two signers aren't enough
+    assert!(coordinator
+        .combine_credential(&request, &partials[..2])
+        .is_err());
+    let jane = coordinator.combine_credential(&request, &partials)?;
+    assert_eq!(
+        serde_json::to_string(&jane.issuer.verifying_key).unwrap(),
+        serde_json::to_string(&issuer_public.verifying_key).unwrap()
+    );
+    // The dealer's key proof covers the shared keys
+    coordinator.get_public().verify_key_proof()?;
+    jane.verify()?;
+    present(&coordinator.get_public(), &jane.credential)?;
+
+    let request =
+        coordinator.signing_request(&claims("9e8d7c6b-5a4f-4e3d-2c1b-0a9f8e7d6c5b", "John Doe"))?;
+    let partials = signers
+        .iter_mut()
+        .map(|s| s.partial_sign_credential(&request))
+        .collect::<CredxResult<Vec<_>>>()?;
+    let mut john = coordinator.combine_credential(&request, &partials)?;
+
+    // The signers revoke Jane and update John's revocation handle
+    let jane_id = RevocationClaim::from("4a3b2c1d-0e9f-4a8b-7c6d-5e4f3a2b1c0d");
+    let john_id = RevocationClaim::from("9e8d7c6b-5a4f-4e3d-2c1b-0a9f8e7d6c5b");
+    let request = coordinator.witness_request(std::slice::from_ref(&jane_id))?;
+    let partials = signers
+        .iter_mut()
+        .map(|s| s.partial_witnesses(&request))
+        .collect::<CredxResult<Vec<_>>>()?;
+    coordinator.combine_revocation(&request, &partials)?;
+    assert!(coordinator
+        .witness_request(std::slice::from_ref(&jane_id))
+        .is_err());
+    let request = coordinator.witness_request(std::slice::from_ref(&john_id))?;
+    let partials = signers
+        .iter_mut()
+        .map(|s| s.partial_witnesses(&request))
+        .collect::<CredxResult<Vec<_>>>()?;
+    john.credential.revocation_handle = coordinator.combine_witnesses(&request, &partials)?[0];
+
+    let public = coordinator.get_public();
+    present(&public, &john.credential)?;
+    assert!(present(&public, &jane.credential).is_err());
+    assert!(coordinator
+        .signing_request(&claims("4a3b2c1d-0e9f-4a8b-7c6d-5e4f3a2b1c0d", "Jane Doe"))
+        .is_err());
+    // All the nonces are used
+    assert!(coordinator
+        .signing_request(&claims("0a1b2c3d-4e5f-4a6b-8c7d-9e0f1a2b3c4d", "June Doe"))
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn threshold_issuance_ps() -> CredxResult<()> {
+    threshold_issuance::<PsScheme>()
+}
+
+#[test]
+fn threshold_issuance_bbs() -> CredxResult<()> {
+    threshold_issuance::<BbsScheme>()
+}
+
+#[test]
+fn threshold_batch_revocation() -> CredxResult<()> {
+    let (_, issuer) = Issuer::<BbsScheme>::new(&schema());
+    let (mut coordinator, mut signers) = ThresholdDealer::new(issuer, 2, 3)?.split(thread_rng())?;
+    let ids = [
+        "4a3b2c1d-0e9f-4a8b-7c6d-5e4f3a2b1c0d",
+        "9e8d7c6b-5a4f-4e3d-2c1b-0a9f8e7d6c5b",
+        "0a1b2c3d-4e5f-4a6b-8c7d-9e0f1a2b3c4d",
+    ];
+    deal_nonces(&mut coordinator, &mut signers, 2 * ids.len() + 3)?;
+    let mut credentials = Vec::new();
+    for id in ids {
+        let request = coordinator.signing_request(&claims(id, "Doe"))?;
+        let partials = signers
+            .iter_mut()
+            .map(|s| s.partial_sign_credential(&request))
+            .collect::<CredxResult<Vec<_>>>()?;
+        credentials.push(coordinator.combine_credential(&request, &partials)?);
+    }
+
+    // Revoke two claims at once and check the third
+    let revoked = [RevocationClaim::from(ids[0]), RevocationClaim::from(ids[2])];
+    assert!(coordinator
+        .witness_request(&[revoked[0].clone(), revoked[0].clone()])
+        .is_err());
+    let request = coordinator.witness_request(&revoked)?;
+    let partials = signers
+        .iter_mut()
+        .map(|s| s.partial_witnesses(&request))
+        .collect::<CredxResult<Vec<_>>>()?;
+    coordinator.combine_revocation(&request, &partials)?;
+    let request = coordinator.witness_request(&[RevocationClaim::from(ids[1])])?;
+    let partials = signers
+        .iter_mut()
+        .map(|s| s.partial_witnesses(&request))
+        .collect::<CredxResult<Vec<_>>>()?;
+    credentials[1].credential.revocation_handle =
+        coordinator.combine_witnesses(&request, &partials)?[0];
+    let public = coordinator.get_public();
+    present(&public, &credentials[1].credential)?;
+    assert!(present(&public, &credentials[0].credential).is_err());
+    assert!(present(&public, &credentials[2].credential).is_err());
+    Ok(())
+}
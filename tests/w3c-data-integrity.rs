@@ -0,0 +1,170 @@
+use credx::claim::{ClaimType, HashedClaim, NumberClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CredentialBundle, CredentialSchema};
+use credx::error::Error;
+use credx::issuer::{Issuer, IssuerKeyRegistry};
+use credx::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use credx::knox::{bbs::BbsScheme, ps::PsScheme};
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::{RevocationStatement, SignatureStatement};
+use credx::w3c::{W3cCredential, W3cError, W3cPresentation, AUTHENTICATION, CREDX_CRYPTOSUITE};
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::btreeset;
+use serde_json::{json, Value};
+
+fn schema() -> CredentialSchema {
+    let claim = |claim_type, label: &str| ClaimSchema {
+        claim_type,
+        label: label.to_string(),
+        print_friendly: false,
+        optional: false,
+        normalization: None,
+        validators: vec![],
+    };
+    CredentialSchema::new(
+        Some("Data Integrity"),
+        None,
+        &[],
+        &[
+            claim(ClaimType::Revocation, "identifier"),
+            claim(ClaimType::Hashed, "name"),
+            claim(ClaimType::Number, "issued"),
+        ],
+    )
+    .unwrap()
+    .with_validity_period(Some("issued"), None)
+    .unwrap()
+}
+
+fn w3c_error<T: std::fmt::Debug>(res: CredxResult<T>) -> W3cError {
+    match res {
+        Err(Error::InvalidW3cData(e)) => e,
+        res => panic!("expected a W3C error, found {:?}", res),
+    }
+}
+
+#[test]
+fn data_integrity_proofs() {
+    let res = test_data_integrity_proofs::<BbsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+    let res = test_data_integrity_proofs::<PsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_data_integrity_proofs<S: ShortGroupSignatureScheme>() -> CredxResult<()> {
+    let (issuer_public, mut issuer) = Issuer::<S>::new(&schema());
+    let bundle = issuer.sign_credential(&[
+        RevocationClaim::from("1f2e3d4c-5b6a-4978-8695-a4b3c2d1e0f9").into(),
+        HashedClaim::from("Jane Doe").into(),
+        NumberClaim::parse_rfc3339_datetime("2026-01-01T00:00:00Z")?.into(),
+    ])?;
+    let mut registry = IssuerKeyRegistry::new();
+    registry.add(&issuer_public);
+
+    let w3c = W3cCredential::try_from(&bundle)?;
+    let json = serde_json::to_value(&w3c).unwrap();
+    assert_eq!(json["validFrom"], json!("2026-01-01T00:00:00Z"));
+    assert_eq!(json["proof"]["cryptosuite"], json!(CREDX_CRYPTOSUITE));
+    let w3c: W3cCredential = serde_json::from_value(json).unwrap();
+    let verified = w3c.verify(&registry)?;
+    assert_eq!(verified.credential.claims, bundle.credential.claims);
+
+    // The claims are covered by the signature
+    let mut tampered = w3c.clone();
+    tampered
+        .credential_subject
+        .insert("name".to_string(), json!("John Doe"));
+    assert!(tampered.to_bundle(&registry).is_ok());
+    assert!(tampered.verify(&registry).is_err());
+    // So is the validity period through its claim
+    let mut tampered = w3c.clone();
+    tampered.valid_from = Some("2025-01-01T00:00:00Z".to_string());
+    assert_eq!(
+        w3c_error(tampered.verify(&registry)),
+        W3cError::ValidityPeriodMismatch
+    );
+    let mut tampered = w3c.clone();
+    tampered.proof.cryptosuite = "bbs-2023".to_string();
+    assert!(matches!(
+        w3c_error(tampered.verify(&registry)),
+        W3cError::UnsupportedProof(_)
+    ));
+    let mut tampered = w3c.clone();
+    tampered.proof.proof_purpose = AUTHENTICATION.to_string();
+    assert!(matches!(
+        w3c_error(tampered.verify(&registry)),
+        W3cError::InvalidProofPurpose(_)
+    ));
+    let mut tampered = w3c.clone();
+    tampered.context = vec![Value::String(
+        "https://www.w3.org/2018/credentials/v1".to_string(),
+    )];
+    assert_eq!(
+        w3c_error(tampered.verify(&registry)),
+        W3cError::InvalidContext
+    );
+    assert!(w3c.verify(&IssuerKeyRegistry::<S>::new()).is_err());
+
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string(), "issued".to_string()},
+        id: "signature".to_string(),
+        issuer: issuer_public.clone(),
+    };
+    let rev_st = RevocationStatement {
+        id: "revocation".to_string(),
+        reference_id: sig_st.id.clone(),
+        accumulator: issuer_public.revocation_registry,
+        verification_key: issuer_public.revocation_verifying_key,
+        claim: 0,
+    };
+    let presentation_schema = PresentationSchema::new(&[sig_st.into(), rev_st.into()]);
+    let nonce = b"data integrity nonce";
+    let credentials = indexmap! {
+        "signature".to_string() => bundle.credential.clone().into(),
+        "revocation".to_string() => bundle.credential.clone().into(),
+    };
+    let presentation = Presentation::create(&credentials, &presentation_schema, nonce)?;
+    let w3c = W3cPresentation::new(&presentation, &presentation_schema, nonce)?;
+    assert_eq!(w3c.proof.challenge, Some(hex::encode(nonce)));
+    assert_eq!(
+        w3c.verifiable_credential[0].valid_from.as_deref(),
+        Some("2026-01-01T00:00:00Z")
+    );
+    let verified = w3c.verify(&presentation_schema, nonce)?;
+    assert_eq!(verified.disclosed_messages, presentation.disclosed_messages);
+
+    assert_eq!(
+        w3c_error(w3c.verify(&presentation_schema, b"another nonce")),
+        W3cError::ChallengeMismatch
+    );
+    let mut tampered = w3c.clone();
+    tampered.verifiable_credential[0]
+        .credential_subject
+        .insert("name".to_string(), json!("John Doe"));
+    assert!(tampered.verify(&presentation_schema, nonce).is_err());
+    let mut tampered = w3c.clone();
+    tampered.verifiable_credential[0].valid_from = None;
+    assert_eq!(
+        w3c_error(tampered.verify(&presentation_schema, nonce)),
+        W3cError::ValidityPeriodMismatch
+    );
+    let mut tampered = w3c.clone();
+    let (other_public, _) = Issuer::<S>::new(&schema());
+    let other = W3cCredential::try_from(&CredentialBundle {
+        issuer: other_public,
+        credential: bundle.credential.clone(),
+    })?;
+    tampered.verifiable_credential[0].proof.verification_method =
+        other.proof.verification_method.clone();
+    assert!(matches!(
+        w3c_error(tampered.verify(&presentation_schema, nonce)),
+        W3cError::IssuerMismatch(_)
+    ));
+    let mut tampered = w3c;
+    tampered.proof.proof_purpose = "assertionMethod".to_string();
+    assert!(matches!(
+        w3c_error(tampered.verify(&presentation_schema, nonce)),
+        W3cError::InvalidProofPurpose(_)
+    ));
+    Ok(())
+}
@@ -0,0 +1,757 @@
+use blsful::inner_types::*;
+use credx::claim::{
+    AbsentClaim, ClaimData, ClaimType, ClaimValidator, HashedClaim, NumberClaim, RevocationClaim,
+    ScalarClaim,
+};
+use credx::credential::{ClaimSchema, CredentialBundle, CredentialSchema};
+use credx::error::Error;
+use credx::issuer::{Issuer, IssuerKeyRegistry};
+use credx::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use credx::knox::{bbs::BbsScheme, ps::PsScheme};
+use credx::prelude::{
+    MembershipClaim, MembershipCredential, MembershipRegistry, MembershipSigningKey,
+    MembershipVerificationKey,
+};
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::{
+    CommitmentStatement, EqualityStatement, MembershipStatement, RangeStatement,
+    RevocationStatement, SignatureStatement, VerifiableEncryptionStatement,
+};
+use credx::w3c::{W3cCredential, W3cError, W3cPresentation};
+use credx::{random_string, CredxResult};
+use indexmap::indexmap;
+use maplit::btreeset;
+use rand::{thread_rng, RngCore};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::fs;
+
+const CREDENTIAL_SAMPLE: &str = "samples/credentials/credential_bundle.json";
+const W3C_CREDENTIAL_SAMPLE: &str = "samples/credentials/w3c_v2_credential.json";
+const PRESENTATION_SCHEMA_SAMPLE: &str = "samples/presentations/presentation_schema.json";
+const W3C_PRESENTATION_SAMPLE: &str = "samples/presentations/w3c_v2_presentation.json";
+const LEGACY_W3C_CREDENTIAL_SAMPLE: &str = "samples/credentials/w3c_credential.json";
+const LEGACY_W3C_PRESENTATION_SAMPLE: &str = "samples/presentations/w3c_presentation.json";
+
+type SamplePresentation = (
+    Presentation<PsScheme>,
+    PresentationSchema<PsScheme>,
+    [u8; 16],
+);
+
+fn sample_schema() -> CredxResult<CredentialSchema> {
+    let claim = |claim_type, label: &str, validators| ClaimSchema {
+        claim_type,
+        label: label.to_string(),
+        print_friendly: true,
+        optional: false,
+        normalization: None,
+        validators,
+    };
+    CredentialSchema::new(
+        Some("Test Schema"),
+        Some("This is a test presentation schema"),
+        &[],
+        &[
+            claim(ClaimType::Revocation, "identifier", vec![]),
+            claim(
+                ClaimType::Hashed,
+                "name",
+                vec![ClaimValidator::Length {
+                    min: Some(3),
+                    max: Some(u8::MAX as usize),
+                }],
+            ),
+            claim(
+                ClaimType::Hashed,
+                "address",
+                vec![ClaimValidator::Length {
+                    min: None,
+                    max: Some(u8::MAX as usize),
+                }],
+            ),
+            claim(
+                ClaimType::Number,
+                "age",
+                vec![ClaimValidator::Range {
+                    min: Some(0),
+                    max: Some(u16::MAX as isize),
+                }],
+            ),
+        ],
+    )
+}
+
+fn create_sample_credential() -> CredxResult<CredentialBundle<PsScheme>> {
+    let (_, mut issuer) = Issuer::new(&sample_schema()?);
+    issuer.sign_credential(&[
+        RevocationClaim::from("91742856-6eda-45fb-a709-d22ebb5ec8a5").into(),
+        HashedClaim::from("John Doe").into(),
+        HashedClaim::from("P Sherman 42 Wallaby Way Sydney").into(),
+        NumberClaim::from(30303).into(),
+    ])
+}
+
+fn create_sample_presentation(
+    bundle: &CredentialBundle<PsScheme>,
+) -> CredxResult<SamplePresentation> {
+    let issuer_public = &bundle.issuer;
+    let membership_key = MembershipSigningKey::new(None);
+    let membership_registry = MembershipRegistry::random(thread_rng());
+    let membership_credential = MembershipCredential::new(
+        MembershipClaim::from(&bundle.credential.claims[2]).0,
+        membership_registry,
+        &membership_key,
+    );
+
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: random_string(16, thread_rng()),
+        issuer: issuer_public.clone(),
+    };
+    let acc_st = RevocationStatement {
+        id: random_string(16, thread_rng()),
+        reference_id: sig_st.id.clone(),
+        accumulator: issuer_public.revocation_registry,
+        verification_key: issuer_public.revocation_verifying_key,
+        claim: 0,
+    };
+    let comm_st = CommitmentStatement {
+        id: random_string(16, thread_rng()),
+        reference_id: sig_st.id.clone(),
+        message_generator: G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(
+            b"message generator",
+            b"BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        ),
+        blinder_generator: G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(
+            b"blinder generator",
+            b"BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        ),
+        claim: 3,
+    };
+    let verenc_st = VerifiableEncryptionStatement {
+        message_generator: G1Projective::GENERATOR,
+        encryption_key: issuer_public.verifiable_encryption_key,
+        id: random_string(16, thread_rng()),
+        reference_id: sig_st.id.clone(),
+        claim: 0,
+        allow_message_decryption: false,
+    };
+    let range_st = RangeStatement {
+        id: random_string(16, thread_rng()),
+        reference_id: comm_st.id.clone(),
+        signature_id: sig_st.id.clone(),
+        claim: 3,
+        lower: Some(0),
+        upper: Some(44829),
+    };
+    let mem_st = MembershipStatement {
+        id: random_string(16, thread_rng()),
+        reference_id: sig_st.id.clone(),
+        accumulator: membership_registry,
+        verification_key: MembershipVerificationKey::from(&membership_key),
+        claim: 2,
+    };
+
+    let mut nonce = [0u8; 16];
+    thread_rng().fill_bytes(&mut nonce);
+    let credentials = indexmap! {
+        sig_st.id.clone() => bundle.credential.clone().into(),
+        mem_st.id.clone() => membership_credential.into(),
+    };
+    let presentation_schema = PresentationSchema::new(&[
+        sig_st.into(),
+        acc_st.into(),
+        comm_st.into(),
+        verenc_st.into(),
+        range_st.into(),
+        mem_st.into(),
+    ]);
+    let presentation = Presentation::create(&credentials, &presentation_schema, &nonce)?;
+    Ok((presentation, presentation_schema, nonce))
+}
+
+fn write_sample<T: serde::Serialize>(path: &str, value: &T) {
+    fs::write(path, serde_json::to_string_pretty(value).unwrap()).unwrap();
+}
+
+fn read_sample<T: serde::de::DeserializeOwned>(path: &str) -> T {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+/// Regenerate the samples after changing the mapping
+#[ignore]
+#[test]
+fn generate_w3c_samples() {
+    let bundle = create_sample_credential().unwrap();
+    let presentation = create_sample_presentation(&bundle).unwrap();
+    write_sample(CREDENTIAL_SAMPLE, &bundle);
+    write_sample(
+        W3C_CREDENTIAL_SAMPLE,
+        &W3cCredential::try_from(&bundle).unwrap(),
+    );
+    write_sample(
+        PRESENTATION_SCHEMA_SAMPLE,
+        &(&presentation.1, &presentation.2),
+    );
+    write_sample(
+        W3C_PRESENTATION_SAMPLE,
+        &W3cPresentation::new(&presentation.0, &presentation.1, &presentation.2).unwrap(),
+    );
+}
+
+#[test]
+fn w3c_credential_sample() {
+    let bundle: CredentialBundle<PsScheme> = read_sample(CREDENTIAL_SAMPLE);
+    let w3c_json: Value = read_sample(W3C_CREDENTIAL_SAMPLE);
+    let w3c: W3cCredential = serde_json::from_value(w3c_json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&w3c).unwrap(), w3c_json);
+
+    let mut registry = IssuerKeyRegistry::new();
+    registry.add(&bundle.issuer);
+    let resolved = w3c.to_bundle(&registry).unwrap();
+    resolved.verify().unwrap();
+    assert_eq!(
+        serde_json::to_value(&resolved).unwrap(),
+        serde_json::to_value(&bundle).unwrap()
+    );
+    assert_eq!(
+        serde_json::to_value(W3cCredential::try_from(&bundle).unwrap()).unwrap(),
+        w3c_json
+    );
+    assert_eq!(
+        w3c_json["credentialSubject"],
+        json!({
+            "identifier": "91742856-6eda-45fb-a709-d22ebb5ec8a5",
+            "name": "John Doe",
+            "address": "P Sherman 42 Wallaby Way Sydney",
+            "age": 30303,
+        })
+    );
+}
+
+#[test]
+fn w3c_presentation_sample() {
+    let (schema, nonce): (PresentationSchema<PsScheme>, [u8; 16]) =
+        read_sample(PRESENTATION_SCHEMA_SAMPLE);
+    let w3c_json: Value = read_sample(W3C_PRESENTATION_SAMPLE);
+    let w3c: W3cPresentation = serde_json::from_value(w3c_json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&w3c).unwrap(), w3c_json);
+
+    let mapped = w3c.to_presentation(&schema).unwrap();
+    mapped.verify(&schema, &nonce).unwrap();
+    assert_eq!(mapped.proofs.len(), schema.statements.len());
+    assert_eq!(
+        serde_json::to_value(W3cPresentation::new(&mapped, &schema, &nonce).unwrap()).unwrap(),
+        w3c_json
+    );
+    assert_eq!(
+        w3c_json["verifiableCredential"][0]["credentialSubject"],
+        json!({ "name": "John Doe" })
+    );
+}
+
+/// The samples written by earlier releases use VC Data Model 1.1 and
+/// their own proofs. They still parse but aren't credx proofs.
+#[test]
+fn w3c_legacy_samples() {
+    let w3c: W3cCredential = read_sample(LEGACY_W3C_CREDENTIAL_SAMPLE);
+    assert_eq!(w3c.issuer, "did:key:7f596d64a4205133a9cad45c02ce44d6");
+    assert_eq!(w3c.valid_from.as_deref(), Some("2025-03-14T15:10:01Z"));
+    assert_eq!(w3c.credential_subject["age"], json!(30303));
+    assert!(w3c.proof.cryptosuite.is_empty());
+    let registry = IssuerKeyRegistry::<PsScheme>::new();
+    assert_eq!(
+        w3c.verify(&registry).unwrap_err(),
+        Error::InvalidW3cData(W3cError::InvalidContext)
+    );
+    assert!(matches!(
+        w3c.to_bundle(&registry),
+        Err(Error::InvalidW3cData(W3cError::InvalidIdentifier(_)))
+    ));
+
+    let w3c: W3cPresentation = read_sample(LEGACY_W3C_PRESENTATION_SAMPLE);
+    assert_eq!(
+        w3c.presentation_schema,
+        "did:key:6b9855d4c2a679ad69bf39247f5d112b"
+    );
+    assert_eq!(w3c.proof.cryptosuite, "anoncreds-2024");
+    assert_eq!(w3c.verifiable_credential.len(), 1);
+    assert_eq!(
+        w3c.verifiable_credential[0].issuer,
+        "did:key:c6c6caa5d0669e5be0f9f077c15e2706"
+    );
+    let schema = PresentationSchema::<PsScheme>::new(&[]);
+    assert_eq!(
+        w3c.verify(&schema, &[0u8; 16]).unwrap_err(),
+        Error::InvalidW3cData(W3cError::InvalidContext)
+    );
+    assert!(matches!(
+        w3c.to_presentation(&schema),
+        Err(Error::InvalidW3cData(W3cError::SchemaMismatch(_)))
+    ));
+}
+
+fn claim(claim_type: ClaimType, label: &str, optional: bool) -> ClaimSchema {
+    ClaimSchema {
+        claim_type,
+        label: label.to_string(),
+        print_friendly: false,
+        optional,
+        normalization: None,
+        validators: vec![],
+    }
+}
+
+fn schema() -> CredentialSchema {
+    CredentialSchema::new(
+        Some("W3C"),
+        None,
+        &[],
+        &[
+            claim(ClaimType::Revocation, "identifier", false),
+            claim(ClaimType::Scalar, "link_secret", false),
+            claim(ClaimType::Hashed, "name", false),
+            claim(ClaimType::Hashed, "middle_name", true),
+            claim(ClaimType::Hashed, "photo", false),
+            claim(ClaimType::Number, "age", false),
+        ],
+    )
+    .unwrap()
+}
+
+fn claims(identifier: &str, link_secret: Scalar) -> Vec<ClaimData> {
+    vec![
+        RevocationClaim::from(identifier).into(),
+        ScalarClaim::from(link_secret).into(),
+        HashedClaim::from("Jane Doe").into(),
+        AbsentClaim.into(),
+        HashedClaim::from(&[0xffu8, 0x00, 0x7f][..]).into(),
+        NumberClaim::from(-42isize).into(),
+    ]
+}
+
+#[test]
+fn w3c_round_trip() {
+    let res = test_w3c_round_trip::<BbsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+    let res = test_w3c_round_trip::<PsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_w3c_round_trip<S: ShortGroupSignatureScheme>() -> CredxResult<()> {
+    let (issuer_public_1, mut issuer_1) = Issuer::<S>::new(&schema());
+    let (issuer_public_2, mut issuer_2) = Issuer::<S>::new(&schema());
+    let link_secret = Scalar::random(thread_rng());
+    let bundle_1 =
+        issuer_1.sign_credential(&claims("3c4d5e6f-7a8b-4c9d-8e0f-1a2b3c4d5e6f", link_secret))?;
+    let bundle_2 =
+        issuer_2.sign_credential(&claims("6f5e4d3c-2b1a-4f0e-9d8c-7b6a5f4e3d2c", link_secret))?;
+
+    let mut registry = IssuerKeyRegistry::new();
+    registry.add(&issuer_public_1);
+    let w3c = W3cCredential::try_from(&bundle_1)?;
+    let subject = serde_json::to_value(&w3c.credential_subject).unwrap();
+    assert_eq!(subject["name"], json!("Jane Doe"));
+    assert_eq!(subject["middle_name"], Value::Null);
+    assert_eq!(subject["photo"], json!({ "@value": "hex:ff007f" }));
+    assert_eq!(subject["age"], json!(-42));
+    let resolved = w3c.to_bundle(&registry)?;
+    resolved.verify()?;
+    assert_eq!(resolved.credential.claims, bundle_1.credential.claims);
+
+    // The issuer must be known and the claims must match its schema
+    assert!(W3cCredential::try_from(&bundle_2)?
+        .to_bundle(&registry)
+        .is_err());
+    let mut tampered = w3c.clone();
+    tampered.credential_subject.remove("photo");
+    assert_eq!(
+        tampered.to_bundle(&registry).unwrap_err(),
+        Error::InvalidW3cData(W3cError::MissingClaim("photo".to_string()))
+    );
+    let mut tampered = w3c.clone();
+    tampered
+        .credential_subject
+        .insert("nickname".to_string(), json!("JD"));
+    assert_eq!(
+        tampered.to_bundle(&registry).unwrap_err(),
+        Error::InvalidW3cData(W3cError::UnknownClaim("nickname".to_string()))
+    );
+    let mut tampered = w3c.clone();
+    tampered
+        .credential_subject
+        .insert("age".to_string(), json!("42"));
+    assert_eq!(
+        tampered.to_bundle(&registry).unwrap_err(),
+        Error::InvalidW3cData(W3cError::InvalidClaim("age".to_string()))
+    );
+    let mut tampered = w3c.clone();
+    tampered.issuer = W3cCredential::try_from(&bundle_2)?.issuer;
+    assert!(matches!(
+        tampered.to_bundle(&registry),
+        Err(Error::InvalidW3cData(W3cError::IssuerMismatch(_)))
+    ));
+    let mut tampered = w3c.clone();
+    tampered.proof.proof_value.insert(1, '!');
+    assert_eq!(
+        tampered.to_bundle(&registry).unwrap_err(),
+        Error::InvalidW3cData(W3cError::InvalidProofValue)
+    );
+    let mut tampered = w3c;
+    tampered.types.clear();
+    assert!(tampered.to_bundle(&registry).is_err());
+
+    let sig_st_1 = SignatureStatement {
+        disclosed: btreeset! {"name".to_string(), "middle_name".to_string(), "photo".to_string()},
+        id: random_string(16, thread_rng()),
+        issuer: issuer_public_1.clone(),
+    };
+    let sig_st_2 = SignatureStatement {
+        disclosed: btreeset! {"age".to_string()},
+        id: random_string(16, thread_rng()),
+        issuer: issuer_public_2.clone(),
+    };
+    let rev_st = RevocationStatement {
+        id: random_string(16, thread_rng()),
+        reference_id: sig_st_2.id.clone(),
+        accumulator: bundle_2.issuer.revocation_registry,
+        verification_key: bundle_2.issuer.revocation_verifying_key,
+        claim: 0,
+    };
+    let eq_st = EqualityStatement {
+        id: random_string(16, thread_rng()),
+        ref_id_claim_index: indexmap! {
+            sig_st_1.id.clone() => 1,
+            sig_st_2.id.clone() => 1,
+        },
+    };
+    let schema = PresentationSchema::new(&[
+        sig_st_1.clone().into(),
+        sig_st_2.clone().into(),
+        rev_st.clone().into(),
+        eq_st.clone().into(),
+    ]);
+    let nonce = [7u8; 16];
+    let credentials = indexmap! {
+        sig_st_1.id.clone() => bundle_1.credential.clone().into(),
+        sig_st_2.id.clone() => bundle_2.credential.clone().into(),
+    };
+    let presentation = Presentation::create(&credentials, &schema, &nonce)?;
+    let w3c = W3cPresentation::new(&presentation, &schema, &nonce)?;
+    assert_eq!(w3c.verifiable_credential.len(), 2);
+    let json = serde_json::to_string(&w3c).unwrap();
+    let w3c: W3cPresentation = serde_json::from_str(&json).unwrap();
+    let mapped = w3c.to_presentation(&schema)?;
+    mapped.verify(&schema, &nonce)?;
+    assert_eq!(mapped.disclosed_messages, presentation.disclosed_messages);
+    assert_eq!(
+        mapped.disclosed_messages[&sig_st_1.id]["middle_name"],
+        AbsentClaim.into()
+    );
+
+    // Changing a disclosed claim or dropping a credential breaks the presentation
+    let mut tampered = w3c.clone();
+    tampered.verifiable_credential[1]
+        .credential_subject
+        .insert("age".to_string(), json!(42));
+    assert!(tampered
+        .to_presentation(&schema)?
+        .verify(&schema, &nonce)
+        .is_err());
+    let mut tampered = w3c.clone();
+    tampered.verifiable_credential.pop();
+    assert!(matches!(
+        tampered.to_presentation(&schema),
+        Err(Error::InvalidW3cData(W3cError::MissingCredential(_)))
+    ));
+    let other_schema = PresentationSchema::new(&[sig_st_1.into(), sig_st_2.into()]);
+    assert!(matches!(
+        w3c.to_presentation(&other_schema),
+        Err(Error::InvalidW3cData(W3cError::SchemaMismatch(_)))
+    ));
+    Ok(())
+}
+
+/// Issuer and presentation schema ids that are URIs are kept, other ids
+/// are written as credx URNs
+#[test]
+fn w3c_identifiers() {
+    let res = test_w3c_identifiers::<BbsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+    let res = test_w3c_identifiers::<PsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_w3c_identifiers<S: ShortGroupSignatureScheme>() -> CredxResult<()> {
+    let link_secret = Scalar::random(thread_rng());
+    for (id, uri) in [
+        ("did:example:issuer", true),
+        ("https://issuer.example/keys/1", true),
+        ("a1b2c3d4e5f60718", false),
+        ("Example Issuer", false),
+        ("example:two words", false),
+        ("1st:issuer", false),
+    ] {
+        let (issuer_public, mut issuer) =
+            Issuer::<S>::new_with_id_and_rng(id, &schema(), thread_rng())?;
+        let bundle =
+            issuer.sign_credential(&claims("2b3c4d5e-6f7a-4b8c-9d0e-1f2a3b4c5d6e", link_secret))?;
+        let w3c = W3cCredential::try_from(&bundle)?;
+        let key_id = hex::encode(issuer_public.key_id());
+        let expected = if uri {
+            id.to_string()
+        } else {
+            format!("urn:credx:issuer:{}", key_id)
+        };
+        assert_eq!(w3c.issuer, expected);
+        let issuer_id = w3c.issuer.clone();
+        assert_eq!(
+            w3c.proof.verification_method,
+            Some(format!("{}#{}", expected, key_id))
+        );
+        let mut registry = IssuerKeyRegistry::new();
+        registry.add(&issuer_public);
+        w3c.verify(&registry)?;
+
+        let sig_st = SignatureStatement {
+            disclosed: btreeset! {"name".to_string()},
+            id: "signature".to_string(),
+            issuer: issuer_public,
+        };
+        let schema = PresentationSchema::new_with_id(&[sig_st.into()], id);
+        let credentials = indexmap! { "signature".to_string() => bundle.credential.into() };
+        let nonce = [3u8; 16];
+        let presentation = Presentation::create(&credentials, &schema, &nonce)?;
+        let w3c = W3cPresentation::new(&presentation, &schema, &nonce)?;
+        let expected = if uri {
+            id.to_string()
+        } else {
+            format!(
+                "urn:credx:presentation-schema:{}",
+                id.replace(' ', "%20").replace(':', "%3A")
+            )
+        };
+        assert_eq!(w3c.presentation_schema, expected);
+        assert_eq!(w3c.verifiable_credential[0].issuer, issuer_id);
+        w3c.verify(&schema, &nonce)?;
+    }
+    Ok(())
+}
+
+fn read_baseline<T: DeserializeOwned>(scheme: &str, name: &str) -> T {
+    read_sample(&format!("tests/fixtures/baseline/{}_{}.json", scheme, name))
+}
+
+/// Credentials and presentations created by credx 0.2.1 convert to W3C
+/// documents that verify
+#[test]
+fn w3c_baseline_data() {
+    let res = test_w3c_baseline_data::<BbsScheme>("bbs");
+    assert!(res.is_ok(), "{:?}", res);
+    let res = test_w3c_baseline_data::<PsScheme>("ps");
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_w3c_baseline_data<S: ShortGroupSignatureScheme>(scheme: &str) -> CredxResult<()> {
+    let bundle: CredentialBundle<S> = read_baseline(scheme, "credential_bundle");
+    let mut registry = IssuerKeyRegistry::new();
+    registry.add(&bundle.issuer);
+    let w3c = W3cCredential::try_from(&bundle)?;
+    assert_eq!(
+        w3c.issuer,
+        format!("urn:credx:issuer:{}", hex::encode(bundle.issuer.key_id()))
+    );
+    let json = serde_json::to_string(&w3c).unwrap();
+    let resolved = serde_json::from_str::<W3cCredential>(&json)
+        .unwrap()
+        .verify(&registry)?;
+    assert_eq!(
+        serde_json::to_value(&resolved).unwrap(),
+        serde_json::to_value(&bundle).unwrap()
+    );
+
+    let schema: PresentationSchema<S> = read_baseline(scheme, "presentation_schema");
+    let presentation: Presentation<S> = read_baseline(scheme, "presentation");
+    let nonce = [7u8; 16];
+    let w3c = W3cPresentation::new(&presentation, &schema, &nonce)?;
+    assert_eq!(
+        w3c.presentation_schema,
+        "urn:credx:presentation-schema:baseline"
+    );
+    let json = serde_json::to_string(&w3c).unwrap();
+    let mapped = serde_json::from_str::<W3cPresentation>(&json)
+        .unwrap()
+        .verify(&schema, &nonce)?;
+    assert_eq!(mapped.challenge, presentation.challenge);
+    assert_eq!(mapped.disclosed_messages, presentation.disclosed_messages);
+    Ok(())
+}
+
+/// The issuer and claims are read from credential JSON
+#[test]
+fn w3c_credential_from_json() {
+    let bundle = create_sample_credential().unwrap();
+    let json = serde_json::to_value(W3cCredential::try_from(&bundle).unwrap()).unwrap();
+    let key_id = hex::encode(bundle.issuer.key_id());
+    assert_eq!(
+        json["proof"]["verificationMethod"],
+        json!(format!("{}#{}", json["issuer"].as_str().unwrap(), key_id))
+    );
+    assert_eq!(json["type"], json!(["VerifiableCredential"]));
+
+    let w3c: W3cCredential = serde_json::from_value(json).unwrap();
+    let mut registry = IssuerKeyRegistry::new();
+    assert_eq!(
+        w3c.to_bundle(&registry).unwrap_err(),
+        Error::General("unknown issuer key id")
+    );
+    registry.add(&bundle.issuer);
+    let resolved = w3c.to_bundle(&registry).unwrap();
+    assert_eq!(
+        serde_json::to_value(&resolved.issuer).unwrap(),
+        serde_json::to_value(&bundle.issuer).unwrap()
+    );
+    let labels = w3c.credential_subject.keys().collect::<Vec<_>>();
+    assert_eq!(labels, ["address", "age", "identifier", "name"]);
+    assert_eq!(resolved.credential.claims, bundle.credential.claims);
+}
+
+/// Proof values are multibase base64url and must decode
+#[test]
+fn w3c_proof_values() {
+    let bundle = create_sample_credential().unwrap();
+    let (presentation, schema, nonce) = create_sample_presentation(&bundle).unwrap();
+    let mut registry = IssuerKeyRegistry::new();
+    registry.add(&bundle.issuer);
+
+    let w3c = W3cCredential::try_from(&bundle).unwrap();
+    assert!(w3c.proof.proof_value.starts_with('u'));
+    let mut tampered = w3c.clone();
+    tampered.proof.proof_value.replace_range(0..1, "z");
+    assert_eq!(
+        tampered.to_bundle(&registry).unwrap_err(),
+        Error::InvalidW3cData(W3cError::InvalidProofValue)
+    );
+    let mut tampered = w3c;
+    tampered.proof.proof_value.truncate(8);
+    assert_eq!(
+        tampered.to_bundle(&registry).unwrap_err(),
+        Error::InvalidW3cData(W3cError::InvalidProofValue)
+    );
+
+    let w3c = W3cPresentation::new(&presentation, &schema, &nonce).unwrap();
+    assert!(w3c.proof.proof_value.starts_with('u'));
+    assert_eq!(w3c.proof.challenge, Some(hex::encode(nonce)));
+    let mut tampered = w3c.clone();
+    tampered.proof.proof_value.truncate(8);
+    assert_eq!(
+        tampered.to_presentation(&schema).unwrap_err(),
+        Error::InvalidW3cData(W3cError::InvalidProofValue)
+    );
+    let mut tampered = w3c;
+    tampered.verifiable_credential[0]
+        .proof
+        .proof_value
+        .replace_range(0..1, "z");
+    assert_eq!(
+        tampered.to_presentation(&schema).unwrap_err(),
+        Error::InvalidW3cData(W3cError::InvalidProofValue)
+    );
+}
+
+/// Each signature statement becomes a credential with its disclosed claims
+/// and the proofs of the statements that refer to it
+#[test]
+fn w3c_presentation_credentials() {
+    let res = test_w3c_presentation_credentials::<BbsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+    let res = test_w3c_presentation_credentials::<PsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_w3c_presentation_credentials<S: ShortGroupSignatureScheme>() -> CredxResult<()> {
+    let (issuer_public_1, mut issuer_1) = Issuer::<S>::new(&schema());
+    let (issuer_public_2, mut issuer_2) = Issuer::<S>::new(&schema());
+    let link_secret = Scalar::random(thread_rng());
+    let bundle_1 =
+        issuer_1.sign_credential(&claims("4d5e6f7a-8b9c-4d0e-8f1a-2b3c4d5e6f7a", link_secret))?;
+    let bundle_2 =
+        issuer_2.sign_credential(&claims("7a6f5e4d-3c2b-4a1f-8e0d-9c8b7a6f5e4d", link_secret))?;
+
+    let sig_st_1 = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "signature 1".to_string(),
+        issuer: issuer_public_1,
+    };
+    let sig_st_2 = SignatureStatement {
+        disclosed: btreeset! {"age".to_string()},
+        id: "signature 2".to_string(),
+        issuer: issuer_public_2,
+    };
+    let comm_st = CommitmentStatement {
+        id: "commitment".to_string(),
+        reference_id: sig_st_2.id.clone(),
+        message_generator: G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(
+            b"message generator",
+            b"BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        ),
+        blinder_generator: G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(
+            b"blinder generator",
+            b"BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        ),
+        claim: 1,
+    };
+    let eq_st = EqualityStatement {
+        id: "equality".to_string(),
+        ref_id_claim_index: indexmap! {
+            sig_st_1.id.clone() => 1,
+            sig_st_2.id.clone() => 1,
+        },
+    };
+    let schema = PresentationSchema::new(&[
+        sig_st_1.clone().into(),
+        sig_st_2.clone().into(),
+        comm_st.into(),
+        eq_st.into(),
+    ]);
+    let nonce = [5u8; 16];
+    let credentials = indexmap! {
+        sig_st_1.id.clone() => bundle_1.credential.into(),
+        sig_st_2.id.clone() => bundle_2.credential.into(),
+    };
+    let presentation = Presentation::create(&credentials, &schema, &nonce)?;
+    let w3c = W3cPresentation::new(&presentation, &schema, &nonce)?;
+    assert_eq!(w3c.verifiable_credential.len(), 2);
+    assert_eq!(
+        serde_json::to_value(&w3c.verifiable_credential[0].credential_subject).unwrap(),
+        json!({ "name": "Jane Doe" })
+    );
+    assert_eq!(
+        serde_json::to_value(&w3c.verifiable_credential[1].credential_subject).unwrap(),
+        json!({ "age": -42 })
+    );
+
+    // The commitment proof travels with the second credential so the
+    // credentials can be in any order
+    let mut reordered = w3c.clone();
+    reordered.verifiable_credential.reverse();
+    let mapped = reordered.verify(&schema, &nonce)?;
+    assert_eq!(
+        mapped.proofs.keys().collect::<Vec<_>>(),
+        presentation.proofs.keys().collect::<Vec<_>>()
+    );
+    let mut moved = w3c.clone();
+    let proof_value = moved.verifiable_credential[1].proof.proof_value.clone();
+    moved.verifiable_credential[0].proof.proof_value = proof_value;
+    assert!(moved.verify(&schema, &nonce).is_err());
+
+    // The equality proof refers to both signatures so it's in the presentation proof
+    let mut dropped = w3c;
+    dropped.verifiable_credential.pop();
+    assert!(matches!(
+        dropped.to_presentation(&schema),
+        Err(Error::InvalidW3cData(W3cError::MissingCredential(_)))
+    ));
+    Ok(())
+}